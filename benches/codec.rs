@@ -0,0 +1,152 @@
+//! Encode and decode benchmarks for representative messages.
+//!
+//! Run with `cargo bench`. Every benchmark reports the median and the fastest time per iteration
+//! over a number of samples. Pass a substring of a benchmark name to run only matching benchmarks.
+
+// Converting to `Str` is only a no-op without the shared-strings feature
+#![allow(clippy::useless_conversion)]
+
+use debug_adapter_protocol::{
+    events::{OutputCategory, OutputEventBody},
+    requests::InitializeRequestArguments,
+    responses::{Response, VariablesResponseBody},
+    types::Variable,
+    ParseOptions, ProtocolMessage, ProtocolMessageContent,
+};
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const SAMPLES: usize = 20;
+const SAMPLE_TIME: Duration = Duration::from_millis(100);
+
+fn main() {
+    let filter = std::env::args().skip(1).find(|arg| !arg.starts_with('-'));
+    let mut bench = Bench { filter };
+
+    let initialize = ProtocolMessage::new(
+        1,
+        InitializeRequestArguments::builder()
+            .client_id(Some("vscode".into()))
+            .client_name(Some("Visual Studio Code".into()))
+            .adapter_id("mock".into())
+            .locale(Some("en".into()))
+            .lines_start_at_1(true)
+            .columns_start_at_1(true)
+            .supports_variable_type(true)
+            .supports_variable_paging(true)
+            .supports_run_in_terminal_request(true)
+            .supports_progress_reporting(true)
+            .supports_invalidated_event(true)
+            .build(),
+    );
+    bench.codec("initialize", &initialize);
+
+    let variables = ProtocolMessage::new(
+        2,
+        ProtocolMessageContent::Response(Response::new(
+            1,
+            Ok(VariablesResponseBody::builder()
+                .variables(
+                    (0..10_000)
+                        .map(|index| {
+                            Variable::builder()
+                                .name(format!("[{}]", index).into())
+                                .value(format!("\"element number {}\"", index).into())
+                                .type_(Some("String".into()))
+                                .evaluate_name(Some(format!("array[{}]", index).into()))
+                                .variables_reference(index)
+                                .build()
+                        })
+                        .collect(),
+                )
+                .build()
+                .into()),
+        )),
+    );
+    bench.codec("variables_10000", &variables);
+
+    let outputs = (0..1_000)
+        .map(|index| {
+            ProtocolMessage::new(
+                index,
+                OutputEventBody::builder()
+                    .category(OutputCategory::Stdout)
+                    .output(format!("line {} of a flood of output\n", index).into())
+                    .build(),
+            )
+            .to_string()
+        })
+        .collect::<String>();
+    bench.run("output_flood_1000/decode", || {
+        outputs
+            .split("Content-Length: ")
+            .filter(|frame| !frame.is_empty())
+            .map(|frame| {
+                let json = &frame[frame.find("\r\n\r\n").unwrap() + 4..];
+                json.parse::<ProtocolMessage>().unwrap()
+            })
+            .count()
+    });
+}
+
+struct Bench {
+    filter: Option<String>,
+}
+
+impl Bench {
+    fn codec(&mut self, name: &str, message: &ProtocolMessage) {
+        let json = serde_json::to_string(message).unwrap();
+        let strict = ParseOptions::builder().strict(true).build();
+        let mut buffer = Vec::new();
+
+        self.run(&format!("{}/encode_to_string", name), || {
+            message.to_string()
+        });
+        self.run(&format!("{}/write_to", name), || {
+            buffer.clear();
+            message.write_to(&mut buffer).unwrap();
+        });
+        self.run(&format!("{}/from_str", name), || {
+            json.parse::<ProtocolMessage>().unwrap()
+        });
+        self.run(&format!("{}/parse_strict", name), || {
+            ProtocolMessage::parse_with(&json, &strict).unwrap()
+        });
+    }
+
+    fn run<T>(&mut self, name: &str, mut f: impl FnMut() -> T) {
+        if let Some(filter) = &self.filter {
+            if !name.contains(filter.as_str()) {
+                return;
+            }
+        }
+
+        // Estimate the number of iterations per sample
+        let start = Instant::now();
+        let mut iterations = 0u32;
+        while start.elapsed() < SAMPLE_TIME / 10 {
+            black_box(f());
+            iterations += 1;
+        }
+        let iterations = (iterations * 10).max(1);
+
+        let mut samples = (0..SAMPLES)
+            .map(|_| {
+                let start = Instant::now();
+                for _ in 0..iterations {
+                    black_box(f());
+                }
+                start.elapsed() / iterations
+            })
+            .collect::<Vec<_>>();
+        samples.sort();
+        println!(
+            "{:40} median {:>12?}   fastest {:>12?}",
+            name,
+            samples[SAMPLES / 2],
+            samples[0]
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! Decodes arbitrary bytes as a stream of framed messages with every decoder of the crate. The
+//! first byte determines the size of the chunks that the stream arrives in. Decoding must never
+//! panic and all decoders that split frames the same way must agree on them.
+//!
+//! Run with `cargo +nightly fuzz run framing`.
+
+#![no_main]
+
+use debug_adapter_protocol::{
+    decoder::Decoder,
+    driver::{Driver, DriverError},
+    framing::{read_frame, split_frame, split_frames},
+};
+use libfuzzer_sys::fuzz_target;
+use std::io::{BufReader, Read};
+
+/// A reader that returns at most `chunk_size` bytes per read, like a socket.
+struct Chunked<'a> {
+    data: &'a [u8],
+    chunk_size: usize,
+}
+
+impl Read for Chunked<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = self.chunk_size.min(buf.len()).min(self.data.len());
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        Ok(len)
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&chunk_size, stream)) = data.split_first() else {
+        return;
+    };
+    let chunk_size = usize::from(chunk_size).max(1);
+    let chunked = || Chunked {
+        data: stream,
+        chunk_size,
+    };
+
+    // The frames up to the first invalid or incomplete one
+    let mut expected = Vec::new();
+    let mut rest = stream;
+    while let Ok(Some((content, frame_len))) = split_frame(rest) {
+        expected.push(content);
+        rest = &rest[frame_len..];
+    }
+    if let Ok(frames) = split_frames(stream) {
+        assert_eq!(frames, expected);
+    }
+
+    let decoded = Decoder::new(chunked())
+        .map_while(Result::ok)
+        .collect::<Vec<_>>();
+    assert_eq!(decoded, expected);
+
+    // read_frame also accepts headers that end with "\n\n", so its frames may differ
+    let mut reader = BufReader::with_capacity(chunk_size, chunked());
+    while let Ok(Some(_)) = read_frame(&mut reader) {}
+
+    let mut driver = Driver::new();
+    for chunk in stream.chunks(chunk_size) {
+        let mut input = chunk;
+        // Skip invalid messages until the header of one is invalid
+        while let Err(error) = driver.handle_input(input) {
+            if let DriverError::Frame(_) = error {
+                return;
+            }
+            input = &[];
+        }
+        while driver.poll_event().is_some() {}
+    }
+});
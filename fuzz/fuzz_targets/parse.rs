@@ -0,0 +1,25 @@
+//! Parses arbitrary bytes as the JSON content of a message. Parsing must never panic and every
+//! message that parses must survive a round trip through its serialization.
+//!
+//! Run with `cargo +nightly fuzz run parse`.
+
+#![no_main]
+
+use debug_adapter_protocol::{ParseOptions, ProtocolMessage};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut strict = ParseOptions::default();
+    strict.strict = true;
+    let mut lenient = ParseOptions::default();
+    lenient.lenient_numbers = true;
+    let _ = ProtocolMessage::parse_slice_with(data, &strict);
+    let _ = ProtocolMessage::parse_slice_with(data, &lenient);
+
+    if let Ok(message) = ProtocolMessage::from_slice(data) {
+        let json = serde_json::to_string(&message).unwrap();
+        let actual = ProtocolMessage::from_slice(json.as_bytes())
+            .unwrap_or_else(|error| panic!("failed to parse {}: {}", json, error));
+        assert_eq!(actual, message, "{}", json);
+    }
+});
@@ -0,0 +1,44 @@
+/* C interface of the debug-adapter-protocol crate, built with the `ffi` feature. */
+
+#ifndef DEBUG_ADAPTER_PROTOCOL_H
+#define DEBUG_ADAPTER_PROTOCOL_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct DapMessage DapMessage;
+
+typedef enum DapMessageKind {
+    DAP_MESSAGE_KIND_REQUEST = 0,
+    DAP_MESSAGE_KIND_RESPONSE = 1,
+    DAP_MESSAGE_KIND_EVENT = 2,
+} DapMessageKind;
+
+const char *dap_last_error(void);
+
+DapMessage *dap_decode(const uint8_t *buffer, size_t len, size_t *consumed);
+void dap_message_free(DapMessage *message);
+
+uint64_t dap_message_seq(const DapMessage *message);
+DapMessageKind dap_message_kind(const DapMessage *message);
+const char *dap_message_name(const DapMessage *message);
+const char *dap_message_body(const DapMessage *message);
+
+DapMessage *dap_request_new(uint64_t seq, const char *command, const char *arguments);
+DapMessage *dap_event_new(uint64_t seq, const char *event, const char *body);
+DapMessage *dap_response_new(uint64_t seq, uint64_t request_seq, const char *command,
+                             bool success, const char *message, const char *body);
+
+uint8_t *dap_encode(const DapMessage *message, size_t *len);
+void dap_buffer_free(uint8_t *buffer, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
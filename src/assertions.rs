@@ -0,0 +1,51 @@
+//! Assertions that compare JSON by its meaning rather than its text.
+//!
+//! Two payloads are equal if they parse to the same value, regardless of the order of keys and of
+//! whitespace. This keeps tests from breaking when the formatting or field order of the serialized
+//! output changes, while still catching every change of the wire format. A failed assertion lists
+//! the JSON pointer of every differing property.
+
+use crate::utils::json_differences;
+use serde::Serialize;
+use serde_json::Value;
+
+fn parse(json: &str, name: &str) -> Value {
+    serde_json::from_str(json)
+        .unwrap_or_else(|error| panic!("{} is not JSON: {}\n{}", name, error, json))
+}
+
+#[track_caller]
+fn assert_value_eq(actual: &Value, expected: &Value) {
+    let mut differences = Vec::new();
+    json_differences(expected, actual, &[], &mut |path, expected, actual| {
+        let format = |value: Option<&Value>| value.map_or("nothing".to_string(), Value::to_string);
+        differences.push(format!(
+            "  {}: expected {} but got {}",
+            if path.is_empty() { "/" } else { path },
+            format(expected),
+            format(actual)
+        ));
+    });
+    if !differences.is_empty() {
+        panic!(
+            "JSON differs:\n{}\nactual:\n{}",
+            differences.join("\n"),
+            serde_json::to_string_pretty(actual).unwrap_or_default()
+        );
+    }
+}
+
+/// Asserts that the JSON payloads `actual` and `expected` are semantically equal, ignoring the
+/// order of keys and insignificant whitespace.
+#[track_caller]
+pub fn assert_json_eq(actual: &str, expected: &str) {
+    assert_value_eq(&parse(actual, "actual"), &parse(expected, "expected"));
+}
+
+/// Asserts that `value` serializes to JSON that is semantically equal to `expected`.
+#[track_caller]
+pub fn assert_serializes_to<T: Serialize + ?Sized>(value: &T, expected: &str) {
+    let actual = serde_json::to_value(value)
+        .unwrap_or_else(|error| panic!("failed to serialize: {}", error));
+    assert_value_eq(&actual, &parse(expected, "expected"));
+}
@@ -0,0 +1,19 @@
+//! Parallel decoding of recorded streams of messages, for tools that post-process large traces.
+
+use crate::{
+    error::{FrameError, ParseError},
+    framing::split_frames,
+    ProtocolMessage,
+};
+use rayon::prelude::*;
+
+/// Splits a recorded stream into messages and parses them in parallel on the rayon thread pool.
+///
+/// Only splitting the stream is sequential. The results are in the order of the stream, and a
+/// message that fails to parse does not prevent parsing the others.
+pub fn decode(stream: &[u8]) -> Result<Vec<Result<ProtocolMessage, ParseError>>, FrameError> {
+    Ok(split_frames(stream)?
+        .into_par_iter()
+        .map(ProtocolMessage::from_slice)
+        .collect())
+}
@@ -0,0 +1,208 @@
+//! A protocol level REPL for debug adapter developers.
+//!
+//! Connects to a debug adapter, performs the 'initialize' handshake and then sends the requests
+//! typed on stdin while printing all messages in the trace format of [TraceWriter]. Build with
+//! `--features cli`.
+//!
+//! ```text
+//! dap-cli [--adapter-id <id>] -- <program> [<args>...]
+//! dap-cli [--adapter-id <id>] --tcp <host>:<port>
+//! ```
+
+use debug_adapter_protocol::{
+    connection::Connection,
+    trace::{Direction, TraceWriter},
+    ProtocolMessage, ProtocolMessageContent, SequenceNumber,
+};
+use serde_json::{json, Value};
+use std::{
+    io::{self, BufRead, Write},
+    net::TcpStream,
+    process::{self, Child, Command, Stdio},
+    str::FromStr,
+    thread,
+};
+
+const USAGE: &str = "\
+usage: dap-cli [--adapter-id <id>] -- <program> [<args>...]
+       dap-cli [--adapter-id <id>] --tcp <host>:<port>";
+
+const HELP: &str = "\
+Enter a request as
+  <command> [<arguments as JSON>]   for example: stackTrace {\"threadId\": 1}
+  {\"command\": ..., ...}             a request without 'seq' and 'type'
+  help                              show this help
+  quit                              exit";
+
+enum Transport {
+    Stdio(Vec<String>),
+    Tcp(String),
+}
+
+struct Options {
+    adapter_id: String,
+    transport: Transport,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut adapter_id = "dap-cli".to_string();
+    let mut transport = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--adapter-id" => adapter_id = args.next().ok_or("--adapter-id needs a value")?,
+            "--tcp" => {
+                transport = Some(Transport::Tcp(args.next().ok_or("--tcp needs an address")?))
+            }
+            "--" => transport = Some(Transport::Stdio(args.by_ref().collect())),
+            "-h" | "--help" => return Err(String::new()),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    match transport {
+        Some(Transport::Stdio(command)) if command.is_empty() => Err("no program given".into()),
+        Some(transport) => Ok(Options {
+            adapter_id,
+            transport,
+        }),
+        None => Err("no debug adapter given".into()),
+    }
+}
+
+fn connect(transport: &Transport) -> io::Result<(Connection, Option<Child>)> {
+    match transport {
+        Transport::Stdio(command) => {
+            let mut child = Command::new(&command[0])
+                .args(&command[1..])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()?;
+            let stdin = child.stdin.take().expect("stdin is piped");
+            let stdout = child.stdout.take().expect("stdout is piped");
+            let (connection, _io_threads) = Connection::from_transport(stdout, stdin);
+            Ok((connection, Some(child)))
+        }
+        Transport::Tcp(address) => {
+            let stream = TcpStream::connect(address)?;
+            let (connection, _io_threads) = Connection::from_transport(stream.try_clone()?, stream);
+            Ok((connection, None))
+        }
+    }
+}
+
+/// Builds a request from a line typed by the user.
+fn parse_request(line: &str, seq: SequenceNumber) -> Result<ProtocolMessage, String> {
+    let mut request = if line.starts_with('{') {
+        serde_json::from_str::<Value>(line).map_err(|error| error.to_string())?
+    } else {
+        let (command, arguments) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let mut request = json!({ "command": command });
+        if !arguments.trim().is_empty() {
+            request["arguments"] =
+                serde_json::from_str(arguments).map_err(|error| error.to_string())?;
+        }
+        request
+    };
+    let object = request
+        .as_object_mut()
+        .ok_or("a request must be an object")?;
+    object.insert("seq".to_string(), seq.into());
+    object.insert("type".to_string(), "request".into());
+    ProtocolMessage::from_str(&request.to_string()).map_err(|error| error.to_string())
+}
+
+fn trace(direction: Direction, message: &ProtocolMessage) {
+    let mut writer = TraceWriter::new(io::stdout().lock());
+    let _ = writer
+        .write(direction, message)
+        .and_then(|()| writer.flush());
+}
+
+fn send(connection: &Connection, message: ProtocolMessage) -> Result<(), String> {
+    trace(Direction::ToAdapter, &message);
+    connection
+        .sender
+        .send(message)
+        .map_err(|_| "the debug adapter closed the connection".to_string())
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let (connection, mut child) = connect(&options.transport).map_err(|error| error.to_string())?;
+    let errors = connection.errors.clone();
+    thread::spawn(move || {
+        for error in errors {
+            eprintln!("{}", error);
+        }
+    });
+
+    let arguments = json!({
+        "clientID": "dap-cli",
+        "clientName": "dap-cli",
+        "adapterID": options.adapter_id,
+        "linesStartAt1": true,
+        "columnsStartAt1": true,
+        "pathFormat": "path",
+    });
+    let initialize = parse_request(&format!("initialize {}", arguments), 1)?;
+    send(&connection, initialize)?;
+    // Wait for the response, so the capabilities are printed before the prompt
+    for message in &connection.receiver {
+        trace(Direction::ToClient, &message);
+        if let ProtocolMessageContent::Response(response) = &message.content {
+            if response.request_seq == 1 {
+                break;
+            }
+        }
+    }
+
+    let receiver = connection.receiver.clone();
+    thread::spawn(move || {
+        for message in receiver {
+            trace(Direction::ToClient, &message);
+        }
+        eprintln!("the debug adapter closed the connection");
+        process::exit(0);
+    });
+
+    eprintln!("{}", HELP);
+    let mut seq = 2;
+    for line in io::stdin().lock().lines() {
+        let line = line.map_err(|error| error.to_string())?;
+        match line.trim() {
+            "" => {}
+            "help" => eprintln!("{}", HELP),
+            "quit" | "exit" => break,
+            line => match parse_request(line, seq) {
+                Ok(request) => {
+                    send(&connection, request)?;
+                    seq += 1;
+                }
+                Err(error) => eprintln!("invalid request: {}", error),
+            },
+        }
+        io::stdout().flush().map_err(|error| error.to_string())?;
+    }
+
+    drop(connection);
+    if let Some(child) = &mut child {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+    Ok(())
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            if !error.is_empty() {
+                eprintln!("dap-cli: {}", error);
+            }
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+    if let Err(error) = run(options) {
+        eprintln!("dap-cli: {}", error);
+        process::exit(1);
+    }
+}
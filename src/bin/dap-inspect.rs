@@ -0,0 +1,222 @@
+//! Validates and summarizes a recorded stream of framed messages, for triaging interop bug reports.
+//!
+//! Every message is parsed into the typed model and checked with [Validate]. With the
+//! `schema-validation` feature it can additionally be checked against the JSON schema of the
+//! specification. Build with `--features inspect`.
+//!
+//! ```text
+//! dap-inspect [--strict] [--schema <debugAdapterProtocol.json>] [--color <when>] [<file>]
+//! ```
+
+use debug_adapter_protocol::{
+    framing::split_frame, trace::summary, validate::Validate, ParseOptions, ProtocolMessage,
+};
+use std::{
+    env, fs,
+    io::{self, IsTerminal, Read},
+    process,
+};
+
+const USAGE: &str = "\
+usage: dap-inspect [--strict] [--schema <debugAdapterProtocol.json>] [--color auto|always|never]
+                   [<file>]
+
+Reads the stream from stdin if no file is given.";
+
+#[derive(Default)]
+struct Options {
+    strict: bool,
+    schema: Option<String>,
+    color: Option<bool>,
+    file: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--strict" => options.strict = true,
+            "--schema" => options.schema = Some(args.next().ok_or("--schema needs a file")?),
+            "--color" => {
+                options.color = match args.next().as_deref() {
+                    Some("auto") => None,
+                    Some("always") => Some(true),
+                    Some("never") => Some(false),
+                    _ => return Err("--color needs one of auto, always or never".into()),
+                }
+            }
+            "-h" | "--help" => return Err(String::new()),
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("unexpected argument '{}'", arg))
+            }
+            _ if options.file.is_none() => options.file = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// Checks a message against the schema of the specification, if one was given.
+struct SchemaCheck {
+    #[cfg(feature = "schema-validation")]
+    schema: Option<debug_adapter_protocol::schema::Schema>,
+}
+
+impl SchemaCheck {
+    fn load(path: Option<&str>) -> Result<SchemaCheck, String> {
+        #[cfg(feature = "schema-validation")]
+        {
+            let schema = path
+                .map(|path| {
+                    fs::read_to_string(path)
+                        .map_err(|error| format!("failed to read {}: {}", path, error))?
+                        .parse()
+                        .map_err(|error| format!("invalid schema {}: {}", path, error))
+                })
+                .transpose()?;
+            Ok(SchemaCheck { schema })
+        }
+        #[cfg(not(feature = "schema-validation"))]
+        match path {
+            Some(_) => Err("--schema needs the schema-validation feature".into()),
+            None => Ok(SchemaCheck {}),
+        }
+    }
+
+    fn violations(&self, _content: &[u8]) -> Vec<String> {
+        #[cfg(feature = "schema-validation")]
+        if let (Some(schema), Ok(json)) = (&self.schema, std::str::from_utf8(_content)) {
+            return match schema.validate_str(json) {
+                Ok(violations) => violations.iter().map(ToString::to_string).collect(),
+                Err(error) => vec![error.to_string()],
+            };
+        }
+        Vec::new()
+    }
+}
+
+struct Printer {
+    color: bool,
+}
+
+impl Printer {
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn ok(&self, index: usize, offset: usize, message: &ProtocolMessage) {
+        let status = self.paint("32", "ok   ");
+        println!("{} #{} @{}: {}", status, index, offset, summary(message));
+    }
+
+    fn error(&self, index: usize, offset: usize, error: &str) {
+        let status = self.paint("1;31", "error");
+        println!("{} #{} @{}: {}", status, index, offset, error);
+    }
+
+    fn violation(&self, kind: &str, violation: &str) {
+        println!("        {} {}", self.paint("33", kind), violation);
+    }
+}
+
+/// Inspects all messages of `stream` and returns the number of invalid messages.
+fn inspect(
+    stream: &[u8],
+    options: &ParseOptions,
+    schema: &SchemaCheck,
+    printer: &Printer,
+) -> usize {
+    let mut offset = 0;
+    let mut index = 1;
+    let mut invalid = 0;
+    while offset < stream.len() {
+        let (content, frame_len) = match split_frame(&stream[offset..]) {
+            Ok(Some(frame)) => frame,
+            Ok(None) => {
+                printer.error(index, offset, "the stream ends within a message");
+                return invalid + 1;
+            }
+            Err(error) => {
+                printer.error(index, offset, &format!("invalid header: {}", error));
+                return invalid + 1;
+            }
+        };
+        let schema_violations = schema.violations(content);
+        match ProtocolMessage::parse_slice_with(content, options) {
+            Ok(message) => {
+                let violations = message.validate();
+                if violations.is_empty() && schema_violations.is_empty() {
+                    printer.ok(index, offset, &message);
+                } else {
+                    invalid += 1;
+                    printer.error(index, offset, &summary(&message));
+                }
+                for violation in violations {
+                    printer.violation("invalid", &violation.to_string());
+                }
+            }
+            Err(error) => {
+                invalid += 1;
+                printer.error(index, offset, &error.to_string());
+            }
+        }
+        for violation in schema_violations {
+            printer.violation("schema ", &violation);
+        }
+        offset += frame_len;
+        index += 1;
+    }
+    invalid
+}
+
+fn run(options: Options) -> Result<usize, String> {
+    let stream = match options.file.as_deref() {
+        Some(file) if file != "-" => {
+            fs::read(file).map_err(|error| format!("failed to read {}: {}", file, error))?
+        }
+        _ => {
+            let mut stream = Vec::new();
+            io::stdin()
+                .read_to_end(&mut stream)
+                .map_err(|error| format!("failed to read stdin: {}", error))?;
+            stream
+        }
+    };
+    let schema = SchemaCheck::load(options.schema.as_deref())?;
+    let printer = Printer {
+        color: options
+            .color
+            .unwrap_or_else(|| io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none()),
+    };
+    let mut parse_options = ParseOptions::default();
+    parse_options.strict = options.strict;
+    Ok(inspect(&stream, &parse_options, &schema, &printer))
+}
+
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            if !error.is_empty() {
+                eprintln!("dap-inspect: {}", error);
+            }
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+    match run(options) {
+        Ok(0) => {}
+        Ok(invalid) => {
+            eprintln!("{} invalid messages", invalid);
+            process::exit(1);
+        }
+        Err(error) => {
+            eprintln!("dap-inspect: {}", error);
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! Borrowed variants of types that occur in large numbers, like the variables of a 'variables'
+//! response or the frames of a 'stackTrace' response.
+//!
+//! When deserialized from a `&str` or `&[u8]`, their strings borrow from the input unless they
+//! contain escape sequences, which saves an allocation per string. Use `into_owned` to convert
+//! them into the corresponding owned types.
+
+#[cfg(feature = "events")]
+use crate::events::{self, OutputCategory, OutputGroup};
+#[cfg(feature = "responses")]
+use crate::responses;
+#[cfg(all(feature = "events", any(feature = "ser", feature = "schemars")))]
+use crate::utils::eq_default;
+use crate::{
+    types::{self, ModuleId, Source, StackFramePresentationHint, VariablePresentationHint},
+    Str,
+};
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "ser")]
+use serde::Serialize;
+#[cfg(feature = "de")]
+use serde::{Deserialize, Deserializer};
+#[cfg(feature = "events")]
+use serde_json::Value;
+use std::borrow::Cow;
+#[cfg(feature = "ts-rs")]
+use ts_rs::TS;
+
+/// Borrowed variant of [events::OutputEventBody].
+#[cfg(feature = "events")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
+pub struct OutputEventBody<'a> {
+    #[serde(rename = "category", default, skip_serializing_if = "eq_default")]
+    pub category: OutputCategory,
+
+    #[serde(rename = "output", borrow)]
+    pub output: Cow<'a, str>,
+
+    #[serde(rename = "group", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub group: Option<OutputGroup>,
+
+    #[serde(rename = "variablesReference", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub variables_reference: Option<i32>,
+
+    #[serde(rename = "source", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub source: Option<Source>,
+
+    #[serde(rename = "line", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub line: Option<i32>,
+
+    #[serde(rename = "column", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub column: Option<i32>,
+
+    #[serde(rename = "data", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub data: Option<Value>,
+
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "locationReference", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub location_reference: Option<i32>,
+}
+#[cfg(feature = "events")]
+impl OutputEventBody<'_> {
+    pub fn into_owned(self) -> events::OutputEventBody {
+        events::OutputEventBody {
+            category: self.category,
+            output: self.output.into(),
+            group: self.group,
+            variables_reference: self.variables_reference,
+            source: self.source,
+            line: self.line,
+            column: self.column,
+            data: self.data,
+            #[cfg(feature = "spec-1_70")]
+            location_reference: self.location_reference,
+        }
+    }
+}
+
+/// Borrowed variant of [types::StackFrame].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
+pub struct StackFrame<'a> {
+    #[serde(rename = "id")]
+    pub id: i32,
+
+    #[serde(rename = "name", borrow)]
+    pub name: Cow<'a, str>,
+
+    #[serde(rename = "source", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub source: Option<Source>,
+
+    #[serde(rename = "line", default)]
+    pub line: i32,
+
+    #[serde(rename = "column", default)]
+    pub column: i32,
+
+    #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub end_line: Option<i32>,
+
+    #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub end_column: Option<i32>,
+
+    #[serde(rename = "canRestart", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub can_restart: Option<bool>,
+
+    #[serde(
+        rename = "instructionPointerReference",
+        borrow,
+        default,
+        deserialize_with = "deserialize_option_cow",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub instruction_pointer_reference: Option<Cow<'a, str>>,
+
+    #[serde(rename = "moduleId", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub module_id: Option<ModuleId>,
+
+    #[serde(rename = "presentationHint", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub presentation_hint: Option<StackFramePresentationHint>,
+}
+impl StackFrame<'_> {
+    pub fn into_owned(self) -> types::StackFrame {
+        types::StackFrame {
+            id: self.id,
+            name: self.name.into(),
+            source: self.source,
+            line: self.line,
+            column: self.column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+            can_restart: self.can_restart,
+            instruction_pointer_reference: self.instruction_pointer_reference.map(Str::from),
+            module_id: self.module_id,
+            presentation_hint: self.presentation_hint,
+        }
+    }
+}
+
+/// Borrowed variant of [types::Variable].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
+pub struct Variable<'a> {
+    #[serde(rename = "name", borrow)]
+    pub name: Cow<'a, str>,
+
+    #[serde(rename = "value", borrow)]
+    pub value: Cow<'a, str>,
+
+    #[serde(
+        rename = "type",
+        borrow,
+        default,
+        deserialize_with = "deserialize_option_cow",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub type_: Option<Cow<'a, str>>,
+
+    #[serde(rename = "presentationHint", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub presentation_hint: Option<VariablePresentationHint>,
+
+    #[serde(
+        rename = "evaluateName",
+        borrow,
+        default,
+        deserialize_with = "deserialize_option_cow",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub evaluate_name: Option<Cow<'a, str>>,
+
+    #[serde(rename = "variablesReference")]
+    pub variables_reference: i32,
+
+    #[serde(rename = "namedVariables", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub named_variables: Option<i32>,
+
+    #[serde(rename = "indexedVariables", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub indexed_variables: Option<i32>,
+
+    #[serde(
+        rename = "memoryReference",
+        borrow,
+        default,
+        deserialize_with = "deserialize_option_cow",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub memory_reference: Option<Cow<'a, str>>,
+
+    #[cfg(feature = "spec-1_70")]
+    #[serde(
+        rename = "declarationLocationReference",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub declaration_location_reference: Option<i32>,
+
+    #[cfg(feature = "spec-1_70")]
+    #[serde(
+        rename = "valueLocationReference",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub value_location_reference: Option<i32>,
+}
+impl Variable<'_> {
+    pub fn into_owned(self) -> types::Variable {
+        types::Variable {
+            name: self.name.into(),
+            value: self.value.into(),
+            type_: self.type_.map(Str::from),
+            presentation_hint: self.presentation_hint,
+            evaluate_name: self.evaluate_name.map(Str::from),
+            variables_reference: self.variables_reference,
+            named_variables: self.named_variables,
+            indexed_variables: self.indexed_variables,
+            memory_reference: self.memory_reference.map(Str::from),
+            #[cfg(feature = "spec-1_70")]
+            declaration_location_reference: self.declaration_location_reference,
+            #[cfg(feature = "spec-1_70")]
+            value_location_reference: self.value_location_reference,
+        }
+    }
+}
+
+/// Borrowed variant of [responses::StackTraceResponseBody].
+#[cfg(feature = "responses")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
+pub struct StackTraceResponseBody<'a> {
+    #[serde(rename = "stackFrames", borrow)]
+    pub stack_frames: Vec<StackFrame<'a>>,
+
+    #[serde(rename = "totalFrames", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub total_frames: Option<i32>,
+}
+#[cfg(feature = "responses")]
+impl StackTraceResponseBody<'_> {
+    pub fn into_owned(self) -> responses::StackTraceResponseBody {
+        responses::StackTraceResponseBody {
+            stack_frames: self
+                .stack_frames
+                .into_iter()
+                .map(StackFrame::into_owned)
+                .collect(),
+            total_frames: self.total_frames,
+        }
+    }
+}
+
+/// Borrowed variant of [responses::VariablesResponseBody].
+#[cfg(feature = "responses")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
+pub struct VariablesResponseBody<'a> {
+    #[serde(rename = "variables", borrow)]
+    pub variables: Vec<Variable<'a>>,
+}
+#[cfg(feature = "responses")]
+impl VariablesResponseBody<'_> {
+    pub fn into_owned(self) -> responses::VariablesResponseBody {
+        responses::VariablesResponseBody {
+            variables: self
+                .variables
+                .into_iter()
+                .map(Variable::into_owned)
+                .collect(),
+        }
+    }
+}
+
+/// serde only borrows a `Cow<str>` if it is not nested in another type like `Option`.
+#[cfg(feature = "de")]
+pub(crate) fn deserialize_option_cow<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> Result<Option<Cow<'a, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Borrowed<'a>(#[serde(borrow)] Cow<'a, str>);
+
+    Ok(Option::<Borrowed>::deserialize(deserializer)?.map(|borrowed| borrowed.0))
+}
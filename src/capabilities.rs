@@ -0,0 +1,475 @@
+//! A compact representation of the boolean [Capabilities] of a debug adapter.
+
+use crate::types::Capabilities;
+#[cfg(feature = "spec-1_70")]
+use crate::types::{BreakpointMode, BreakpointModeApplicability};
+#[cfg(feature = "de")]
+use serde::{
+    de::{self, DeserializeSeed, IgnoredAny, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+#[cfg(feature = "ser")]
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use std::{
+    fmt::{self, Debug},
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Sub, SubAssign},
+};
+
+/// A set of the boolean [Capabilities] stored in a single integer.
+///
+/// [Capabilities] stores its boolean capabilities as flags, which are read with
+/// [Capabilities::flags] or the `supports_*` methods and written with [Capabilities::set_flags]
+/// or [Capabilities::set_supports]. In JSON every set flag is an attribute with the value `true`,
+/// like the boolean attributes of the 'Capabilities' type of the specification. Merging or
+/// comparing the capabilities of debug adapters is a single bit operation on the flags, for
+/// example `new.difference(old)` yields the capabilities that were added by a 'capabilities'
+/// event.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct CapabilityFlags(u64);
+
+macro_rules! capability_flags {
+    ($(
+        $(#[doc = $doc:literal])*
+        $(#[cfg($cfg:meta)])?
+        $constant:ident = $bit:literal, $field:ident, $name:literal;
+    )*) => {
+        impl CapabilityFlags {
+            $(
+                $(#[doc = $doc])*
+                $(#[cfg($cfg)])?
+                pub const $constant: CapabilityFlags = CapabilityFlags(1 << $bit);
+            )*
+
+            const ALL: &'static [(CapabilityFlags, &'static str)] = &[
+                $($(#[cfg($cfg)])? (CapabilityFlags::$constant, $name),)*
+            ];
+
+            #[cfg(feature = "de")]
+            const NAMES: &'static [&'static str] = &[$($(#[cfg($cfg)])? $name,)*];
+
+            #[cfg(any(feature = "schemars", feature = "ts-rs"))]
+            const DOCS: &'static [&'static [&'static str]] = &[$($(#[cfg($cfg)])? &[$($doc),*],)*];
+        }
+
+        impl Capabilities {
+            $(
+                $(#[doc = $doc])*
+                $(#[cfg($cfg)])?
+                pub fn $field(&self) -> bool {
+                    self.flags.contains(CapabilityFlags::$constant)
+                }
+            )*
+        }
+    };
+}
+
+capability_flags! {
+    /// The debug adapter supports the 'configurationDone' request.
+    SUPPORTS_CONFIGURATION_DONE_REQUEST = 0, supports_configuration_done_request, "supportsConfigurationDoneRequest";
+    /// The debug adapter supports function breakpoints.
+    SUPPORTS_FUNCTION_BREAKPOINTS = 1, supports_function_breakpoints, "supportsFunctionBreakpoints";
+    /// The debug adapter supports conditional breakpoints.
+    SUPPORTS_CONDITIONAL_BREAKPOINTS = 2, supports_conditional_breakpoints, "supportsConditionalBreakpoints";
+    /// The debug adapter supports breakpoints that break execution after a specified number of hits.
+    SUPPORTS_HIT_CONDITIONAL_BREAKPOINTS = 3, supports_hit_conditional_breakpoints, "supportsHitConditionalBreakpoints";
+    /// The debug adapter supports a (side effect free) evaluate request for data hovers.
+    SUPPORTS_EVALUATE_FOR_HOVERS = 4, supports_evaluate_for_hovers, "supportsEvaluateForHovers";
+    /// The debug adapter supports stepping back via the 'stepBack' and 'reverseContinue' requests.
+    SUPPORTS_STEP_BACK = 5, supports_step_back, "supportsStepBack";
+    /// The debug adapter supports setting a variable to a value.
+    SUPPORTS_SET_VARIABLE = 6, supports_set_variable, "supportsSetVariable";
+    /// The debug adapter supports restarting a frame.
+    SUPPORTS_RESTART_FRAME = 7, supports_restart_frame, "supportsRestartFrame";
+    /// The debug adapter supports the 'gotoTargets' request.
+    SUPPORTS_GOTO_TARGETS_REQUEST = 8, supports_goto_targets_request, "supportsGotoTargetsRequest";
+    /// The debug adapter supports the 'stepInTargets' request.
+    SUPPORTS_STEP_IN_TARGETS_REQUEST = 9, supports_step_in_targets_request, "supportsStepInTargetsRequest";
+    /// The debug adapter supports the 'completions' request.
+    SUPPORTS_COMPLETIONS_REQUEST = 10, supports_completions_request, "supportsCompletionsRequest";
+    /// The debug adapter supports the 'modules' request.
+    SUPPORTS_MODULES_REQUEST = 11, supports_modules_request, "supportsModulesRequest";
+    /// The debug adapter supports the 'restart' request. In this case a client should not implement 'restart' by terminating and relaunching the adapter but by calling the RestartRequest.
+    SUPPORTS_RESTART_REQUEST = 12, supports_restart_request, "supportsRestartRequest";
+    /// The debug adapter supports 'exceptionOptions' on the setExceptionBreakpoints request.
+    SUPPORTS_EXCEPTION_OPTIONS = 13, supports_exception_options, "supportsExceptionOptions";
+    /// The debug adapter supports a 'format' attribute on the stackTraceRequest, variablesRequest, and evaluateRequest.
+    SUPPORTS_VALUE_FORMATTING_OPTIONS = 14, supports_value_formatting_options, "supportsValueFormattingOptions";
+    /// The debug adapter supports the 'exceptionInfo' request.
+    SUPPORTS_EXCEPTION_INFO_REQUEST = 15, supports_exception_info_request, "supportsExceptionInfoRequest";
+    /// The debug adapter supports the 'terminateDebuggee' attribute on the 'disconnect' request.
+    SUPPORT_TERMINATE_DEBUGGEE = 16, support_terminate_debuggee, "supportTerminateDebuggee";
+    /// The debug adapter supports the 'suspendDebuggee' attribute on the 'disconnect' request.
+    SUPPORT_SUSPEND_DEBUGGEE = 17, support_suspend_debuggee, "supportSuspendDebuggee";
+    /// The debug adapter supports the delayed loading of parts of the stack, which requires that both the 'startFrame' and 'levels' arguments and an optional 'totalFrames' result of the 'StackTrace' request are supported.
+    SUPPORTS_DELAYED_STACK_TRACE_LOADING = 18, supports_delayed_stack_trace_loading, "supportsDelayedStackTraceLoading";
+    /// The debug adapter supports the 'loadedSources' request.
+    SUPPORTS_LOADED_SOURCES_REQUEST = 19, supports_loaded_sources_request, "supportsLoadedSourcesRequest";
+    /// The debug adapter supports logpoints by interpreting the 'logMessage' attribute of the SourceBreakpoint.
+    SUPPORTS_LOG_POINTS = 20, supports_log_points, "supportsLogPoints";
+    /// The debug adapter supports the 'terminateThreads' request.
+    SUPPORTS_TERMINATE_THREADS_REQUEST = 21, supports_terminate_threads_request, "supportsTerminateThreadsRequest";
+    /// The debug adapter supports the 'setExpression' request.
+    SUPPORTS_SET_EXPRESSION = 22, supports_set_expression, "supportsSetExpression";
+    /// The debug adapter supports the 'terminate' request.
+    SUPPORTS_TERMINATE_REQUEST = 23, supports_terminate_request, "supportsTerminateRequest";
+    /// The debug adapter supports data breakpoints.
+    SUPPORTS_DATA_BREAKPOINTS = 24, supports_data_breakpoints, "supportsDataBreakpoints";
+    /// The debug adapter supports the 'readMemory' request.
+    SUPPORTS_READ_MEMORY_REQUEST = 25, supports_read_memory_request, "supportsReadMemoryRequest";
+    /// The debug adapter supports the 'disassemble' request.
+    SUPPORTS_DISASSEMBLE_REQUEST = 26, supports_disassemble_request, "supportsDisassembleRequest";
+    /// The debug adapter supports the 'cancel' request.
+    SUPPORTS_CANCEL_REQUEST = 27, supports_cancel_request, "supportsCancelRequest";
+    /// The debug adapter supports the 'breakpointLocations' request.
+    SUPPORTS_BREAKPOINT_LOCATIONS_REQUEST = 28, supports_breakpoint_locations_request, "supportsBreakpointLocationsRequest";
+    /// The debug adapter supports the 'clipboard' context value in the 'evaluate' request.
+    SUPPORTS_CLIPBOARD_CONTEXT = 29, supports_clipboard_context, "supportsClipboardContext";
+    /// The debug adapter supports stepping granularities (argument 'granularity') for the stepping requests.
+    SUPPORTS_STEPPING_GRANULARITY = 30, supports_stepping_granularity, "supportsSteppingGranularity";
+    /// The debug adapter supports adding breakpoints based on instruction references.
+    SUPPORTS_INSTRUCTION_BREAKPOINTS = 31, supports_instruction_breakpoints, "supportsInstructionBreakpoints";
+    /// The debug adapter supports 'filterOptions' as an argument on the 'setExceptionBreakpoints' request.
+    SUPPORTS_EXCEPTION_FILTER_OPTIONS = 32, supports_exception_filter_options, "supportsExceptionFilterOptions";
+    /// The debug adapter supports the 'singleThread' property on the execution requests ('continue', 'next', 'stepIn', 'stepOut', 'reverseContinue', 'stepBack').
+    #[cfg(feature = "spec-1_70")]
+    SUPPORTS_SINGLE_THREAD_EXECUTION_REQUESTS = 33, supports_single_thread_execution_requests, "supportsSingleThreadExecutionRequests";
+    /// The debug adapter supports the 'asAddress' and 'bytes' fields in the 'dataBreakpointInfo' request.
+    #[cfg(feature = "spec-1_70")]
+    SUPPORTS_DATA_BREAKPOINT_BYTES = 34, supports_data_breakpoint_bytes, "supportsDataBreakpointBytes";
+}
+
+impl CapabilityFlags {
+    pub const fn empty() -> CapabilityFlags {
+        CapabilityFlags(0)
+    }
+
+    pub fn all() -> CapabilityFlags {
+        CapabilityFlags::ALL
+            .iter()
+            .fold(CapabilityFlags::empty(), |all, (flag, _)| all | *flag)
+    }
+
+    /// The raw bits of the flags. The bit assigned to a capability may change between versions.
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if all flags in `other` are set.
+    pub const fn contains(self, other: CapabilityFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn union(self, other: CapabilityFlags) -> CapabilityFlags {
+        CapabilityFlags(self.0 | other.0)
+    }
+
+    pub const fn intersection(self, other: CapabilityFlags) -> CapabilityFlags {
+        CapabilityFlags(self.0 & other.0)
+    }
+
+    /// Returns the flags that are set in `self` but not in `other`.
+    pub const fn difference(self, other: CapabilityFlags) -> CapabilityFlags {
+        CapabilityFlags(self.0 & !other.0)
+    }
+
+    pub fn insert(&mut self, other: CapabilityFlags) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: CapabilityFlags) {
+        self.0 &= !other.0;
+    }
+
+    /// Inserts or removes `other` depending on `value`.
+    pub fn set(&mut self, other: CapabilityFlags, value: bool) {
+        if value {
+            self.insert(other)
+        } else {
+            self.remove(other)
+        }
+    }
+
+    /// Returns the set flags one by one, each with its name as it appears in the JSON of
+    /// [Capabilities].
+    pub fn iter(self) -> impl Iterator<Item = (CapabilityFlags, &'static str)> {
+        CapabilityFlags::ALL
+            .iter()
+            .copied()
+            .filter(move |(flag, _)| self.contains(*flag))
+    }
+
+    /// Returns the names of the set flags as they appear in the JSON of [Capabilities].
+    pub fn names(self) -> impl Iterator<Item = &'static str> {
+        self.iter().map(|(_, name)| name)
+    }
+
+    /// Returns the flag with the name `name` as it appears in the JSON of [Capabilities].
+    pub fn from_name(name: &str) -> Option<CapabilityFlags> {
+        CapabilityFlags::ALL
+            .iter()
+            .find(|(_, flag_name)| *flag_name == name)
+            .map(|(flag, _)| *flag)
+    }
+}
+
+impl Debug for CapabilityFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.names()).finish()
+    }
+}
+
+impl Capabilities {
+    /// Returns the boolean capabilities of the debug adapter as [CapabilityFlags].
+    pub fn flags(&self) -> CapabilityFlags {
+        self.flags
+    }
+
+    /// Sets all boolean capabilities of the debug adapter to the values in `flags`.
+    pub fn set_flags(&mut self, flags: CapabilityFlags) {
+        self.flags = flags;
+    }
+
+    /// Returns whether the debug adapter advertises all capabilities in `flags`.
+    pub fn supports(&self, flags: CapabilityFlags) -> bool {
+        self.flags.contains(flags)
+    }
+
+    /// Advertises the capabilities in `flags`, or removes them if `supported` is false.
+    pub fn set_supports(&mut self, flags: CapabilityFlags, supported: bool) {
+        self.flags.set(flags, supported)
+    }
+
+    /// Returns whether the debug adapter advertises the implementation specific capability `name`,
+    /// for example `supportsFooRequest`, with the value `true`.
+    pub fn supports_custom(&self, name: &str) -> bool {
+        self.additional_attributes.get(name) == Some(&Value::Bool(true))
+    }
+
+    /// Advertises the implementation specific capability `name`, or removes it if `supported` is
+    /// false.
+    pub fn set_supports_custom(&mut self, name: impl Into<String>, supported: bool) {
+        let name = name.into();
+        if supported {
+            self.additional_attributes.insert(name, Value::Bool(true));
+        } else {
+            self.additional_attributes.remove(&name);
+        }
+    }
+
+    /// The breakpoint modes of the debug adapter that apply to breakpoints of the type
+    /// `applicability`, the default mode first.
+    #[cfg(feature = "spec-1_70")]
+    pub fn breakpoint_modes_for(
+        &self,
+        applicability: BreakpointModeApplicability,
+    ) -> impl Iterator<Item = &BreakpointMode> {
+        self.breakpoint_modes
+            .iter()
+            .filter(move |mode| mode.applies_to.contains(&applicability))
+    }
+}
+
+impl From<&Capabilities> for CapabilityFlags {
+    fn from(capabilities: &Capabilities) -> Self {
+        capabilities.flags()
+    }
+}
+
+impl BitOr for CapabilityFlags {
+    type Output = CapabilityFlags;
+
+    fn bitor(self, other: CapabilityFlags) -> CapabilityFlags {
+        self.union(other)
+    }
+}
+
+impl BitOrAssign for CapabilityFlags {
+    fn bitor_assign(&mut self, other: CapabilityFlags) {
+        self.insert(other)
+    }
+}
+
+impl BitAnd for CapabilityFlags {
+    type Output = CapabilityFlags;
+
+    fn bitand(self, other: CapabilityFlags) -> CapabilityFlags {
+        self.intersection(other)
+    }
+}
+
+impl BitAndAssign for CapabilityFlags {
+    fn bitand_assign(&mut self, other: CapabilityFlags) {
+        *self = self.intersection(other)
+    }
+}
+
+impl Sub for CapabilityFlags {
+    type Output = CapabilityFlags;
+
+    fn sub(self, other: CapabilityFlags) -> CapabilityFlags {
+        self.difference(other)
+    }
+}
+
+impl SubAssign for CapabilityFlags {
+    fn sub_assign(&mut self, other: CapabilityFlags) {
+        self.remove(other)
+    }
+}
+
+impl Not for CapabilityFlags {
+    type Output = CapabilityFlags;
+
+    fn not(self) -> CapabilityFlags {
+        CapabilityFlags::all().difference(self)
+    }
+}
+
+/// Serializes the set flags as attributes with the value `true`, so the flags that are not set are
+/// omitted like the boolean attributes of [Capabilities] that are `false`.
+#[cfg(feature = "ser")]
+impl Serialize for CapabilityFlags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.names().map(|name| (name, true)))
+    }
+}
+
+/// Deserializes the flags from boolean attributes and ignores all other attributes. Deserialized as
+/// a struct, so that flattening the flags into [Capabilities] only takes the attributes of flags and
+/// leaves the others to [Capabilities::additional_attributes].
+#[cfg(feature = "de")]
+impl<'de> Deserialize<'de> for CapabilityFlags {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CapabilityFlagsVisitor;
+
+        impl<'de> Visitor<'de> for CapabilityFlagsVisitor {
+            type Value = CapabilityFlags;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("boolean capabilities")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut flags = CapabilityFlags::empty();
+                let mut seen = CapabilityFlags::empty();
+                while let Some(flag) = map.next_key_seed(FlagName)? {
+                    match flag {
+                        Some(flag) if seen.contains(flag) => {
+                            let name = flag.names().next().unwrap_or_default();
+                            return Err(de::Error::duplicate_field(name));
+                        }
+                        Some(flag) => {
+                            seen.insert(flag);
+                            flags.set(flag, map.next_value()?);
+                        }
+                        None => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(flags)
+            }
+        }
+
+        deserializer.deserialize_struct(
+            "CapabilityFlags",
+            CapabilityFlags::NAMES,
+            CapabilityFlagsVisitor,
+        )
+    }
+}
+
+/// Deserializes an attribute name to its flag, if there is one.
+#[cfg(feature = "de")]
+struct FlagName;
+
+#[cfg(feature = "de")]
+impl<'de> DeserializeSeed<'de> for FlagName {
+    type Value = Option<CapabilityFlags>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+#[cfg(feature = "de")]
+impl<'de> Visitor<'de> for FlagName {
+    type Value = Option<CapabilityFlags>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a capability")
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<Self::Value, E> {
+        Ok(CapabilityFlags::from_name(name))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for CapabilityFlags {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(CapabilityFlags(u.arbitrary()?) & CapabilityFlags::all())
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u64::size_hint(depth)
+    }
+}
+
+/// Describes every flag as an optional boolean property.
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for CapabilityFlags {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "CapabilityFlags".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let properties = CapabilityFlags::ALL
+            .iter()
+            .zip(CapabilityFlags::DOCS)
+            .map(|((_, name), docs)| {
+                let description = docs.concat().trim().to_string();
+                let schema = serde_json::json!({
+                    "description": description,
+                    "type": "boolean",
+                    "default": false,
+                });
+                (name.to_string(), schema)
+            })
+            .collect::<serde_json::Map<_, _>>();
+        schemars::json_schema!({ "type": "object", "properties": properties })
+    }
+}
+
+/// Exports every flag as an optional boolean property.
+#[cfg(feature = "ts-rs")]
+impl ts_rs::TS for CapabilityFlags {
+    type WithoutGenerics = Self;
+    type OptionInnerType = Self;
+
+    fn name(_: &ts_rs::Config) -> String {
+        "CapabilityFlags".to_string()
+    }
+
+    fn inline(config: &ts_rs::Config) -> String {
+        Self::inline_flattened(config)
+    }
+
+    fn inline_flattened(_: &ts_rs::Config) -> String {
+        let properties = CapabilityFlags::ALL
+            .iter()
+            .zip(CapabilityFlags::DOCS)
+            .map(|((_, name), docs)| format!("{}{}?: boolean, ", ts_rs::format_docs(docs), name))
+            .collect::<String>();
+        format!("{{ {}}}", properties)
+    }
+}
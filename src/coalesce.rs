@@ -0,0 +1,161 @@
+//! Coalescing of 'output' events, for debug adapters that forward the output of the debuggee as it
+//! arrives, which can be one event per byte.
+//!
+//! An [OutputCoalescer] appends the output of consecutive events of the same category to a pending
+//! event until the time window since the first of them elapsed or the output reaches the maximum
+//! length. Only events that consist of nothing but a category and output are coalesced; others,
+//! like events with a source location or a group, are passed through in order.
+
+use crate::{
+    events::{OutputCategory, OutputEventBody},
+    sources::{InstantClock, MonotonicClock},
+    Str,
+};
+use std::time::{Duration, Instant};
+
+/// Batches rapid small 'output' events into fewer ones and splits oversized ones. See the
+/// [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct OutputCoalescer<C = InstantClock> {
+    window: Duration,
+    max_len: usize,
+    clock: C,
+    pending: Option<Pending>,
+}
+
+#[derive(Clone, Debug)]
+struct Pending {
+    category: OutputCategory,
+    output: String,
+    since: Instant,
+}
+
+impl OutputCoalescer {
+    /// Creates a coalescer that batches output for at most `window` and emits at most `max_len`
+    /// bytes of output per event.
+    pub fn new(window: Duration, max_len: usize) -> OutputCoalescer {
+        OutputCoalescer::with_clock(window, max_len, InstantClock)
+    }
+}
+
+impl<C: MonotonicClock> OutputCoalescer<C> {
+    /// Like [OutputCoalescer::new], but tells the time with `clock`.
+    pub fn with_clock(window: Duration, max_len: usize, clock: C) -> OutputCoalescer<C> {
+        OutputCoalescer {
+            window,
+            max_len: max_len.max(1),
+            clock,
+            pending: None,
+        }
+    }
+
+    /// Adds the output of `body` and returns the events that are ready to be sent, in order.
+    pub fn push(&mut self, body: OutputEventBody) -> Vec<OutputEventBody> {
+        let mut ready = Vec::new();
+        if !is_plain(&body) {
+            ready.extend(self.flush());
+            ready.push(body);
+            return ready;
+        }
+        let now = self.clock.now();
+        let expired = self.pending.as_ref().is_some_and(|pending| {
+            pending.category != body.category || self.is_expired(pending, now)
+        });
+        if expired {
+            ready.extend(self.flush());
+        }
+        let pending = self.pending.get_or_insert_with(|| Pending {
+            category: body.category.clone(),
+            output: String::new(),
+            since: now,
+        });
+        pending.output.push_str(&body.output);
+        while pending.output.len() >= self.max_len {
+            let rest = pending
+                .output
+                .split_off(split_index(&pending.output, self.max_len));
+            let chunk = std::mem::replace(&mut pending.output, rest);
+            ready.push(output_event(pending.category.clone(), chunk));
+        }
+        if pending.output.is_empty() {
+            self.pending = None;
+        }
+        ready
+    }
+
+    /// Returns the pending event if its time window elapsed. Call this periodically, for example
+    /// at the [deadline](OutputCoalescer::deadline).
+    pub fn poll(&mut self) -> Option<OutputEventBody> {
+        let now = self.clock.now();
+        if self
+            .pending
+            .as_ref()
+            .is_some_and(|pending| self.is_expired(pending, now))
+        {
+            self.flush()
+        } else {
+            None
+        }
+    }
+
+    /// When the time window of the pending event elapses, or `None` if there is no pending event.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.pending
+            .as_ref()
+            .map(|pending| pending.since + self.window)
+    }
+
+    /// Returns the pending event regardless of its time window, for example before the debug
+    /// adapter sends a 'stopped' or 'terminated' event, which should not overtake the output.
+    pub fn flush(&mut self) -> Option<OutputEventBody> {
+        let pending = self.pending.take()?;
+        Some(output_event(pending.category, pending.output))
+    }
+
+    fn is_expired(&self, pending: &Pending, now: Instant) -> bool {
+        now.saturating_duration_since(pending.since) >= self.window
+    }
+}
+
+/// Whether `body` has nothing but a category and output, so it can be merged with other events.
+fn is_plain(body: &OutputEventBody) -> bool {
+    #[cfg(feature = "spec-1_70")]
+    if body.location_reference.is_some() {
+        return false;
+    }
+    body.category != OutputCategory::Telemetry
+        && body.group.is_none()
+        && body.variables_reference.is_none()
+        && body.source.is_none()
+        && body.line.is_none()
+        && body.column.is_none()
+        && body.data.is_none()
+}
+
+fn output_event(category: OutputCategory, output: String) -> OutputEventBody {
+    OutputEventBody {
+        category,
+        output: Str::from(output),
+        group: None,
+        variables_reference: None,
+        source: None,
+        line: None,
+        column: None,
+        data: None,
+        #[cfg(feature = "spec-1_70")]
+        location_reference: None,
+    }
+}
+
+/// The largest char boundary of `output` that is at most `max_len`, but at least the end of the
+/// first char, so every chunk makes progress.
+fn split_index(output: &str, max_len: usize) -> usize {
+    let mut index = max_len.min(output.len());
+    while !output.is_char_boundary(index) {
+        index -= 1;
+    }
+    if index == 0 {
+        index = output.chars().next().map_or(0, char::len_utf8);
+    }
+    index
+}
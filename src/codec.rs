@@ -0,0 +1,110 @@
+//! Encodings of the content of a message.
+//!
+//! The base protocol frames every message with a Content-Length header, so the content does not
+//! have to be JSON. Peers that agree on a more compact encoding, for example over a slow serial
+//! link, can exchange the same messages as MessagePack (feature `msgpack`) or CBOR (feature
+//! `cbor`). [Json] is the encoding of the specification and the one to use by default.
+
+use crate::{error::ParseError, framing::write_framed, ProtocolMessage};
+use std::{
+    error::Error,
+    io::{self, Write},
+};
+
+/// An encoding of the content of a message.
+pub trait ContentCodec {
+    type EncodeError: Error + Send + Sync + 'static;
+    type DecodeError: Error + Send + Sync + 'static;
+
+    /// Appends the encoded `message` to `buffer`.
+    fn encode(
+        &self,
+        message: &ProtocolMessage,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), Self::EncodeError>;
+
+    /// Decodes the content of a message, for example a frame split off with
+    /// [split_frame](crate::framing::split_frame).
+    fn decode(&self, content: &[u8]) -> Result<ProtocolMessage, Self::DecodeError>;
+
+    /// Writes the encoded `message` and its Content-Length header to `writer`.
+    fn write_to<W: Write>(&self, message: &ProtocolMessage, mut writer: W) -> io::Result<()> {
+        let mut content = Vec::new();
+        self.encode(message, &mut content)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        write!(writer, "Content-Length: {}\r\n\r\n", content.len())?;
+        writer.write_all(&content)
+    }
+}
+
+/// The JSON encoding of the specification.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Json;
+
+impl ContentCodec for Json {
+    type EncodeError = serde_json::Error;
+    type DecodeError = ParseError;
+
+    fn encode(
+        &self,
+        message: &ProtocolMessage,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), serde_json::Error> {
+        serde_json::to_writer(buffer, message)
+    }
+
+    fn decode(&self, content: &[u8]) -> Result<ProtocolMessage, ParseError> {
+        ProtocolMessage::from_slice(content)
+    }
+
+    fn write_to<W: Write>(&self, message: &ProtocolMessage, writer: W) -> io::Result<()> {
+        write_framed(writer, message)
+    }
+}
+
+/// MessagePack with the same field names as the JSON encoding.
+#[cfg(feature = "msgpack")]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MessagePack;
+
+#[cfg(feature = "msgpack")]
+impl ContentCodec for MessagePack {
+    type EncodeError = rmp_serde::encode::Error;
+    type DecodeError = rmp_serde::decode::Error;
+
+    fn encode(
+        &self,
+        message: &ProtocolMessage,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), rmp_serde::encode::Error> {
+        // Structs are encoded as maps, because the messages have many optional fields
+        rmp_serde::encode::write_named(buffer, message)
+    }
+
+    fn decode(&self, content: &[u8]) -> Result<ProtocolMessage, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(content)
+    }
+}
+
+/// CBOR with the same field names as the JSON encoding.
+#[cfg(feature = "cbor")]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Cbor;
+
+#[cfg(feature = "cbor")]
+impl ContentCodec for Cbor {
+    type EncodeError = ciborium::ser::Error<io::Error>;
+    type DecodeError = ciborium::de::Error<io::Error>;
+
+    fn encode(
+        &self,
+        message: &ProtocolMessage,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), ciborium::ser::Error<io::Error>> {
+        ciborium::into_writer(message, buffer)
+    }
+
+    fn decode(&self, content: &[u8]) -> Result<ProtocolMessage, ciborium::de::Error<io::Error>> {
+        ciborium::from_reader(content)
+    }
+}
@@ -0,0 +1,453 @@
+//! Conversions between the types of this crate and the types of other crates for the Debug Adapter
+//! Protocol, so projects can migrate step by step or bridge libraries that use another crate.
+//!
+//! With the `dap-types` feature the types of the `dap-types` crate convert to and from the
+//! corresponding types of this crate, and with the `debugserver-types` feature the types of the
+//! `debugserver-types` crate. The messages of `debugserver-types`, for example `StoppedEvent`,
+//! convert to and from [ProtocolMessage].
+//!
+//! Both crates model the JSON of the specification, so the conversions serialize the value to JSON
+//! and deserialize it as the other type. They fail if the other type can't represent the value, for
+//! example because it lacks a variant that was added to the specification later. Fields that are
+//! `null` are dropped, because the other crates serialize absent optional fields as `null`.
+
+#[allow(unused_imports)]
+use crate::{types, ProtocolMessage};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+/// Converts `value` to the type with the same JSON representation.
+fn convert<T, U>(value: T) -> serde_json::Result<U>
+where
+    T: Serialize,
+    U: DeserializeOwned,
+{
+    let mut value = serde_json::to_value(value)?;
+    remove_null_fields(&mut value);
+    serde_json::from_value(value)
+}
+
+fn remove_null_fields(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, field| !field.is_null());
+            map.values_mut().for_each(remove_null_fields);
+        }
+        Value::Array(values) => values.iter_mut().for_each(remove_null_fields),
+        _ => {}
+    }
+}
+
+/// Implements [TryFrom] in both directions for each pair of types.
+macro_rules! convert {
+    ($($ours:ty => $other:ty,)*) => {
+        $(
+            impl TryFrom<$other> for $ours {
+                type Error = serde_json::Error;
+
+                fn try_from(value: $other) -> serde_json::Result<$ours> {
+                    convert(value)
+                }
+            }
+
+            impl TryFrom<$ours> for $other {
+                type Error = serde_json::Error;
+
+                fn try_from(value: $ours) -> serde_json::Result<$other> {
+                    convert(value)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "dap-types")]
+mod dap_types {
+    use super::*;
+    #[cfg(feature = "events")]
+    use crate::events;
+    #[cfg(feature = "requests")]
+    use crate::requests;
+    #[cfg(feature = "responses")]
+    use crate::responses;
+
+    convert! {
+        types::Breakpoint => ::dap_types::Breakpoint,
+        types::BreakpointLocation => ::dap_types::BreakpointLocation,
+        types::Capabilities => ::dap_types::Capabilities,
+        types::Checksum => ::dap_types::Checksum,
+        types::ChecksumAlgorithm => ::dap_types::ChecksumAlgorithm,
+        types::ColumnDescriptor => ::dap_types::ColumnDescriptor,
+        types::ColumnDescriptorType => ::dap_types::ColumnDescriptorType,
+        types::CompletionItem => ::dap_types::CompletionItem,
+        types::CompletionItemType => ::dap_types::CompletionItemType,
+        types::DataBreakpoint => ::dap_types::DataBreakpoint,
+        types::DataBreakpointAccessType => ::dap_types::DataBreakpointAccessType,
+        types::DisassembledInstruction => ::dap_types::DisassembledInstruction,
+        types::ExceptionBreakMode => ::dap_types::ExceptionBreakMode,
+        types::ExceptionBreakpointsFilter => ::dap_types::ExceptionBreakpointsFilter,
+        types::ExceptionDetails => ::dap_types::ExceptionDetails,
+        types::ExceptionFilterOptions => ::dap_types::ExceptionFilterOptions,
+        types::ExceptionOptions => ::dap_types::ExceptionOptions,
+        types::ExceptionPathSegment => ::dap_types::ExceptionPathSegment,
+        types::FunctionBreakpoint => ::dap_types::FunctionBreakpoint,
+        types::GotoTarget => ::dap_types::GotoTarget,
+        types::InstructionBreakpoint => ::dap_types::InstructionBreakpoint,
+        types::InvalidatedAreas => ::dap_types::InvalidatedAreas,
+        types::Message => ::dap_types::Message,
+        types::Module => ::dap_types::Module,
+        types::ModuleId => ::dap_types::ModuleId,
+        types::Scope => ::dap_types::Scope,
+        types::ScopePresentationHint => ::dap_types::ScopePresentationHint,
+        types::Source => ::dap_types::Source,
+        types::SourcePresentationHint => ::dap_types::SourcePresentationHint,
+        types::SourceBreakpoint => ::dap_types::SourceBreakpoint,
+        types::StackFrame => ::dap_types::StackFrame,
+        types::StackFramePresentationHint => ::dap_types::StackFramePresentationHint,
+        types::StackFrameFormat => ::dap_types::StackFrameFormat,
+        types::StepInTarget => ::dap_types::StepInTarget,
+        types::SteppingGranularity => ::dap_types::SteppingGranularity,
+        types::Thread => ::dap_types::Thread,
+        types::ValueFormat => ::dap_types::ValueFormat,
+        types::Variable => ::dap_types::Variable,
+        types::VariablePresentationHint => ::dap_types::VariablePresentationHint,
+    }
+
+    #[cfg(feature = "spec-1_70")]
+    convert! {
+        types::BreakpointMode => ::dap_types::BreakpointMode,
+        types::BreakpointModeApplicability => ::dap_types::BreakpointModeApplicability,
+    }
+
+    #[cfg(feature = "events")]
+    convert! {
+        events::BreakpointEventBody => ::dap_types::BreakpointEvent,
+        events::CapabilitiesEventBody => ::dap_types::CapabilitiesEvent,
+        events::ContinuedEventBody => ::dap_types::ContinuedEvent,
+        events::ExitedEventBody => ::dap_types::ExitedEvent,
+        events::InvalidatedEventBody => ::dap_types::InvalidatedEvent,
+        events::LoadedSourceEventBody => ::dap_types::LoadedSourceEvent,
+        events::ModuleEventBody => ::dap_types::ModuleEvent,
+        events::OutputEventBody => ::dap_types::OutputEvent,
+        events::ProcessEventBody => ::dap_types::ProcessEvent,
+        events::ProgressEndEventBody => ::dap_types::ProgressEndEvent,
+        events::ProgressStartEventBody => ::dap_types::ProgressStartEvent,
+        events::ProgressUpdateEventBody => ::dap_types::ProgressUpdateEvent,
+        events::StoppedEventBody => ::dap_types::StoppedEvent,
+        events::TerminatedEventBody => ::dap_types::TerminatedEvent,
+        events::ThreadEventBody => ::dap_types::ThreadEvent,
+    }
+
+    #[cfg(feature = "requests")]
+    convert! {
+        requests::AttachRequestArguments => ::dap_types::AttachRequestArguments,
+        requests::BreakpointLocationsRequestArguments => ::dap_types::BreakpointLocationsArguments,
+        requests::CancelRequestArguments => ::dap_types::CancelArguments,
+        requests::CompletionsRequestArguments => ::dap_types::CompletionsArguments,
+        requests::ContinueRequestArguments => ::dap_types::ContinueArguments,
+        requests::DataBreakpointInfoRequestArguments => ::dap_types::DataBreakpointInfoArguments,
+        requests::DisassembleRequestArguments => ::dap_types::DisassembleArguments,
+        requests::DisconnectRequestArguments => ::dap_types::DisconnectArguments,
+        requests::EvaluateRequestArguments => ::dap_types::EvaluateArguments,
+        requests::ExceptionInfoRequestArguments => ::dap_types::ExceptionInfoArguments,
+        requests::GotoRequestArguments => ::dap_types::GotoArguments,
+        requests::GotoTargetsRequestArguments => ::dap_types::GotoTargetsArguments,
+        requests::InitializeRequestArguments => ::dap_types::InitializeRequestArguments,
+        requests::LaunchRequestArguments => ::dap_types::LaunchRequestArguments,
+        requests::ModulesRequestArguments => ::dap_types::ModulesArguments,
+        requests::NextRequestArguments => ::dap_types::NextArguments,
+        requests::PauseRequestArguments => ::dap_types::PauseArguments,
+        requests::ReadMemoryRequestArguments => ::dap_types::ReadMemoryArguments,
+        requests::RestartFrameRequestArguments => ::dap_types::RestartFrameArguments,
+        requests::ReverseContinueRequestArguments => ::dap_types::ReverseContinueArguments,
+        requests::RunInTerminalRequestArguments => ::dap_types::RunInTerminalRequestArguments,
+        requests::ScopesRequestArguments => ::dap_types::ScopesArguments,
+        requests::SetBreakpointsRequestArguments => ::dap_types::SetBreakpointsArguments,
+        requests::SetDataBreakpointsRequestArguments => ::dap_types::SetDataBreakpointsArguments,
+        requests::SetExceptionBreakpointsRequestArguments => ::dap_types::SetExceptionBreakpointsArguments,
+        requests::SetExpressionRequestArguments => ::dap_types::SetExpressionArguments,
+        requests::SetFunctionBreakpointsRequestArguments => ::dap_types::SetFunctionBreakpointsArguments,
+        requests::SetInstructionBreakpointsRequestArguments => ::dap_types::SetInstructionBreakpointsArguments,
+        requests::SetVariableRequestArguments => ::dap_types::SetVariableArguments,
+        requests::SourceRequestArguments => ::dap_types::SourceArguments,
+        requests::StackTraceRequestArguments => ::dap_types::StackTraceArguments,
+        requests::StepBackRequestArguments => ::dap_types::StepBackArguments,
+        requests::StepInRequestArguments => ::dap_types::StepInArguments,
+        requests::StepInTargetsRequestArguments => ::dap_types::StepInTargetsArguments,
+        requests::StepOutRequestArguments => ::dap_types::StepOutArguments,
+        requests::TerminateRequestArguments => ::dap_types::TerminateArguments,
+        requests::TerminateThreadsRequestArguments => ::dap_types::TerminateThreadsArguments,
+        requests::VariablesRequestArguments => ::dap_types::VariablesArguments,
+    }
+
+    #[cfg(feature = "responses")]
+    convert! {
+        responses::BreakpointLocationsResponseBody => ::dap_types::BreakpointLocationsResponse,
+        responses::CompletionsResponseBody => ::dap_types::CompletionsResponse,
+        responses::ContinueResponseBody => ::dap_types::ContinueResponse,
+        responses::DataBreakpointInfoResponseBody => ::dap_types::DataBreakpointInfoResponse,
+        responses::DisassembleResponseBody => ::dap_types::DisassembleResponse,
+        responses::EvaluateResponseBody => ::dap_types::EvaluateResponse,
+        responses::ExceptionInfoResponseBody => ::dap_types::ExceptionInfoResponse,
+        responses::GotoTargetsResponseBody => ::dap_types::GotoTargetsResponse,
+        responses::LoadedSourcesResponseBody => ::dap_types::LoadedSourcesResponse,
+        responses::ModulesResponseBody => ::dap_types::ModulesResponse,
+        responses::ReadMemoryResponseBody => ::dap_types::ReadMemoryResponse,
+        responses::RunInTerminalResponseBody => ::dap_types::RunInTerminalResponse,
+        responses::ScopesResponseBody => ::dap_types::ScopesResponse,
+        responses::SetBreakpointsResponseBody => ::dap_types::SetBreakpointsResponse,
+        responses::SetDataBreakpointsResponseBody => ::dap_types::SetDataBreakpointsResponse,
+        responses::SetExceptionBreakpointsResponseBody => ::dap_types::SetExceptionBreakpointsResponse,
+        responses::SetExpressionResponseBody => ::dap_types::SetExpressionResponse,
+        responses::SetFunctionBreakpointsResponseBody => ::dap_types::SetFunctionBreakpointsResponse,
+        responses::SetInstructionBreakpointsResponseBody => ::dap_types::SetInstructionBreakpointsResponse,
+        responses::SetVariableResponseBody => ::dap_types::SetVariableResponse,
+        responses::SourceResponseBody => ::dap_types::SourceResponse,
+        responses::StackTraceResponseBody => ::dap_types::StackTraceResponse,
+        responses::StepInTargetsResponseBody => ::dap_types::StepInTargetsResponse,
+        responses::ThreadsResponseBody => ::dap_types::ThreadsResponse,
+        responses::VariablesResponseBody => ::dap_types::VariablesResponse,
+    }
+}
+
+#[cfg(feature = "debugserver-types")]
+mod debugserver_types {
+    use super::*;
+    #[cfg(feature = "events")]
+    use crate::events;
+    #[cfg(feature = "requests")]
+    use crate::requests;
+    #[cfg(feature = "responses")]
+    use crate::responses;
+
+    /// Converts `message` to a message type of `debugserver-types`, whose messages don't check
+    /// their type and command or event name.
+    #[allow(dead_code)]
+    fn convert_message<U>(message: ProtocolMessage, kind: &str, name: &str) -> serde_json::Result<U>
+    where
+        U: DeserializeOwned,
+    {
+        let value = serde_json::to_value(message)?;
+        let name_key = if kind == "event" { "event" } else { "command" };
+        if value["type"] != kind || value[name_key] != name {
+            return Err(serde::de::Error::custom(format_args!(
+                "expected a '{}' {}",
+                name, kind
+            )));
+        }
+        serde_json::from_value(value)
+    }
+
+    /// Implements [TryFrom] in both directions between [ProtocolMessage] and each message type.
+    macro_rules! convert_messages {
+        ($kind:literal { $($other:ty => $name:literal,)* }) => {
+            $(
+                impl TryFrom<$other> for ProtocolMessage {
+                    type Error = serde_json::Error;
+
+                    fn try_from(value: $other) -> serde_json::Result<ProtocolMessage> {
+                        convert(value)
+                    }
+                }
+
+                impl TryFrom<ProtocolMessage> for $other {
+                    type Error = serde_json::Error;
+
+                    fn try_from(message: ProtocolMessage) -> serde_json::Result<$other> {
+                        convert_message(message, $kind, $name)
+                    }
+                }
+            )*
+        };
+    }
+
+    convert! {
+        types::Breakpoint => ::debugserver_types::Breakpoint,
+        types::Capabilities => ::debugserver_types::Capabilities,
+        types::Checksum => ::debugserver_types::Checksum,
+        types::ChecksumAlgorithm => ::debugserver_types::ChecksumAlgorithm,
+        types::ColumnDescriptor => ::debugserver_types::ColumnDescriptor,
+        types::CompletionItem => ::debugserver_types::CompletionItem,
+        types::CompletionItemType => ::debugserver_types::CompletionItemType,
+        types::DataBreakpoint => ::debugserver_types::DataBreakpoint,
+        types::DataBreakpointAccessType => ::debugserver_types::DataBreakpointAccessType,
+        types::ExceptionBreakMode => ::debugserver_types::ExceptionBreakMode,
+        types::ExceptionBreakpointsFilter => ::debugserver_types::ExceptionBreakpointsFilter,
+        types::ExceptionDetails => ::debugserver_types::ExceptionDetails,
+        types::ExceptionOptions => ::debugserver_types::ExceptionOptions,
+        types::ExceptionPathSegment => ::debugserver_types::ExceptionPathSegment,
+        types::FunctionBreakpoint => ::debugserver_types::FunctionBreakpoint,
+        types::GotoTarget => ::debugserver_types::GotoTarget,
+        types::Message => ::debugserver_types::Message,
+        types::Module => ::debugserver_types::Module,
+        types::ModulesViewDescriptor => ::debugserver_types::ModulesViewDescriptor,
+        types::Scope => ::debugserver_types::Scope,
+        types::Source => ::debugserver_types::Source,
+        types::SourceBreakpoint => ::debugserver_types::SourceBreakpoint,
+        types::StackFrame => ::debugserver_types::StackFrame,
+        types::StackFrameFormat => ::debugserver_types::StackFrameFormat,
+        types::StepInTarget => ::debugserver_types::StepInTarget,
+        types::Thread => ::debugserver_types::Thread,
+        types::ValueFormat => ::debugserver_types::ValueFormat,
+        types::Variable => ::debugserver_types::Variable,
+        types::VariablePresentationHint => ::debugserver_types::VariablePresentationHint,
+    }
+
+    #[cfg(feature = "events")]
+    convert! {
+        events::BreakpointEventBody => ::debugserver_types::BreakpointEventBody,
+        events::CapabilitiesEventBody => ::debugserver_types::CapabilitiesEventBody,
+        events::ContinuedEventBody => ::debugserver_types::ContinuedEventBody,
+        events::ExitedEventBody => ::debugserver_types::ExitedEventBody,
+        events::LoadedSourceEventBody => ::debugserver_types::LoadedSourceEventBody,
+        events::ModuleEventBody => ::debugserver_types::ModuleEventBody,
+        events::OutputEventBody => ::debugserver_types::OutputEventBody,
+        events::ProcessEventBody => ::debugserver_types::ProcessEventBody,
+        events::StoppedEventBody => ::debugserver_types::StoppedEventBody,
+        events::TerminatedEventBody => ::debugserver_types::TerminatedEventBody,
+        events::ThreadEventBody => ::debugserver_types::ThreadEventBody,
+    }
+
+    #[cfg(feature = "requests")]
+    convert! {
+        requests::AttachRequestArguments => ::debugserver_types::AttachRequestArguments,
+        requests::CompletionsRequestArguments => ::debugserver_types::CompletionsArguments,
+        requests::ContinueRequestArguments => ::debugserver_types::ContinueArguments,
+        requests::DataBreakpointInfoRequestArguments => ::debugserver_types::DataBreakpointInfoArguments,
+        requests::DisconnectRequestArguments => ::debugserver_types::DisconnectArguments,
+        requests::EvaluateRequestArguments => ::debugserver_types::EvaluateArguments,
+        requests::ExceptionInfoRequestArguments => ::debugserver_types::ExceptionInfoArguments,
+        requests::GotoRequestArguments => ::debugserver_types::GotoArguments,
+        requests::GotoTargetsRequestArguments => ::debugserver_types::GotoTargetsArguments,
+        requests::InitializeRequestArguments => ::debugserver_types::InitializeRequestArguments,
+        requests::LaunchRequestArguments => ::debugserver_types::LaunchRequestArguments,
+        requests::ModulesRequestArguments => ::debugserver_types::ModulesArguments,
+        requests::NextRequestArguments => ::debugserver_types::NextArguments,
+        requests::PauseRequestArguments => ::debugserver_types::PauseArguments,
+        requests::RestartFrameRequestArguments => ::debugserver_types::RestartFrameArguments,
+        requests::ReverseContinueRequestArguments => ::debugserver_types::ReverseContinueArguments,
+        requests::RunInTerminalRequestArguments => ::debugserver_types::RunInTerminalRequestArguments,
+        requests::ScopesRequestArguments => ::debugserver_types::ScopesArguments,
+        requests::SetBreakpointsRequestArguments => ::debugserver_types::SetBreakpointsArguments,
+        requests::SetDataBreakpointsRequestArguments => ::debugserver_types::SetDataBreakpointsArguments,
+        requests::SetExceptionBreakpointsRequestArguments => ::debugserver_types::SetExceptionBreakpointsArguments,
+        requests::SetExpressionRequestArguments => ::debugserver_types::SetExpressionArguments,
+        requests::SetFunctionBreakpointsRequestArguments => ::debugserver_types::SetFunctionBreakpointsArguments,
+        requests::SetVariableRequestArguments => ::debugserver_types::SetVariableArguments,
+        requests::SourceRequestArguments => ::debugserver_types::SourceArguments,
+        requests::StackTraceRequestArguments => ::debugserver_types::StackTraceArguments,
+        requests::StepBackRequestArguments => ::debugserver_types::StepBackArguments,
+        requests::StepInRequestArguments => ::debugserver_types::StepInArguments,
+        requests::StepInTargetsRequestArguments => ::debugserver_types::StepInTargetsArguments,
+        requests::StepOutRequestArguments => ::debugserver_types::StepOutArguments,
+        requests::TerminateRequestArguments => ::debugserver_types::TerminateArguments,
+        requests::TerminateThreadsRequestArguments => ::debugserver_types::TerminateThreadsArguments,
+        requests::VariablesRequestArguments => ::debugserver_types::VariablesArguments,
+    }
+
+    #[cfg(feature = "responses")]
+    convert! {
+        responses::ErrorResponseBody => ::debugserver_types::ErrorResponseBody,
+        responses::CompletionsResponseBody => ::debugserver_types::CompletionsResponseBody,
+        responses::ContinueResponseBody => ::debugserver_types::ContinueResponseBody,
+        responses::DataBreakpointInfoResponseBody => ::debugserver_types::DataBreakpointInfoResponseBody,
+        responses::EvaluateResponseBody => ::debugserver_types::EvaluateResponseBody,
+        responses::ExceptionInfoResponseBody => ::debugserver_types::ExceptionInfoResponseBody,
+        responses::GotoTargetsResponseBody => ::debugserver_types::GotoTargetsResponseBody,
+        responses::LoadedSourcesResponseBody => ::debugserver_types::LoadedSourcesResponseBody,
+        responses::ModulesResponseBody => ::debugserver_types::ModulesResponseBody,
+        responses::RunInTerminalResponseBody => ::debugserver_types::RunInTerminalResponseBody,
+        responses::ScopesResponseBody => ::debugserver_types::ScopesResponseBody,
+        responses::SetBreakpointsResponseBody => ::debugserver_types::SetBreakpointsResponseBody,
+        responses::SetDataBreakpointsResponseBody => ::debugserver_types::SetDataBreakpointsResponseBody,
+        responses::SetExpressionResponseBody => ::debugserver_types::SetExpressionResponseBody,
+        responses::SetFunctionBreakpointsResponseBody => ::debugserver_types::SetFunctionBreakpointsResponseBody,
+        responses::SetVariableResponseBody => ::debugserver_types::SetVariableResponseBody,
+        responses::SourceResponseBody => ::debugserver_types::SourceResponseBody,
+        responses::StackTraceResponseBody => ::debugserver_types::StackTraceResponseBody,
+        responses::StepInTargetsResponseBody => ::debugserver_types::StepInTargetsResponseBody,
+        responses::ThreadsResponseBody => ::debugserver_types::ThreadsResponseBody,
+        responses::VariablesResponseBody => ::debugserver_types::VariablesResponseBody,
+    }
+
+    #[cfg(feature = "events")]
+    convert_messages!("event" {
+        ::debugserver_types::BreakpointEvent => "breakpoint",
+        ::debugserver_types::CapabilitiesEvent => "capabilities",
+        ::debugserver_types::ContinuedEvent => "continued",
+        ::debugserver_types::ExitedEvent => "exited",
+        ::debugserver_types::InitializedEvent => "initialized",
+        ::debugserver_types::LoadedSourceEvent => "loadedSource",
+        ::debugserver_types::ModuleEvent => "module",
+        ::debugserver_types::OutputEvent => "output",
+        ::debugserver_types::ProcessEvent => "process",
+        ::debugserver_types::StoppedEvent => "stopped",
+        ::debugserver_types::TerminatedEvent => "terminated",
+        ::debugserver_types::ThreadEvent => "thread",
+    });
+
+    #[cfg(feature = "requests")]
+    convert_messages!("request" {
+        ::debugserver_types::AttachRequest => "attach",
+        ::debugserver_types::CompletionsRequest => "completions",
+        ::debugserver_types::ConfigurationDoneRequest => "configurationDone",
+        ::debugserver_types::ContinueRequest => "continue",
+        ::debugserver_types::DataBreakpointInfoRequest => "dataBreakpointInfo",
+        ::debugserver_types::DisconnectRequest => "disconnect",
+        ::debugserver_types::EvaluateRequest => "evaluate",
+        ::debugserver_types::ExceptionInfoRequest => "exceptionInfo",
+        ::debugserver_types::GotoRequest => "goto",
+        ::debugserver_types::GotoTargetsRequest => "gotoTargets",
+        ::debugserver_types::InitializeRequest => "initialize",
+        ::debugserver_types::LaunchRequest => "launch",
+        ::debugserver_types::LoadedSourcesRequest => "loadedSources",
+        ::debugserver_types::ModulesRequest => "modules",
+        ::debugserver_types::NextRequest => "next",
+        ::debugserver_types::PauseRequest => "pause",
+        ::debugserver_types::RestartFrameRequest => "restartFrame",
+        ::debugserver_types::ReverseContinueRequest => "reverseContinue",
+        ::debugserver_types::RunInTerminalRequest => "runInTerminal",
+        ::debugserver_types::ScopesRequest => "scopes",
+        ::debugserver_types::SetBreakpointsRequest => "setBreakpoints",
+        ::debugserver_types::SetDataBreakpointsRequest => "setDataBreakpoints",
+        ::debugserver_types::SetExceptionBreakpointsRequest => "setExceptionBreakpoints",
+        ::debugserver_types::SetExpressionRequest => "setExpression",
+        ::debugserver_types::SetFunctionBreakpointsRequest => "setFunctionBreakpoints",
+        ::debugserver_types::SetVariableRequest => "setVariable",
+        ::debugserver_types::SourceRequest => "source",
+        ::debugserver_types::StackTraceRequest => "stackTrace",
+        ::debugserver_types::StepBackRequest => "stepBack",
+        ::debugserver_types::StepInRequest => "stepIn",
+        ::debugserver_types::StepInTargetsRequest => "stepInTargets",
+        ::debugserver_types::StepOutRequest => "stepOut",
+        ::debugserver_types::TerminateRequest => "terminate",
+        ::debugserver_types::TerminateThreadsRequest => "terminateThreads",
+        ::debugserver_types::ThreadsRequest => "threads",
+        ::debugserver_types::VariablesRequest => "variables",
+    });
+
+    #[cfg(feature = "responses")]
+    convert_messages!("response" {
+        ::debugserver_types::CompletionsResponse => "completions",
+        ::debugserver_types::ContinueResponse => "continue",
+        ::debugserver_types::DataBreakpointInfoResponse => "dataBreakpointInfo",
+        ::debugserver_types::EvaluateResponse => "evaluate",
+        ::debugserver_types::ExceptionInfoResponse => "exceptionInfo",
+        ::debugserver_types::GotoTargetsResponse => "gotoTargets",
+        ::debugserver_types::LoadedSourcesResponse => "loadedSources",
+        ::debugserver_types::ModulesResponse => "modules",
+        ::debugserver_types::RunInTerminalResponse => "runInTerminal",
+        ::debugserver_types::ScopesResponse => "scopes",
+        ::debugserver_types::SetBreakpointsResponse => "setBreakpoints",
+        ::debugserver_types::SetDataBreakpointsResponse => "setDataBreakpoints",
+        ::debugserver_types::SetExceptionBreakpointsResponse => "setExceptionBreakpoints",
+        ::debugserver_types::SetExpressionResponse => "setExpression",
+        ::debugserver_types::SetFunctionBreakpointsResponse => "setFunctionBreakpoints",
+        ::debugserver_types::SetVariableResponse => "setVariable",
+        ::debugserver_types::SourceResponse => "source",
+        ::debugserver_types::StackTraceResponse => "stackTrace",
+        ::debugserver_types::StepInTargetsResponse => "stepInTargets",
+        ::debugserver_types::ThreadsResponse => "threads",
+        ::debugserver_types::VariablesResponse => "variables",
+    });
+}
@@ -0,0 +1,129 @@
+//! A synchronous connection that exchanges messages over channels, like the `lsp-server` crate
+//! does for the Language Server Protocol.
+//!
+//! Two threads do the IO: one reads messages from the transport and sends them to
+//! [Connection::receiver], the other writes the messages received from [Connection::sender].
+//! Messages that are framed correctly but fail to parse are sent to [Connection::errors] instead,
+//! and the reader continues with the next message.
+
+use crate::{error::ParseError, framing::read_frame, ProtocolMessage};
+use crossbeam_channel::{bounded, unbounded, Receiver, Sender};
+use std::{
+    io::{self, BufReader, BufWriter, Read, Write},
+    thread::{self, JoinHandle},
+};
+
+/// Both halves of a connection to a client or debug adapter.
+#[derive(Debug)]
+pub struct Connection {
+    pub sender: Sender<ProtocolMessage>,
+    pub receiver: Receiver<ProtocolMessage>,
+    /// The errors of messages that could not be parsed. Unbounded, so the reader thread does not
+    /// wait for them to be received.
+    pub errors: Receiver<ParseError>,
+}
+
+impl Connection {
+    /// Creates a connection over stdin and stdout, which is how a debug adapter talks to the client
+    /// that launched it.
+    pub fn stdio() -> (Connection, IoThreads) {
+        Connection::from_transport(io::stdin(), io::stdout())
+    }
+
+    /// Creates a connection that reads messages from `reader` and writes messages to `writer`, for
+    /// example the halves of a `TcpStream`.
+    pub fn from_transport<R, W>(reader: R, writer: W) -> (Connection, IoThreads)
+    where
+        R: Read + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        let (reader_sender, receiver) = bounded::<ProtocolMessage>(0);
+        let (error_sender, errors) = unbounded::<ParseError>();
+        let reader = thread::Builder::new()
+            .name("dap reader".to_string())
+            .spawn(move || {
+                let mut reader = BufReader::new(reader);
+                while let Some(content) = read_frame(&mut reader)? {
+                    let sent = match ProtocolMessage::from_slice(&content) {
+                        Ok(message) => reader_sender.send(message).is_ok(),
+                        // The frame was complete, so the next one can still be read
+                        Err(error) => error_sender.send(error).is_ok(),
+                    };
+                    if !sent {
+                        // The connection was dropped
+                        break;
+                    }
+                }
+                Ok(())
+            })
+            .expect("failed to spawn the reader thread");
+
+        let (sender, writer_receiver) = bounded::<ProtocolMessage>(0);
+        let writer = thread::Builder::new()
+            .name("dap writer".to_string())
+            .spawn(move || {
+                let mut writer = BufWriter::new(writer);
+                for message in writer_receiver {
+                    message.write_to(&mut writer)?;
+                    writer.flush()?;
+                }
+                Ok(())
+            })
+            .expect("failed to spawn the writer thread");
+
+        (
+            Connection {
+                sender,
+                receiver,
+                errors,
+            },
+            IoThreads { reader, writer },
+        )
+    }
+
+    /// Creates a pair of connections that are connected to each other, for example to test a
+    /// debug adapter without a client.
+    pub fn memory() -> (Connection, Connection) {
+        let (first_sender, second_receiver) = unbounded();
+        let (second_sender, first_receiver) = unbounded();
+        // Messages are never parsed, so there are no errors
+        let (_, errors) = unbounded();
+        (
+            Connection {
+                sender: first_sender,
+                receiver: first_receiver,
+                errors: errors.clone(),
+            },
+            Connection {
+                sender: second_sender,
+                receiver: second_receiver,
+                errors,
+            },
+        )
+    }
+}
+
+/// The threads doing the IO of a [Connection].
+#[derive(Debug)]
+pub struct IoThreads {
+    reader: JoinHandle<io::Result<()>>,
+    writer: JoinHandle<io::Result<()>>,
+}
+
+impl IoThreads {
+    /// Waits for both threads and returns the first error they ran into.
+    ///
+    /// The reader thread ends at the end of the stream and the writer thread once the [Connection]
+    /// is dropped, so drop it before calling this.
+    pub fn join(self) -> io::Result<()> {
+        let reader = self
+            .reader
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+        let writer = self
+            .writer
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+        reader.and(writer)
+    }
+}
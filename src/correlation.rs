@@ -0,0 +1,166 @@
+use crate::{requests::Request, responses::Response, SequenceNumber};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+/// A response that can't be attributed to the request it claims to answer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CorrelationError {
+    /// No request with the `request_seq` of the response is outstanding.
+    UnknownRequest { request_seq: SequenceNumber },
+
+    /// The command of the response differs from the command of the request.
+    CommandMismatch {
+        request_seq: SequenceNumber,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl Display for CorrelationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CorrelationError::UnknownRequest { request_seq } => {
+                write!(f, "response to unknown request {}", request_seq)
+            }
+            CorrelationError::CommandMismatch {
+                request_seq,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "response to '{}' request {} has command '{}'",
+                expected, request_seq, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CorrelationError {}
+
+/// Checks that `response` answers `request`, which was sent with the sequence number `seq`.
+pub fn check_response(
+    seq: SequenceNumber,
+    request: &Request,
+    response: &Response,
+) -> Result<(), CorrelationError> {
+    if response.request_seq != seq {
+        return Err(CorrelationError::UnknownRequest {
+            request_seq: response.request_seq,
+        });
+    }
+    if response.command() != request.command() {
+        return Err(CorrelationError::CommandMismatch {
+            request_seq: seq,
+            expected: request.command().to_string(),
+            actual: response.command().to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// The requests of a session that have been sent but not yet answered.
+///
+/// Responses are checked against the outstanding requests with [check_response] to flag debug
+/// adapters that misattribute responses. Only the `request_seq` of a response is used, because
+/// some debug adapters don't maintain the `seq` of their own messages.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PendingRequests {
+    requests: HashMap<SequenceNumber, Request>,
+}
+
+impl PendingRequests {
+    pub fn new() -> PendingRequests {
+        PendingRequests::default()
+    }
+
+    /// Records that `request` was sent with the sequence number `seq`. Returns the request that
+    /// was previously outstanding with the same sequence number, if any.
+    pub fn insert(&mut self, seq: SequenceNumber, request: Request) -> Option<Request> {
+        self.requests.insert(seq, request)
+    }
+
+    /// Returns the outstanding request with the sequence number `seq`.
+    pub fn get(&self, seq: SequenceNumber) -> Option<&Request> {
+        self.requests.get(&seq)
+    }
+
+    /// Removes and returns the request answered by `response`.
+    ///
+    /// If the command of the response does not match the request, an error is returned. The
+    /// request is removed anyway, because the debug adapter won't answer it again.
+    pub fn complete(&mut self, response: &Response) -> Result<Request, CorrelationError> {
+        let request_seq = response.request_seq;
+        let request = self
+            .requests
+            .remove(&request_seq)
+            .ok_or(CorrelationError::UnknownRequest { request_seq })?;
+        check_response(request_seq, &request, response)?;
+        Ok(request)
+    }
+
+    /// The number of outstanding requests.
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+}
+
+/// An irregular sequence number of a message.
+///
+/// Some debug adapters send `seq: 0` on all responses and events. Because responses are correlated
+/// by their `request_seq` alone, this is only reported as a warning.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum SequenceWarning {
+    /// The sequence number is 0.
+    Zero,
+
+    /// The sequence number is not greater than the one of the previous message.
+    NotIncreasing {
+        previous: SequenceNumber,
+        seq: SequenceNumber,
+    },
+}
+
+impl Display for SequenceWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SequenceWarning::Zero => f.write_str("sequence number is 0"),
+            SequenceWarning::NotIncreasing { previous, seq } => write!(
+                f,
+                "sequence number {} does not increase from {}",
+                seq, previous
+            ),
+        }
+    }
+}
+
+/// Observes the sequence numbers of the messages sent by one side of a session.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SequenceTracker {
+    previous: Option<SequenceNumber>,
+}
+
+impl SequenceTracker {
+    pub fn new() -> SequenceTracker {
+        SequenceTracker::default()
+    }
+
+    /// Records the sequence number of the next message and returns a warning if it is irregular.
+    pub fn observe(&mut self, seq: SequenceNumber) -> Option<SequenceWarning> {
+        let previous = self.previous.replace(seq);
+        match previous {
+            _ if seq == 0 => Some(SequenceWarning::Zero),
+            Some(previous) if seq <= previous => {
+                Some(SequenceWarning::NotIncreasing { previous, seq })
+            }
+            _ => None,
+        }
+    }
+}
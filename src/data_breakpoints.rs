@@ -0,0 +1,269 @@
+//! A client side model of the data breakpoints of a session.
+//!
+//! Setting a data breakpoint takes two requests: 'dataBreakpointInfo' asks the debug adapter
+//! whether a variable or expression can be watched and with which access types, and
+//! 'setDataBreakpoints' replaces all data breakpoints at once. [DataBreakpoints] keeps the full set
+//! so that every change can be sent as a complete request, and stores the verification result the
+//! debug adapter returned for each breakpoint.
+
+use crate::{
+    requests::{DataBreakpointInfoRequestArguments, SetDataBreakpointsRequestArguments},
+    responses::{DataBreakpointInfoResponseBody, ErrorResponse, SuccessResponse},
+    types::{Breakpoint, DataBreakpoint, DataBreakpointAccessType},
+    Str,
+};
+use std::fmt::{self, Display};
+
+/// Why a data breakpoint could not be added or its verification results not be stored.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DataBreakpointError {
+    /// The debug adapter can't set a data breakpoint on the variable or expression. Contains the
+    /// description the debug adapter gave, which explains why.
+    Unavailable { description: Str },
+
+    /// The debug adapter does not support the access type for the variable or expression.
+    UnsupportedAccessType {
+        access_type: DataBreakpointAccessType,
+        supported: Vec<DataBreakpointAccessType>,
+    },
+
+    /// The debug adapter returned another number of breakpoints than were set.
+    WrongCount { expected: usize, actual: usize },
+
+    /// The 'setDataBreakpoints' request failed.
+    Failed(ErrorResponse),
+}
+
+impl Display for DataBreakpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataBreakpointError::Unavailable { description } => {
+                write!(f, "data breakpoint unavailable: {}", description)
+            }
+            DataBreakpointError::UnsupportedAccessType {
+                access_type,
+                supported,
+            } => write!(
+                f,
+                "access type {:?} is not supported, only {:?}",
+                access_type, supported
+            ),
+            DataBreakpointError::WrongCount { expected, actual } => {
+                write!(f, "expected {} breakpoints, but got {}", expected, actual)
+            }
+            DataBreakpointError::Failed(error) => {
+                write!(f, "'setDataBreakpoints' request failed: {}", error.message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DataBreakpointError {}
+
+/// Creates the request that asks whether the child `name` of the container with
+/// `variables_reference` can be watched.
+pub fn variable_info(
+    variables_reference: i32,
+    name: impl Into<Str>,
+) -> DataBreakpointInfoRequestArguments {
+    DataBreakpointInfoRequestArguments {
+        variables_reference: Some(variables_reference),
+        name: name.into(),
+        #[cfg(feature = "spec-1_70")]
+        frame_id: None,
+        #[cfg(feature = "spec-1_70")]
+        bytes: None,
+        #[cfg(feature = "spec-1_70")]
+        as_address: false,
+        #[cfg(feature = "spec-1_70")]
+        mode: None,
+    }
+}
+
+/// Creates the request that asks whether `expression` can be watched.
+pub fn expression_info(expression: impl Into<Str>) -> DataBreakpointInfoRequestArguments {
+    DataBreakpointInfoRequestArguments {
+        variables_reference: None,
+        name: expression.into(),
+        #[cfg(feature = "spec-1_70")]
+        frame_id: None,
+        #[cfg(feature = "spec-1_70")]
+        bytes: None,
+        #[cfg(feature = "spec-1_70")]
+        as_address: false,
+        #[cfg(feature = "spec-1_70")]
+        mode: None,
+    }
+}
+
+/// Creates the request that asks whether the `bytes` bytes of memory starting at `address` can be
+/// watched. The address is hexadecimal if prefixed with `0x` and decimal otherwise. Only debug
+/// adapters with the 'supportsDataBreakpointBytes' capability support this.
+#[cfg(feature = "spec-1_70")]
+pub fn address_info(address: impl Into<Str>, bytes: i32) -> DataBreakpointInfoRequestArguments {
+    DataBreakpointInfoRequestArguments {
+        variables_reference: None,
+        name: address.into(),
+        frame_id: None,
+        bytes: Some(bytes),
+        as_address: true,
+        mode: None,
+    }
+}
+
+/// The data breakpoints of a session and their verification results. See the
+/// [module documentation](self).
+#[derive(Clone, Debug, Default)]
+pub struct DataBreakpoints {
+    breakpoints: Vec<DataBreakpoint>,
+    results: Vec<Option<Breakpoint>>,
+}
+
+impl DataBreakpoints {
+    pub fn new() -> DataBreakpoints {
+        DataBreakpoints::default()
+    }
+
+    /// Adds a data breakpoint for the variable or expression described by `info`, the response to
+    /// a 'dataBreakpointInfo' request. Without an `access_type`, the debug adapter picks one.
+    /// Returns the request that sets all data breakpoints.
+    pub fn add(
+        &mut self,
+        info: &DataBreakpointInfoResponseBody,
+        access_type: Option<DataBreakpointAccessType>,
+    ) -> Result<SetDataBreakpointsRequestArguments, DataBreakpointError> {
+        let data_id = match &info.data_id {
+            Some(data_id) => data_id.clone(),
+            None => {
+                return Err(DataBreakpointError::Unavailable {
+                    description: info.description.clone(),
+                })
+            }
+        };
+        if let (Some(access_type), Some(supported)) = (&access_type, &info.access_types) {
+            if !supported.contains(access_type) {
+                return Err(DataBreakpointError::UnsupportedAccessType {
+                    access_type: access_type.clone(),
+                    supported: supported.clone(),
+                });
+            }
+        }
+        Ok(self.insert(DataBreakpoint {
+            data_id,
+            access_type,
+            condition: None,
+            hit_condition: None,
+            #[cfg(feature = "spec-1_70")]
+            mode: None,
+        }))
+    }
+
+    /// Adds `breakpoint`, replacing the data breakpoint with the same `dataId` if there is one.
+    /// Returns the request that sets all data breakpoints.
+    pub fn insert(&mut self, breakpoint: DataBreakpoint) -> SetDataBreakpointsRequestArguments {
+        match self
+            .breakpoints
+            .iter()
+            .position(|existing| existing.data_id == breakpoint.data_id)
+        {
+            Some(index) => {
+                self.breakpoints[index] = breakpoint;
+                self.results[index] = None;
+            }
+            None => {
+                self.breakpoints.push(breakpoint);
+                self.results.push(None);
+            }
+        }
+        self.request()
+    }
+
+    /// Removes the data breakpoint with `data_id` and returns the request that sets the remaining
+    /// ones, or `None` if there is no such data breakpoint.
+    pub fn remove(&mut self, data_id: &str) -> Option<SetDataBreakpointsRequestArguments> {
+        let index = self
+            .breakpoints
+            .iter()
+            .position(|breakpoint| &*breakpoint.data_id == data_id)?;
+        self.breakpoints.remove(index);
+        self.results.remove(index);
+        Some(self.request())
+    }
+
+    /// Removes all data breakpoints and returns the request that clears them.
+    pub fn clear(&mut self) -> SetDataBreakpointsRequestArguments {
+        self.breakpoints.clear();
+        self.results.clear();
+        self.request()
+    }
+
+    /// The request that sets all data breakpoints.
+    pub fn request(&self) -> SetDataBreakpointsRequestArguments {
+        SetDataBreakpointsRequestArguments {
+            breakpoints: self.breakpoints.clone(),
+        }
+    }
+
+    /// Stores the verification results of the response to `request`, which are in the order of
+    /// the breakpoints in the request. Responses to a request that was superseded by a later
+    /// change are ignored.
+    pub fn handle_response(
+        &mut self,
+        request: &SetDataBreakpointsRequestArguments,
+        result: &Result<SuccessResponse, ErrorResponse>,
+    ) -> Result<(), DataBreakpointError> {
+        let body = match result {
+            Ok(SuccessResponse::SetDataBreakpoints(body)) => body,
+            Ok(_) => return Ok(()),
+            Err(error) => return Err(DataBreakpointError::Failed(error.clone())),
+        };
+        if request.breakpoints != self.breakpoints {
+            return Ok(());
+        }
+        if body.breakpoints.len() != self.breakpoints.len() {
+            return Err(DataBreakpointError::WrongCount {
+                expected: self.breakpoints.len(),
+                actual: body.breakpoints.len(),
+            });
+        }
+        self.results = body.breakpoints.iter().cloned().map(Some).collect();
+        Ok(())
+    }
+
+    /// Replaces the verification result with the id of `breakpoint`, usually from a 'breakpoint'
+    /// event with reason 'changed'. Returns whether there was one.
+    pub fn update(&mut self, breakpoint: &Breakpoint) -> bool {
+        if breakpoint.id.is_none() {
+            return false;
+        }
+        match self
+            .results
+            .iter_mut()
+            .flatten()
+            .find(|result| result.id == breakpoint.id)
+        {
+            Some(result) => {
+                *result = breakpoint.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The data breakpoints with their verification results, or `None` if the debug adapter did
+    /// not answer the request that set them yet.
+    pub fn iter(&self) -> impl Iterator<Item = (&DataBreakpoint, Option<&Breakpoint>)> {
+        self.breakpoints
+            .iter()
+            .zip(self.results.iter().map(Option::as_ref))
+    }
+
+    pub fn len(&self) -> usize {
+        self.breakpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.breakpoints.is_empty()
+    }
+}
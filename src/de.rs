@@ -0,0 +1,539 @@
+//! A [Deserializer] over a borrowed [Value] that keeps track of the path to the value that is
+//! currently being deserialized, so errors can be attributed to the offending field.
+
+#[cfg(feature = "events")]
+use crate::events::Event;
+#[cfg(feature = "requests")]
+use crate::requests::Request;
+#[cfg(feature = "responses")]
+use crate::responses::Response;
+use crate::{
+    error::{MessageKind, ParseError},
+    validate::Validate,
+    ParseOptions, ProtocolMessage, ProtocolMessageContent,
+};
+use serde::{
+    de::{
+        value::BorrowedStrDeserializer, DeserializeSeed, Deserializer, Error as _, MapAccess,
+        SeqAccess, Visitor,
+    },
+    forward_to_deserialize_any, Deserialize,
+};
+use serde_json::{Error, Map, Value};
+use std::{
+    cell::RefCell,
+    fmt::{self, Display},
+};
+
+/// The path from the root value to a nested value.
+#[derive(Clone, Copy)]
+pub(crate) enum Path<'a> {
+    Root,
+    Key(&'a Path<'a>, &'a str),
+    Index(&'a Path<'a>, usize),
+}
+
+impl Path<'_> {
+    /// The number of keys and indices from the root.
+    fn depth(&self) -> usize {
+        match self {
+            Path::Root => 0,
+            Path::Key(parent, _) | Path::Index(parent, _) => parent.depth() + 1,
+        }
+    }
+}
+
+impl Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Path::Root => Ok(()),
+            Path::Key(Path::Root, key) => write!(f, "{}", key),
+            Path::Key(parent, key) => write!(f, "{}.{}", parent, key),
+            Path::Index(parent, index) => write!(f, "{}[{}]", parent, index),
+        }
+    }
+}
+
+/// Shared state of one deserialization run.
+pub(crate) struct State {
+    strict: bool,
+    lenient_numbers: bool,
+    max_depth: Option<usize>,
+    error_path: RefCell<Option<String>>,
+}
+
+impl State {
+    pub(crate) fn new(options: &ParseOptions) -> State {
+        State {
+            strict: options.strict,
+            lenient_numbers: options.lenient_numbers,
+            max_depth: options.max_depth,
+            error_path: RefCell::default(),
+        }
+    }
+
+    /// The path of the innermost value that failed to deserialize, if any.
+    pub(crate) fn into_error_path(self) -> Option<String> {
+        self.error_path.into_inner()
+    }
+
+    pub(crate) fn record_error(&self, path: &Path) {
+        self.record_error_path(path.to_string());
+    }
+
+    fn record_error_path(&self, path: String) {
+        let mut error_path = self.error_path.borrow_mut();
+        // Errors propagate outwards, so the first recorded path is the innermost one
+        if error_path.is_none() {
+            *error_path = Some(path);
+        }
+    }
+}
+
+pub(crate) struct ValueDeserializer<'a, 'de> {
+    value: &'de Value,
+    path: Path<'a>,
+    state: &'a State,
+}
+
+impl ValueDeserializer<'_, '_> {
+    fn track<T>(&self, result: Result<T, Error>) -> Result<T, Error> {
+        if result.is_err() {
+            self.state.record_error(&self.path);
+        }
+        result
+    }
+}
+
+macro_rules! delegate {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                let result = self.value.$method(visitor);
+                self.track(result)
+            }
+        )*
+    };
+}
+
+macro_rules! delegate_integer {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                let result = match coerce_integer(self.value).filter(|_| self.state.lenient_numbers) {
+                    Some(value) => value.$method(visitor),
+                    None => self.value.$method(visitor),
+                };
+                self.track(result)
+            }
+        )*
+    };
+}
+
+/// Converts floats without a fractional part and numeric strings to integers.
+fn coerce_integer(value: &Value) -> Option<Value> {
+    let float = match value {
+        Value::Number(number) if number.is_f64() => number.as_f64()?,
+        Value::String(string) => {
+            if let Ok(integer) = string.parse::<u64>() {
+                return Some(integer.into());
+            }
+            if let Ok(integer) = string.parse::<i64>() {
+                return Some(integer.into());
+            }
+            string.parse::<f64>().ok()?
+        }
+        _ => return None,
+    };
+    // Casts saturate, so values outside of the range of 64 bit integers are not lossless
+    if float.fract() != 0.0 || float < i64::MIN as f64 || float >= u64::MAX as f64 {
+        None
+    } else if float < 0.0 {
+        Some((float as i64).into())
+    } else {
+        Some((float as u64).into())
+    }
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'_, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        if let (Value::Array(_) | Value::Object(_), Some(max_depth)) =
+            (self.value, self.state.max_depth)
+        {
+            // The message itself is at depth 1
+            if self.path.depth() + 1 > max_depth {
+                let result = Err(Error::custom(format_args!(
+                    "exceeded the maximum nesting depth of {}",
+                    max_depth
+                )));
+                return self.track(result);
+            }
+        }
+        let result = match self.value {
+            Value::Array(array) => visitor.visit_seq(SeqDeserializer::new(array, &self)),
+            Value::Object(map) => visitor.visit_map(MapDeserializer::new(map, &self)),
+            value => value.deserialize_any(visitor),
+        };
+        self.track(result)
+    }
+
+    delegate! {
+        deserialize_bool
+        deserialize_f32 deserialize_f64
+        deserialize_char deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf
+        deserialize_unit deserialize_identifier
+    }
+
+    delegate_integer! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        // Derived implementations ignore the values of unknown fields
+        if let (true, Path::Key(_, key)) = (self.state.strict, self.path) {
+            let result = Err(Error::custom(format_args!("unknown field `{}`", key)));
+            return self.track(result);
+        }
+        let result = self.value.deserialize_ignored_any(visitor);
+        self.track(result)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = self.value.deserialize_unit_struct(name, visitor);
+        self.track(result)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = self.value.deserialize_enum(name, variants, visitor);
+        self.track(result)
+    }
+
+    forward_to_deserialize_any! {
+        seq tuple tuple_struct map
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+}
+
+struct SeqDeserializer<'a, 'de> {
+    iter: std::iter::Enumerate<std::slice::Iter<'de, Value>>,
+    path: &'a Path<'a>,
+    state: &'a State,
+}
+
+impl<'a, 'de> SeqDeserializer<'a, 'de> {
+    fn new(array: &'de [Value], parent: &'a ValueDeserializer<'a, 'de>) -> Self {
+        SeqDeserializer {
+            iter: array.iter().enumerate(),
+            path: &parent.path,
+            state: parent.state,
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((index, value)) => seed
+                .deserialize(ValueDeserializer {
+                    value,
+                    path: Path::Index(self.path, index),
+                    state: self.state,
+                })
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapDeserializer<'a, 'de, I> {
+    iter: I,
+    value: Option<(&'de str, &'de Value)>,
+    path: &'a Path<'a>,
+    state: &'a State,
+}
+
+impl<'a, 'de> MapDeserializer<'a, 'de, serde_json::map::Iter<'de>> {
+    fn new(map: &'de Map<String, Value>, parent: &'a ValueDeserializer<'a, 'de>) -> Self {
+        MapDeserializer {
+            iter: map.iter(),
+            value: None,
+            path: &parent.path,
+            state: parent.state,
+        }
+    }
+}
+
+impl<'de, I> MapAccess<'de> for MapDeserializer<'_, 'de, I>
+where
+    I: Iterator<Item = (&'de String, &'de Value)> + ExactSizeIterator,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                let key = key.as_str();
+                self.value = Some((key, value));
+                seed.deserialize(BorrowedStrDeserializer::new(key))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| Error::custom("value is missing"))?;
+        seed.deserialize(ValueDeserializer {
+            value,
+            path: Path::Key(self.path, key),
+            state: self.state,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Deserializes the protocol message `root` attribute by attribute, so that every value is
+/// deserialized through a [ValueDeserializer] and errors can be attributed to a path.
+pub(crate) fn from_value(
+    root: &Value,
+    options: &ParseOptions,
+) -> Result<ProtocolMessage, ParseError> {
+    let state = State::new(options);
+    let (kind, name) = describe(root);
+    deserialize_message(root, kind, &state)
+        .map_err(|error| ParseError::new(kind, name, state.into_error_path(), error))
+}
+
+fn describe(root: &Value) -> (Option<MessageKind>, Option<String>) {
+    let kind = root
+        .get("type")
+        .and_then(Value::as_str)
+        .and_then(MessageKind::from_type);
+    let name_key = match kind {
+        Some(MessageKind::Event) => "event",
+        _ => "command",
+    };
+    let name = root
+        .get(name_key)
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    (kind, name)
+}
+
+fn deserialize_message(
+    root: &Value,
+    kind: Option<MessageKind>,
+    state: &State,
+) -> Result<ProtocolMessage, Error> {
+    let message = root
+        .as_object()
+        .ok_or_else(|| Error::custom("a protocol message must be a JSON object"))?;
+    let seq = field(message, "seq", state)?;
+    let type_ = field::<&str>(message, "type", state)?;
+    let kind = kind.ok_or_else(|| {
+        state.record_error(&Path::Key(&Path::Root, "type"));
+        Error::unknown_variant(type_, &["request", "response", "event"])
+    })?;
+    let (content, keys): (ProtocolMessageContent, &[&str]) = match kind {
+        #[cfg(feature = "requests")]
+        MessageKind::Request => (
+            entries::<Request>(message, &["command", "arguments"], state)?.into(),
+            &["seq", "type", "command", "arguments"],
+        ),
+        #[cfg(feature = "responses")]
+        MessageKind::Response => {
+            let request_seq = field(message, "request_seq", state)?;
+            let result = if field(message, "success", state)? {
+                Ok(entries(message, &["command", "body"], state)?)
+            } else {
+                Err(entries(message, &["command", "message", "body"], state)?)
+            };
+            let keys: &[&str] = &[
+                "seq",
+                "type",
+                "request_seq",
+                "success",
+                "command",
+                "message",
+                "body",
+            ];
+            (
+                Response {
+                    request_seq,
+                    result,
+                }
+                .into(),
+                keys,
+            )
+        }
+        #[cfg(feature = "events")]
+        MessageKind::Event => (
+            entries::<Event>(message, &["event", "body"], state)?.into(),
+            &["seq", "type", "event", "body"],
+        ),
+        #[cfg(not(feature = "requests"))]
+        MessageKind::Request => return Err(unsupported(kind, "requests", state)),
+        #[cfg(not(feature = "responses"))]
+        MessageKind::Response => return Err(unsupported(kind, "responses", state)),
+        #[cfg(not(feature = "events"))]
+        MessageKind::Event => return Err(unsupported(kind, "events", state)),
+    };
+    if state.strict {
+        if let Some(key) = message.keys().find(|key| !keys.contains(&key.as_str())) {
+            state.record_error(&Path::Key(&Path::Root, key));
+            return Err(Error::unknown_field(key, keys));
+        }
+        if let Some(violation) = content.validate().into_iter().next() {
+            state.record_error_path(violation.path().to_string());
+            return Err(Error::custom(violation.message()));
+        }
+    }
+    Ok(ProtocolMessage::new(seq, content))
+}
+
+/// The error for a message of a kind whose feature is disabled.
+#[cfg(not(all(feature = "events", feature = "requests", feature = "responses")))]
+fn unsupported(kind: MessageKind, feature: &str, state: &State) -> Error {
+    state.record_error(&Path::Key(&Path::Root, "type"));
+    Error::custom(format_args!(
+        "{} messages require the `{}` feature",
+        kind, feature
+    ))
+}
+
+fn field<'de, T: Deserialize<'de>>(
+    message: &'de Map<String, Value>,
+    key: &'static str,
+    state: &State,
+) -> Result<T, Error> {
+    match message.get(key) {
+        Some(value) => T::deserialize(ValueDeserializer {
+            value,
+            path: Path::Key(&Path::Root, key),
+            state,
+        }),
+        None => Err(Error::missing_field(key)),
+    }
+}
+
+/// Deserializes the given entries of `map` (in the given order) as if they were a JSON object of
+/// their own. The order matters for adjacently tagged enums, which only deserialize their content
+/// in a streaming fashion (and thus through a [ValueDeserializer]) if the tag comes first.
+fn entries<'de, T: Deserialize<'de>>(
+    map: &'de Map<String, Value>,
+    keys: &[&str],
+    state: &State,
+) -> Result<T, Error> {
+    let entries = keys
+        .iter()
+        .filter_map(|key| map.get_key_value(*key))
+        .collect::<Vec<_>>();
+    T::deserialize(EntriesDeserializer { entries, state })
+}
+
+struct EntriesDeserializer<'a, 'de> {
+    entries: Vec<(&'de String, &'de Value)>,
+    state: &'a State,
+}
+
+impl<'de> Deserializer<'de> for EntriesDeserializer<'_, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(MapDeserializer {
+            iter: self.entries.into_iter(),
+            value: None,
+            path: &Path::Root,
+            state: self.state,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
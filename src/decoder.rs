@@ -0,0 +1,143 @@
+//! A decoder that reads the messages of a stream into pooled buffers.
+
+use crate::framing::split_frame;
+use bytes::{Buf, Bytes, BytesMut};
+use std::{
+    io::{self, Read},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+/// The capacity of the buffers that a [Decoder] reads into, unless a message is larger.
+const BUFFER_CAPACITY: usize = 64 * 1024;
+
+/// The minimum number of bytes that a [Decoder] tries to read at once.
+const MIN_READ: usize = 4 * 1024;
+
+/// A pool of buffers that [Decoder]s read messages into.
+///
+/// The content of a message is handed out as [Bytes] that share the buffer it was read into. Once
+/// all messages of a buffer are dropped, the buffer is reused instead of allocating a new one.
+/// Clones share the same buffers, so one pool can serve many decoders.
+#[derive(Clone, Debug)]
+pub struct BufferPool {
+    buffers: Arc<Mutex<Vec<BytesMut>>>,
+    max_buffers: usize,
+}
+
+impl BufferPool {
+    /// Creates a pool that keeps at most `max_buffers` unused buffers.
+    pub fn new(max_buffers: usize) -> BufferPool {
+        BufferPool {
+            buffers: Arc::default(),
+            max_buffers,
+        }
+    }
+
+    /// Takes an empty buffer with room for at least `capacity` bytes from the pool. If no pooled
+    /// buffer has enough room that isn't used by live messages, a new buffer is allocated.
+    pub fn take(&self, capacity: usize) -> BytesMut {
+        let mut buffers = self.buffers.lock().unwrap_or_else(PoisonError::into_inner);
+        let reusable = buffers.iter_mut().position(|buffer| {
+            buffer.clear();
+            buffer.try_reclaim(capacity)
+        });
+        match reusable {
+            Some(index) => buffers.swap_remove(index),
+            None => BytesMut::with_capacity(capacity),
+        }
+    }
+
+    /// Returns a buffer to the pool. If the pool is full the buffer is dropped.
+    pub fn give(&self, buffer: BytesMut) {
+        let mut buffers = self.buffers.lock().unwrap_or_else(PoisonError::into_inner);
+        if buffers.len() < self.max_buffers {
+            buffers.push(buffer);
+        }
+    }
+}
+
+impl Default for BufferPool {
+    /// Creates a pool that keeps at most 4 unused buffers.
+    fn default() -> BufferPool {
+        BufferPool::new(4)
+    }
+}
+
+/// Reads messages with Content-Length headers from a byte stream.
+///
+/// Instead of allocating a `Vec` for every message, the decoder reads into large buffers from a
+/// [BufferPool] and hands out the content of each message as a slice of such a buffer. Parse it
+/// with [ProtocolMessage::from_slice](crate::ProtocolMessage::from_slice) or forward it as is.
+#[derive(Debug)]
+pub struct Decoder<R> {
+    reader: R,
+    buffer: BytesMut,
+    pool: BufferPool,
+}
+
+impl<R: Read> Decoder<R> {
+    /// Creates a decoder with its own [BufferPool].
+    pub fn new(reader: R) -> Decoder<R> {
+        Decoder::with_pool(reader, BufferPool::default())
+    }
+
+    /// Creates a decoder that takes its buffers from `pool`.
+    pub fn with_pool(reader: R, pool: BufferPool) -> Decoder<R> {
+        Decoder {
+            reader,
+            buffer: pool.take(BUFFER_CAPACITY),
+            pool,
+        }
+    }
+
+    /// Reads the content of the next message. Returns `None` if the stream ends between two
+    /// messages.
+    pub fn read_frame(&mut self) -> io::Result<Option<Bytes>> {
+        loop {
+            let frame = split_frame(&self.buffer)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?
+                .map(|(content, frame_len)| (frame_len - content.len(), content.len()));
+            if let Some((header_len, content_len)) = frame {
+                self.buffer.advance(header_len);
+                return Ok(Some(self.buffer.split_to(content_len).freeze()));
+            }
+            match self.read() {
+                Ok(0) if self.buffer.is_empty() => return Ok(None),
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(_) => {}
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Reads more bytes into the buffer, switching to a larger buffer if there is not enough room.
+    fn read(&mut self) -> io::Result<usize> {
+        let len = self.buffer.len();
+        if self.buffer.capacity() - len < MIN_READ {
+            let capacity = BUFFER_CAPACITY.max(2 * len).max(len + MIN_READ);
+            let mut buffer = self.pool.take(capacity);
+            buffer.extend_from_slice(&self.buffer);
+            let previous = std::mem::replace(&mut self.buffer, buffer);
+            self.pool.give(previous);
+        }
+        self.buffer.resize(self.buffer.capacity(), 0);
+        let result = self.reader.read(&mut self.buffer[len..]);
+        self.buffer.truncate(len + *result.as_ref().unwrap_or(&0));
+        result
+    }
+}
+
+impl<R> Drop for Decoder<R> {
+    fn drop(&mut self) {
+        self.pool.give(std::mem::take(&mut self.buffer));
+    }
+}
+
+impl<R: Read> Iterator for Decoder<R> {
+    type Item = io::Result<Bytes>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_frame().transpose()
+    }
+}
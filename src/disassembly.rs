@@ -0,0 +1,225 @@
+//! A client side model of a disassembly view that scrolls around an instruction pointer.
+//!
+//! The 'disassemble' request addresses instructions by a memory reference, usually the
+//! instruction pointer of a stack frame, and an offset in instructions from it. Instructions
+//! before the reference have negative offsets. A [DisassemblyView] keeps the instructions it
+//! received by their offset from its memory reference and by their address, and computes the
+//! requests for the instructions that are missing after the view was scrolled:
+//!
+//! 1. [DisassemblyView::fetch] returns the request for the instructions that are missing in the
+//!    visible rows, extended by a page in both directions so that scrolling does not need to wait
+//!    for every row.
+//! 2. [DisassemblyView::handle_response] stores the instructions the debug adapter returned.
+//! 3. [DisassemblyView::scroll] moves the visible rows and returns the next request, if any.
+
+use crate::{
+    requests::DisassembleRequestArguments, responses::DisassembleResponseBody,
+    types::DisassembledInstruction, Str,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Display},
+};
+
+/// Why a response could not be stored in a [DisassemblyView].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DisassemblyError {
+    /// The request was for another memory reference than the one of the view.
+    OtherMemoryReference,
+
+    /// The request had a byte offset, so the instruction offsets of the response are not relative
+    /// to the memory reference of the view.
+    ByteOffset,
+
+    /// The debug adapter returned another number of instructions than requested. The specification
+    /// requires it to fill unavailable instructions with invalid ones, because otherwise the
+    /// offsets of the instructions can't be determined.
+    WrongCount { expected: i32, actual: usize },
+}
+
+impl Display for DisassemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisassemblyError::OtherMemoryReference => {
+                f.write_str("the request is for another memory reference")
+            }
+            DisassemblyError::ByteOffset => f.write_str("the request has a byte offset"),
+            DisassemblyError::WrongCount { expected, actual } => {
+                write!(f, "expected {} instructions, but got {}", expected, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DisassemblyError {}
+
+/// Parses the address of a [DisassembledInstruction], which is hexadecimal if prefixed with `0x`
+/// and decimal otherwise.
+pub fn parse_address(address: &str) -> Option<u64> {
+    match address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => address.parse().ok(),
+    }
+}
+
+/// The rows of a disassembly view and the instructions received for it. See the
+/// [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct DisassemblyView {
+    memory_reference: Str,
+    resolve_symbols: bool,
+    rows: i32,
+    top: i32,
+    instructions: BTreeMap<i32, DisassembledInstruction>,
+    addresses: HashMap<u64, i32>,
+}
+
+impl DisassemblyView {
+    /// Creates a view of `rows` instructions centered on `memory_reference`, usually the
+    /// instruction pointer of a stack frame.
+    pub fn new(memory_reference: impl Into<Str>, rows: usize) -> DisassemblyView {
+        let rows = i32::try_from(rows.max(1)).unwrap_or(i32::MAX);
+        DisassemblyView {
+            memory_reference: memory_reference.into(),
+            resolve_symbols: false,
+            rows,
+            top: -(rows / 2),
+            instructions: BTreeMap::new(),
+            addresses: HashMap::new(),
+        }
+    }
+
+    /// Sets whether requests ask the debug adapter to resolve addresses to symbolic names.
+    pub fn set_resolve_symbols(&mut self, resolve_symbols: bool) {
+        self.resolve_symbols = resolve_symbols;
+    }
+
+    pub fn memory_reference(&self) -> &str {
+        &self.memory_reference
+    }
+
+    /// The offset of the first visible row from the memory reference, in instructions.
+    pub fn top(&self) -> i32 {
+        self.top
+    }
+
+    /// The number of visible rows.
+    pub fn rows(&self) -> usize {
+        self.rows as usize
+    }
+
+    /// Creates the request for `count` instructions starting `instruction_offset` instructions
+    /// from the memory reference of the view.
+    pub fn request(&self, instruction_offset: i32, count: i32) -> DisassembleRequestArguments {
+        DisassembleRequestArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: 0,
+            instruction_offset,
+            instruction_count: count,
+            resolve_symbols: self.resolve_symbols,
+        }
+    }
+
+    /// Returns the request for the instructions that are missing in the visible rows, extended by
+    /// a page in both directions, or `None` if all visible instructions were received.
+    pub fn fetch(&self) -> Option<DisassembleRequestArguments> {
+        let visible = self.top..self.top.saturating_add(self.rows);
+        let first_missing = visible
+            .clone()
+            .find(|offset| !self.instructions.contains_key(offset))?;
+        let last_missing = visible
+            .rev()
+            .find(|offset| !self.instructions.contains_key(offset))?;
+        let start = self.extend_missing(first_missing, -1);
+        let end = self.extend_missing(last_missing, 1);
+        Some(self.request(start, end - start + 1))
+    }
+
+    /// Extends the missing `offset` by up to a page of missing instructions in `direction`.
+    fn extend_missing(&self, offset: i32, direction: i32) -> i32 {
+        let mut extended = offset;
+        for _ in 0..self.rows {
+            let next = extended.saturating_add(direction);
+            if next == extended || self.instructions.contains_key(&next) {
+                break;
+            }
+            extended = next;
+        }
+        extended
+    }
+
+    /// Scrolls the view by `rows`, up if negative and down if positive, and returns the request for
+    /// the instructions that are missing now.
+    pub fn scroll(&mut self, rows: i32) -> Option<DisassembleRequestArguments> {
+        self.top = self.top.saturating_add(rows);
+        self.fetch()
+    }
+
+    /// Scrolls the view so that the instruction at `address` is in the middle, if it was received.
+    /// Returns whether it was.
+    pub fn reveal(&mut self, address: u64) -> bool {
+        match self.addresses.get(&address) {
+            Some(offset) => {
+                self.top = offset.saturating_sub(self.rows / 2);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stores the instructions of `response`, which answers `request`.
+    pub fn handle_response(
+        &mut self,
+        request: &DisassembleRequestArguments,
+        response: &DisassembleResponseBody,
+    ) -> Result<(), DisassemblyError> {
+        if request.memory_reference != self.memory_reference {
+            return Err(DisassemblyError::OtherMemoryReference);
+        }
+        if request.offset != 0 {
+            return Err(DisassemblyError::ByteOffset);
+        }
+        if usize::try_from(request.instruction_count).ok() != Some(response.instructions.len()) {
+            return Err(DisassemblyError::WrongCount {
+                expected: request.instruction_count,
+                actual: response.instructions.len(),
+            });
+        }
+        for (offset, instruction) in (request.instruction_offset..).zip(&response.instructions) {
+            if let Some(address) = parse_address(&instruction.address) {
+                self.addresses.insert(address, offset);
+            }
+            self.instructions.insert(offset, instruction.clone());
+        }
+        Ok(())
+    }
+
+    /// The instruction `offset` instructions from the memory reference, if it was received.
+    pub fn get(&self, offset: i32) -> Option<&DisassembledInstruction> {
+        self.instructions.get(&offset)
+    }
+
+    /// The received instruction at `address`.
+    pub fn get_by_address(&self, address: u64) -> Option<&DisassembledInstruction> {
+        self.addresses
+            .get(&address)
+            .and_then(|offset| self.instructions.get(offset))
+    }
+
+    /// The visible rows with their offsets and instructions, or `None` for instructions that were
+    /// not received yet.
+    pub fn visible(&self) -> impl Iterator<Item = (i32, Option<&DisassembledInstruction>)> {
+        (self.top..self.top.saturating_add(self.rows))
+            .map(move |offset| (offset, self.instructions.get(&offset)))
+    }
+
+    /// Forgets all received instructions, for example after the debuggee modified its code.
+    pub fn clear(&mut self) {
+        self.instructions.clear();
+        self.addresses.clear();
+    }
+}
@@ -0,0 +1,560 @@
+//! Fast dispatch on the tag of the adjacently tagged [Request](crate::requests::Request),
+//! [SuccessResponse](crate::responses::SuccessResponse) and [Event](crate::events::Event).
+//!
+//! The derived Deserialize impls compare the tag with the name of every variant in turn. Instead,
+//! the tag is looked up with a binary search and the derived impls are given the index of the
+//! variant, which they accept in place of its name.
+//!
+//! Many debug adapters send an empty object as the content of a variant without content, or omit
+//! the content if all of its attributes are optional. So content that follows the tag is
+//! deserialized through [Content] and missing content with [missing_content].
+//!
+//! Each enum has a fallback variant after the named ones, like
+//! [Request::Custom](crate::requests::Request::Custom), whose fields are named like the tag and the
+//! content. A message with an unknown tag is deserialized as this variant with the tag and the raw
+//! content. Since the variant is only known once the tag is, content that precedes the tag is
+//! buffered and fed after it.
+
+use serde::{
+    de::{
+        value::{MapDeserializer, StrDeserializer, U32Deserializer},
+        DeserializeSeed, Deserializer, Error, IgnoredAny, MapAccess, SeqAccess, Unexpected,
+        Visitor,
+    },
+    forward_to_deserialize_any,
+};
+use serde_json::{Map, Value};
+use std::{fmt, marker::PhantomData, sync::OnceLock};
+
+/// The names of the variants of an enum in declaration order, which is the order of the variant
+/// indices. The named variants are followed by the fallback variant.
+pub(crate) struct Variants {
+    names: &'static [&'static str],
+    sorted: OnceLock<Vec<(&'static str, u32)>>,
+}
+
+impl Variants {
+    pub(crate) const fn new(names: &'static [&'static str]) -> Variants {
+        Variants {
+            names,
+            sorted: OnceLock::new(),
+        }
+    }
+
+    /// The index of the variant with the given name.
+    pub(crate) fn index(&self, name: &str) -> Option<u32> {
+        let sorted = self.sorted.get_or_init(|| {
+            let mut sorted = self.names.iter().copied().zip(0..).collect::<Vec<_>>();
+            sorted.sort_unstable();
+            sorted
+        });
+        let index = sorted.binary_search_by_key(&name, |(name, _)| name).ok()?;
+        Some(sorted[index].1)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn names(&self) -> &'static [&'static str] {
+        self.names
+    }
+}
+
+/// Feeds the index of a variant to a derived Deserialize impl if `name` is known. Otherwise the
+/// index of the fallback variant is fed and `name` is stored in `fallback`.
+fn deserialize_tag<'de, S, E>(
+    seed: S,
+    name: &str,
+    variants: &Variants,
+    fallback: &mut Option<String>,
+) -> Result<S::Value, E>
+where
+    S: DeserializeSeed<'de>,
+    E: Error,
+{
+    match variants.index(name) {
+        Some(index) => seed.deserialize(U32Deserializer::new(index)),
+        None => {
+            *fallback = Some(name.to_string());
+            seed.deserialize(U32Deserializer::new(variants.names.len() as u32))
+        }
+    }
+}
+
+/// Wraps the deserializer of an adjacently tagged enum to look up the tag in `variants`.
+pub(crate) struct TagDispatch<D> {
+    pub(crate) deserializer: D,
+    pub(crate) tag: &'static str,
+    pub(crate) content: &'static str,
+    pub(crate) variants: &'static Variants,
+}
+
+impl<D> TagDispatch<D> {
+    fn visitor<V>(&self, visitor: V) -> TagVisitor<V> {
+        TagVisitor {
+            visitor,
+            tag: self.tag,
+            content: self.content,
+            variants: self.variants,
+        }
+    }
+}
+
+impl<'de, D> Deserializer<'de> for TagDispatch<D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let visitor = self.visitor(visitor);
+        self.deserializer.deserialize_any(visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let visitor = self.visitor(visitor);
+        self.deserializer.deserialize_struct(name, fields, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct TagVisitor<V> {
+    visitor: V,
+    tag: &'static str,
+    content: &'static str,
+    variants: &'static Variants,
+}
+
+impl<'de, V> Visitor<'de> for TagVisitor<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<V::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.visitor.visit_map(TagMap {
+            map,
+            tag: self.tag,
+            content: self.content,
+            variants: self.variants,
+            at: None,
+            seen_tag: false,
+            seen_content: false,
+            fallback: None,
+            buffered: None,
+        })
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<V::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.visitor.visit_seq(seq)
+    }
+}
+
+struct TagMap<A> {
+    map: A,
+    tag: &'static str,
+    content: &'static str,
+    variants: &'static Variants,
+    at: Option<Key>,
+    seen_tag: bool,
+    seen_content: bool,
+    /// The tag if it is unknown and fed as the fallback variant.
+    fallback: Option<String>,
+    /// The content if it precedes the tag.
+    buffered: Option<Value>,
+}
+
+impl<'de, A> MapAccess<'de> for TagMap<A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let key = loop {
+            let key = match self.map.next_key_seed(KeySeed {
+                tag: self.tag,
+                content: self.content,
+            })? {
+                Some(key) => key,
+                None if self.buffered.is_some() => Key::Buffered,
+                None if self.seen_tag && !self.seen_content => Key::Missing,
+                None => return Ok(None),
+            };
+            if key == Key::Content && !self.seen_tag {
+                self.buffered = Some(self.map.next_value()?);
+                continue;
+            }
+            break key;
+        };
+        match key {
+            Key::Tag => self.seen_tag = true,
+            Key::Content | Key::Missing | Key::Buffered => self.seen_content = true,
+            Key::Other => {}
+        }
+        self.at = Some(key);
+        // The derived impls ignore unknown keys, so their names don't matter
+        let key = match key {
+            Key::Tag => self.tag,
+            Key::Content | Key::Missing | Key::Buffered => self.content,
+            Key::Other => "",
+        };
+        seed.deserialize(StrDeserializer::new(key)).map(Some)
+    }
+
+    fn next_value_seed<S>(&mut self, seed: S) -> Result<S::Value, A::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        match self.at.take() {
+            Some(Key::Tag) => self.map.next_value_seed(TagSeed {
+                seed,
+                variants: self.variants,
+                fallback: &mut self.fallback,
+            }),
+            Some(key @ (Key::Content | Key::Missing | Key::Buffered))
+                if self.fallback.is_some() =>
+            {
+                let content = match key {
+                    Key::Content => self.map.next_value()?,
+                    _ => self.buffered.take(),
+                };
+                let name = self.fallback.take().unwrap_or_default();
+                fallback(seed, (self.tag, name), (self.content, content))
+            }
+            Some(Key::Buffered) => match self.buffered.take() {
+                Some(content) => ContentSeed(seed).deserialize(Buffered::new(content)),
+                None => missing_content(seed, self.content),
+            },
+            Some(Key::Content) => self.map.next_value_seed(ContentSeed(seed)),
+            Some(Key::Missing) => missing_content(seed, self.content),
+            Some(Key::Other) | None => self.map.next_value_seed(seed),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.map.size_hint()
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Key {
+    Tag,
+    Content,
+    Other,
+    /// The content after the last key, if the map has none.
+    Missing,
+    /// The content after the last key, if it preceded the tag.
+    Buffered,
+}
+
+struct KeySeed {
+    tag: &'static str,
+    content: &'static str,
+}
+
+impl<'de> DeserializeSeed<'de> for KeySeed {
+    type Value = Key;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Key, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl Visitor<'_> for KeySeed {
+    type Value = Key;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a field name")
+    }
+
+    fn visit_str<E: Error>(self, key: &str) -> Result<Key, E> {
+        Ok(if key == self.tag {
+            Key::Tag
+        } else if key == self.content {
+            Key::Content
+        } else {
+            Key::Other
+        })
+    }
+
+    fn visit_bytes<E: Error>(self, key: &[u8]) -> Result<Key, E> {
+        match std::str::from_utf8(key) {
+            Ok(key) => self.visit_str(key),
+            Err(_) => Ok(Key::Other),
+        }
+    }
+}
+
+/// Deserializes the name of a variant and feeds its index to `seed`.
+struct TagSeed<'a, S> {
+    seed: S,
+    variants: &'static Variants,
+    fallback: &'a mut Option<String>,
+}
+
+impl<'de, S> DeserializeSeed<'de> for TagSeed<'_, S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<S::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, S> Visitor<'de> for TagSeed<'_, S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a variant name")
+    }
+
+    fn visit_str<E: Error>(self, name: &str) -> Result<S::Value, E> {
+        deserialize_tag(self.seed, name, self.variants, self.fallback)
+    }
+}
+
+/// Deserializes `seed` from the deserializer wrapped in [Content].
+struct ContentSeed<S>(S);
+
+impl<'de, S> DeserializeSeed<'de> for ContentSeed<S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<S::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.0.deserialize(Content(deserializer))
+    }
+}
+
+/// Wraps the deserializer of the content of a variant to accept an object for variants without
+/// content. Its attributes are ignored like unknown fields.
+struct Content<D>(D);
+
+impl<'de, D> Deserializer<'de> for Content<D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    // The derived impls deserialize the content of variants without content with deserialize_any
+    // and the content of all other variants, which are structs, with deserialize_struct
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_any(UnitVisitor(visitor))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_map(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct enum identifier ignored_any
+    }
+}
+
+/// Visits an object as unit.
+struct UnitVisitor<V>(V);
+
+impl<'de, V> Visitor<'de> for UnitVisitor<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(formatter)
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<V::Value, E> {
+        self.0.visit_unit()
+    }
+
+    fn visit_none<E: Error>(self) -> Result<V::Value, E> {
+        self.0.visit_none()
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<V::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+        self.0.visit_unit()
+    }
+
+    fn visit_seq<A>(self, _seq: A) -> Result<V::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Err(A::Error::invalid_type(Unexpected::Seq, &self))
+    }
+}
+
+/// Deserializes missing content like an empty object, which succeeds if all of its attributes are
+/// optional. Otherwise the error is that `content` is missing.
+fn missing_content<'de, S, E>(seed: S, content: &'static str) -> Result<S::Value, E>
+where
+    S: DeserializeSeed<'de>,
+    E: Error,
+{
+    let empty = MapDeserializer::<_, E>::new(std::iter::empty::<(&str, ())>());
+    seed.deserialize(Content(empty))
+        .map_err(|_| E::missing_field(content))
+}
+
+/// Deserializes a fallback variant from its fields, which are named like the tag and the content.
+fn fallback<'de, S, E>(
+    seed: S,
+    (tag, name): (&str, String),
+    (content, value): (&str, Option<Value>),
+) -> Result<S::Value, E>
+where
+    S: DeserializeSeed<'de>,
+    E: Error,
+{
+    let mut fields = Map::new();
+    fields.insert(tag.to_string(), Value::String(name));
+    if let Some(value) = value {
+        fields.insert(content.to_string(), value);
+    }
+    seed.deserialize(Buffered::new(Value::Object(fields)))
+}
+
+/// Deserializes a buffered value with the error type of the deserializer it was read from.
+struct Buffered<E> {
+    value: Value,
+    error: PhantomData<E>,
+}
+
+impl<E> Buffered<E> {
+    fn new(value: Value) -> Buffered<E> {
+        Buffered {
+            value,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<'de, E> Deserializer<'de> for Buffered<E>
+where
+    E: Error,
+{
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.value.deserialize_any(visitor).map_err(E::custom)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.value
+            .deserialize_struct(name, fields, visitor)
+            .map_err(E::custom)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.value.deserialize_option(visitor).map_err(E::custom)
+    }
+
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.value
+            .deserialize_newtype_struct(name, visitor)
+            .map_err(E::custom)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E>
+    where
+        V: Visitor<'de>,
+    {
+        self.value
+            .deserialize_enum(name, variants, visitor)
+            .map_err(E::custom)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map identifier ignored_any
+    }
+}
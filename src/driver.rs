@@ -0,0 +1,266 @@
+//! A sans-IO driver for the client side of a session.
+//!
+//! [Driver] bundles the framing, the allocation of sequence numbers, the correlation of responses
+//! with their requests and the lifecycle of the session, but does no IO itself. Feed it the bytes
+//! read from the debug adapter with [Driver::handle_input], take what it decoded with
+//! [Driver::poll_event] and write the bytes returned by [Driver::poll_output] to the debug
+//! adapter. This way the protocol logic can be embedded into any event loop, synchronous or async.
+
+use crate::{
+    correlation::{CorrelationError, PendingRequests},
+    error::{FrameError, ParseError},
+    events::Event,
+    framing::{split_frame, write_framed},
+    requests::Request,
+    responses::{Response, SuccessResponse},
+    sources::{Counter, SequenceSource},
+    types::Capabilities,
+    ProtocolMessage, ProtocolMessageContent, SequenceNumber,
+};
+use std::{
+    collections::VecDeque,
+    fmt::{self, Display},
+};
+
+/// The lifecycle of a session as seen by the client.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SessionState {
+    /// The 'initialize' request has not been sent yet.
+    Uninitialized,
+
+    /// The 'initialize' request has been sent, but not answered.
+    Initializing,
+
+    /// The debug adapter answered the 'initialize' request successfully.
+    Initialized,
+
+    /// The debug adapter sent the 'terminated' event.
+    Terminated,
+
+    /// The debug adapter answered the 'disconnect' request. No further messages are expected.
+    Disconnected,
+}
+
+/// Something the [Driver] decoded from its input.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum DriverEvent {
+    /// A response to a request that was sent with [Driver::enqueue_request].
+    Response {
+        request: Request,
+        response: Response,
+    },
+
+    /// A response that does not answer an outstanding request.
+    UncorrelatedResponse {
+        response: Response,
+        error: CorrelationError,
+    },
+
+    /// A reverse request of the debug adapter, like 'runInTerminal'. Answer it with
+    /// [Driver::enqueue_response].
+    Request {
+        seq: SequenceNumber,
+        request: Request,
+    },
+
+    Event(Event),
+
+    /// The [SessionState] changed.
+    StateChanged(SessionState),
+}
+
+/// An error decoding the input of a [Driver].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DriverError {
+    /// The header of a message is invalid. The stream can't be resynchronized.
+    Frame(FrameError),
+
+    /// The content of a message is invalid. The message was skipped.
+    Parse(ParseError),
+}
+
+impl Display for DriverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DriverError::Frame(error) => write!(f, "invalid frame: {}", error),
+            DriverError::Parse(error) => write!(f, "invalid message: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for DriverError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DriverError::Frame(error) => Some(error),
+            DriverError::Parse(error) => Some(error),
+        }
+    }
+}
+
+/// The protocol logic of a client without IO. See the [module documentation](self).
+///
+/// The sequence numbers of sent messages are taken from `S`, which counts up from 1 by default.
+#[derive(Debug)]
+pub struct Driver<S = Counter> {
+    input: Vec<u8>,
+    output: Vec<u8>,
+    events: VecDeque<DriverEvent>,
+    pending: PendingRequests,
+    sequence: S,
+    state: SessionState,
+    capabilities: Option<Capabilities>,
+}
+
+impl Default for Driver {
+    fn default() -> Driver {
+        Driver::with_sequence(Counter::new())
+    }
+}
+
+impl Driver {
+    pub fn new() -> Driver {
+        Driver::default()
+    }
+}
+
+impl<S: SequenceSource> Driver<S> {
+    /// Creates a driver that assigns the sequence numbers from `sequence`.
+    pub fn with_sequence(sequence: S) -> Driver<S> {
+        Driver {
+            input: Vec::new(),
+            output: Vec::new(),
+            events: VecDeque::new(),
+            pending: PendingRequests::new(),
+            sequence,
+            state: SessionState::Uninitialized,
+            capabilities: None,
+        }
+    }
+
+    /// The current state of the session.
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    /// The capabilities of the debug adapter from its response to the 'initialize' request.
+    pub fn capabilities(&self) -> Option<&Capabilities> {
+        self.capabilities.as_ref()
+    }
+
+    /// The requests that have been sent but not yet answered.
+    pub fn pending_requests(&self) -> &PendingRequests {
+        &self.pending
+    }
+
+    /// Queues `request` for sending and returns the sequence number it was assigned.
+    pub fn enqueue_request(&mut self, request: Request) -> SequenceNumber {
+        if let Request::Initialize(_) = request {
+            self.set_state(SessionState::Initializing);
+        }
+        let seq = self.enqueue(request.clone());
+        self.pending.insert(seq, request);
+        seq
+    }
+
+    /// Queues the response to a reverse request for sending and returns the sequence number it
+    /// was assigned.
+    pub fn enqueue_response(&mut self, response: Response) -> SequenceNumber {
+        self.enqueue(response)
+    }
+
+    fn enqueue(&mut self, content: impl Into<ProtocolMessageContent>) -> SequenceNumber {
+        let seq = self.sequence.next_seq();
+        write_framed(&mut self.output, &ProtocolMessage::new(seq, content))
+            .expect("failed to serialize message");
+        seq
+    }
+
+    /// Returns the bytes to send to the debug adapter, or `None` if there are none.
+    pub fn poll_output(&mut self) -> Option<Vec<u8>> {
+        if self.output.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.output))
+        }
+    }
+
+    /// Returns the next thing decoded from the input, or `None` if there is none.
+    pub fn poll_event(&mut self) -> Option<DriverEvent> {
+        self.events.pop_front()
+    }
+
+    /// Decodes the messages in `input`, which was read from the debug adapter. Incomplete messages
+    /// are buffered until the rest arrives.
+    ///
+    /// Decoding stops at the first invalid message. After a [DriverError::Parse] the message is
+    /// skipped, so call this again with an empty `input` to decode the rest of the buffer.
+    pub fn handle_input(&mut self, input: &[u8]) -> Result<(), DriverError> {
+        self.input.extend_from_slice(input);
+        let mut consumed = 0;
+        let result = loop {
+            let (content, frame_len) = match split_frame(&self.input[consumed..]) {
+                Ok(Some(frame)) => frame,
+                Ok(None) => break Ok(()),
+                Err(error) => break Err(DriverError::Frame(error)),
+            };
+            let message = ProtocolMessage::from_slice(content);
+            consumed += frame_len;
+            match message {
+                Ok(message) => self.handle_message(message),
+                Err(error) => break Err(DriverError::Parse(error)),
+            }
+        };
+        self.input.drain(..consumed);
+        result
+    }
+
+    fn handle_message(&mut self, message: ProtocolMessage) {
+        match message.content {
+            ProtocolMessageContent::Request(request) => {
+                self.events.push_back(DriverEvent::Request {
+                    seq: message.seq,
+                    request,
+                });
+            }
+            ProtocolMessageContent::Response(response) => self.handle_response(response),
+            ProtocolMessageContent::Event(event) => {
+                if let Event::Terminated(_) = event {
+                    self.set_state(SessionState::Terminated);
+                }
+                self.events.push_back(DriverEvent::Event(event));
+            }
+        }
+    }
+
+    fn handle_response(&mut self, response: Response) {
+        let request = match self.pending.complete(&response) {
+            Ok(request) => request,
+            Err(error) => {
+                let event = DriverEvent::UncorrelatedResponse { response, error };
+                self.events.push_back(event);
+                return;
+            }
+        };
+        match (&request, &response.result) {
+            (Request::Initialize(_), Ok(SuccessResponse::Initialize(capabilities))) => {
+                self.capabilities = Some((**capabilities).clone());
+                self.set_state(SessionState::Initialized);
+            }
+            (Request::Initialize(_), _) => self.set_state(SessionState::Uninitialized),
+            (Request::Disconnect(_), Ok(_)) => self.set_state(SessionState::Disconnected),
+            _ => {}
+        }
+        self.events
+            .push_back(DriverEvent::Response { request, response });
+    }
+
+    fn set_state(&mut self, state: SessionState) {
+        if self.state != state {
+            self.state = state;
+            self.events.push_back(DriverEvent::StateChanged(state));
+        }
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "de")]
+use serde::Deserialize;
+#[cfg(feature = "ser")]
+use serde::Serialize;
+use std::fmt::{self, Display};
+#[cfg(feature = "ts-rs")]
+use ts_rs::TS;
+
+/// The kind of a protocol message as specified by its 'type' attribute.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum MessageKind {
+    Request,
+    Response,
+    Event,
+}
+
+impl MessageKind {
+    #[cfg(feature = "de")]
+    pub(crate) fn from_type(type_: &str) -> Option<MessageKind> {
+        match type_ {
+            "request" => Some(MessageKind::Request),
+            "response" => Some(MessageKind::Response),
+            "event" => Some(MessageKind::Event),
+            _ => None,
+        }
+    }
+}
+
+impl Display for MessageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MessageKind::Request => "request",
+            MessageKind::Response => "response",
+            MessageKind::Event => "event",
+        })
+    }
+}
+
+/// An error that occurred while parsing a [ProtocolMessage](crate::ProtocolMessage).
+///
+/// In addition to the underlying JSON error this reports as much context as could be determined:
+/// the kind of the message, the command or event name and the path of the offending field.
+#[derive(Debug)]
+pub struct ParseError {
+    kind: Option<MessageKind>,
+    name: Option<String>,
+    path: Option<String>,
+    source: serde_json::Error,
+}
+
+impl ParseError {
+    #[cfg(feature = "de")]
+    pub(crate) fn new(
+        kind: Option<MessageKind>,
+        name: Option<String>,
+        path: Option<String>,
+        source: serde_json::Error,
+    ) -> ParseError {
+        ParseError {
+            kind,
+            name,
+            path,
+            source,
+        }
+    }
+
+    /// The kind of the message, if the 'type' attribute could be read.
+    pub fn kind(&self) -> Option<MessageKind> {
+        self.kind
+    }
+
+    /// The command of a request or response or the event of an event, if it could be read.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The path of the offending field relative to the message, for example
+    /// `body.stackFrames[3].line`.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Returns true if the input was not syntactically valid JSON.
+    pub fn is_syntax(&self) -> bool {
+        self.source.is_syntax() || self.source.is_eof()
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to parse ")?;
+        match self.kind {
+            Some(kind) => write!(f, "{}", kind)?,
+            None => f.write_str("message")?,
+        }
+        if let Some(name) = &self.name {
+            write!(f, " '{}'", name)?;
+        }
+        if let Some(path) = self.path.as_deref().filter(|path| !path.is_empty()) {
+            write!(f, " at '{}'", path)?;
+        }
+        write!(f, ": {}", self.source)
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// An error in the header of a message read with [split_frame](crate::framing::split_frame).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FrameError {
+    /// A header line is not of the form `name: value`.
+    InvalidHeader,
+
+    /// The header has no Content-Length field.
+    MissingContentLength,
+
+    /// The value of the Content-Length field is not a non-negative integer.
+    InvalidContentLength,
+
+    /// The stream ends within a message.
+    Truncated,
+}
+
+impl Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FrameError::InvalidHeader => "invalid header line",
+            FrameError::MissingContentLength => "missing Content-Length header",
+            FrameError::InvalidContentLength => "invalid Content-Length header",
+            FrameError::Truncated => "stream ends within a message",
+        })
+    }
+}
+
+impl std::error::Error for FrameError {}
@@ -1,18 +1,42 @@
+#[cfg(feature = "de")]
+use crate::dispatch::{TagDispatch, Variants};
+#[cfg(any(feature = "ser", feature = "schemars"))]
+use crate::utils::eq_default;
 use crate::{
     types::{Breakpoint, Capabilities, InvalidatedAreas, Module, Source},
-    utils::eq_default,
-    ProtocolMessageContent,
+    ProtocolMessageContent, SmallVec, Str,
 };
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "ser")]
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+#[cfg(feature = "de")]
+use serde::{Deserialize, Deserializer};
 use serde_json::Value;
+#[cfg(feature = "ts-rs")]
+use ts_rs::TS;
+#[cfg(feature = "builders")]
 use typed_builder::TypedBuilder;
 
 /// A debug adapter initiated event.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(rename_all = "camelCase", tag = "event", content = "body")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[serde(
+    remote = "Self",
+    rename_all = "camelCase",
+    tag = "event",
+    content = "body"
+)]
+#[non_exhaustive]
 pub enum Event {
     /// The event indicates that some information about a breakpoint has changed.
-    Breakpoint(BreakpointEventBody),
+    Breakpoint(Box<BreakpointEventBody>),
 
     /// The event indicates that one or more capabilities have changed.
     ///
@@ -21,7 +45,7 @@ pub enum Event {
     /// Consequently this event has a hint characteristic: a frontend can only be expected to make a 'best effort' in honouring individual capabilities but there are no guarantees.
     ///
     /// Only changed capabilities need to be included, all other capabilities keep their values.
-    Capabilities(CapabilitiesEventBody),
+    Capabilities(Box<CapabilitiesEventBody>),
 
     /// The event indicates that the execution of the debuggee has continued.
     ///
@@ -60,13 +84,13 @@ pub enum Event {
     Invalidated(InvalidatedEventBody),
 
     /// The event indicates that some source has been added, changed, or removed from the set of all loaded sources.
-    LoadedSource(LoadedSourceEventBody),
+    LoadedSource(Box<LoadedSourceEventBody>),
 
     /// The event indicates that some information about a module has changed.
-    Module(ModuleEventBody),
+    Module(Box<ModuleEventBody>),
 
     /// The event indicates that the target has produced some output.
-    Output(OutputEventBody),
+    Output(Box<OutputEventBody>),
 
     /// The event indicates that the debugger has begun debugging a new process. Either one that it has launched, or one that it has attached to.
     Process(ProcessEventBody),
@@ -83,7 +107,7 @@ pub enum Event {
     /// The client is free to delay the showing of the UI in order to reduce flicker.
     ///
     /// This event should only be sent if the client has passed the value true for the 'supportsProgressReporting' capability of the 'initialize' request.
-    ProgressStart(ProgressStartEventBody),
+    ProgressStart(Box<ProgressStartEventBody>),
 
     /// The event signals that the progress reporting needs to updated with a new message and/or percentage.
     ///
@@ -95,21 +119,125 @@ pub enum Event {
     /// The event indicates that the execution of the debuggee has stopped due to some condition.
     ///
     /// This can be caused by a break point previously set, a stepping request has completed, by executing a debugger statement etc.
-    Stopped(StoppedEventBody),
+    Stopped(Box<StoppedEventBody>),
 
     /// The event indicates that debugging of the debuggee has terminated. This does **not** mean that the debuggee itself has exited.
     Terminated(TerminatedEventBody),
 
     /// The event indicates that a thread has started or exited.
     Thread(ThreadEventBody),
+
+    /// An event that is not part of the specification, like the telemetry or extensions of some
+    /// debug adapters. Events of an unknown type deserialize as this variant.
+    #[cfg_attr(feature = "ser", serde(skip_serializing))]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    #[cfg_attr(feature = "ts-rs", ts(skip))]
+    Custom {
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))]
+        event: Str,
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_boxed_value))]
+        body: Option<Box<Value>>,
+    },
+}
+
+// Large variants are boxed to keep every message small
+const _: () = assert!(std::mem::size_of::<Event>() <= 80);
+impl Event {
+    /// The type of this event as it is sent in the 'event' attribute.
+    pub fn event(&self) -> &str {
+        match self {
+            Event::Breakpoint(_) => "breakpoint",
+            Event::Capabilities(_) => "capabilities",
+            Event::Continued(_) => "continued",
+            Event::Exited(_) => "exited",
+            Event::Initialized => "initialized",
+            Event::Invalidated(_) => "invalidated",
+            Event::LoadedSource(_) => "loadedSource",
+            Event::Module(_) => "module",
+            Event::Output(_) => "output",
+            Event::Process(_) => "process",
+            Event::ProgressEnd(_) => "progressEnd",
+            Event::ProgressStart(_) => "progressStart",
+            Event::ProgressUpdate(_) => "progressUpdate",
+            Event::Stopped(_) => "stopped",
+            Event::Terminated(_) => "terminated",
+            Event::Thread(_) => "thread",
+            Event::Custom { event, .. } => event,
+        }
+    }
+}
+
+#[cfg(feature = "ser")]
+impl Serialize for Event {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Event::Custom { event, body } => {
+                let mut state = serializer.serialize_struct("Event", 2)?;
+                state.serialize_field("event", event)?;
+                match body {
+                    Some(body) => state.serialize_field("body", body)?,
+                    None => state.skip_field("body")?,
+                }
+                state.end()
+            }
+            _ => Event::serialize(self, serializer),
+        }
+    }
+}
+
+#[cfg(feature = "de")]
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Event::deserialize(TagDispatch {
+            deserializer,
+            tag: "event",
+            content: "body",
+            variants: &EVENTS,
+        })
+    }
 }
+
+/// The events of the variants of [Event] in declaration order, followed by [Event::Custom].
+#[cfg(feature = "de")]
+pub(crate) static EVENTS: Variants = Variants::new(&[
+    "breakpoint",
+    "capabilities",
+    "continued",
+    "exited",
+    "initialized",
+    "invalidated",
+    "loadedSource",
+    "module",
+    "output",
+    "process",
+    "progressEnd",
+    "progressStart",
+    "progressUpdate",
+    "stopped",
+    "terminated",
+    "thread",
+]);
+
 impl From<Event> for ProtocolMessageContent {
     fn from(event: Event) -> Self {
         Self::Event(event)
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct BreakpointEventBody {
     /// The reason for the event.
     #[serde(rename = "reason")]
@@ -118,14 +246,15 @@ pub struct BreakpointEventBody {
     /// The 'id' attribute is used to find the target breakpoint and the other attributes are used as the new values.
     #[serde(rename = "breakpoint")]
     pub breakpoint: Breakpoint,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl BreakpointEventBody {
+    pub fn new(reason: BreakpointEventReason, breakpoint: Breakpoint) -> BreakpointEventBody {
+        BreakpointEventBody { reason, breakpoint }
+    }
 }
 impl From<BreakpointEventBody> for Event {
     fn from(body: BreakpointEventBody) -> Self {
-        Self::Breakpoint(body)
+        Self::Breakpoint(Box::new(body))
     }
 }
 impl From<BreakpointEventBody> for ProtocolMessageContent {
@@ -135,7 +264,13 @@ impl From<BreakpointEventBody> for ProtocolMessageContent {
 }
 
 /// The reason for the event.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum BreakpointEventReason {
     #[serde(rename = "changed")]
     Changed,
@@ -145,21 +280,33 @@ pub enum BreakpointEventReason {
 
     #[serde(rename = "removed")]
     Removed,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct CapabilitiesEventBody {
     /// The set of updated capabilities.
     #[serde(rename = "capabilities")]
     pub capabilities: Capabilities,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl CapabilitiesEventBody {
+    pub fn new(capabilities: Capabilities) -> CapabilitiesEventBody {
+        CapabilitiesEventBody { capabilities }
+    }
 }
 impl From<CapabilitiesEventBody> for Event {
     fn from(body: CapabilitiesEventBody) -> Self {
-        Self::Capabilities(body)
+        Self::Capabilities(Box::new(body))
     }
 }
 impl From<CapabilitiesEventBody> for ProtocolMessageContent {
@@ -168,7 +315,14 @@ impl From<CapabilitiesEventBody> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ContinuedEventBody {
     /// The thread which was continued.
     #[serde(rename = "threadId")]
@@ -180,12 +334,16 @@ pub struct ContinuedEventBody {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub all_threads_continued: bool,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ContinuedEventBody {
+    pub fn new(thread_id: i32) -> ContinuedEventBody {
+        ContinuedEventBody {
+            thread_id,
+            all_threads_continued: Default::default(),
+        }
+    }
 }
 impl From<ContinuedEventBody> for Event {
     fn from(body: ContinuedEventBody) -> Self {
@@ -198,15 +356,23 @@ impl From<ContinuedEventBody> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ExitedEventBody {
     /// The exit code returned from the debuggee.
     #[serde(rename = "exitCode")]
     pub exit_code: i32,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ExitedEventBody {
+    pub fn new(exit_code: i32) -> ExitedEventBody {
+        ExitedEventBody { exit_code }
+    }
 }
 impl From<ExitedEventBody> for Event {
     fn from(body: ExitedEventBody) -> Self {
@@ -219,26 +385,31 @@ impl From<ExitedEventBody> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct InvalidatedEventBody {
     /// Optional set of logical areas that got invalidated. This property has a hint characteristic: a client can only be expected to make a 'best effort' in honouring the areas but there are no guarantees. If this property is missing, empty, or if values are not understand the client should assume a single value 'all'.
-    #[serde(rename = "areas", default, skip_serializing_if = "Vec::is_empty")]
-    #[builder(default)]
-    pub areas: Vec<InvalidatedAreas>,
+    #[serde(rename = "areas", default, skip_serializing_if = "<[_]>::is_empty")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub areas: SmallVec<InvalidatedAreas>,
 
     /// If specified, the client only needs to refetch data related to this thread.
     #[serde(rename = "threadId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub thread_id: Option<i32>,
 
     /// If specified, the client only needs to refetch data related to this stack frame (and the 'threadId' is ignored).
     #[serde(rename = "stackFrameId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub stack_frame_id: Option<i32>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 impl From<InvalidatedEventBody> for Event {
     fn from(body: InvalidatedEventBody) -> Self {
@@ -251,7 +422,14 @@ impl From<InvalidatedEventBody> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct LoadedSourceEventBody {
     /// The reason for the event.
     #[serde(rename = "reason")]
@@ -260,14 +438,15 @@ pub struct LoadedSourceEventBody {
     /// The new, changed, or removed source.
     #[serde(rename = "source")]
     pub source: Source,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl LoadedSourceEventBody {
+    pub fn new(reason: LoadedSourceEventReason, source: Source) -> LoadedSourceEventBody {
+        LoadedSourceEventBody { reason, source }
+    }
 }
 impl From<LoadedSourceEventBody> for Event {
     fn from(body: LoadedSourceEventBody) -> Self {
-        Self::LoadedSource(body)
+        Self::LoadedSource(Box::new(body))
     }
 }
 impl From<LoadedSourceEventBody> for ProtocolMessageContent {
@@ -277,7 +456,13 @@ impl From<LoadedSourceEventBody> for ProtocolMessageContent {
 }
 
 /// The reason for the event.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum LoadedSourceEventReason {
     #[serde(rename = "new")]
     New,
@@ -287,9 +472,20 @@ pub enum LoadedSourceEventReason {
 
     #[serde(rename = "removed")]
     Removed,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ModuleEventBody {
     /// The reason for the event.
     #[serde(rename = "reason")]
@@ -298,14 +494,15 @@ pub struct ModuleEventBody {
     /// The new, changed, or removed module. In case of 'removed' only the module id is used.
     #[serde(rename = "module")]
     pub module: Module,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ModuleEventBody {
+    pub fn new(reason: ModuleEventReason, module: Module) -> ModuleEventBody {
+        ModuleEventBody { reason, module }
+    }
 }
 impl From<ModuleEventBody> for Event {
     fn from(body: ModuleEventBody) -> Self {
-        Self::Module(body)
+        Self::Module(Box::new(body))
     }
 }
 impl From<ModuleEventBody> for ProtocolMessageContent {
@@ -315,7 +512,13 @@ impl From<ModuleEventBody> for ProtocolMessageContent {
 }
 
 /// The reason for the event.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum ModuleEventReason {
     #[serde(rename = "new")]
     New,
@@ -325,56 +528,95 @@ pub enum ModuleEventReason {
 
     #[serde(rename = "removed")]
     Removed,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct OutputEventBody {
     /// The output category. If not specified, 'console' is assumed.
     #[serde(rename = "category", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub category: OutputCategory,
 
     /// The output to report.
     #[serde(rename = "output")]
-    pub output: String,
+    pub output: Str,
 
     /// Support for keeping an output log organized by grouping related messages.
     #[serde(rename = "group", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub group: Option<OutputGroup>,
 
     /// If an attribute 'variablesReference' exists and its value is > 0, the output contains objects which can be retrieved by passing 'variablesReference' to the 'variables' request. The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(rename = "variablesReference", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub variables_reference: Option<i32>,
 
     /// An optional source location where the output was produced.
     #[serde(rename = "source", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub source: Option<Source>,
 
     /// An optional source location line where the output was produced.
     #[serde(rename = "line", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub line: Option<i32>,
 
     /// An optional source location column where the output was produced.
     #[serde(rename = "column", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub column: Option<i32>,
 
     /// Optional data to report. For the 'telemetry' category the data will be sent to telemetry, for the other categories the data is shown in JSON format.
     #[serde(rename = "data", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_value))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub data: Option<Value>,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// A reference that allows the client to request the location where the new value is declared. For example, if the logged value is function pointer, the adapter may be able to look up the function's location. This should be present only if the adapter is likely to be able to resolve the location.
+    ///
+    /// This reference shares the same lifetime as the 'variablesReference'.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "locationReference", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub location_reference: Option<i32>,
+}
+impl OutputEventBody {
+    pub fn new(output: Str) -> OutputEventBody {
+        OutputEventBody {
+            category: Default::default(),
+            output,
+            group: Default::default(),
+            variables_reference: Default::default(),
+            source: Default::default(),
+            line: Default::default(),
+            column: Default::default(),
+            data: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            location_reference: Default::default(),
+        }
+    }
 }
 impl From<OutputEventBody> for Event {
     fn from(body: OutputEventBody) -> Self {
-        Self::Output(body)
+        Self::Output(Box::new(body))
     }
 }
 impl From<OutputEventBody> for ProtocolMessageContent {
@@ -384,10 +626,17 @@ impl From<OutputEventBody> for ProtocolMessageContent {
 }
 
 /// The output category. If not specified, 'console' is assumed.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum OutputCategory {
     /// Show the output in the client's default message UI, e.g. a 'debug console'. This category should only be used for informational output from the debugger (as opposed to the debuggee).
     #[serde(rename = "console")]
+    #[default]
     Console,
 
     /// A hint for the client to show the ouput in the client's UI for important and highly visible information, e.g. as a popup notification. This category should only be used for important messages from the debugger (as opposed to the debuggee). Since this category value is a hint, clients might ignore the hint and assume the 'console' category.
@@ -405,16 +654,20 @@ pub enum OutputCategory {
     /// Send the output to telemetry instead of showing it to the user.
     #[serde(rename = "telemetry")]
     Telemetry,
-}
 
-impl Default for OutputCategory {
-    fn default() -> Self {
-        OutputCategory::Console
-    }
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
 /// Support for keeping an output log organized by grouping related messages.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum OutputGroup {
     /// Start a new group in expanded mode. Subsequent output events are members of the group and should be shown indented.
     ///
@@ -433,37 +686,59 @@ pub enum OutputGroup {
     /// A non empty 'output' attribute is shown as the unindented end of the group.
     #[serde(rename = "end")]
     End,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ProcessEventBody {
     /// The logical name of the process. This is usually the full path to process's executable file. Example: /home/example/myproj/program.js.
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: Str,
 
     /// The system process id of the debugged process. This property will be missing for non-system processes.
     #[serde(rename = "systemProcessId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub system_process_id: Option<i32>,
 
     /// If true, the process is running on the same computer as the debug adapter.
     #[serde(rename = "isLocalProcess", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub is_local_process: Option<bool>,
 
     /// Describes how the debug engine started debugging this process.
     #[serde(rename = "startMethod", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub start_method: Option<ProcessStartMethod>,
 
     /// The size of a pointer or address for this process, in bits. This value may be used by clients when formatting addresses for display.
     #[serde(rename = "pointerSize", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub pointer_size: Option<i32>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ProcessEventBody {
+    pub fn new(name: Str) -> ProcessEventBody {
+        ProcessEventBody {
+            name,
+            system_process_id: Default::default(),
+            is_local_process: Default::default(),
+            start_method: Default::default(),
+            pointer_size: Default::default(),
+        }
+    }
 }
 impl From<ProcessEventBody> for Event {
     fn from(body: ProcessEventBody) -> Self {
@@ -477,7 +752,13 @@ impl From<ProcessEventBody> for ProtocolMessageContent {
 }
 
 /// Describes how the debug engine started debugging this process.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum ProcessStartMethod {
     /// Process was launched under the debugger.
     #[serde(rename = "launch")]
@@ -490,22 +771,38 @@ pub enum ProcessStartMethod {
     /// A project launcher component has launched a new process in a suspended state and then asked the debugger to attach.
     #[serde(rename = "attachForSuspendedLaunch")]
     AttachForSuspendedLaunch,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ProgressEndEventBody {
     /// The ID that was introduced in the initial 'ProgressStartEvent'.
     #[serde(rename = "progressId")]
-    pub progress_id: String,
+    pub progress_id: Str,
 
     /// Optional, more detailed progress message. If omitted, the previous message (if any) is used.
     #[serde(rename = "message", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub message: Option<String>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub message: Option<Str>,
+}
+impl ProgressEndEventBody {
+    pub fn new(progress_id: Str) -> ProgressEndEventBody {
+        ProgressEndEventBody {
+            progress_id,
+            message: Default::default(),
+        }
+    }
 }
 impl From<ProgressEndEventBody> for Event {
     fn from(body: ProgressEndEventBody) -> Self {
@@ -518,17 +815,24 @@ impl From<ProgressEndEventBody> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ProgressStartEventBody {
     /// An ID that must be used in subsequent 'progressUpdate' and 'progressEnd' events to make them refer to the same progress reporting.
     ///
     /// IDs must be unique within a debug session.
     #[serde(rename = "progressId")]
-    pub progress_id: String,
+    pub progress_id: Str,
 
     /// Mandatory (short) title of the progress reporting. Shown in the UI to describe the long running operation.
     #[serde(rename = "title")]
-    pub title: String,
+    pub title: Str,
 
     /// The request ID that this progress report is related to. If specified a debug adapter is expected to emit
     ///
@@ -536,7 +840,8 @@ pub struct ProgressStartEventBody {
     ///
     /// If the request ID is omitted, the progress report is assumed to be related to some general activity of the debug adapter.
     #[serde(rename = "requestId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub request_id: Option<i32>,
 
     /// If true, the request that reports progress may be canceled with a 'cancel' request.
@@ -545,26 +850,36 @@ pub struct ProgressStartEventBody {
     ///
     /// Clients that don't support cancellation are allowed to ignore the setting.
     #[serde(rename = "cancellable", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub cancellable: bool,
 
     /// Optional, more detailed progress message.
     #[serde(rename = "message", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub message: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub message: Option<Str>,
 
     /// Optional progress percentage to display (value range: 0 to 100). If omitted no percentage will be shown.
     #[serde(rename = "percentage", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub percentage: Option<u8>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ProgressStartEventBody {
+    pub fn new(progress_id: Str, title: Str) -> ProgressStartEventBody {
+        ProgressStartEventBody {
+            progress_id,
+            title,
+            request_id: Default::default(),
+            cancellable: Default::default(),
+            message: Default::default(),
+            percentage: Default::default(),
+        }
+    }
 }
 impl From<ProgressStartEventBody> for Event {
     fn from(body: ProgressStartEventBody) -> Self {
-        Self::ProgressStart(body)
+        Self::ProgressStart(Box::new(body))
     }
 }
 impl From<ProgressStartEventBody> for ProtocolMessageContent {
@@ -573,25 +888,39 @@ impl From<ProgressStartEventBody> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ProgressUpdateEventBody {
     /// The ID that was introduced in the initial 'progressStart' event.
     #[serde(rename = "progressId")]
-    pub progress_id: String,
+    pub progress_id: Str,
 
     /// Optional, more detailed progress message. If omitted, the previous message (if any) is used.
     #[serde(rename = "message", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub message: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub message: Option<Str>,
 
     /// Optional progress percentage to display (value range: 0 to 100). If omitted no percentage will be shown.
     #[serde(rename = "percentage", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub percentage: Option<u8>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ProgressUpdateEventBody {
+    pub fn new(progress_id: Str) -> ProgressUpdateEventBody {
+        ProgressUpdateEventBody {
+            progress_id,
+            message: Default::default(),
+            percentage: Default::default(),
+        }
+    }
 }
 impl From<ProgressUpdateEventBody> for Event {
     fn from(body: ProgressUpdateEventBody) -> Self {
@@ -604,7 +933,14 @@ impl From<ProgressUpdateEventBody> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct StoppedEventBody {
     /// The reason for the event.
     ///
@@ -614,12 +950,14 @@ pub struct StoppedEventBody {
 
     /// The full reason for the event, e.g. 'Paused on exception'. This string is shown in the UI as is and must be translated.
     #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub description: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub description: Option<Str>,
 
     /// The thread which was stopped.
     #[serde(rename = "threadId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub thread_id: Option<i32>,
 
     /// A value of true hints to the frontend that this event should not change the focus.
@@ -628,13 +966,14 @@ pub struct StoppedEventBody {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub preserve_focus_hint: bool,
 
     /// Additional information. E.g. if reason is 'exception', text contains the exception name. This string is shown in the UI.
     #[serde(rename = "text", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub text: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub text: Option<Str>,
 
     /// If 'allThreadsStopped' is true, a debug adapter can announce that all threads have stopped.
     ///
@@ -646,7 +985,7 @@ pub struct StoppedEventBody {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub all_threads_stopped: bool,
 
     /// Ids of the breakpoints that triggered the event. In most cases there will be only a single breakpoint but here are some examples for multiple breakpoints:
@@ -659,18 +998,27 @@ pub struct StoppedEventBody {
     #[serde(
         rename = "hitBreakpointIds",
         default,
-        skip_serializing_if = "Vec::is_empty"
+        skip_serializing_if = "<[_]>::is_empty"
     )]
-    #[builder(default)]
-    pub hit_breakpoint_ids: Vec<i32>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub hit_breakpoint_ids: SmallVec<i32>,
+}
+impl StoppedEventBody {
+    pub fn new(reason: StoppedEventReason) -> StoppedEventBody {
+        StoppedEventBody {
+            reason,
+            description: Default::default(),
+            thread_id: Default::default(),
+            preserve_focus_hint: Default::default(),
+            text: Default::default(),
+            all_threads_stopped: Default::default(),
+            hit_breakpoint_ids: Default::default(),
+        }
+    }
 }
 impl From<StoppedEventBody> for Event {
     fn from(body: StoppedEventBody) -> Self {
-        Self::Stopped(body)
+        Self::Stopped(Box::new(body))
     }
 }
 impl From<StoppedEventBody> for ProtocolMessageContent {
@@ -682,7 +1030,13 @@ impl From<StoppedEventBody> for ProtocolMessageContent {
 /// The reason for the event.
 ///
 /// For backward compatibility this string is shown in the UI if the 'description' attribute is missing (but it must not be translated).
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum StoppedEventReason {
     #[serde(rename = "step")]
     Step,
@@ -710,20 +1064,29 @@ pub enum StoppedEventReason {
 
     #[serde(rename = "instruction breakpoint")]
     InstructionBreakpoint,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct TerminatedEventBody {
     /// A debug adapter may set 'restart' to true (or to an arbitrary object) to request that the front end restarts the session.
     ///
     /// The value is not interpreted by the client and passed unmodified as an attribute '__restart' to the 'launch' and 'attach' requests.
     #[serde(rename = "restart", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_value))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub restart: Option<Value>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 impl From<TerminatedEventBody> for Event {
     fn from(body: TerminatedEventBody) -> Self {
@@ -736,7 +1099,14 @@ impl From<TerminatedEventBody> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ThreadEventBody {
     /// The reason for the event.
     #[serde(rename = "reason")]
@@ -745,10 +1115,11 @@ pub struct ThreadEventBody {
     /// The identifier of the thread.
     #[serde(rename = "threadId")]
     pub thread_id: i32,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ThreadEventBody {
+    pub fn new(reason: ThreadEventReason, thread_id: i32) -> ThreadEventBody {
+        ThreadEventBody { reason, thread_id }
+    }
 }
 impl From<ThreadEventBody> for Event {
     fn from(body: ThreadEventBody) -> Self {
@@ -762,11 +1133,21 @@ impl From<ThreadEventBody> for ProtocolMessageContent {
 }
 
 /// The reason for the event.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum ThreadEventReason {
     #[serde(rename = "started")]
     Started,
 
     #[serde(rename = "exited")]
     Exited,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
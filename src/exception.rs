@@ -0,0 +1,125 @@
+//! Presentation of the information about an exception for an exception widget.
+//!
+//! An [ExceptionNode] is a tree of the exception, the lines of its stack trace and its inner
+//! exceptions. UIs can show it as a tree view, or render it as indented text with its [Display]
+//! implementation. Optional fields that are missing are left out, and an exception without any
+//! name is shown as `<unknown exception>`.
+
+use crate::types::ExceptionDetails;
+#[cfg(feature = "responses")]
+use crate::{responses::ExceptionInfoResponseBody, types::ExceptionBreakMode};
+use std::fmt::{self, Display};
+
+const INDENT: &str = "  ";
+
+/// What an [ExceptionNode] represents.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ExceptionNodeKind {
+    /// The exception that was thrown.
+    Exception,
+
+    /// An exception that caused its parent.
+    InnerException,
+
+    /// A line of the stack trace of its parent.
+    StackTraceLine,
+}
+
+/// A node of the tree representation of an exception.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExceptionNode {
+    pub kind: ExceptionNodeKind,
+    pub label: String,
+    pub children: Vec<ExceptionNode>,
+}
+
+impl ExceptionNode {
+    /// Creates the tree of the response to an 'exceptionInfo' request. Its label names the
+    /// exception, with its description and whether it was unhandled.
+    #[cfg(feature = "responses")]
+    pub fn from_exception_info(body: &ExceptionInfoResponseBody) -> ExceptionNode {
+        let mut node = match &body.details {
+            Some(details) => ExceptionNode::from_details(details),
+            None => ExceptionNode {
+                kind: ExceptionNodeKind::Exception,
+                label: String::new(),
+                children: Vec::new(),
+            },
+        };
+        let name = body
+            .details
+            .as_ref()
+            .and_then(type_name)
+            .unwrap_or(&body.exception_id);
+        let description = body
+            .description
+            .as_deref()
+            .or_else(|| body.details.as_ref()?.message.as_deref());
+        node.label = headline(Some(name), description);
+        match body.break_mode {
+            ExceptionBreakMode::Unhandled => node.label.push_str(" (unhandled)"),
+            ExceptionBreakMode::UserUnhandled => node.label.push_str(" (unhandled in user code)"),
+            _ => {}
+        }
+        node
+    }
+
+    /// Creates the tree of `details`, whose inner exceptions become children of kind
+    /// [InnerException](ExceptionNodeKind::InnerException) after the lines of its stack trace.
+    pub fn from_details(details: &ExceptionDetails) -> ExceptionNode {
+        let mut children = details
+            .stack_trace
+            .iter()
+            .flat_map(|stack_trace| stack_trace.lines())
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| ExceptionNode {
+                kind: ExceptionNodeKind::StackTraceLine,
+                label: line.to_string(),
+                children: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        children.extend(details.inner_exception.iter().map(|inner| {
+            let mut node = ExceptionNode::from_details(inner);
+            node.kind = ExceptionNodeKind::InnerException;
+            node.label = format!("Inner exception: {}", node.label);
+            node
+        }));
+        ExceptionNode {
+            kind: ExceptionNodeKind::Exception,
+            label: headline(type_name(details), details.message.as_deref()),
+            children,
+        }
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        writeln!(f, "{}{}", INDENT.repeat(depth), self.label)?;
+        for child in &self.children {
+            child.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ExceptionNode {
+    /// Renders the tree with one line per node, children indented below their parent.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+fn type_name(details: &ExceptionDetails) -> Option<&str> {
+    details
+        .full_type_name
+        .as_deref()
+        .or(details.type_name.as_deref())
+}
+
+fn headline(name: Option<&str>, message: Option<&str>) -> String {
+    let name = name.unwrap_or("<unknown exception>");
+    match message {
+        Some(message) if !message.is_empty() => format!("{}: {}", name, message),
+        _ => name.to_string(),
+    }
+}
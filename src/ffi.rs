@@ -0,0 +1,361 @@
+//! A C interface for native debugger backends that embed this crate for their protocol layer.
+//!
+//! Build the crate as a static or dynamic library with the `ffi` feature, for example with
+//! `cargo rustc --release --features ffi --crate-type staticlib`, and include
+//! `include/debug_adapter_protocol.h`.
+//!
+//! Messages are opaque [DapMessage] handles that are created by [dap_decode] or one of the
+//! `dap_*_new` functions and must be released with [dap_message_free]. Functions that fail return
+//! null and store a description of the error that [dap_last_error] returns.
+
+use crate::{
+    de, error::MessageKind, framing::split_frame, ParseOptions, ProtocolMessage, SequenceNumber,
+};
+use serde_json::{json, Map, Value};
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    fmt::Display,
+    ptr, slice,
+};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(error: impl Display) {
+    let message = CString::new(error.to_string().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = None);
+}
+
+/// A decoded or built protocol message.
+pub struct DapMessage {
+    message: ProtocolMessage,
+    kind: DapMessageKind,
+    name: CString,
+    body: Option<CString>,
+}
+
+impl DapMessage {
+    fn new(message: ProtocolMessage) -> Result<DapMessage, String> {
+        let value = serde_json::to_value(&message).map_err(|error| error.to_string())?;
+        let kind = value["type"]
+            .as_str()
+            .and_then(MessageKind::from_type)
+            .ok_or("message has no type")?;
+        let (name, body) = match kind {
+            MessageKind::Request => ("command", "arguments"),
+            MessageKind::Response => ("command", "body"),
+            MessageKind::Event => ("event", "body"),
+        };
+        let name = value[name].as_str().unwrap_or_default();
+        let body = value.get(body).map(Value::to_string);
+        Ok(DapMessage {
+            kind: kind.into(),
+            name: CString::new(name).map_err(|error| error.to_string())?,
+            body: body
+                .map(CString::new)
+                .transpose()
+                .map_err(|error| error.to_string())?,
+            message,
+        })
+    }
+
+    fn from_value(value: Value) -> *mut DapMessage {
+        match de::from_value(&value, &ParseOptions::default())
+            .map_err(|error| error.to_string())
+            .and_then(DapMessage::new)
+        {
+            Ok(message) => {
+                clear_last_error();
+                Box::into_raw(Box::new(message))
+            }
+            Err(error) => {
+                set_last_error(error);
+                ptr::null_mut()
+            }
+        }
+    }
+}
+
+/// The type of a protocol message.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DapMessageKind {
+    Request = 0,
+    Response = 1,
+    Event = 2,
+}
+
+impl From<MessageKind> for DapMessageKind {
+    fn from(kind: MessageKind) -> DapMessageKind {
+        match kind {
+            MessageKind::Request => DapMessageKind::Request,
+            MessageKind::Response => DapMessageKind::Response,
+            MessageKind::Event => DapMessageKind::Event,
+        }
+    }
+}
+
+/// Returns the error of the last failed call on this thread, or null. The string is valid until
+/// the next call on this thread.
+#[no_mangle]
+pub extern "C" fn dap_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| match &*last_error.borrow() {
+        Some(error) => error.as_ptr(),
+        None => ptr::null(),
+    })
+}
+
+/// Decodes the first message of `buffer`, which holds `len` bytes read from a stream of messages.
+///
+/// Stores the number of bytes the message occupies including its header in `consumed`. If the
+/// buffer does not hold a complete message yet, returns null and stores 0. If the message is
+/// invalid, returns null and stores its length, so the caller can skip it.
+///
+/// # Safety
+///
+/// `buffer` must point to `len` readable bytes and `consumed` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn dap_decode(
+    buffer: *const u8,
+    len: usize,
+    consumed: *mut usize,
+) -> *mut DapMessage {
+    *consumed = 0;
+    let buffer = if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(buffer, len)
+    };
+    let (content, frame_len) = match split_frame(buffer) {
+        Ok(Some(frame)) => frame,
+        Ok(None) => {
+            clear_last_error();
+            return ptr::null_mut();
+        }
+        Err(error) => {
+            set_last_error(error);
+            return ptr::null_mut();
+        }
+    };
+    *consumed = frame_len;
+    match ProtocolMessage::from_slice(content)
+        .map_err(|error| error.to_string())
+        .and_then(DapMessage::new)
+    {
+        Ok(message) => {
+            clear_last_error();
+            Box::into_raw(Box::new(message))
+        }
+        Err(error) => {
+            set_last_error(error);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Releases a message. Does nothing if `message` is null.
+///
+/// # Safety
+///
+/// `message` must be null or a handle that has not been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn dap_message_free(message: *mut DapMessage) {
+    if !message.is_null() {
+        drop(Box::from_raw(message));
+    }
+}
+
+/// Returns the sequence number of a message.
+///
+/// # Safety
+///
+/// `message` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn dap_message_seq(message: *const DapMessage) -> SequenceNumber {
+    (*message).message.seq
+}
+
+/// Returns the type of a message.
+///
+/// # Safety
+///
+/// `message` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn dap_message_kind(message: *const DapMessage) -> DapMessageKind {
+    (*message).kind
+}
+
+/// Returns the command of a request or response or the name of an event. The string is valid as
+/// long as the message.
+///
+/// # Safety
+///
+/// `message` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn dap_message_name(message: *const DapMessage) -> *const c_char {
+    (*message).name.as_ptr()
+}
+
+/// Returns the JSON of the arguments of a request or the body of a response or event, or null if
+/// the message has none. The string is valid as long as the message.
+///
+/// # Safety
+///
+/// `message` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn dap_message_body(message: *const DapMessage) -> *const c_char {
+    match &(*message).body {
+        Some(body) => body.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+/// Parses an optional JSON argument of a `dap_*_new` function into `object` under `key`.
+unsafe fn insert_json(
+    object: &mut Map<String, Value>,
+    key: &str,
+    json: *const c_char,
+) -> Result<(), String> {
+    if !json.is_null() {
+        let json = CStr::from_ptr(json)
+            .to_str()
+            .map_err(|error| error.to_string())?;
+        let value = serde_json::from_str(json).map_err(|error| error.to_string())?;
+        object.insert(key.to_string(), value);
+    }
+    Ok(())
+}
+
+/// Builds a message from the object returned by `object`, or stores its error.
+fn new_message(object: impl FnOnce() -> Result<Map<String, Value>, String>) -> *mut DapMessage {
+    match object() {
+        Ok(object) => DapMessage::from_value(Value::Object(object)),
+        Err(error) => {
+            set_last_error(error);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Reads the string argument `name` of a `dap_*_new` function.
+unsafe fn str_arg<'a>(name: &str, string: *const c_char) -> Result<&'a str, String> {
+    CStr::from_ptr(string)
+        .to_str()
+        .map_err(|error| format!("{} is not valid UTF-8: {}", name, error))
+}
+
+/// Builds a request. `arguments` is the JSON of the arguments or null.
+///
+/// # Safety
+///
+/// `command` must be a NUL-terminated string and `arguments` null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn dap_request_new(
+    seq: SequenceNumber,
+    command: *const c_char,
+    arguments: *const c_char,
+) -> *mut DapMessage {
+    new_message(|| {
+        let command = str_arg("command", command)?;
+        let mut object = into_map(json!({ "seq": seq, "type": "request", "command": command }));
+        insert_json(&mut object, "arguments", arguments)?;
+        Ok(object)
+    })
+}
+
+/// Builds an event. `body` is the JSON of the body or null.
+///
+/// # Safety
+///
+/// `event` must be a NUL-terminated string and `body` null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn dap_event_new(
+    seq: SequenceNumber,
+    event: *const c_char,
+    body: *const c_char,
+) -> *mut DapMessage {
+    new_message(|| {
+        let event = str_arg("event", event)?;
+        let mut object = into_map(json!({ "seq": seq, "type": "event", "event": event }));
+        insert_json(&mut object, "body", body)?;
+        Ok(object)
+    })
+}
+
+/// Builds a response to the request with sequence number `request_seq`. `message` is the error
+/// message of an unsuccessful response or null and `body` is the JSON of the body or null.
+///
+/// # Safety
+///
+/// `command` must be a NUL-terminated string and `message` and `body` null or NUL-terminated
+/// strings.
+#[no_mangle]
+pub unsafe extern "C" fn dap_response_new(
+    seq: SequenceNumber,
+    request_seq: SequenceNumber,
+    command: *const c_char,
+    success: bool,
+    message: *const c_char,
+    body: *const c_char,
+) -> *mut DapMessage {
+    new_message(|| {
+        let mut object = into_map(json!({
+            "seq": seq,
+            "type": "response",
+            "request_seq": request_seq,
+            "success": success,
+            "command": str_arg("command", command)?,
+        }));
+        if !message.is_null() {
+            object.insert("message".to_string(), str_arg("message", message)?.into());
+        }
+        insert_json(&mut object, "body", body)?;
+        Ok(object)
+    })
+}
+
+fn into_map(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(object) => object,
+        _ => Map::new(),
+    }
+}
+
+/// Encodes a message with its Content-Length header. Stores the length of the returned buffer in
+/// `len`, which must be passed to [dap_buffer_free] to release it.
+///
+/// # Safety
+///
+/// `message` must be a valid handle and `len` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn dap_encode(message: *const DapMessage, len: *mut usize) -> *mut u8 {
+    let mut buffer = Vec::new();
+    if let Err(error) = (*message).message.write_to(&mut buffer) {
+        *len = 0;
+        set_last_error(error);
+        return ptr::null_mut();
+    }
+    clear_last_error();
+    let buffer = buffer.into_boxed_slice();
+    *len = buffer.len();
+    Box::into_raw(buffer) as *mut u8
+}
+
+/// Releases a buffer returned by [dap_encode]. Does nothing if `buffer` is null.
+///
+/// # Safety
+///
+/// `buffer` must be null or a buffer returned by [dap_encode] with its length `len` that has not
+/// been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn dap_buffer_free(buffer: *mut u8, len: usize) {
+    if !buffer.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(buffer, len)));
+    }
+}
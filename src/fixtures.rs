@@ -0,0 +1,202 @@
+//! Ready-made sample values for tests of crates that use this one.
+//!
+//! The values describe a consistent session: a client debugs `/project/src/main.rs` with a single
+//! thread 1, which stops at a breakpoint in line 12 of `main`. Every call returns a fresh value,
+//! so tests can modify it freely.
+
+use crate::{
+    capabilities::CapabilityFlags,
+    events::{Event, OutputCategory, OutputEventBody, StoppedEventBody, StoppedEventReason},
+    requests::{
+        InitializeRequestArguments, LaunchRequestArguments, PathFormat, Request,
+        SetBreakpointsRequestArguments,
+    },
+    responses::{
+        Response, SetBreakpointsResponseBody, StackTraceResponseBody, SuccessResponse,
+        ThreadsResponseBody,
+    },
+    types::{
+        Breakpoint, Capabilities, ExceptionBreakpointsFilter, Source, SourceBreakpoint, StackFrame,
+        Thread,
+    },
+    ProtocolMessage, SequenceNumber, SmallVec, Str,
+};
+use serde_json::{json, Value};
+
+/// The id of the only thread of the session.
+pub const THREAD_ID: i32 = 1;
+
+/// The line of the breakpoint the thread stops at.
+pub const BREAKPOINT_LINE: i32 = 12;
+
+/// The source file of the session, `/project/src/main.rs`.
+pub fn source() -> Source {
+    Source::builder()
+        .name(Some("main.rs".into()))
+        .path(Some("/project/src/main.rs".into()))
+        .build()
+}
+
+/// An 'initialize' request like the one VS Code sends.
+pub fn initialize_request() -> Request {
+    InitializeRequestArguments::builder()
+        .client_id(Some("vscode".into()))
+        .client_name(Some("Visual Studio Code".into()))
+        .adapter_id("mock".into())
+        .locale(Some("en".into()))
+        .lines_start_at_1(true)
+        .columns_start_at_1(true)
+        .path_format(PathFormat::Path)
+        .supports_variable_type(true)
+        .supports_variable_paging(true)
+        .supports_run_in_terminal_request(true)
+        .supports_memory_references(true)
+        .supports_progress_reporting(true)
+        .supports_invalidated_event(true)
+        .build()
+        .into()
+}
+
+/// The capabilities of a typical debug adapter, with an exception filter for uncaught exceptions.
+pub fn capabilities() -> Capabilities {
+    Capabilities::builder()
+        .flags(
+            CapabilityFlags::SUPPORTS_CONFIGURATION_DONE_REQUEST
+                | CapabilityFlags::SUPPORTS_FUNCTION_BREAKPOINTS
+                | CapabilityFlags::SUPPORTS_CONDITIONAL_BREAKPOINTS
+                | CapabilityFlags::SUPPORTS_HIT_CONDITIONAL_BREAKPOINTS
+                | CapabilityFlags::SUPPORTS_EVALUATE_FOR_HOVERS
+                | CapabilityFlags::SUPPORTS_SET_VARIABLE
+                | CapabilityFlags::SUPPORTS_TERMINATE_REQUEST,
+        )
+        .exception_breakpoint_filters(vec![ExceptionBreakpointsFilter::builder()
+            .filter("uncaught".into())
+            .label("Uncaught Exceptions".into())
+            .default(true)
+            .build()])
+        .build()
+}
+
+/// The response to [initialize_request] with [capabilities].
+pub fn initialize_response() -> SuccessResponse {
+    SuccessResponse::Initialize(Box::new(capabilities()))
+}
+
+/// A 'launch' request for the program `/project/target/debug/project`.
+pub fn launch_request() -> Request {
+    let attributes = match json!({ "program": "/project/target/debug/project" }) {
+        Value::Object(attributes) => attributes,
+        _ => unreachable!(),
+    };
+    LaunchRequestArguments::builder()
+        .additional_attributes(attributes)
+        .build()
+        .into()
+}
+
+/// A 'setBreakpoints' request for [BREAKPOINT_LINE] of [source].
+pub fn set_breakpoints_request() -> Request {
+    SetBreakpointsRequestArguments::builder()
+        .source(source())
+        .breakpoints(vec![SourceBreakpoint::builder()
+            .line(BREAKPOINT_LINE)
+            .build()])
+        .build()
+        .into()
+}
+
+/// The response to [set_breakpoints_request] with a verified breakpoint.
+pub fn set_breakpoints_response() -> SuccessResponse {
+    SetBreakpointsResponseBody::builder()
+        .breakpoints(vec![Breakpoint::builder()
+            .id(Some(1))
+            .verified(true)
+            .source(Some(source()))
+            .line(Some(BREAKPOINT_LINE))
+            .build()])
+        .build()
+        .into()
+}
+
+/// A 'threads' response with the only thread [THREAD_ID].
+pub fn threads_response() -> SuccessResponse {
+    ThreadsResponseBody::builder()
+        .threads(vec![Thread::builder()
+            .id(THREAD_ID)
+            .name("main".into())
+            .build()])
+        .build()
+        .into()
+}
+
+/// A 'stopped' event of [THREAD_ID] at the breakpoint.
+pub fn stopped_event() -> Event {
+    StoppedEventBody::builder()
+        .reason(StoppedEventReason::Breakpoint)
+        .thread_id(Some(THREAD_ID))
+        .all_threads_stopped(true)
+        .hit_breakpoint_ids(SmallVec::from(vec![1]))
+        .build()
+        .into()
+}
+
+/// An 'output' event of the debuggee writing `line` to stdout.
+pub fn output_event(line: &str) -> Event {
+    OutputEventBody::builder()
+        .category(OutputCategory::Stdout)
+        .output(Str::from(format!("{}\n", line)))
+        .build()
+        .into()
+}
+
+/// The three frames of the stopped thread, innermost first: `main` at the breakpoint, called by
+/// the runtime.
+pub fn stack_frames() -> Vec<StackFrame> {
+    let frame = |id, name: &str, source, line| {
+        StackFrame::builder()
+            .id(id)
+            .name(name.into())
+            .source(source)
+            .line(line)
+            .column(5)
+            .build()
+    };
+    vec![
+        frame(1000, "project::main", Some(source()), BREAKPOINT_LINE),
+        frame(1001, "core::ops::function::FnOnce::call_once", None, 250),
+        frame(1002, "std::rt::lang_start", None, 159),
+    ]
+}
+
+/// A 'stackTrace' response with [stack_frames].
+pub fn stack_trace_response() -> SuccessResponse {
+    let frames = stack_frames();
+    StackTraceResponseBody::builder()
+        .total_frames(Some(frames.len() as i32))
+        .stack_frames(frames)
+        .build()
+        .into()
+}
+
+/// The messages of a short session from 'initialize' to the stop at the breakpoint, with the
+/// sequence numbers of both sides starting at 1.
+pub fn session() -> Vec<ProtocolMessage> {
+    let response = |seq: SequenceNumber, request_seq, response| {
+        ProtocolMessage::new(seq, Response::new(request_seq, Ok(response)))
+    };
+    vec![
+        ProtocolMessage::new(1, initialize_request()),
+        response(1, 1, initialize_response()),
+        ProtocolMessage::new(2, Event::Initialized),
+        ProtocolMessage::new(2, set_breakpoints_request()),
+        response(3, 2, set_breakpoints_response()),
+        ProtocolMessage::new(3, Request::ConfigurationDone),
+        response(4, 3, SuccessResponse::ConfigurationDone),
+        ProtocolMessage::new(4, launch_request()),
+        response(5, 4, SuccessResponse::Launch),
+        ProtocolMessage::new(6, output_event("Hello, world!")),
+        ProtocolMessage::new(7, stopped_event()),
+        ProtocolMessage::new(5, Request::Threads),
+        response(8, 5, threads_response()),
+    ]
+}
@@ -0,0 +1,264 @@
+//! The base protocol: every message consists of a header with a Content-Length field, followed by
+//! the JSON content.
+
+use crate::error::FrameError;
+#[cfg(feature = "ser")]
+use crate::{ProtocolMessage, ProtocolMessageContent, SequenceNumber};
+#[cfg(feature = "ser")]
+use serde::Serialize;
+use std::io::{self, BufRead, Read};
+#[cfg(feature = "ser")]
+use std::{fmt, io::Write};
+
+/// Splits the first message off `buffer`, which holds bytes read from a stream of messages.
+///
+/// Returns the JSON content of the message and the total number of bytes it occupies including its
+/// header, or `None` if `buffer` does not hold a complete message yet. The content can be parsed
+/// with [ProtocolMessage::from_slice](crate::ProtocolMessage::from_slice) without copying it.
+pub fn split_frame(buffer: &[u8]) -> Result<Option<(&[u8], usize)>, FrameError> {
+    let header_end = match buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+        Some(index) => index,
+        None => return Ok(None),
+    };
+    let mut content_length = None;
+    for line in buffer[..header_end].split(|byte| *byte == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let colon = line
+            .iter()
+            .position(|byte| *byte == b':')
+            .ok_or(FrameError::InvalidHeader)?;
+        if line[..colon].eq_ignore_ascii_case(b"Content-Length") {
+            content_length = Some(parse_content_length(&line[colon + 1..])?);
+        }
+    }
+    let content_length = content_length.ok_or(FrameError::MissingContentLength)?;
+    let content_start = header_end + 4;
+    let frame_len = content_start
+        .checked_add(content_length)
+        .ok_or(FrameError::InvalidContentLength)?;
+    Ok(buffer
+        .get(content_start..frame_len)
+        .map(|content| (content, frame_len)))
+}
+
+/// Splits a complete recorded stream of messages into the JSON content of each message.
+pub fn split_frames(mut stream: &[u8]) -> Result<Vec<&[u8]>, FrameError> {
+    let mut frames = Vec::new();
+    while !stream.is_empty() {
+        let (content, frame_len) = split_frame(stream)?.ok_or(FrameError::Truncated)?;
+        frames.push(content);
+        stream = &stream[frame_len..];
+    }
+    Ok(frames)
+}
+
+/// Reads the content of the next message from `reader`, which is positioned at the start of a
+/// header. Returns `None` if the stream ends before the header.
+///
+/// Unlike [split_frame] this allocates a `Vec` for every message. Use the `Decoder` of the `decoder`
+/// feature to avoid that.
+pub fn read_frame<R: BufRead>(mut reader: R) -> io::Result<Option<Vec<u8>>> {
+    let invalid_data = |error| io::Error::new(io::ErrorKind::InvalidData, error);
+    let mut content_length = None;
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            return match content_length {
+                None if line.is_empty() => Ok(None),
+                _ => Err(io::ErrorKind::UnexpectedEof.into()),
+            };
+        }
+        let line = line.strip_suffix(b"\n").unwrap_or(&line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.is_empty() {
+            break;
+        }
+        let colon = line
+            .iter()
+            .position(|byte| *byte == b':')
+            .ok_or(FrameError::InvalidHeader)
+            .map_err(invalid_data)?;
+        if line[..colon].eq_ignore_ascii_case(b"Content-Length") {
+            content_length = Some(parse_content_length(&line[colon + 1..]).map_err(invalid_data)?);
+        }
+    }
+    let content_length = content_length
+        .ok_or(FrameError::MissingContentLength)
+        .map_err(invalid_data)?;
+    // Don't trust the Content-Length with an allocation before the content arrived
+    let mut content = Vec::new();
+    reader
+        .take(content_length as u64)
+        .read_to_end(&mut content)?;
+    if content.len() < content_length {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(Some(content))
+}
+
+fn parse_content_length(value: &[u8]) -> Result<usize, FrameError> {
+    std::str::from_utf8(value)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .ok_or(FrameError::InvalidContentLength)
+}
+
+/// Writes `value` as JSON with a Content-Length header without buffering the JSON.
+///
+/// To compute the Content-Length the value is serialized twice: first into a [ByteCounter] and
+/// then into `writer`.
+#[cfg(feature = "ser")]
+pub(crate) fn write_framed<W, T>(mut writer: W, value: &T) -> io::Result<()>
+where
+    W: Write,
+    T: Serialize + ?Sized,
+{
+    let mut counter = ByteCounter::default();
+    serde_json::to_writer(&mut counter, value)?;
+    write!(writer, "Content-Length: {}\r\n\r\n", counter.0)?;
+    serde_json::to_writer(&mut writer, value)?;
+    Ok(())
+}
+
+/// A message that is serialized once and can then be written many times with different sequence
+/// numbers, for example the 'initialized' event or the response to 'initialize'.
+#[cfg(feature = "ser")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreEncodedMessage {
+    /// The JSON of the message with seq 0.
+    json: Box<[u8]>,
+
+    /// The offset of the request_seq of a response in `json`.
+    request_seq_offset: Option<usize>,
+
+    /// The length of the request_seq of a response in `json`.
+    request_seq_len: usize,
+}
+
+/// The offset of the seq in the JSON of a message, which is always serialized first.
+#[cfg(feature = "ser")]
+const SEQ_OFFSET: usize = br#"{"seq":"#.len();
+
+#[cfg(feature = "ser")]
+impl PreEncodedMessage {
+    /// Serializes `content`. The sequence number is filled in when the message is written.
+    pub fn new(
+        content: impl Into<ProtocolMessageContent>,
+    ) -> serde_json::Result<PreEncodedMessage> {
+        let json = serde_json::to_vec(&ProtocolMessage::new(0, content))?.into_boxed_slice();
+        let request_seq_key = br#","request_seq":"#;
+        // request_seq is serialized before the command and body, so the first match is the right one
+        let request_seq_offset = json
+            .windows(request_seq_key.len())
+            .position(|window| window == request_seq_key)
+            .filter(|_| json.starts_with(br#"{"seq":0,"type":"response""#))
+            .map(|index| index + request_seq_key.len());
+        let request_seq_len = request_seq_offset.map_or(0, |offset| {
+            json[offset..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count()
+        });
+        Ok(PreEncodedMessage {
+            json,
+            request_seq_offset,
+            request_seq_len,
+        })
+    }
+
+    /// Writes this message with the sequence number `seq` and its Content-Length header to
+    /// `writer`.
+    pub fn write_to<W: Write>(&self, writer: W, seq: SequenceNumber) -> io::Result<()> {
+        self.write_parts(writer, seq, None)
+    }
+
+    /// Writes this response with the sequence number `seq`, answering the request `request_seq`.
+    ///
+    /// Fails with [io::ErrorKind::InvalidInput] if this message is not a response.
+    pub fn write_response_to<W: Write>(
+        &self,
+        writer: W,
+        seq: SequenceNumber,
+        request_seq: SequenceNumber,
+    ) -> io::Result<()> {
+        if self.request_seq_offset.is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "only responses have a request_seq",
+            ));
+        }
+        self.write_parts(writer, seq, Some(request_seq))
+    }
+
+    fn write_parts<W: Write>(
+        &self,
+        mut writer: W,
+        seq: SequenceNumber,
+        request_seq: Option<SequenceNumber>,
+    ) -> io::Result<()> {
+        let (middle, rest) = match (self.request_seq_offset, request_seq) {
+            (Some(offset), Some(_)) => (
+                &self.json[SEQ_OFFSET + 1..offset],
+                &self.json[offset + self.request_seq_len..],
+            ),
+            _ => (&self.json[SEQ_OFFSET + 1..], &[][..]),
+        };
+        let len = SEQ_OFFSET
+            + decimal_len(seq)
+            + middle.len()
+            + request_seq.map_or(0, decimal_len)
+            + rest.len();
+        write!(writer, "Content-Length: {}\r\n\r\n", len)?;
+        writer.write_all(&self.json[..SEQ_OFFSET])?;
+        write!(writer, "{}", seq)?;
+        writer.write_all(middle)?;
+        if let Some(request_seq) = request_seq {
+            write!(writer, "{}", request_seq)?;
+        }
+        writer.write_all(rest)
+    }
+}
+
+/// The number of decimal digits of `n`.
+#[cfg(feature = "ser")]
+fn decimal_len(n: u64) -> usize {
+    n.checked_ilog10().map_or(1, |log| log as usize + 1)
+}
+
+/// A writer that only counts the bytes written to it.
+#[cfg(feature = "ser")]
+#[derive(Default)]
+pub(crate) struct ByteCounter(pub(crate) usize);
+
+#[cfg(feature = "ser")]
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Adapts a [fmt::Formatter] to [io::Write].
+///
+/// serde_json only writes complete UTF-8 sequences, so every chunk is valid UTF-8.
+#[cfg(feature = "ser")]
+pub(crate) struct FmtWriter<'a, 'b>(pub(crate) &'a mut fmt::Formatter<'b>);
+
+#[cfg(feature = "ser")]
+impl Write for FmtWriter<'_, '_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let s = std::str::from_utf8(buf)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        self.0.write_str(s).map_err(io::Error::other)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
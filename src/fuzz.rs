@@ -0,0 +1,89 @@
+//! Generators for the fields whose types don't implement [Arbitrary](arbitrary::Arbitrary).
+
+use crate::Str;
+use arbitrary::{Arbitrary, Result, Unstructured};
+use serde_json::{Map, Number, Value};
+use std::{collections::HashMap, hash::Hash};
+
+/// Arrays and objects are nested at most this deep, so generation always terminates.
+const MAX_DEPTH: usize = 3;
+
+pub(crate) fn number(u: &mut Unstructured) -> Result<Number> {
+    if u.arbitrary()? {
+        Ok(Number::from(u.arbitrary::<i64>()?))
+    } else {
+        // JSON has no representation for NaN or infinity
+        Ok(Number::from_f64(u.arbitrary()?).unwrap_or_else(|| Number::from(0)))
+    }
+}
+
+/// A number that is not an i64, which would be deserialized as
+/// [ModuleId::Integer](crate::types::ModuleId::Integer).
+pub(crate) fn non_i64_number(u: &mut Unstructured) -> Result<Number> {
+    if u.arbitrary()? {
+        Ok(Number::from(
+            u.int_in_range(i64::MAX as u64 + 1..=u64::MAX)?,
+        ))
+    } else {
+        Ok(Number::from_f64(u.arbitrary()?).unwrap_or_else(|| Number::from(u64::MAX)))
+    }
+}
+
+/// The value of an `Other` variant of an open enum. It contains a `:`, which none of the known
+/// values do, because those would be deserialized as their variant.
+pub(crate) fn other(u: &mut Unstructured) -> Result<Str> {
+    Ok(Str::from(format!("x:{}", u.arbitrary::<&str>()?)))
+}
+
+pub(crate) fn value(u: &mut Unstructured) -> Result<Value> {
+    value_with_depth(u, 0)
+}
+
+/// An absent value, because a null one is deserialized as [None].
+pub(crate) fn option_value(u: &mut Unstructured) -> Result<Option<Value>> {
+    Ok(Some(value(u)?).filter(|value| !value.is_null()))
+}
+
+pub(crate) fn option_boxed_value(u: &mut Unstructured) -> Result<Option<Box<Value>>> {
+    Ok(option_value(u)?.map(Box::new))
+}
+
+/// An absent map rather than an empty one, because an empty map is omitted when serializing.
+pub(crate) fn option_hash_map<'a, K, V>(u: &mut Unstructured<'a>) -> Result<Option<HashMap<K, V>>>
+where
+    K: Arbitrary<'a> + Eq + Hash,
+    V: Arbitrary<'a>,
+{
+    Ok(Option::<HashMap<K, V>>::arbitrary(u)?.filter(|map| !map.is_empty()))
+}
+
+pub(crate) fn map(u: &mut Unstructured) -> Result<Map<String, Value>> {
+    map_with_depth(u, 0)
+}
+
+fn value_with_depth(u: &mut Unstructured, depth: usize) -> Result<Value> {
+    let choices = if depth < MAX_DEPTH { 6 } else { 4 };
+    Ok(match u.choose_index(choices)? {
+        0 => Value::Null,
+        1 => Value::Bool(u.arbitrary()?),
+        2 => Value::Number(number(u)?),
+        3 => Value::String(u.arbitrary()?),
+        4 => {
+            let len = u.int_in_range(0..=4)?;
+            let array = (0..len)
+                .map(|_| value_with_depth(u, depth + 1))
+                .collect::<Result<_>>()?;
+            Value::Array(array)
+        }
+        _ => Value::Object(map_with_depth(u, depth + 1)?),
+    })
+}
+
+fn map_with_depth(u: &mut Unstructured, depth: usize) -> Result<Map<String, Value>> {
+    let len = u.int_in_range(0..=4)?;
+    let mut map = Map::new();
+    for _ in 0..len {
+        map.insert(u.arbitrary()?, value_with_depth(u, depth + 1)?);
+    }
+    Ok(map)
+}
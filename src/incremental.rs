@@ -0,0 +1,157 @@
+//! Incremental parsing of large arrays like the variables of a 'variables' response.
+//!
+//! A client that only renders the first page of a 100000 element array doesn't need to parse the
+//! remaining elements. [Elements] parses the elements of an array one at a time, when the iterator
+//! is advanced.
+
+use crate::types::{StackFrame, Variable};
+use serde::{
+    de::{Error as _, IgnoredAny},
+    Deserialize,
+};
+use serde_json::{Deserializer, Error};
+use std::marker::PhantomData;
+
+/// Returns an iterator over the variables of a serialized 'variables' response.
+pub fn variables(json: &str) -> Result<Elements<'_, Variable>, Error> {
+    Elements::at(json, &["body", "variables"])
+}
+
+/// Returns an iterator over the stack frames of a serialized 'stackTrace' response.
+pub fn stack_frames(json: &str) -> Result<Elements<'_, StackFrame>, Error> {
+    Elements::at(json, &["body", "stackFrames"])
+}
+
+/// An iterator that parses the elements of a JSON array one at a time.
+///
+/// The JSON following the array is not parsed at all, so a syntax error after the array is not
+/// detected. Use [borrowed](crate::borrowed) types as elements to avoid copying their strings.
+pub struct Elements<'de, T> {
+    json: &'de str,
+    position: usize,
+    first: bool,
+    done: bool,
+    element: PhantomData<fn() -> T>,
+}
+
+impl<'de, T: Deserialize<'de>> Elements<'de, T> {
+    /// Returns an iterator over the elements of the array found by following the object `path`
+    /// from the root of `json`, for example `["body", "variables"]`.
+    ///
+    /// Attributes before the array are skipped without being deserialized.
+    pub fn at(json: &'de str, path: &[&str]) -> Result<Elements<'de, T>, Error> {
+        let mut scanner = Scanner { json, position: 0 };
+        for (index, key) in path.iter().enumerate() {
+            if !scanner.find_key(key)? {
+                return Err(Error::custom(format_args!(
+                    "missing field `{}`",
+                    path[..=index].join(".")
+                )));
+            }
+        }
+        scanner.expect(b'[')?;
+        Ok(Elements {
+            json,
+            position: scanner.position,
+            first: true,
+            done: false,
+            element: PhantomData,
+        })
+    }
+
+    fn next_element(&mut self) -> Result<Option<T>, Error> {
+        let mut scanner = Scanner {
+            json: self.json,
+            position: self.position,
+        };
+        match scanner.peek() {
+            Some(b']') => return Ok(None),
+            Some(b',') if !self.first => scanner.position += 1,
+            _ if self.first => {}
+            _ => return Err(scanner.unexpected("`,` or `]`")),
+        }
+        self.first = false;
+        let element = scanner.parse()?;
+        self.position = scanner.position;
+        Ok(Some(element))
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Iterator for Elements<'de, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = self.next_element().transpose();
+        self.done = !matches!(next, Some(Ok(_)));
+        next
+    }
+}
+
+struct Scanner<'de> {
+    json: &'de str,
+    position: usize,
+}
+
+impl<'de> Scanner<'de> {
+    /// Skips whitespace and returns the next byte without consuming it.
+    fn peek(&mut self) -> Option<u8> {
+        let bytes = self.json.as_bytes();
+        while let Some(b' ' | b'\n' | b'\r' | b'\t') = bytes.get(self.position) {
+            self.position += 1;
+        }
+        bytes.get(self.position).copied()
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), Error> {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", expected as char)))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> Error {
+        Error::custom(format_args!(
+            "expected {} at byte offset {}",
+            expected, self.position
+        ))
+    }
+
+    /// Parses the next value and advances past it.
+    fn parse<T: Deserialize<'de>>(&mut self) -> Result<T, Error> {
+        let mut stream = Deserializer::from_str(&self.json[self.position..]).into_iter();
+        let value = stream
+            .next()
+            .unwrap_or_else(|| Err(self.unexpected("a value")))?;
+        self.position += stream.byte_offset();
+        Ok(value)
+    }
+
+    /// Advances into the object at the current position until the value of `key`. Returns false if
+    /// the object has no such key.
+    fn find_key(&mut self, key: &str) -> Result<bool, Error> {
+        self.expect(b'{')?;
+        if self.peek() == Some(b'}') {
+            return Ok(false);
+        }
+        loop {
+            self.peek();
+            let actual = self.parse::<String>()?;
+            self.expect(b':')?;
+            self.peek();
+            if actual == key {
+                return Ok(true);
+            }
+            self.parse::<IgnoredAny>()?;
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b'}') => return Ok(false),
+                _ => return Err(self.unexpected("`,` or `}`")),
+            }
+        }
+    }
+}
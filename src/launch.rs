@@ -0,0 +1,220 @@
+//! Parsing of VS Code style `launch.json` files.
+//!
+//! A `launch.json` holds a list of named debug configurations. Every configuration has a `type`
+//! that selects the debug adapter, a `request` that is either `launch` or `attach` and a `name`.
+//! All other attributes are implementation specific and are passed to the debug adapter as the
+//! arguments of the launch or attach request.
+//!
+//! Like VS Code, [LaunchJson::parse] accepts comments and trailing commas. Variables like
+//! `${workspaceFolder}` are not resolved automatically, call [LaunchConfiguration::substitute]
+//! with a resolver instead.
+
+use crate::requests::{AttachRequestArguments, LaunchRequestArguments, Request};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The keys of the platform specific attributes of a configuration.
+const PLATFORMS: [&str; 3] = ["windows", "osx", "linux"];
+
+/// The content of a `launch.json` file.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct LaunchJson {
+    /// The version of the file format, usually '0.2.0'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    #[serde(default)]
+    pub configurations: Vec<LaunchConfiguration>,
+
+    /// Other attributes like 'compounds' or 'inputs'.
+    #[serde(flatten)]
+    pub additional_attributes: Map<String, Value>,
+}
+
+impl LaunchJson {
+    /// Parses the content of a `launch.json` file, which may contain comments and trailing commas.
+    pub fn parse(json: &str) -> Result<LaunchJson, serde_json::Error> {
+        serde_json::from_str(&strip_jsonc(json))
+    }
+
+    /// Returns the first configuration with the given name.
+    pub fn configuration(&self, name: &str) -> Option<&LaunchConfiguration> {
+        self.configurations
+            .iter()
+            .find(|configuration| configuration.name == name)
+    }
+}
+
+/// A single debug configuration of a `launch.json` file.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct LaunchConfiguration {
+    /// The type of debug adapter to use for this configuration.
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// Whether the debug adapter should launch or attach to the debuggee.
+    pub request: LaunchRequestKind,
+
+    /// The name of the configuration that is shown to the user.
+    pub name: String,
+
+    /// The implementation specific attributes of this configuration.
+    #[serde(flatten)]
+    pub attributes: Map<String, Value>,
+}
+
+/// The 'request' attribute of a [LaunchConfiguration].
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchRequestKind {
+    Launch,
+    Attach,
+}
+
+impl LaunchConfiguration {
+    /// Replaces the variables of the form `${name}` in all string attributes with the value that
+    /// `resolver` returns for `name`, for example `workspaceFolder` or `env:HOME`. Variables that
+    /// the resolver returns `None` for are kept as is.
+    pub fn substitute(&mut self, resolver: impl Fn(&str) -> Option<String>) {
+        substitute_string(&mut self.name, &resolver);
+        for value in self.attributes.values_mut() {
+            substitute_value(value, &resolver);
+        }
+    }
+
+    /// Merges the attributes under the key of `platform` ('windows', 'osx' or 'linux') into the
+    /// configuration and removes the attributes of all platforms.
+    pub fn apply_platform(&mut self, platform: &str) {
+        let mut overrides = None;
+        for key in PLATFORMS {
+            if let Some(value) = self.attributes.remove(key) {
+                if key == platform {
+                    overrides = Some(value);
+                }
+            }
+        }
+        if let Some(Value::Object(overrides)) = overrides {
+            self.attributes.extend(overrides);
+        }
+    }
+
+    /// Converts the configuration into a launch or attach request. Like VS Code, the request
+    /// arguments include the 'type', 'request' and 'name' attributes of the configuration.
+    pub fn into_request(self) -> Result<Request, serde_json::Error> {
+        let kind = self.request;
+        let value = serde_json::to_value(self)?;
+        Ok(match kind {
+            LaunchRequestKind::Launch => {
+                serde_json::from_value::<LaunchRequestArguments>(value)?.into()
+            }
+            LaunchRequestKind::Attach => {
+                serde_json::from_value::<AttachRequestArguments>(value)?.into()
+            }
+        })
+    }
+}
+
+fn substitute_value(value: &mut Value, resolver: &impl Fn(&str) -> Option<String>) {
+    match value {
+        Value::String(string) => substitute_string(string, resolver),
+        Value::Array(values) => {
+            for value in values {
+                substitute_value(value, resolver);
+            }
+        }
+        Value::Object(map) => {
+            for value in map.values_mut() {
+                substitute_value(value, resolver);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn substitute_string(string: &mut String, resolver: &impl Fn(&str) -> Option<String>) {
+    if !string.contains("${") {
+        return;
+    }
+    let mut substituted = String::with_capacity(string.len());
+    let mut rest = string.as_str();
+    while let Some(start) = rest.find("${") {
+        substituted.push_str(&rest[..start]);
+        let variable = &rest[start + 2..];
+        match variable
+            .find('}')
+            .and_then(|end| Some((end, resolver(&variable[..end])?)))
+        {
+            Some((end, value)) => {
+                substituted.push_str(&value);
+                rest = &variable[end + 1..];
+            }
+            None => {
+                substituted.push_str("${");
+                rest = variable;
+            }
+        }
+    }
+    substituted.push_str(rest);
+    *string = substituted;
+}
+
+/// Removes comments and trailing commas, which VS Code allows in `launch.json` files.
+fn strip_jsonc(json: &str) -> String {
+    let mut stripped = String::with_capacity(json.len());
+    // The position in `stripped` of a comma that is trailing if only whitespace follows
+    let mut comma = None;
+    let mut chars = json.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                comma = None;
+                stripped.push(c);
+                while let Some(c) = chars.next() {
+                    stripped.push(c);
+                    match c {
+                        '\\' => stripped.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        stripped.push(c);
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = None;
+                for c in chars.by_ref() {
+                    if previous == Some('*') && c == '/' {
+                        break;
+                    }
+                    previous = Some(c);
+                }
+                stripped.push(' ');
+            }
+            ',' => {
+                comma = Some(stripped.len());
+                stripped.push(c);
+            }
+            '}' | ']' => {
+                if let Some(comma) = comma.take() {
+                    stripped.replace_range(comma..comma + 1, " ");
+                }
+                stripped.push(c);
+            }
+            c if c.is_whitespace() => stripped.push(c),
+            c => {
+                comma = None;
+                stripped.push(c);
+            }
+        }
+    }
+    stripped
+}
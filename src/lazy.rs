@@ -0,0 +1,185 @@
+//! Protocol messages whose arguments and bodies are only parsed on demand.
+
+use crate::{
+    borrowed::deserialize_option_cow, error::MessageKind, framing, ParseError, ProtocolMessage,
+    SequenceNumber,
+};
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::value::RawValue;
+use std::{
+    borrow::Cow,
+    io::{self, Write},
+    str::FromStr,
+};
+#[cfg(feature = "ts-rs")]
+use ts_rs::TS;
+
+/// A protocol message of which only the envelope is parsed.
+///
+/// The 'arguments' of requests and the 'body' of responses and events are kept as raw JSON, so
+/// proxies, recorders and routers can forward messages without decoding the parts they don't touch.
+/// When deserialized from a `&str` the raw JSON and strings borrow from the input.
+///
+/// Use [parse](LazyProtocolMessage::parse) to upgrade to a fully typed [ProtocolMessage] and
+/// [from_message](LazyProtocolMessage::from_message) to downgrade one.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
+pub struct LazyProtocolMessage<'a> {
+    #[serde(rename = "seq")]
+    #[cfg_attr(feature = "ts-rs", ts(type = "number"))]
+    pub seq: SequenceNumber,
+
+    #[serde(rename = "type")]
+    pub type_: MessageKind,
+
+    /// The command of a request or response.
+    #[serde(
+        rename = "command",
+        borrow,
+        default,
+        deserialize_with = "deserialize_option_cow",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub command: Option<Cow<'a, str>>,
+
+    /// The type of an event.
+    #[serde(
+        rename = "event",
+        borrow,
+        default,
+        deserialize_with = "deserialize_option_cow",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub event: Option<Cow<'a, str>>,
+
+    /// The sequence number of the request a response answers.
+    #[serde(rename = "request_seq", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(type = "number", optional))]
+    pub request_seq: Option<SequenceNumber>,
+
+    /// Whether a response is successful.
+    #[serde(rename = "success", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub success: Option<bool>,
+
+    /// The short error message of an error response.
+    #[serde(
+        rename = "message",
+        borrow,
+        default,
+        deserialize_with = "deserialize_option_cow",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub message: Option<Cow<'a, str>>,
+
+    /// The raw 'arguments' of a request.
+    #[serde(
+        rename = "arguments",
+        borrow,
+        default,
+        deserialize_with = "deserialize_raw",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<serde_json::Value>"))]
+    #[cfg_attr(feature = "ts-rs", ts(as = "Option<serde_json::Value>", optional))]
+    pub arguments: Option<Cow<'a, RawValue>>,
+
+    /// The raw 'body' of a response or event.
+    #[serde(
+        rename = "body",
+        borrow,
+        default,
+        deserialize_with = "deserialize_raw",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<serde_json::Value>"))]
+    #[cfg_attr(feature = "ts-rs", ts(as = "Option<serde_json::Value>", optional))]
+    pub body: Option<Cow<'a, RawValue>>,
+}
+
+impl<'a> LazyProtocolMessage<'a> {
+    /// Parses the envelope of a JSON encoded protocol message.
+    pub fn from_json(json: &'a str) -> serde_json::Result<LazyProtocolMessage<'a>> {
+        serde_json::from_str(json)
+    }
+
+    /// Parses the envelope of a JSON encoded protocol message from bytes. The raw arguments and body
+    /// and strings without escape sequences borrow from `json`.
+    pub fn from_slice(json: &'a [u8]) -> serde_json::Result<LazyProtocolMessage<'a>> {
+        serde_json::from_slice(json)
+    }
+
+    /// Downgrades a typed message.
+    pub fn from_message(
+        message: &ProtocolMessage,
+    ) -> serde_json::Result<LazyProtocolMessage<'static>> {
+        let json = serde_json::to_string(message)?;
+        Ok(LazyProtocolMessage::from_json(&json)?.into_owned())
+    }
+
+    /// Upgrades to a fully typed message by parsing the arguments or body.
+    pub fn parse(&self) -> Result<ProtocolMessage, ParseError> {
+        let json = serde_json::to_string(self).map_err(|error| {
+            ParseError::new(
+                Some(self.type_),
+                self.name().map(str::to_string),
+                None,
+                error,
+            )
+        })?;
+        ProtocolMessage::from_str(&json)
+    }
+
+    /// The command of a request or response or the type of an event.
+    pub fn name(&self) -> Option<&str> {
+        self.command.as_deref().or(self.event.as_deref())
+    }
+
+    /// Copies all borrowed data, so the message no longer borrows from its input.
+    pub fn into_owned(self) -> LazyProtocolMessage<'static> {
+        LazyProtocolMessage {
+            seq: self.seq,
+            type_: self.type_,
+            command: self.command.map(|command| Cow::Owned(command.into_owned())),
+            event: self.event.map(|event| Cow::Owned(event.into_owned())),
+            request_seq: self.request_seq,
+            success: self.success,
+            message: self.message.map(|message| Cow::Owned(message.into_owned())),
+            arguments: self.arguments.map(|raw| Cow::Owned(raw.into_owned())),
+            body: self.body.map(|raw| Cow::Owned(raw.into_owned())),
+        }
+    }
+
+    /// Writes this message with its Content-Length header to `writer`. The raw arguments and body
+    /// are copied verbatim.
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        framing::write_framed(writer, self)
+    }
+}
+
+/// Reads only the 'seq' of a JSON encoded protocol message, skipping all other attributes without
+/// decoding them. This is the cheapest way to log or acknowledge a message.
+pub fn probe_seq(json: &[u8]) -> serde_json::Result<SequenceNumber> {
+    #[derive(Deserialize)]
+    struct Envelope {
+        seq: SequenceNumber,
+    }
+    Ok(serde_json::from_slice::<Envelope>(json)?.seq)
+}
+
+/// Like [deserialize_option_cow] for raw JSON.
+fn deserialize_raw<'de: 'a, 'a, D>(deserializer: D) -> Result<Option<Cow<'a, RawValue>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<&'a RawValue>::deserialize(deserializer)?.map(Cow::Borrowed))
+}
@@ -1,28 +1,183 @@
+#[cfg(not(any(feature = "events", feature = "requests", feature = "responses")))]
+compile_error!("at least one of the features `events`, `requests` and `responses` must be enabled");
+#[cfg(not(any(feature = "ser", feature = "de")))]
+compile_error!("at least one of the features `ser` and `de` must be enabled");
+
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod assertions;
+#[cfg(feature = "rayon")]
+pub mod batch;
+pub mod borrowed;
+pub mod capabilities;
+#[cfg(feature = "events")]
+pub mod coalesce;
+#[cfg(all(feature = "ser", feature = "de"))]
+pub mod codec;
+#[cfg(any(feature = "dap-types", feature = "debugserver-types"))]
+pub mod compat;
+#[cfg(feature = "connection")]
+pub mod connection;
+#[cfg(all(feature = "requests", feature = "responses"))]
+pub mod correlation;
+#[cfg(all(feature = "requests", feature = "responses"))]
+pub mod data_breakpoints;
+#[cfg(feature = "decoder")]
+pub mod decoder;
+#[cfg(all(feature = "requests", feature = "responses"))]
+pub mod disassembly;
+#[cfg(all(
+    feature = "events",
+    feature = "requests",
+    feature = "responses",
+    feature = "ser",
+    feature = "de"
+))]
+pub mod driver;
+pub mod error;
+#[cfg(feature = "events")]
 pub mod events;
+pub mod exception;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "test-fixtures")]
+pub mod fixtures;
+pub mod framing;
+#[cfg(feature = "de")]
+pub mod incremental;
+#[cfg(all(feature = "requests", feature = "de"))]
+pub mod launch;
+#[cfg(all(feature = "ser", feature = "de"))]
+pub mod lazy;
+#[cfg(all(feature = "events", feature = "requests", feature = "responses"))]
+pub mod lint;
+pub mod localize;
+#[cfg(all(feature = "metrics", feature = "requests", feature = "responses"))]
+pub mod metrics;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod paths;
+#[cfg(feature = "bincode")]
+pub mod recording;
+pub mod redact;
+#[cfg(all(
+    feature = "de",
+    feature = "ser",
+    feature = "requests",
+    feature = "responses"
+))]
+pub mod registry;
+#[cfg(all(feature = "requests", feature = "responses"))]
+pub mod repl;
+#[cfg(feature = "replay")]
+pub mod replay;
+#[cfg(feature = "requests")]
 pub mod requests;
+#[cfg(feature = "responses")]
 pub mod responses;
+#[cfg(feature = "schema-validation")]
+pub mod schema;
+#[cfg(feature = "small-vec")]
+pub mod small_vec;
+pub mod sources;
+pub mod spec;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+#[cfg(feature = "ser")]
+pub mod trace;
+#[cfg(all(feature = "ser", feature = "de"))]
+pub mod transport;
 pub mod types;
+pub mod validate;
+#[cfg(all(feature = "requests", feature = "responses"))]
+pub mod watch;
 
+#[cfg(feature = "de")]
+mod de;
+#[cfg(feature = "de")]
+mod dispatch;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
 mod utils;
 
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "de")]
+use error::ParseError;
+#[cfg(feature = "events")]
 use events::Event;
+#[cfg(feature = "requests")]
 use requests::Request;
+#[cfg(feature = "responses")]
 use responses::Response;
-use serde::{Deserialize, Serialize};
-use std::fmt::Display;
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "de")]
+use serde::Deserialize;
+#[cfg(feature = "ser")]
+use serde::Serialize;
+#[cfg(feature = "de")]
+use serde_json::Value;
+#[cfg(feature = "de")]
+use std::str::FromStr;
+#[cfg(feature = "ser")]
+use std::{
+    fmt::Display,
+    io::{self, Write},
+};
+#[cfg(feature = "ts-rs")]
+use ts_rs::TS;
+#[cfg(feature = "builders")]
+use typed_builder::TypedBuilder;
 
 pub type SequenceNumber = u64;
 
+/// The type of the string fields of messages.
+///
+/// With the `shared-strings` feature this is `Arc<str>`, so clients that hand a message to several
+/// consumers can clone it without copying every string.
+#[cfg(not(feature = "shared-strings"))]
+pub type Str = String;
+
+/// The type of the string fields of messages.
+///
+/// With the `shared-strings` feature this is `Arc<str>`, so clients that hand a message to several
+/// consumers can clone it without copying every string.
+#[cfg(feature = "shared-strings")]
+pub type Str = std::sync::Arc<str>;
+
+/// The type of arrays that almost always hold zero or one elements.
+///
+/// With the `small-vec` feature this is `small_vec::InlineVec`, which stores a single element
+/// without allocating.
+#[cfg(not(feature = "small-vec"))]
+pub type SmallVec<T> = Vec<T>;
+
+/// The type of arrays that almost always hold zero or one elements.
+///
+/// With the `small-vec` feature this is [small_vec::InlineVec], which stores a single element
+/// without allocating.
+#[cfg(feature = "small-vec")]
+pub type SmallVec<T> = small_vec::InlineVec<T>;
+
 /// Base class of requests, responses, and events.
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub struct ProtocolMessage {
     /// Sequence number (also known as message ID). For protocol messages of type 'request' this ID can be used to cancel the request.
+    #[cfg_attr(feature = "ts-rs", ts(type = "number"))]
     pub seq: SequenceNumber,
 
     #[serde(flatten)]
     pub content: ProtocolMessageContent,
 }
 
+const _: () = assert!(std::mem::size_of::<ProtocolMessage>() <= 104);
+
 impl ProtocolMessage {
     pub fn new(seq: SequenceNumber, content: impl Into<ProtocolMessageContent>) -> ProtocolMessage {
         ProtocolMessage {
@@ -32,33 +187,141 @@ impl ProtocolMessage {
     }
 }
 
+#[cfg(feature = "ser")]
+impl ProtocolMessage {
+    /// Writes this message with its Content-Length header to `writer`.
+    ///
+    /// Unlike formatting the message with [Display] this streams the JSON straight into `writer`
+    /// without building it in memory first, at the cost of serializing the message twice to
+    /// compute its length. This avoids large transient allocations for big messages.
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        framing::write_framed(writer, self)
+    }
+}
+
+#[cfg(feature = "de")]
+impl ProtocolMessage {
+    /// Parses a JSON encoded protocol message using the given options.
+    pub fn parse_with(json: &str, options: &ParseOptions) -> Result<ProtocolMessage, ParseError> {
+        let value = serde_json::from_str::<Value>(json)
+            .map_err(|error| ParseError::new(None, None, None, error))?;
+        de::from_value(&value, options)
+    }
+
+    /// Parses a JSON encoded protocol message from bytes, for example the content of a frame split
+    /// off with [split_frame](framing::split_frame), without converting them to a `str` first.
+    ///
+    /// Like [from_str](ProtocolMessage::from_str) this reports the kind, command or event name and
+    /// the path of the offending field if the message is invalid.
+    pub fn from_slice(json: &[u8]) -> Result<ProtocolMessage, ParseError> {
+        serde_json::from_slice(json).or_else(|error| match serde_json::from_slice::<Value>(json) {
+            Ok(value) => de::from_value(&value, &ParseOptions::default())
+                .and(Err(ParseError::new(None, None, None, error))),
+            Err(_) => Err(ParseError::new(None, None, None, error)),
+        })
+    }
+
+    /// Parses a JSON encoded protocol message from bytes using the given options.
+    pub fn parse_slice_with(
+        json: &[u8],
+        options: &ParseOptions,
+    ) -> Result<ProtocolMessage, ParseError> {
+        let value = serde_json::from_slice::<Value>(json)
+            .map_err(|error| ParseError::new(None, None, None, error))?;
+        de::from_value(&value, options)
+    }
+}
+
+#[cfg(feature = "de")]
+impl FromStr for ProtocolMessage {
+    type Err = ParseError;
+
+    /// Parses a JSON encoded protocol message.
+    ///
+    /// Unlike `serde_json::from_str` this reports the kind, command or event name and the path of
+    /// the offending field if the message is invalid.
+    fn from_str(json: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(json).or_else(|error| match serde_json::from_str::<Value>(json) {
+            Ok(value) => de::from_value(&value, &ParseOptions::default())
+                .and(Err(ParseError::new(None, None, None, error))),
+            Err(_) => Err(ParseError::new(None, None, None, error)),
+        })
+    }
+}
+
+/// Options for parsing protocol messages with [ProtocolMessage::parse_with].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// Reject messages that do not strictly conform to the specification instead of tolerating
+    /// them: unknown attributes and violations of the constraints checked by
+    /// [Validate](validate::Validate) are reported as errors. This is intended for conformance
+    /// test suites.
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub strict: bool,
+
+    /// Accept floats without a fractional part (`1.0`) and numeric strings (`"1"`) for integer
+    /// attributes. Some clients and debug adapters encode integers this way.
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub lenient_numbers: bool,
+
+    /// The maximum nesting depth of objects and arrays, where the message itself is at depth 1.
+    /// Exceeding it is reported as an error instead of recursing any deeper, which protects
+    /// against hostile input with deeply nested recursive structures like
+    /// [ExceptionDetails](types::ExceptionDetails) or [Source](types::Source). Independently of
+    /// this option the JSON parser rejects nesting deeper than 128.
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub max_depth: Option<usize>,
+}
+
+#[cfg(feature = "ser")]
 impl Display for ProtocolMessage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let json = serde_json::to_string(&self).unwrap();
-        write!(f, "Content-Length: {}\r\n\r\n{}", json.len(), json)
+        framing::write_framed(framing::FmtWriter(f), self).map_err(|_| std::fmt::Error)
     }
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
 #[serde(rename_all = "camelCase", tag = "type")]
+#[non_exhaustive]
 pub enum ProtocolMessageContent {
     /// A client or debug adapter initiated request.
+    #[cfg(feature = "requests")]
     Request(Request),
 
     /// Response for a request.
+    #[cfg(feature = "responses")]
     Response(Response),
 
     /// A debug adapter initiated event.
+    #[cfg(feature = "events")]
     Event(Event),
 }
 
-#[cfg(test)]
+#[cfg(all(
+    test,
+    feature = "builders",
+    feature = "events",
+    feature = "requests",
+    feature = "responses",
+    feature = "ser",
+    feature = "de"
+))]
 mod tests {
     use serde_json::{Map, Number, Value};
 
     use super::*;
-    use crate::{events::*, requests::*, responses::*, types::*};
-    use std::{collections::HashMap, iter::FromIterator};
+    use crate::{
+        assertions::*, capabilities::*, correlation::*, error::*, events::*, paths::*, redact::*,
+        requests::*, responses::*, types::*, validate::*,
+    };
+    use std::{borrow::Cow, collections::HashMap, iter::FromIterator};
 
     #[test]
     fn test_deserialize_request_initialize() {
@@ -84,7 +347,7 @@ mod tests {
         }"#;
 
         // when:
-        let actual = serde_json::from_str::<ProtocolMessage>(&json).unwrap();
+        let actual = serde_json::from_str::<ProtocolMessage>(json).unwrap();
 
         // then:
         assert_eq!(
@@ -92,10 +355,10 @@ mod tests {
             ProtocolMessage {
                 seq: 1,
                 content: InitializeRequestArguments::builder()
-                    .client_id(Some("vscode".to_string()))
-                    .client_name(Some("Visual Studio Code".to_string()))
-                    .adapter_id("mock".to_string())
-                    .locale(Some("de".to_string()))
+                    .client_id(Some("vscode".into()))
+                    .client_name(Some("Visual Studio Code".into()))
+                    .adapter_id("mock".into())
+                    .locale(Some("de".into()))
                     .lines_start_at_1(true)
                     .columns_start_at_1(true)
                     .path_format(PathFormat::Path)
@@ -116,12 +379,12 @@ mod tests {
         // given:
         let under_test = ProtocolMessage {
             seq: 1,
-            content: ProtocolMessageContent::Request(Request::Initialize(
+            content: ProtocolMessageContent::Request(Request::Initialize(Box::new(
                 InitializeRequestArguments::builder()
-                    .client_id(Some("vscode".to_string()))
-                    .client_name(Some("Visual Studio Code".to_string()))
-                    .adapter_id("mock".to_string())
-                    .locale(Some("de".to_string()))
+                    .client_id(Some("vscode".into()))
+                    .client_name(Some("Visual Studio Code".into()))
+                    .adapter_id("mock".into())
+                    .locale(Some("de".into()))
                     .lines_start_at_1(true)
                     .columns_start_at_1(true)
                     .path_format(PathFormat::Path)
@@ -132,15 +395,15 @@ mod tests {
                     .supports_progress_reporting(true)
                     .supports_invalidated_event(true)
                     .build(),
-            )),
+            ))),
         };
 
         // when:
-        let actual = serde_json::to_string_pretty(&under_test).unwrap();
+        let actual = serde_json::to_string(&under_test).unwrap();
 
         // then:
-        assert_eq!(
-            actual,
+        assert_json_eq(
+            &actual,
             r#"{
   "seq": 1,
   "type": "request",
@@ -150,18 +413,33 @@ mod tests {
     "clientName": "Visual Studio Code",
     "adapterID": "mock",
     "locale": "de",
-    "linesStartAt1": true,
-    "columnsStartAt1": true,
     "supportsVariableType": true,
     "supportsVariablePaging": true,
     "supportsRunInTerminalRequest": true,
     "supportsProgressReporting": true,
     "supportsInvalidatedEvent": true
   }
-}"#
+}"#,
         );
     }
 
+    #[cfg(feature = "spec-1_70")]
+    #[test]
+    fn test_newer_client_capabilities() {
+        // given:
+        let json = r#"{"adapterID":"mock","supportsMemoryEvent":true,"supportsArgsCanBeInterpretedByShell":true,"supportsStartDebuggingRequest":true,"supportsANSIStyling":true}"#;
+
+        // when:
+        let actual = serde_json::from_str::<InitializeRequestArguments>(json).unwrap();
+
+        // then:
+        assert!(actual.supports_memory_event);
+        assert!(actual.supports_args_can_be_interpreted_by_shell);
+        assert!(actual.supports_start_debugging_request);
+        assert!(actual.supports_ansi_styling);
+        assert_eq!(serde_json::to_string(&actual).unwrap(), json);
+    }
+
     #[test]
     fn test_deserialize_response_initialize() {
         // given:
@@ -191,16 +469,18 @@ mod tests {
                 seq: 1,
                 content: ProtocolMessageContent::Response(Response {
                     request_seq: 1,
-                    result: Ok(SuccessResponse::Initialize(
+                    result: Ok(SuccessResponse::Initialize(Box::new(
                         Capabilities::builder()
-                            .supports_configuration_done_request(true)
-                            .supports_function_breakpoints(true)
-                            .supports_conditional_breakpoints(true)
-                            .supports_hit_conditional_breakpoints(true)
-                            .supports_data_breakpoints(true)
-                            .supports_instruction_breakpoints(true)
+                            .flags(
+                                CapabilityFlags::SUPPORTS_CONFIGURATION_DONE_REQUEST
+                                    | CapabilityFlags::SUPPORTS_FUNCTION_BREAKPOINTS
+                                    | CapabilityFlags::SUPPORTS_CONDITIONAL_BREAKPOINTS
+                                    | CapabilityFlags::SUPPORTS_HIT_CONDITIONAL_BREAKPOINTS
+                                    | CapabilityFlags::SUPPORTS_DATA_BREAKPOINTS
+                                    | CapabilityFlags::SUPPORTS_INSTRUCTION_BREAKPOINTS
+                            )
                             .build()
-                    ))
+                    )))
                 })
             }
         )
@@ -213,25 +493,27 @@ mod tests {
             seq: 1,
             content: ProtocolMessageContent::Response(Response {
                 request_seq: 1,
-                result: Ok(SuccessResponse::Initialize(
+                result: Ok(SuccessResponse::Initialize(Box::new(
                     Capabilities::builder()
-                        .supports_configuration_done_request(true)
-                        .supports_function_breakpoints(true)
-                        .supports_conditional_breakpoints(true)
-                        .supports_hit_conditional_breakpoints(true)
-                        .supports_data_breakpoints(true)
-                        .supports_instruction_breakpoints(true)
+                        .flags(
+                            CapabilityFlags::SUPPORTS_CONFIGURATION_DONE_REQUEST
+                                | CapabilityFlags::SUPPORTS_FUNCTION_BREAKPOINTS
+                                | CapabilityFlags::SUPPORTS_CONDITIONAL_BREAKPOINTS
+                                | CapabilityFlags::SUPPORTS_HIT_CONDITIONAL_BREAKPOINTS
+                                | CapabilityFlags::SUPPORTS_DATA_BREAKPOINTS
+                                | CapabilityFlags::SUPPORTS_INSTRUCTION_BREAKPOINTS,
+                        )
                         .build(),
-                )),
+                ))),
             }),
         };
 
         // when:
-        let actual = serde_json::to_string_pretty(&under_test).unwrap();
+        let actual = serde_json::to_string(&under_test).unwrap();
 
         // then:
-        assert_eq!(
-            actual,
+        assert_json_eq(
+            &actual,
             r#"{
   "seq": 1,
   "type": "response",
@@ -246,7 +528,7 @@ mod tests {
     "supportsDataBreakpoints": true,
     "supportsInstructionBreakpoints": true
   }
-}"#
+}"#,
         )
     }
 
@@ -279,13 +561,13 @@ mod tests {
                 content: ProtocolMessageContent::Response(Response {
                     request_seq: 2,
                     result: Err(ErrorResponse::builder()
-                        .command("initialize".to_string())
-                        .message("Something went wrong".to_string())
+                        .command("initialize".into())
+                        .message("Something went wrong".into())
                         .body(ErrorResponseBody::new(Some(
                             Message::builder()
                                 .id(3)
-                                .format("This thing went wrong".to_string())
-                                .variables(HashMap::new())
+                                .format("This thing went wrong".into())
+                                .variables(None)
                                 .send_telemetry(false)
                                 .show_user(false)
                                 .url(None)
@@ -306,13 +588,13 @@ mod tests {
             content: ProtocolMessageContent::Response(Response {
                 request_seq: 2,
                 result: Err(ErrorResponse::builder()
-                    .command("initialize".to_string())
-                    .message("Something went wrong".to_string())
+                    .command("initialize".into())
+                    .message("Something went wrong".into())
                     .body(ErrorResponseBody::new(Some(
                         Message::builder()
                             .id(3)
-                            .format("This thing went wrong".to_string())
-                            .variables(HashMap::new())
+                            .format("This thing went wrong".into())
+                            .variables(None)
                             .send_telemetry(false)
                             .show_user(false)
                             .url(None)
@@ -324,11 +606,11 @@ mod tests {
         };
 
         // when:
-        let actual = serde_json::to_string_pretty(&under_test).unwrap();
+        let actual = serde_json::to_string(&under_test).unwrap();
 
         // then:
-        assert_eq!(
-            actual,
+        assert_json_eq(
+            &actual,
             r#"{
   "seq": 1,
   "type": "response",
@@ -342,7 +624,7 @@ mod tests {
       "format": "This thing went wrong"
     }
   }
-}"#
+}"#,
         )
     }
 
@@ -371,6 +653,34 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_assert_json_eq_ignores_key_order_and_whitespace() {
+        // given:
+        let actual = r#"{"seq":1,"body":{"a":[1,2]},"type":"event"}"#;
+        let expected = r#"{
+            "type": "event",
+            "seq": 1,
+            "body": { "a": [1, 3], "b": true }
+        }"#;
+
+        // when:
+        let failure = std::panic::catch_unwind(|| assert_json_eq(actual, expected)).unwrap_err();
+
+        // then:
+        assert_json_eq(actual, &actual.replace(',', " , "));
+        let message = failure.downcast_ref::<String>().unwrap();
+        assert!(
+            message.contains("/body/a/1: expected 3 but got 2"),
+            "{}",
+            message
+        );
+        assert!(
+            message.contains("/body/b: expected true but got nothing"),
+            "{}",
+            message
+        );
+    }
+
     #[test]
     fn test_serialize_event_exited() {
         // given:
@@ -380,11 +690,11 @@ mod tests {
         };
 
         // when:
-        let actual = serde_json::to_string_pretty(&under_test).unwrap();
+        let actual = serde_json::to_string(&under_test).unwrap();
 
         // then:
-        assert_eq!(
-            actual,
+        assert_json_eq(
+            &actual,
             r#"{
   "seq": 1,
   "type": "event",
@@ -392,7 +702,7 @@ mod tests {
   "body": {
     "exitCode": 0
   }
-}"#
+}"#,
         )
     }
 
@@ -413,14 +723,14 @@ mod tests {
         }"#;
 
         // when:
-        let actual = serde_json::from_str::<ProtocolMessage>(&json).unwrap();
+        let actual = serde_json::from_str::<ProtocolMessage>(json).unwrap();
 
         // then:
         assert_eq!(
             actual,
             ProtocolMessage {
                 seq: 1,
-                content: ProtocolMessageContent::Request(Request::Launch(
+                content: ProtocolMessageContent::Request(Request::Launch(Box::new(
                     LaunchRequestArguments::builder()
                         .no_debug(true)
                         .restart(Some(Value::String("Some Value".to_string())))
@@ -436,7 +746,7 @@ mod tests {
                             ("blub".to_string(), Value::Bool(true))
                         ]))
                         .build()
-                ))
+                )))
             }
         );
     }
@@ -446,7 +756,7 @@ mod tests {
         // given:
         let under_test = ProtocolMessage {
             seq: 1,
-            content: ProtocolMessageContent::Request(Request::Launch(
+            content: ProtocolMessageContent::Request(Request::Launch(Box::new(
                 LaunchRequestArguments::builder()
                     .no_debug(true)
                     .restart(Some(Value::String("Some Value".to_string())))
@@ -462,15 +772,15 @@ mod tests {
                         ("blub".to_string(), Value::Bool(true)),
                     ]))
                     .build(),
-            )),
+            ))),
         };
 
         // when:
-        let actual = serde_json::to_string_pretty(&under_test).unwrap();
+        let actual = serde_json::to_string(&under_test).unwrap();
 
         // then:
-        assert_eq!(
-            actual,
+        assert_json_eq(
+            &actual,
             r#"{
   "seq": 1,
   "type": "request",
@@ -484,7 +794,7 @@ mod tests {
     "bla": 1,
     "blub": true
   }
-}"#
+}"#,
         );
     }
 
@@ -499,16 +809,16 @@ mod tests {
         }"#;
 
         // when:
-        let actual = serde_json::from_str::<ProtocolMessage>(&json).unwrap();
+        let actual = serde_json::from_str::<ProtocolMessage>(json).unwrap();
 
         // then:
         assert_eq!(
             actual,
             ProtocolMessage {
                 seq: 1,
-                content: ProtocolMessageContent::Request(Request::Launch(
+                content: ProtocolMessageContent::Request(Request::Launch(Box::new(
                     LaunchRequestArguments::builder().build()
-                ))
+                )))
             }
         );
     }
@@ -518,23 +828,3687 @@ mod tests {
         // given:
         let under_test = ProtocolMessage {
             seq: 1,
-            content: ProtocolMessageContent::Request(Request::Launch(
+            content: ProtocolMessageContent::Request(Request::Launch(Box::new(
                 LaunchRequestArguments::builder().build(),
-            )),
+            ))),
         };
 
         // when:
-        let actual = serde_json::to_string_pretty(&under_test).unwrap();
+        let actual = serde_json::to_string(&under_test).unwrap();
 
         // then:
-        assert_eq!(
-            actual,
+        assert_json_eq(
+            &actual,
             r#"{
   "seq": 1,
   "type": "request",
   "command": "launch",
   "arguments": {}
-}"#
+}"#,
+        );
+    }
+
+    #[test]
+    fn test_parse_error_reports_response_body_path() {
+        // given:
+        let json = r#"{
+            "seq": 5,
+            "type": "response",
+            "request_seq": 4,
+            "success": true,
+            "command": "stackTrace",
+            "body": {
+                "stackFrames": [
+                    { "id": 1, "name": "main", "line": 1, "column": 1 },
+                    { "id": 2, "name": "foo", "line": "2", "column": 1 }
+                ]
+            }
+        }"#;
+
+        // when:
+        let actual = json.parse::<ProtocolMessage>().unwrap_err();
+
+        // then:
+        assert_eq!(actual.kind(), Some(MessageKind::Response));
+        assert_eq!(actual.name(), Some("stackTrace"));
+        assert_eq!(actual.path(), Some("body.stackFrames[1].line"));
+    }
+
+    #[test]
+    fn test_parse_error_reports_missing_request_argument() {
+        // given:
+        let json = r#"{
+            "seq": 1,
+            "type": "request",
+            "command": "initialize",
+            "arguments": { "clientID": "vscode" }
+        }"#;
+
+        // when:
+        let actual = json.parse::<ProtocolMessage>().unwrap_err();
+
+        // then:
+        assert_eq!(actual.kind(), Some(MessageKind::Request));
+        assert_eq!(actual.name(), Some("initialize"));
+        assert_eq!(actual.path(), Some("arguments"));
+        assert!(actual.to_string().contains("adapterID"), "{}", actual);
+    }
+
+    #[test]
+    fn test_parse_strict_rejects_unknown_fields() {
+        // given:
+        let json = r#"{
+            "seq": 3,
+            "type": "event",
+            "event": "exited",
+            "body": { "exitCode": 0, "exitSignal": 9 }
+        }"#;
+        let options = ParseOptions::builder().strict(true).build();
+
+        // when:
+        let lenient = ProtocolMessage::parse_with(json, &ParseOptions::default());
+        let strict = ProtocolMessage::parse_with(json, &options);
+
+        // then:
+        assert_eq!(
+            lenient.unwrap(),
+            ProtocolMessage::new(3, ExitedEventBody::builder().exit_code(0).build())
+        );
+        let actual = strict.unwrap_err();
+        assert_eq!(actual.name(), Some("exited"));
+        assert_eq!(actual.path(), Some("body.exitSignal"));
+    }
+
+    #[test]
+    fn test_parse_empty_and_missing_content() {
+        use crate::events::TerminatedEventBody;
+
+        // given:
+        let initialized = r#"{"seq":1,"type":"event","event":"initialized","body":{}}"#;
+        let configuration_done =
+            r#"{"seq":2,"type":"request","command":"configurationDone","arguments":{}}"#;
+        let terminated = r#"{"seq":3,"type":"event","event":"terminated"}"#;
+        let stopped = r#"{"seq":4,"type":"event","event":"stopped"}"#;
+        let unit_with_attributes =
+            r#"{"seq":5,"type":"event","event":"initialized","body":{"a":1}}"#;
+        let strict = ParseOptions::builder().strict(true).build();
+
+        // when:
+        let actual = [initialized, configuration_done, terminated]
+            .map(|json| ProtocolMessage::from_str(json).unwrap());
+
+        // then:
+        assert_eq!(
+            actual,
+            [
+                ProtocolMessage::new(1, Event::Initialized),
+                ProtocolMessage::new(2, Request::ConfigurationDone),
+                ProtocolMessage::new(3, Event::Terminated(TerminatedEventBody { restart: None })),
+            ]
+        );
+        let error = ProtocolMessage::from_str(stopped).unwrap_err();
+        assert!(
+            error.to_string().contains("missing field `body`"),
+            "{}",
+            error
+        );
+        assert!(ProtocolMessage::parse_with(initialized, &strict).is_ok());
+        assert!(ProtocolMessage::from_str(unit_with_attributes).is_ok());
+        assert!(ProtocolMessage::parse_with(unit_with_attributes, &strict).is_err());
+    }
+
+    #[test]
+    fn test_parse_strict_rejects_out_of_range_percentage() {
+        // given:
+        let json = r#"{
+            "seq": 3,
+            "type": "event",
+            "event": "progressUpdate",
+            "body": { "progressId": "1", "percentage": 150 }
+        }"#;
+        let options = ParseOptions::builder().strict(true).build();
+
+        // when:
+        let actual = ProtocolMessage::parse_with(json, &options).unwrap_err();
+
+        // then:
+        assert_eq!(actual.path(), Some("body.percentage"));
+    }
+
+    #[test]
+    fn test_validate_reports_all_violations() {
+        // given:
+        let message = ProtocolMessage::new(
+            2,
+            ProtocolMessageContent::Response(Response {
+                request_seq: 1,
+                result: Ok(VariablesResponseBody::builder()
+                    .variables(vec![
+                        Variable::builder()
+                            .name("a".into())
+                            .value("1".into())
+                            .variables_reference(0)
+                            .build(),
+                        Variable::builder()
+                            .name("b".into())
+                            .value("2".into())
+                            .variables_reference(-1)
+                            .build(),
+                    ])
+                    .build()
+                    .into()),
+            }),
+        );
+
+        // when:
+        let actual = message.validate();
+
+        // then:
+        let paths = actual.iter().map(Violation::path).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["body.variables[1].variablesReference"]);
+    }
+
+    #[test]
+    fn test_validate_source_without_path_or_reference() {
+        // given:
+        let arguments = BreakpointLocationsRequestArguments::builder()
+            .source(Source::builder().name(Some("main.rs".into())).build())
+            .line(5)
+            .end_line(Some(3))
+            .build();
+
+        // when:
+        let actual = arguments.validate();
+
+        // then:
+        let paths = actual.iter().map(Violation::path).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["source", "endLine"]);
+    }
+
+    #[cfg(feature = "spec-1_70")]
+    #[test]
+    fn test_validate_step_in_target_range() {
+        // given:
+        let body = StepInTargetsResponseBody::builder()
+            .targets(vec![
+                StepInTarget::builder()
+                    .id(1)
+                    .label("foo".into())
+                    .line(Some(4))
+                    .column(Some(9))
+                    .end_line(Some(4))
+                    .end_column(Some(14))
+                    .build(),
+                StepInTarget::builder()
+                    .id(2)
+                    .label("bar".into())
+                    .line(Some(4))
+                    .column(Some(20))
+                    .end_column(Some(16))
+                    .build(),
+            ])
+            .build();
+
+        // when:
+        let actual = ProtocolMessage::new(2, Response::new(1, Ok(body.into()))).validate();
+
+        // then:
+        let paths = actual.iter().map(Violation::path).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["body.targets[1].endColumn"]);
+    }
+
+    #[test]
+    fn test_parse_malformed_input_does_not_panic() {
+        // given:
+        let valid = r#"{"seq":3,"type":"response","request_seq":2,"success":true,"command":"stackTrace","body":{"stackFrames":[{"id":1,"name":"main","line":1,"column":1,"source":{"path":"a.rs"}}],"totalFrames":1}}"#;
+        let mut inputs = (0..valid.len())
+            .map(|index| valid[..index].to_string())
+            .collect::<Vec<_>>();
+        inputs.extend(
+            [
+                r#"{"seq":1,"type":"response","request_seq":1,"success":1e300,"command":"threads"}"#,
+                r#"{"seq":1,"type":"response","request_seq":1,"success":-1,"command":"threads"}"#,
+                r#"{"seq":1,"type":"response","request_seq":1,"success":null,"command":"threads"}"#,
+                r#"{"seq":1,"type":"response","request_seq":1,"success":true,"command":7}"#,
+                r#"{"seq":1,"type":"response","request_seq":-1,"success":false}"#,
+                r#"{"seq":-1,"type":"event","event":"stopped","body":[]}"#,
+                r#"{"seq":1,"type":[],"command":{}}"#,
+                r#"{"seq":1e400,"type":"request","command":"threads"}"#,
+                r#"[{"seq":1}]"#,
+                r#""response""#,
+                "\u{feff}{}",
+            ]
+            .iter()
+            .map(ToString::to_string),
+        );
+        let strict = ParseOptions::builder().strict(true).build();
+
+        for input in inputs {
+            // when:
+            let actual = input.parse::<ProtocolMessage>();
+            let actual_strict = ProtocolMessage::parse_with(&input, &strict);
+
+            // then:
+            assert!(actual.is_err(), "{}", input);
+            assert!(actual_strict.is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_lenient_numbers() {
+        // given:
+        let json = r#"{
+            "seq": "7",
+            "type": "event",
+            "event": "thread",
+            "body": { "reason": "started", "threadId": 3.0 }
+        }"#;
+        let options = ParseOptions::builder().lenient_numbers(true).build();
+
+        // when:
+        let default = ProtocolMessage::parse_with(json, &ParseOptions::default());
+        let lenient = ProtocolMessage::parse_with(json, &options);
+
+        // then:
+        assert_eq!(default.unwrap_err().path(), Some("seq"));
+        assert_eq!(
+            lenient.unwrap(),
+            ProtocolMessage::new(
+                7,
+                ThreadEventBody::builder()
+                    .reason(ThreadEventReason::Started)
+                    .thread_id(3)
+                    .build()
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient_numbers_rejects_lossy_values() {
+        // given:
+        let json = r#"{
+            "seq": 1,
+            "type": "event",
+            "event": "thread",
+            "body": { "reason": "started", "threadId": 3.5 }
+        }"#;
+        let options = ParseOptions::builder().lenient_numbers(true).build();
+
+        // when:
+        let actual = ProtocolMessage::parse_with(json, &options);
+
+        // then:
+        assert_eq!(actual.unwrap_err().path(), Some("body.threadId"));
+    }
+
+    #[test]
+    fn test_deserialize_stack_frame_without_line_and_column() {
+        // given:
+        let json = r#"{
+            "seq": 4,
+            "type": "response",
+            "request_seq": 3,
+            "success": true,
+            "command": "stackTrace",
+            "body": {
+                "stackFrames": [
+                    { "id": 1, "name": "main", "line": 5, "column": 1 },
+                    { "id": 2, "name": "[Async boundary]", "presentationHint": "label" }
+                ]
+            }
+        }"#;
+
+        // when:
+        let actual = json.parse::<ProtocolMessage>().unwrap();
+
+        // then:
+        let stack_frames = match actual.content {
+            ProtocolMessageContent::Response(Response {
+                result: Ok(SuccessResponse::StackTrace(body)),
+                ..
+            }) => body.stack_frames,
+            content => panic!("unexpected content: {:?}", content),
+        };
+        assert_eq!((stack_frames[1].line, stack_frames[1].column), (0, 0));
+    }
+
+    #[cfg(feature = "spec-1_70")]
+    #[test]
+    fn test_resolve_location_reference() {
+        // given:
+        let request = r#"{
+            "seq": 5,
+            "type": "request",
+            "command": "locations",
+            "arguments": { "locationReference": 3 }
+        }"#;
+        let response = r#"{
+            "seq": 6,
+            "type": "response",
+            "request_seq": 5,
+            "success": true,
+            "command": "locations",
+            "body": {
+                "source": { "path": "/src/main.rs" },
+                "line": 7,
+                "column": 9,
+                "endLine": 6
+            }
+        }"#;
+
+        // when:
+        let request = request.parse::<ProtocolMessage>().unwrap();
+        let response = response.parse::<ProtocolMessage>().unwrap();
+
+        // then:
+        assert_eq!(
+            request.content,
+            Request::from(
+                LocationsRequestArguments::builder()
+                    .location_reference(3)
+                    .build()
+            )
+            .into()
+        );
+        let body = match &response.content {
+            ProtocolMessageContent::Response(Response {
+                result: Ok(SuccessResponse::Locations(body)),
+                ..
+            }) => body,
+            content => panic!("unexpected content: {:?}", content),
+        };
+        assert_eq!(body.source.path.as_deref(), Some("/src/main.rs"));
+        assert_eq!((body.line, body.column), (7, Some(9)));
+        let violations = response.validate();
+        let paths = violations.iter().map(Violation::path).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["body.endLine"]);
+    }
+
+    #[test]
+    fn test_deserialize_module_ids() {
+        // given:
+        let json = r#"[140694538682368,18446744073709551615,-1,2.5,"kernel32.dll"]"#;
+
+        // when:
+        let actual = serde_json::from_str::<Vec<ModuleId>>(json).unwrap();
+
+        // then:
+        assert_eq!(
+            actual,
+            vec![
+                ModuleId::Integer(0x7FF6_0000_0000),
+                ModuleId::Number(u64::MAX.into()),
+                ModuleId::Integer(-1),
+                ModuleId::Number(Number::from_f64(2.5).unwrap()),
+                ModuleId::String("kernel32.dll".into()),
+            ]
+        );
+        assert_eq!(serde_json::to_string(&actual).unwrap(), json);
+    }
+
+    #[test]
+    fn test_source_reference_zero_is_absent() {
+        // given:
+        let with_zero = Source::builder()
+            .path(Some("/a.rs".into()))
+            .source_reference(Some(0))
+            .build();
+        let without = Source::builder().path(Some("/a.rs".into())).build();
+        let with_reference = Source::builder()
+            .path(Some("/a.rs".into()))
+            .source_reference(Some(4))
+            .build();
+
+        // when:
+        let actual = (
+            with_zero.effective_source_reference(),
+            with_zero.is_same_source(&without),
+            with_zero.is_same_source(&with_reference),
+        );
+
+        // then:
+        assert_eq!(actual, (None, true, false));
+    }
+
+    #[test]
+    fn test_parse_max_depth() {
+        // given:
+        let json = r#"{
+            "seq": 1,
+            "type": "event",
+            "event": "loadedSource",
+            "body": {
+                "reason": "new",
+                "source": {
+                    "path": "/a.js",
+                    "sources": [{ "path": "/b.ts", "sources": [{ "path": "/c.ts" }] }]
+                }
+            }
+        }"#;
+        let options = ParseOptions::builder().max_depth(Some(5)).build();
+
+        // when:
+        let actual = ProtocolMessage::parse_with(json, &options);
+
+        // then:
+        let actual = actual.unwrap_err();
+        assert_eq!(actual.path(), Some("body.source.sources[0].sources"));
+        assert!(actual.to_string().contains("maximum nesting depth of 5"));
+    }
+
+    #[test]
+    fn test_pending_requests_complete() {
+        // given:
+        let mut pending = PendingRequests::new();
+        pending.insert(1, Request::Threads);
+        pending.insert(2, Request::ConfigurationDone);
+        let misattributed = Response {
+            request_seq: 1,
+            result: Ok(SuccessResponse::ConfigurationDone),
+        };
+        let unknown = Response {
+            request_seq: 3,
+            result: Ok(SuccessResponse::ConfigurationDone),
+        };
+        let matching = Response {
+            request_seq: 2,
+            result: Err(ErrorResponse::builder()
+                .command("configurationDone".into())
+                .message("failed".into())
+                .build()),
+        };
+
+        // when:
+        let actual = (
+            pending.complete(&misattributed),
+            pending.complete(&unknown),
+            pending.complete(&matching),
+        );
+
+        // then:
+        assert_eq!(
+            actual,
+            (
+                Err(CorrelationError::CommandMismatch {
+                    request_seq: 1,
+                    expected: "threads".to_string(),
+                    actual: "configurationDone".to_string(),
+                }),
+                Err(CorrelationError::UnknownRequest { request_seq: 3 }),
+                Ok(Request::ConfigurationDone),
+            )
+        );
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_error_response_preserves_additional_attributes() {
+        // given:
+        let json = r#"{"seq":5,"type":"response","request_seq":4,"success":false,"command":"evaluate","message":"failed","body":{"error":{"id":7,"format":"boom","stackTrace":"at main"},"traceId":"abc"}}"#;
+
+        // when:
+        let actual = json.parse::<ProtocolMessage>().unwrap();
+
+        // then:
+        let body = match &actual.content {
+            ProtocolMessageContent::Response(Response {
+                result: Err(ErrorResponse { body, .. }),
+                ..
+            }) => body,
+            content => panic!("unexpected content: {:?}", content),
+        };
+        assert_eq!(body.additional_attributes["traceId"], "abc");
+        assert_eq!(
+            body.error.as_ref().unwrap().additional_attributes["stackTrace"],
+            "at main"
+        );
+        assert_eq!(serde_json::to_string(&actual).unwrap(), json);
+    }
+
+    #[test]
+    fn test_defaulted_enums_are_omitted() {
+        // given:
+        let json = r#"{"category":"console","output":"hello"}"#;
+        let arguments = r#"{"kind":"integrated","cwd":"/tmp","args":["ls"]}"#;
+
+        // when:
+        let actual = serde_json::from_str::<OutputEventBody>(json).unwrap();
+        let arguments = serde_json::from_str::<RunInTerminalRequestArguments>(arguments).unwrap();
+
+        // then:
+        assert_eq!(actual.category, OutputCategory::default());
+        assert_eq!(
+            serde_json::to_string(&actual).unwrap(),
+            r#"{"output":"hello"}"#
+        );
+        assert_eq!(arguments.kind, TerminalKind::default());
+        assert_eq!(
+            serde_json::to_string(&arguments).unwrap(),
+            r#"{"cwd":"/tmp","args":["ls"]}"#
+        );
+    }
+
+    #[test]
+    fn test_exception_break_mode_defaults_to_never() {
+        // given:
+        let options = vec![ExceptionOptions::new(ExceptionBreakMode::Always)];
+        let path = ["Python Exceptions", "ValueError"];
+
+        // when:
+        let actual = ExceptionOptions::effective_break_mode(&[], &path)
+            .cloned()
+            .unwrap_or_default();
+
+        // then:
+        assert_eq!(actual, ExceptionBreakMode::Never);
+        assert_eq!(
+            ExceptionOptions::effective_break_mode(&options, &path),
+            Some(&ExceptionBreakMode::Always)
+        );
+    }
+
+    #[cfg(feature = "spec-1_70")]
+    #[test]
+    fn test_output_location_reference() {
+        // given:
+        let json = r#"{"output":"fn main","variablesReference":3,"locationReference":4}"#;
+
+        // when:
+        let actual = serde_json::from_str::<borrowed::OutputEventBody>(json).unwrap();
+
+        // then:
+        assert_eq!(actual.location_reference, Some(4));
+        let owned = actual.into_owned();
+        assert_eq!(owned.location_reference, Some(4));
+        assert_eq!(serde_json::to_string(&owned).unwrap(), json);
+    }
+
+    #[test]
+    fn test_message_telemetry_safe() {
+        // given:
+        let message = Message::builder()
+            .id(1)
+            .format("Cannot read '{path}' ({_errno})".into())
+            .variables(Some(HashMap::from_iter([
+                ("path".to_string(), "/home/user/secret.txt".to_string()),
+                ("_errno".to_string(), "13".to_string()),
+            ])))
+            .build();
+
+        // when:
+        let actual = message.telemetry_safe();
+
+        // then:
+        assert_eq!(actual.variables.as_ref().unwrap()["path"], REDACTED);
+        assert_eq!(actual.variables.as_ref().unwrap()["_errno"], "13");
+    }
+
+    #[test]
+    fn test_redacted_debug_redacts_variables_with_safe_names() {
+        // given:
+        let message = Message::builder()
+            .id(1)
+            .format("{type} {reason}: {_errno}".into())
+            .variables(Some(HashMap::from_iter([
+                ("type".to_string(), "SecretType".to_string()),
+                ("reason".to_string(), "/home/user".to_string()),
+                ("_errno".to_string(), "13".to_string()),
+            ])))
+            .build();
+        let variable = Variable::new("token".into(), "'abc'".into(), 0);
+        let variable = Variable {
+            type_: Some("my_app.Secret".into()),
+            ..variable
+        };
+
+        // when:
+        let message = serde_json::from_str::<Value>(&format!("{:?}", Redacted(&message))).unwrap();
+        let variable = format!("{:?}", Redacted(&variable));
+
+        // then:
+        assert_eq!(message["variables"]["type"], REDACTED);
+        assert_eq!(message["variables"]["reason"], REDACTED);
+        assert_eq!(message["variables"]["_errno"], "13");
+        assert_eq!(message["format"], "{type} {reason}: {_errno}");
+        assert!(!variable.contains("my_app.Secret"), "{}", variable);
+    }
+
+    #[test]
+    fn test_redacted_debug() {
+        // given:
+        let message = ProtocolMessage::new(
+            3,
+            OutputEventBody::builder()
+                .category(OutputCategory::Stderr)
+                .output("/home/user/secret.txt not found".into())
+                .line(Some(7))
+                .build(),
+        );
+
+        // when:
+        let actual = format!("{:?}", Redacted(&message));
+
+        // then:
+        assert_eq!(
+            actual,
+            r#"{"seq":3,"type":"<redacted>","event":"output","body":{"category":"stderr","output":"<redacted>","line":7}}"#
+        );
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        // given:
+        let paths = [
+            r"C:\Users\me\.\project\\main.rs",
+            r"c:/Users/me/project/main.rs",
+            r"\\server\share\a.rs",
+            "/home/me//project/./src/../main.rs",
+            "./",
+        ];
+
+        // when:
+        let actual = paths
+            .iter()
+            .map(|path| normalize_path(path))
+            .collect::<Vec<_>>();
+
+        // then:
+        assert_eq!(
+            actual,
+            vec![
+                "c:/Users/me/project/main.rs",
+                "c:/Users/me/project/main.rs",
+                "//server/share/a.rs",
+                "/home/me/project/src/../main.rs",
+                ".",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_path_lexically() {
+        // when:
+        let actual = (
+            resolve_path("/does-not-exist/project/src/../main.rs"),
+            resolve_path(r"C:\does-not-exist\..\..\main.rs"),
+            resolve_path("a/../../b"),
+        );
+
+        // then:
+        assert_eq!(
+            actual,
+            (
+                "/does-not-exist/project/main.rs".to_string(),
+                "c:/main.rs".to_string(),
+                "../b".to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_prefix_mapper() {
+        // given:
+        let mapper = PrefixMapper::new()
+            .with_prefix(r"C:\Users\dev\project", "/workspace")
+            .with_prefix(r"C:\Users\dev\project\vendor", "/opt/vendor")
+            .case_insensitive(true);
+
+        // when:
+        let actual = (
+            mapper.to_adapter(r"c:\users\DEV\Project\src\main.rs"),
+            mapper.to_adapter(r"C:\Users\dev\project\vendor\lib.rs"),
+            mapper.to_adapter(r"C:\Users\dev\projects\main.rs"),
+            mapper.to_client("/workspace/src/main.rs"),
+            mapper.to_client("/workspace"),
+        );
+
+        // then:
+        assert_eq!(
+            actual,
+            (
+                Some("/workspace/src/main.rs".to_string()),
+                Some("/opt/vendor/lib.rs".to_string()),
+                None,
+                Some(r"C:\Users\dev\project\src\main.rs".to_string()),
+                Some(r"C:\Users\dev\project".to_string()),
+            )
+        );
+    }
+
+    #[test]
+    fn test_map_paths_of_messages() {
+        // given:
+        let mapper = PrefixMapper::new().with_prefix("/home/dev", "/app");
+        let source = |path: &str| Source::builder().path(Some(path.into())).build();
+        let mut request = ProtocolMessage::new(
+            1,
+            SetBreakpointsRequestArguments::builder()
+                .source(source("/home/dev/main.rs"))
+                .build(),
+        );
+        let mut response = ProtocolMessage::new(
+            2,
+            Response::new(
+                1,
+                Ok(StackTraceResponseBody::builder()
+                    .stack_frames(vec![StackFrame::builder()
+                        .id(1)
+                        .name("main".into())
+                        .source(Some(Source {
+                            sources: vec![source("/app/generated.rs")],
+                            ..source("/app/main.rs")
+                        }))
+                        .line(1)
+                        .column(1)
+                        .build()])
+                    .build()
+                    .into()),
+            ),
+        );
+
+        // when:
+        map_client_message(&mut request, &mapper);
+        map_adapter_message(&mut response, &mapper);
+
+        // then:
+        let request_path = match &request.content {
+            ProtocolMessageContent::Request(Request::SetBreakpoints(arguments)) => {
+                arguments.source.path.clone()
+            }
+            content => panic!("unexpected {:?}", content),
+        };
+        assert_eq!(request_path.as_deref(), Some("/app/main.rs"));
+        let frame_source = match &response.content {
+            ProtocolMessageContent::Response(Response {
+                result: Ok(SuccessResponse::StackTrace(body)),
+                ..
+            }) => body.stack_frames[0].source.clone().unwrap(),
+            content => panic!("unexpected {:?}", content),
+        };
+        assert_eq!(frame_source.path.as_deref(), Some("/home/dev/main.rs"));
+        assert_eq!(
+            frame_source.sources[0].path.as_deref(),
+            Some("/home/dev/generated.rs")
+        );
+    }
+
+    #[test]
+    fn test_exception_options_effective_break_mode() {
+        // given:
+        let segment = |negate, names: &[&str]| {
+            ExceptionPathSegment::builder()
+                .negate(negate)
+                .names(names.iter().map(|&name| name.into()).collect())
+                .build()
+        };
+        let options = [
+            ExceptionOptions::builder()
+                .break_mode(ExceptionBreakMode::Unhandled)
+                .build(),
+            ExceptionOptions::builder()
+                .path(vec![segment(false, &["Java Exceptions"])])
+                .break_mode(ExceptionBreakMode::Always)
+                .build(),
+            ExceptionOptions::builder()
+                .path(vec![
+                    segment(false, &["Java Exceptions"]),
+                    segment(true, &["java.lang.NullPointerException"]),
+                ])
+                .break_mode(ExceptionBreakMode::Never)
+                .build(),
+        ];
+
+        // when:
+        let actual = [
+            vec!["Java Exceptions", "java.lang.NullPointerException"],
+            vec!["Java Exceptions", "java.io.IOException"],
+            vec!["JavaScript Exceptions", "TypeError"],
+        ]
+        .iter()
+        .map(|path| ExceptionOptions::effective_break_mode(&options, path))
+        .collect::<Vec<_>>();
+
+        // then:
+        assert_eq!(
+            actual,
+            vec![
+                Some(&ExceptionBreakMode::Always),
+                Some(&ExceptionBreakMode::Never),
+                Some(&ExceptionBreakMode::Unhandled),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sequence_zero_is_a_warning() {
+        // given:
+        let json = r#"{"seq":0,"type":"event","event":"initialized"}"#;
+        let strict = ParseOptions::builder().strict(true).build();
+        let mut tracker = SequenceTracker::new();
+
+        // when:
+        let actual = ProtocolMessage::parse_with(json, &strict);
+        let warnings = [1, 0, 3, 2]
+            .iter()
+            .map(|seq| tracker.observe(*seq))
+            .collect::<Vec<_>>();
+
+        // then:
+        assert_eq!(actual.unwrap().seq, 0);
+        assert_eq!(
+            warnings,
+            vec![
+                None,
+                Some(SequenceWarning::Zero),
+                None,
+                Some(SequenceWarning::NotIncreasing {
+                    previous: 3,
+                    seq: 2
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_response_in_any_attribute_order() {
+        // given:
+        let jsons = [
+            r#"{"request_seq":1,"success":true,"command":"threads","body":{"threads":[]}}"#,
+            r#"{"body":{"threads":[]},"command":"threads","success":true,"request_seq":1}"#,
+        ];
+
+        // when:
+        let actual = jsons
+            .iter()
+            .map(|json| serde_json::from_str::<Response>(json).unwrap())
+            .collect::<Vec<_>>();
+
+        // then:
+        let expected = Response::new(
+            1,
+            Ok(ThreadsResponseBody::builder()
+                .threads(vec![])
+                .build()
+                .into()),
+        );
+        assert_eq!(actual, vec![expected.clone(), expected]);
+    }
+
+    #[test]
+    fn test_deserialize_response_reports_command() {
+        // given:
+        let json = r#"{"request_seq":1,"success":true,"command":"threads","body":{}}"#;
+
+        // when:
+        let actual = serde_json::from_str::<Response>(json);
+
+        // then:
+        assert!(actual
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid 'threads' response: missing field `threads`"));
+    }
+
+    #[test]
+    fn test_deserialize_borrowed_variables() {
+        // given:
+        let json = r#"{"variables":[
+            {"name":"a","value":"1","type":"i32","variablesReference":0},
+            {"name":"b","value":"\"quoted\"","variablesReference":3,"declarationLocationReference":7}
+        ]}"#;
+
+        // when:
+        let actual = serde_json::from_str::<borrowed::VariablesResponseBody>(json).unwrap();
+
+        // then:
+        let variables = &actual.variables;
+        assert!(matches!(variables[0].name, Cow::Borrowed("a")));
+        assert!(matches!(variables[0].type_, Some(Cow::Borrowed("i32"))));
+        assert!(matches!(variables[1].value, Cow::Owned(_)));
+        #[cfg(feature = "spec-1_70")]
+        assert_eq!(variables[1].declaration_location_reference, Some(7));
+        assert_eq!(
+            actual.into_owned(),
+            serde_json::from_str::<VariablesResponseBody>(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_to() {
+        // given:
+        let message = ProtocolMessage::new(
+            1,
+            OutputEventBody::builder().output("Grüße\n".into()).build(),
+        );
+        let mut buffer = Vec::new();
+
+        // when:
+        message.write_to(&mut buffer).unwrap();
+
+        // then:
+        let expected = r#"{"seq":1,"type":"event","event":"output","body":{"output":"Grüße\n"}}"#;
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            format!("Content-Length: {}\r\n\r\n{}", expected.len(), expected)
+        );
+        assert_eq!(
+            message.to_string(),
+            format!("Content-Length: {}\r\n\r\n{}", expected.len(), expected)
+        );
+    }
+
+    #[test]
+    fn test_custom_capabilities_round_trip() {
+        // given:
+        let json = r#"{"seq":1,"type":"event","event":"capabilities","body":{"capabilities":{"supportsStepBack":true,"supportsFooRequest":true,"fooVersion":2}}}"#;
+
+        // when:
+        let message = ProtocolMessage::from_str(json).unwrap();
+
+        // then:
+        let capabilities = match &message.content {
+            ProtocolMessageContent::Event(Event::Capabilities(body)) => &body.capabilities,
+            content => panic!("unexpected content: {:?}", content),
+        };
+        assert!(capabilities.supports_step_back());
+        assert!(capabilities.supports_custom("supportsFooRequest"));
+        assert!(!capabilities.supports_custom("fooVersion"));
+        assert!(!capabilities.supports_custom("supportsBarRequest"));
+        assert_eq!(serde_json::to_string(&message).unwrap(), json);
+
+        let mut capabilities = capabilities.clone();
+        capabilities.set_supports_custom("supportsFooRequest", false);
+        assert!(!capabilities.supports_custom("supportsFooRequest"));
+    }
+
+    #[cfg(feature = "spec-1_70")]
+    #[test]
+    fn test_breakpoint_modes() {
+        // given:
+        let json = r#"{
+            "breakpointModes": [
+                { "mode": "software", "label": "Software", "appliesTo": ["source"] },
+                { "mode": "hardware", "label": "Hardware", "appliesTo": ["source", "instruction"] }
+            ]
+        }"#;
+        let capabilities = serde_json::from_str::<Capabilities>(json).unwrap();
+
+        // when:
+        let modes = |applicability| {
+            capabilities
+                .breakpoint_modes_for(applicability)
+                .map(|mode| &*mode.mode)
+                .collect::<Vec<_>>()
+        };
+
+        // then:
+        assert_eq!(
+            modes(BreakpointModeApplicability::Source),
+            vec!["software", "hardware"]
+        );
+        assert_eq!(
+            modes(BreakpointModeApplicability::Instruction),
+            vec!["hardware"]
+        );
+        assert!(modes(BreakpointModeApplicability::Data).is_empty());
+        let breakpoint = InstructionBreakpoint::builder()
+            .instruction_reference("0x1000".into())
+            .mode(Some("hardware".into()))
+            .build();
+        assert_eq!(
+            serde_json::to_string(&breakpoint).unwrap(),
+            r#"{"instructionReference":"0x1000","mode":"hardware"}"#
+        );
+    }
+
+    #[test]
+    fn test_capability_flags() {
+        // given:
+        let old = Capabilities::builder()
+            .flags(
+                CapabilityFlags::SUPPORTS_CONFIGURATION_DONE_REQUEST
+                    | CapabilityFlags::SUPPORTS_STEP_BACK,
+            )
+            .build();
+        let new = Capabilities::builder()
+            .flags(
+                CapabilityFlags::SUPPORTS_CONFIGURATION_DONE_REQUEST
+                    | CapabilityFlags::SUPPORTS_EXCEPTION_FILTER_OPTIONS,
+            )
+            .build();
+
+        // when:
+        let added = new.flags() - old.flags();
+        let mut merged = old.clone();
+        merged.set_flags(old.flags() | new.flags());
+
+        // then:
+        assert_eq!(added, CapabilityFlags::SUPPORTS_EXCEPTION_FILTER_OPTIONS);
+        assert_eq!(
+            added.names().collect::<Vec<_>>(),
+            vec!["supportsExceptionFilterOptions"]
+        );
+        assert!(merged.supports_step_back());
+        assert!(merged.supports_exception_filter_options());
+        let json = serde_json::to_value(&merged).unwrap();
+        let names = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(merged.flags().names().collect::<Vec<_>>(), names);
+        assert_eq!((!CapabilityFlags::empty()), CapabilityFlags::all());
+        let count = if cfg!(feature = "spec-1_70") { 35 } else { 33 };
+        assert_eq!(CapabilityFlags::all().names().count(), count);
+    }
+
+    #[test]
+    fn test_capability_flags_serialize_under_their_names() {
+        for (flag, name) in CapabilityFlags::all().iter() {
+            // given:
+            let mut capabilities = Capabilities::default();
+            capabilities.set_flags(flag);
+
+            // when:
+            let json = serde_json::to_value(&capabilities).unwrap();
+            let parsed = serde_json::from_value::<Capabilities>(json.clone()).unwrap();
+            let from_name =
+                serde_json::from_value::<Capabilities>(serde_json::json!({ name: true })).unwrap();
+
+            // then:
+            assert_eq!(json, serde_json::json!({ name: true }), "{}", name);
+            assert_eq!(parsed.flags(), flag, "{}", name);
+            assert_eq!(from_name, capabilities, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_all_capability_flags_serialize() {
+        // given:
+        let mut capabilities = Capabilities::default();
+        capabilities.set_flags(CapabilityFlags::all());
+
+        // when:
+        let json = serde_json::to_value(&capabilities).unwrap();
+
+        // then:
+        let keys = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        let names = CapabilityFlags::all().names().collect::<Vec<_>>();
+        assert_eq!(keys, names);
+        assert!(json
+            .as_object()
+            .unwrap()
+            .values()
+            .all(|value| value == true));
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_capability_flags_cover_all_boolean_capabilities() {
+        // given:
+        let schema = serde_json::to_value(schemars::schema_for!(Capabilities)).unwrap();
+
+        // when:
+        let booleans = schema["properties"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .filter(|(_, property)| property["type"] == "boolean")
+            .map(|(name, _)| name.as_str())
+            .collect::<std::collections::BTreeSet<_>>();
+
+        // then:
+        let names = CapabilityFlags::all().names().collect();
+        assert_eq!(booleans, names);
+    }
+
+    #[test]
+    fn test_capability_flags_leave_other_attributes() {
+        // given:
+        let json = r#"{"supportsStepBack":true,"supportsRestartFrame":false,"supportsFooRequest":true,"exceptionBreakpointFilters":[]}"#;
+
+        // when:
+        let capabilities = serde_json::from_str::<Capabilities>(json).unwrap();
+        let duplicate = serde_json::from_str::<Capabilities>(
+            r#"{"supportsStepBack":true,"supportsStepBack":false}"#,
+        );
+
+        // then:
+        assert_eq!(capabilities.flags(), CapabilityFlags::SUPPORTS_STEP_BACK);
+        assert!(capabilities.supports_custom("supportsFooRequest"));
+        assert_eq!(capabilities.additional_attributes.len(), 1);
+        assert_eq!(
+            serde_json::to_string(&capabilities).unwrap(),
+            r#"{"supportsStepBack":true,"supportsFooRequest":true}"#
+        );
+        assert!(duplicate.is_err());
+    }
+
+    #[cfg(feature = "spec-1_70")]
+    #[test]
+    fn test_single_thread_execution() {
+        // given:
+        let json = r#"{"seq":3,"type":"request","command":"stepOut","arguments":{"threadId":2,"singleThread":true}}"#;
+
+        // when:
+        let message = ProtocolMessage::from_str(json).unwrap();
+
+        // then:
+        let expected = StepOutRequestArguments::builder()
+            .thread_id(2)
+            .single_thread(true)
+            .build();
+        assert_eq!(message.content, Request::from(expected).into());
+        assert_eq!(serde_json::to_string(&message).unwrap(), json);
+        let arguments = ContinueRequestArguments::builder().thread_id(2).build();
+        assert_eq!(
+            serde_json::to_string(&arguments).unwrap(),
+            r#"{"threadId":2}"#
+        );
+    }
+
+    #[cfg(feature = "spec-1_70")]
+    #[test]
+    fn test_value_location_reference() {
+        // given:
+        let json = r#"{"seq":4,"type":"response","request_seq":3,"success":true,"command":"setVariable","body":{"value":"3","memoryReference":"0x10","valueLocationReference":5}}"#;
+
+        // when:
+        let message = ProtocolMessage::from_str(json).unwrap();
+
+        // then:
+        let expected = SetVariableResponseBody::builder()
+            .value("3".into())
+            .memory_reference(Some("0x10".into()))
+            .value_location_reference(Some(5))
+            .build();
+        assert_eq!(
+            message.content,
+            Response::new(3, Ok(expected.into())).into()
+        );
+        assert_eq!(serde_json::to_string(&message).unwrap(), json);
+    }
+
+    #[cfg(feature = "shared-strings")]
+    #[test]
+    fn test_clone_shares_strings() {
+        // given:
+        let json = r#"{"output":"Hello World!"}"#;
+        let body = serde_json::from_str::<OutputEventBody>(json).unwrap();
+
+        // when:
+        let clone = body.clone();
+
+        // then:
+        assert!(std::sync::Arc::ptr_eq(&body.output, &clone.output));
+    }
+
+    #[cfg(feature = "small-vec")]
+    #[test]
+    fn test_small_vec_stores_single_element_inline() {
+        // given:
+        let json = r#"{"reason":"breakpoint","hitBreakpointIds":[3]}"#;
+
+        // when:
+        let body = serde_json::from_str::<StoppedEventBody>(json).unwrap();
+
+        // then:
+        assert_eq!(*body.hit_breakpoint_ids, [3]);
+        assert!(!body.hit_breakpoint_ids.spilled());
+        assert_eq!(serde_json::to_string(&body).unwrap(), json);
+
+        let mut ids = body.hit_breakpoint_ids;
+        ids.push(4);
+        assert!(ids.spilled());
+        assert_eq!(ids.into_vec(), vec![3, 4]);
+    }
+
+    #[cfg(feature = "small-vec")]
+    #[test]
+    fn test_small_vec_equality_ignores_representation() {
+        // given:
+        let inline = SmallVec::from(vec![3]);
+        let mut spilled = SmallVec::from(vec![3, 4]);
+
+        // when:
+        spilled.pop();
+
+        // then:
+        assert!(spilled.spilled());
+        assert_eq!(inline, spilled);
+    }
+
+    #[test]
+    fn test_incremental_variables() {
+        // given:
+        let json = r#"{"seq":2,"type":"response","request_seq":1,"success":true,
+            "command":"variables","message":null,"body":{"ignored":{"a":[1,{}]},"variables":[
+                {"name":"a","value":"1","variablesReference":0},
+                {"name":"b","value":"2","variablesReference":0},
+                {"name":"c","value":"3" ,"variablesReference":0}
+            ] , "more": }}"#;
+
+        // when:
+        let actual = incremental::variables(json)
+            .unwrap()
+            .take(2)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        // then:
+        let names = actual.iter().map(|v| &*v.name).collect::<Vec<_>>();
+        assert_eq!(names, vec!["a", "b"]);
+
+        let borrowed =
+            incremental::Elements::<borrowed::Variable>::at(json, &["body", "variables"])
+                .unwrap()
+                .map(|v| v.unwrap().name)
+                .collect::<Vec<_>>();
+        assert_eq!(borrowed, vec!["a", "b", "c"]);
+        assert!(matches!(borrowed[0], Cow::Borrowed(_)));
+
+        let error = incremental::stack_frames(r#"{"body":{"variables":[]}}"#)
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "missing field `body.stackFrames`");
+        let mut invalid = incremental::variables(r#"{"body":{"variables":[{} {}]}}"#).unwrap();
+        assert!(invalid.next().unwrap().is_err());
+        assert!(invalid.next().is_none());
+    }
+
+    #[test]
+    fn test_lazy_protocol_message() {
+        // given:
+        let json = r#"{"seq":3,"type":"event","event":"output","body":{"output":"hello","category":"stdout"}}"#;
+
+        // when:
+        let actual = lazy::LazyProtocolMessage::from_json(json).unwrap();
+
+        // then:
+        assert_eq!(actual.type_, MessageKind::Event);
+        assert_eq!(actual.name(), Some("output"));
+        assert!(matches!(actual.event, Some(Cow::Borrowed(_))));
+        let body = actual.body.as_ref().unwrap();
+        assert!(matches!(body, Cow::Borrowed(_)));
+        assert_eq!(body.get(), r#"{"output":"hello","category":"stdout"}"#);
+
+        let mut framed = Vec::new();
+        actual.write_to(&mut framed).unwrap();
+        assert_eq!(
+            String::from_utf8(framed).unwrap(),
+            format!("Content-Length: {}\r\n\r\n{}", json.len(), json)
+        );
+
+        let message = actual.parse().unwrap();
+        assert_eq!(
+            message,
+            ProtocolMessage::new(
+                3,
+                Event::Output(Box::new(
+                    OutputEventBody::builder()
+                        .category(OutputCategory::Stdout)
+                        .output("hello".into())
+                        .build()
+                ))
+            )
+        );
+        let downgraded = lazy::LazyProtocolMessage::from_message(&message).unwrap();
+        assert_eq!(downgraded.name(), Some("output"));
+        assert_eq!(downgraded.parse().unwrap(), message);
+    }
+
+    #[test]
+    fn test_from_slice() {
+        // given:
+        let stream = b"Content-Length: 46\r\nContent-Type: application/vscode-jsonrpc\r\n\r\n{\"seq\":7,\"type\":\"event\",\"event\":\"initialized\"}Content-Length: 9";
+
+        // when:
+        let (content, frame_len) = framing::split_frame(stream).unwrap().unwrap();
+
+        // then:
+        assert_eq!(
+            content,
+            br#"{"seq":7,"type":"event","event":"initialized"}"#
+        );
+        assert_eq!(lazy::probe_seq(content).unwrap(), 7);
+        assert_eq!(
+            ProtocolMessage::from_slice(content).unwrap(),
+            ProtocolMessage::new(7, Event::Initialized)
+        );
+        assert_eq!(
+            lazy::LazyProtocolMessage::from_slice(content)
+                .unwrap()
+                .name(),
+            Some("initialized")
+        );
+        assert_eq!(framing::split_frame(&stream[frame_len..]), Ok(None));
+        assert_eq!(
+            framing::split_frame(b"Content-Length: x\r\n\r\n{}"),
+            Err(FrameError::InvalidContentLength)
+        );
+        assert_eq!(
+            framing::split_frame(b"Content-Type: json\r\n\r\n{}"),
+            Err(FrameError::MissingContentLength)
+        );
+    }
+
+    #[test]
+    fn test_read_frame_with_excessive_content_length() {
+        // given: a header found by fuzzing, which announces about 17 TB of content
+        let stream = b"Content-Length: 17533333333335\r\n\r\n{}";
+
+        // when:
+        let actual = framing::read_frame(&stream[..]);
+
+        // then:
+        assert_eq!(
+            actual.unwrap_err().kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[cfg(feature = "decoder")]
+    #[test]
+    fn test_decoder_reuses_pooled_buffers() {
+        // given:
+        let frame =
+            "Content-Length: 46\r\n\r\n{\"seq\":7,\"type\":\"event\",\"event\":\"initialized\"}";
+        let stream = frame.repeat(3);
+        let pool = decoder::BufferPool::new(1);
+
+        // when:
+        let actual = decoder::Decoder::with_pool(stream.as_bytes(), pool.clone())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        // then:
+        assert_eq!(actual.len(), 3);
+        assert_eq!(
+            ProtocolMessage::from_slice(&actual[2]).unwrap(),
+            ProtocolMessage::new(7, Event::Initialized)
+        );
+        let start = actual[0]
+            .as_ptr()
+            .wrapping_sub(frame.len() - actual[0].len());
+        let unused = pool.take(64 * 1024);
+        assert_ne!(unused.as_ptr(), start);
+        drop(actual);
+        assert_eq!(pool.take(64 * 1024).as_ptr(), start);
+
+        let truncated = decoder::Decoder::new(&frame.as_bytes()[..30])
+            .next()
+            .unwrap();
+        assert_eq!(truncated.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_batch_decode_preserves_order() {
+        // given:
+        let stream = (1..=100)
+            .map(|seq| {
+                let json = format!(r#"{{"seq":{},"type":"event","event":"initialized"}}"#, seq);
+                format!("Content-Length: {}\r\n\r\n{}", json.len(), json)
+            })
+            .collect::<String>()
+            + "Content-Length: 2\r\n\r\n{}";
+
+        // when:
+        let actual = batch::decode(stream.as_bytes()).unwrap();
+
+        // then:
+        assert_eq!(actual.len(), 101);
+        for (index, message) in actual[..100].iter().enumerate() {
+            assert_eq!(message.as_ref().unwrap().seq, index as u64 + 1);
+        }
+        assert!(actual[100].is_err());
+        assert_eq!(
+            batch::decode(&stream.as_bytes()[..stream.len() - 1]).unwrap_err(),
+            FrameError::Truncated
+        );
+    }
+
+    #[test]
+    fn test_pre_encoded_message() {
+        // given:
+        let initialized = framing::PreEncodedMessage::new(Event::Initialized).unwrap();
+        let response = Response {
+            request_seq: 1,
+            result: Ok(SuccessResponse::Initialize(Box::new(
+                Capabilities::builder()
+                    .flags(CapabilityFlags::SUPPORTS_CONFIGURATION_DONE_REQUEST)
+                    .build(),
+            ))),
+        };
+        let initialize = framing::PreEncodedMessage::new(response.clone()).unwrap();
+
+        // when:
+        let mut actual = Vec::new();
+        initialized.write_to(&mut actual, 1234).unwrap();
+        initialize.write_response_to(&mut actual, 8, 12).unwrap();
+        initialize.write_to(&mut actual, 9).unwrap();
+
+        // then:
+        let mut expected = Vec::new();
+        ProtocolMessage::new(1234, Event::Initialized)
+            .write_to(&mut expected)
+            .unwrap();
+        let mut answer = response.clone();
+        answer.request_seq = 12;
+        ProtocolMessage::new(8, answer)
+            .write_to(&mut expected)
+            .unwrap();
+        ProtocolMessage::new(9, response)
+            .write_to(&mut expected)
+            .unwrap();
+        assert_eq!(String::from_utf8(actual), String::from_utf8(expected));
+        assert_eq!(
+            initialized
+                .write_response_to(Vec::new(), 1, 1)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn test_serialized_sizes_of_defaults() {
+        // given:
+        let messages: Vec<(ProtocolMessage, usize)> = vec![
+            (
+                ProtocolMessage::new(
+                    1,
+                    InitializeRequestArguments::builder()
+                        .adapter_id("mock".into())
+                        .build(),
+                ),
+                82,
+            ),
+            (
+                ProtocolMessage::new(
+                    2,
+                    Response {
+                        request_seq: 1,
+                        result: Ok(ContinueResponseBody::builder()
+                            .all_threads_continued(true)
+                            .build()
+                            .into()),
+                    },
+                ),
+                89,
+            ),
+            (
+                ProtocolMessage::new(
+                    3,
+                    Response {
+                        request_seq: 2,
+                        result: Err(ErrorResponse::builder()
+                            .command("evaluate".into())
+                            .message("cancelled".into())
+                            .build()),
+                    },
+                ),
+                112,
+            ),
+            (
+                ProtocolMessage::new(
+                    4,
+                    Response {
+                        request_seq: 3,
+                        result: Ok(SetBreakpointsResponseBody::builder()
+                            .breakpoints(vec![Breakpoint::builder().verified(true).build()])
+                            .build()
+                            .into()),
+                    },
+                ),
+                128,
+            ),
+            (
+                ProtocolMessage::new(
+                    5,
+                    Response {
+                        request_seq: 4,
+                        result: Ok(CompletionsResponseBody::builder()
+                            .targets(vec![CompletionItem::builder().label("x".into()).build()])
+                            .build()
+                            .into()),
+                    },
+                ),
+                117,
+            ),
+        ];
+
+        for (message, expected) in messages {
+            // when:
+            let actual = serde_json::to_string(&message).unwrap();
+
+            // then:
+            assert_eq!(actual.len(), expected, "{}", actual);
+        }
+    }
+
+    #[test]
+    fn test_tag_dispatch_tables_match_variants() {
+        // given:
+        // Unknown commands and events deserialize as the fallback variants, so the derived impls
+        // report them
+        let cases = [
+            (
+                Request::deserialize(serde_json::json!({"command": "unknown", "arguments": {}}))
+                    .map(|_| ()),
+                &crate::requests::COMMANDS,
+            ),
+            (
+                SuccessResponse::deserialize(serde_json::json!({"command": "unknown"})).map(|_| ()),
+                &crate::responses::COMMANDS,
+            ),
+            (
+                Event::deserialize(serde_json::json!({"event": "unknown"})).map(|_| ()),
+                &crate::events::EVENTS,
+            ),
+        ];
+
+        for (result, variants) in cases {
+            // when:
+            let actual = result.unwrap_err().to_string();
+
+            // then:
+            let expected = variants
+                .names()
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            assert!(actual.contains(&expected), "{}", actual);
+            for name in variants.names() {
+                assert_eq!(
+                    variants.index(name).map(|i| variants.names()[i as usize]),
+                    Some(*name)
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema() {
+        // when:
+        let schema = schemars::schema_for!(ProtocolMessage);
+
+        // then:
+        let schema = serde_json::to_value(&schema).unwrap();
+        let defs = &schema["$defs"];
+        assert_eq!(schema["required"], serde_json::json!(["seq"]));
+        assert_eq!(
+            defs["StoppedEventBody"]["properties"]["reason"]["$ref"],
+            "#/$defs/StoppedEventReason"
+        );
+        assert!(defs["Request"]
+            .to_string()
+            .contains(r#""const":"initialize""#));
+        assert!(defs["Event"].to_string().contains(r#""const":"stopped""#));
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary_messages_round_trip() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        // given:
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let bytes = (0..1 << 20)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect::<Vec<_>>();
+
+        for chunk in bytes.chunks(1024) {
+            let message = ProtocolMessage::arbitrary(&mut Unstructured::new(chunk)).unwrap();
+
+            // when:
+            let json = serde_json::to_string(&message).unwrap();
+            let actual =
+                ProtocolMessage::from_str(&json).unwrap_or_else(|e| panic!("{}: {}", e, json));
+
+            // then:
+            // Without serde_json's float_roundtrip feature, parsing floats may round the last digit
+            let expected = Value::from_str(&json).unwrap();
+            assert_eq!(serde_json::to_value(&actual).unwrap(), expected);
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn test_strategies_generate_messages_that_round_trip(
+            message in crate::strategies::protocol_message()
+        ) {
+            // when:
+            let json = serde_json::to_string(&message).unwrap();
+            let actual = ProtocolMessage::from_str(&json).unwrap();
+
+            // then:
+            proptest::prop_assert_eq!(actual, message);
+        }
+    }
+
+    #[test]
+    fn test_content_codecs_round_trip() {
+        use crate::codec::{ContentCodec, Json};
+
+        fn round_trip<C: ContentCodec>(codec: C) {
+            let messages = [
+                r#"{"seq":1,"type":"request","command":"next","arguments":{"threadId":3}}"#,
+                r#"{"seq":2,"type":"response","request_seq":1,"success":true,"command":"threads","body":{"threads":[{"id":3,"name":"main"}]}}"#,
+                r#"{"seq":3,"type":"response","request_seq":2,"success":false,"command":"evaluate","message":"notStopped","body":{"detail":[1,2.5]}}"#,
+                r#"{"seq":4,"type":"event","event":"output","body":{"output":"Hello\n","data":{"key":null}}}"#,
+            ];
+            for json in messages {
+                // given:
+                let message = ProtocolMessage::from_str(json).unwrap();
+                let mut stream = Vec::new();
+
+                // when:
+                codec.write_to(&message, &mut stream).unwrap();
+
+                // then:
+                let (content, frame_len) = framing::split_frame(&stream).unwrap().unwrap();
+                assert_eq!(frame_len, stream.len());
+                assert_eq!(codec.decode(content).unwrap(), message);
+            }
+        }
+
+        round_trip(Json);
+        #[cfg(feature = "msgpack")]
+        round_trip(crate::codec::MessagePack);
+        #[cfg(feature = "cbor")]
+        round_trip(crate::codec::Cbor);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_typed_timestamps() {
+        // given:
+        let json = r#"{"seq":1,"type":"event","event":"module","body":{"reason":"new","module":{"id":1,"name":"a.out","dateTimeStamp":"2021-06-01T12:30:00+02:00"}}}"#;
+        let checksum = |algorithm, checksum: &str| {
+            Checksum::builder()
+                .algorithm(algorithm)
+                .checksum(checksum.into())
+                .build()
+        };
+
+        // when:
+        let message = ProtocolMessage::from_str(json).unwrap();
+
+        // then:
+        let module = match message.content {
+            ProtocolMessageContent::Event(Event::Module(body)) => body.module,
+            content => panic!("{:?}", content),
+        };
+        let expected = chrono::DateTime::parse_from_rfc3339("2021-06-01T10:30:00Z").unwrap();
+        assert_eq!(module.date_time(), Some(Ok(expected)));
+        assert_eq!(
+            module.date_time_stamp.as_deref(),
+            Some("2021-06-01T12:30:00+02:00")
+        );
+        assert_eq!(
+            checksum(ChecksumAlgorithm::Timestamp, "2021-06-01T10:30:00Z").timestamp(),
+            Some(Ok(expected))
+        );
+        assert!(checksum(ChecksumAlgorithm::Timestamp, "yesterday")
+            .timestamp()
+            .unwrap()
+            .is_err());
+        assert_eq!(checksum(ChecksumAlgorithm::MD5, "").timestamp(), None);
+    }
+
+    #[cfg(feature = "connection")]
+    #[test]
+    fn test_connection_from_transport() {
+        use crate::connection::Connection;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for SharedBuffer {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        // given:
+        let input = b"Content-Length: 46\r\n\r\n{\"seq\":1,\"type\":\"request\",\"command\":\"threads\"}\
+            Content-Length: 56\r\n\r\n{\"seq\":2,\"type\":\"request\",\"command\":\"configurationDone\"}";
+        let output = SharedBuffer::default();
+        let (connection, io_threads) =
+            Connection::from_transport(std::io::Cursor::new(input), output.clone());
+
+        // when:
+        let received = connection.receiver.iter().collect::<Vec<_>>();
+        connection
+            .sender
+            .send(ProtocolMessage::new(1, Event::Initialized))
+            .unwrap();
+        drop(connection);
+        io_threads.join().unwrap();
+
+        // then:
+        assert_eq!(
+            received,
+            vec![
+                ProtocolMessage::new(1, Request::Threads),
+                ProtocolMessage::new(2, Request::ConfigurationDone),
+            ]
+        );
+        let expected = r#"{"seq":1,"type":"event","event":"initialized"}"#;
+        assert_eq!(
+            String::from_utf8(output.0.lock().unwrap().clone()).unwrap(),
+            format!("Content-Length: {}\r\n\r\n{}", expected.len(), expected)
+        );
+    }
+
+    #[cfg(feature = "connection")]
+    #[test]
+    fn test_connection_forwards_parse_errors() {
+        use crate::connection::Connection;
+
+        // given:
+        let input = b"Content-Length: 42\r\n\r\n{\"seq\":1,\"type\":\"request\",\"command\":false}\
+            Content-Length: 46\r\n\r\n{\"seq\":2,\"type\":\"request\",\"command\":\"threads\"}";
+        let (connection, io_threads) =
+            Connection::from_transport(std::io::Cursor::new(input), std::io::sink());
+
+        // when:
+        let received = connection.receiver.iter().collect::<Vec<_>>();
+        let errors = connection.errors.iter().collect::<Vec<_>>();
+        drop(connection);
+        io_threads.join().unwrap();
+
+        // then:
+        assert_eq!(received, vec![ProtocolMessage::new(2, Request::Threads)]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind(), Some(MessageKind::Request));
+    }
+
+    #[cfg(any(feature = "mock", feature = "replay"))]
+    fn spawn_mock_adapter(
+        connection: crate::connection::Connection,
+    ) -> std::thread::JoinHandle<()> {
+        use crate::{
+            events::{OutputEventBody, StoppedEventBody, StoppedEventReason},
+            responses::EvaluateResponseBody,
+        };
+
+        std::thread::spawn(move || {
+            let mut seq = 0;
+            let mut send = |content: ProtocolMessageContent| {
+                seq += 1;
+                connection.sender.send(ProtocolMessage::new(seq, content))
+            };
+            for message in connection.receiver.iter() {
+                let request = match message.content {
+                    ProtocolMessageContent::Request(request) => request,
+                    _ => continue,
+                };
+                let result = match request {
+                    Request::Initialize(_) => {
+                        let capabilities = Capabilities::builder()
+                            .flags(CapabilityFlags::SUPPORTS_CONFIGURATION_DONE_REQUEST)
+                            .build();
+                        Ok(SuccessResponse::Initialize(Box::new(capabilities)))
+                    }
+                    Request::Launch(_) => Ok(SuccessResponse::Launch),
+                    Request::Evaluate(arguments) if &*arguments.expression == "x" => {
+                        let body = EvaluateResponseBody::builder()
+                            .result("42".into())
+                            .variables_reference(0)
+                            .build();
+                        Ok(body.into())
+                    }
+                    _ => Err(ErrorResponse::builder()
+                        .command(request.command().into())
+                        .message("unsupported".into())
+                        .build()),
+                };
+                let launch = matches!(result, Ok(SuccessResponse::Launch));
+                send(Response::new(message.seq, result).into()).unwrap();
+                if launch {
+                    let output = OutputEventBody::builder().output("hello\n".into()).build();
+                    send(output.into()).unwrap();
+                    let stopped = StoppedEventBody::builder()
+                        .reason(StoppedEventReason::Entry)
+                        .thread_id(Some(1))
+                        .build();
+                    send(stopped.into()).unwrap();
+                }
+            }
+        })
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn test_mock_client_runs_script() {
+        use crate::{
+            connection::Connection,
+            mock::{MockClient, Script},
+            requests::{EvaluateRequestArguments, LaunchRequestArguments},
+        };
+
+        // given:
+        let (client, adapter) = Connection::memory();
+        let adapter = spawn_mock_adapter(adapter);
+        let mut client = MockClient::new(client);
+        let script = Script::new()
+            .request_with(
+                InitializeRequestArguments::builder()
+                    .adapter_id("mock".into())
+                    .build(),
+                |response| match response {
+                    SuccessResponse::Initialize(capabilities)
+                        if capabilities.supports_configuration_done_request() =>
+                    {
+                        Ok(())
+                    }
+                    response => Err(format!("unexpected capabilities: {:?}", response)),
+                },
+            )
+            .request(LaunchRequestArguments::builder().build())
+            .event("stopped")
+            .request_with(
+                EvaluateRequestArguments::builder()
+                    .expression("x".into())
+                    .build(),
+                |response| match response {
+                    SuccessResponse::Evaluate(body) if &*body.result == "42" => Ok(()),
+                    response => Err(format!("unexpected result: {:?}", response)),
+                },
+            )
+            .failing_request(
+                EvaluateRequestArguments::builder()
+                    .expression("y".into())
+                    .build(),
+            );
+
+        // when:
+        let actual = client.run(script);
+
+        // then:
+        assert_eq!(actual, Ok(()));
+        drop(client);
+        adapter.join().unwrap();
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn test_mock_client_reports_failed_step() {
+        use crate::{
+            connection::Connection,
+            mock::{MockClient, MockError, Script, ScriptError},
+            requests::LaunchRequestArguments,
+        };
+        use std::time::Duration;
+
+        // given:
+        let (client, adapter) = Connection::memory();
+        let adapter = spawn_mock_adapter(adapter);
+        let mut client = MockClient::new(client);
+        client.set_timeout(Duration::from_millis(100));
+        let script = Script::new()
+            .request(LaunchRequestArguments::builder().build())
+            .event("exited")
+            .request(Request::Threads);
+
+        // when:
+        let actual = client.run(script);
+
+        // then:
+        assert_eq!(
+            actual,
+            Err(ScriptError {
+                step: 1,
+                description: "'exited' event".to_string(),
+                error: MockError::Timeout,
+            })
+        );
+        drop(client);
+        adapter.join().unwrap();
+    }
+
+    #[cfg(feature = "replay")]
+    fn replay_recording(result: &str, thread_id: i32) -> Vec<ProtocolMessage> {
+        let json = format!(
+            r#"[
+                {{"seq":10,"type":"request","command":"initialize","arguments":{{"adapterID":"mock"}}}},
+                {{"seq":1,"type":"response","request_seq":10,"success":true,"command":"initialize",
+                    "body":{{"supportsConfigurationDoneRequest":true}}}},
+                {{"seq":11,"type":"request","command":"launch","arguments":{{}}}},
+                {{"seq":2,"type":"response","request_seq":11,"success":true,"command":"launch"}},
+                {{"seq":3,"type":"event","event":"stopped","body":{{"reason":"entry","threadId":{}}}}},
+                {{"seq":12,"type":"request","command":"evaluate","arguments":{{"expression":"x"}}}},
+                {{"seq":4,"type":"response","request_seq":12,"success":true,"command":"evaluate",
+                    "body":{{"result":"{}","variablesReference":0}}}}
+            ]"#,
+            thread_id, result
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[cfg(feature = "replay")]
+    #[test]
+    fn test_replay_matching_session() {
+        use crate::{
+            connection::Connection,
+            replay::{replay, ReplayOptions},
+        };
+
+        // given:
+        let recording = replay_recording("42", 1);
+        let (client, adapter) = Connection::memory();
+        let adapter = spawn_mock_adapter(adapter);
+        let options = ReplayOptions {
+            ignored_events: vec!["output".to_string()],
+            ..ReplayOptions::default()
+        };
+
+        // when:
+        let report = replay(&client, recording, &options);
+
+        // then:
+        assert!(report.is_success(), "{}", report);
+        assert_eq!(report.matched, 4);
+        drop(client);
+        adapter.join().unwrap();
+    }
+
+    #[cfg(feature = "replay")]
+    #[test]
+    fn test_replay_reports_differences() {
+        use crate::{
+            connection::Connection,
+            replay::{replay, Difference, ReplayOptions},
+        };
+        use std::time::Duration;
+
+        // given:
+        let recording = replay_recording("41", 7);
+        let (client, adapter) = Connection::memory();
+        let adapter = spawn_mock_adapter(adapter);
+        let options = ReplayOptions {
+            ignored_properties: vec!["threadId".to_string()],
+            timeout: Duration::from_millis(100),
+            ..ReplayOptions::default()
+        };
+
+        // when:
+        let report = replay(&client, recording, &options);
+
+        // then:
+        assert_eq!(report.matched, 3);
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(
+            report.mismatches[0].differences,
+            vec![Difference {
+                path: "/body/result".to_string(),
+                expected: Some(Value::from("41")),
+                actual: Some(Value::from("42")),
+            }]
+        );
+        assert_eq!(report.missing, vec![]);
+        let unexpected = report
+            .unexpected
+            .iter()
+            .map(|message| match &message.content {
+                ProtocolMessageContent::Event(event) => event.event(),
+                _ => "",
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(unexpected, vec!["output"]);
+        drop(client);
+        adapter.join().unwrap();
+    }
+
+    #[cfg(feature = "test-fixtures")]
+    #[test]
+    fn test_fixtures_session_is_valid() {
+        // given:
+        let session = crate::fixtures::session();
+
+        // when:
+        let violations = session
+            .iter()
+            .flat_map(|message| message.validate())
+            .collect::<Vec<_>>();
+        let round_tripped = session
+            .iter()
+            .map(|message| {
+                ProtocolMessage::from_str(&serde_json::to_string(message).unwrap()).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // then:
+        assert_eq!(violations, vec![]);
+        assert_eq!(round_tripped, session);
+        assert_eq!(
+            crate::fixtures::stack_trace_response(),
+            StackTraceResponseBody::builder()
+                .stack_frames(crate::fixtures::stack_frames())
+                .total_frames(Some(3))
+                .build()
+                .into()
+        );
+    }
+
+    #[cfg(all(feature = "dap-types", feature = "debugserver-types"))]
+    #[test]
+    fn test_compat_conversions() {
+        use crate::events::{StoppedEventBody, StoppedEventReason};
+
+        // given:
+        let body = StoppedEventBody::builder()
+            .reason(StoppedEventReason::Breakpoint)
+            .thread_id(Some(1))
+            .build();
+        let message = ProtocolMessage::new(3, Event::Stopped(Box::new(body.clone())));
+
+        // when:
+        let dap_types_body = dap_types::StoppedEvent::try_from(body.clone()).unwrap();
+        let debugserver_message =
+            debugserver_types::StoppedEvent::try_from(message.clone()).unwrap();
+        let wrong_message = debugserver_types::ExitedEvent::try_from(message.clone());
+
+        // then:
+        assert_eq!(dap_types_body.thread_id, Some(1));
+        assert_eq!(StoppedEventBody::try_from(dap_types_body).unwrap(), body);
+        assert_eq!(debugserver_message.seq, 3);
+        assert_eq!(
+            ProtocolMessage::try_from(debugserver_message).unwrap(),
+            message
+        );
+        assert!(wrong_message.is_err());
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_schema_validation() {
+        use crate::schema::Schema;
+
+        // given:
+        let schema = Schema::new(serde_json::json!({
+            "definitions": {
+                "ProtocolMessage": {
+                    "type": "object",
+                    "properties": {
+                        "seq": { "type": "integer" },
+                        "type": { "type": "string", "_enum": ["request", "response", "event"] }
+                    },
+                    "required": ["seq", "type"]
+                },
+                "Event": {
+                    "allOf": [{ "$ref": "#/definitions/ProtocolMessage" }, {
+                        "type": "object",
+                        "properties": {
+                            "type": { "type": "string", "enum": ["event"] },
+                            "event": { "type": "string" }
+                        },
+                        "required": ["type", "event"]
+                    }]
+                },
+                "StoppedEvent": {
+                    "allOf": [{ "$ref": "#/definitions/Event" }, {
+                        "type": "object",
+                        "properties": {
+                            "event": { "type": "string", "enum": ["stopped"] },
+                            "body": {
+                                "type": "object",
+                                "properties": {
+                                    "reason": { "type": "string" },
+                                    "threadId": { "type": "integer" }
+                                },
+                                "required": ["reason"]
+                            }
+                        },
+                        "required": ["event", "body"]
+                    }]
+                }
+            }
+        }));
+
+        // when:
+        let valid = schema
+            .validate_str(r#"{"seq":1,"type":"event","event":"stopped","body":{"reason":"step"}}"#)
+            .unwrap();
+        let invalid = schema
+            .validate_str(r#"{"seq":1.5,"type":"event","event":"stopped","body":{"threadId":"1"}}"#)
+            .unwrap();
+        let unknown = schema
+            .validate_str(r#"{"seq":2,"type":"event","event":"custom"}"#)
+            .unwrap();
+
+        // then:
+        assert_eq!(valid, vec![]);
+        assert_eq!(
+            invalid.iter().map(Violation::path).collect::<Vec<_>>(),
+            vec!["seq", "body.reason", "body.threadId"]
+        );
+        assert_eq!(unknown, vec![]);
+    }
+
+    #[test]
+    fn test_new_equals_builder_with_required_attributes() {
+        // given:
+        let reason = StoppedEventReason::Breakpoint;
+
+        // when:
+        let actual = StoppedEventBody::new(reason.clone());
+
+        // then:
+        assert_eq!(actual, StoppedEventBody::builder().reason(reason).build());
+        assert_eq!(
+            StackFrame::new(1, "main".into(), 2, 3),
+            StackFrame::builder()
+                .id(1)
+                .name("main".into())
+                .line(2)
+                .column(3)
+                .build()
+        );
+        assert_eq!(
+            TerminatedEventBody::default(),
+            TerminatedEventBody::builder().build()
+        );
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_schema_one_of_requires_exactly_one_match() {
+        use crate::schema::Schema;
+
+        // given:
+        let schema = Schema::new(serde_json::json!({
+            "definitions": {
+                "Number": {
+                    "oneOf": [{ "type": "integer" }, { "type": "number" }]
+                }
+            }
+        }));
+
+        // when:
+        let one = schema.validate_definition("Number", &serde_json::json!(1.5));
+        let two = schema.validate_definition("Number", &serde_json::json!(1));
+        let none = schema.validate_definition("Number", &serde_json::json!("1"));
+
+        // then:
+        assert_eq!(one, vec![]);
+        assert_eq!(
+            two.iter().map(Violation::message).collect::<Vec<_>>(),
+            vec!["value matches 2 schemas of 'oneOf'"]
+        );
+        assert_eq!(
+            none.iter().map(Violation::message).collect::<Vec<_>>(),
+            vec!["value does not match any schema of 'oneOf'"]
+        );
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn test_bundled_schema() {
+        use crate::schema::Schema;
+
+        // given:
+        let schema = Schema::bundled();
+
+        // when:
+        let valid = schema
+            .validate_str(r#"{"seq":1,"type":"event","event":"stopped","body":{"reason":"step","threadId":1}}"#)
+            .unwrap();
+        let invalid = schema
+            .validate_str(
+                r#"{"seq":1,"type":"request","command":"next","arguments":{"threadId":"1"}}"#,
+            )
+            .unwrap();
+
+        // then:
+        assert_eq!(valid, vec![]);
+        assert_eq!(
+            invalid.iter().map(Violation::path).collect::<Vec<_>>(),
+            vec!["arguments.threadId"]
+        );
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_decode_and_encode() {
+        use crate::ffi::*;
+        use std::ffi::CStr;
+
+        // given:
+        let input = b"Content-Length: 70\r\n\r\n{\"seq\":1,\"type\":\"request\",\"command\":\"next\",\"arguments\":{\"threadId\":3}}";
+        let mut consumed = 0;
+
+        // when:
+        let request = unsafe { dap_decode(input.as_ptr(), input.len(), &mut consumed) };
+        let incomplete = unsafe { dap_decode(input.as_ptr(), 10, &mut consumed) };
+        let event =
+            unsafe { dap_event_new(2, c"stopped".as_ptr(), c"{\"reason\":\"step\"}".as_ptr()) };
+        let invalid = unsafe { dap_event_new(3, c"stopped".as_ptr(), c"{}".as_ptr()) };
+        let invalid_utf8 = unsafe { dap_event_new(4, c"stop\xffped".as_ptr(), std::ptr::null()) };
+        let invalid_utf8_error = unsafe { CStr::from_ptr(dap_last_error()) };
+
+        // then:
+        unsafe {
+            assert!(!request.is_null());
+            assert_eq!(dap_message_seq(request), 1);
+            assert_eq!(dap_message_kind(request), DapMessageKind::Request);
+            assert_eq!(CStr::from_ptr(dap_message_name(request)), c"next");
+            assert_eq!(
+                CStr::from_ptr(dap_message_body(request)),
+                c"{\"threadId\":3}"
+            );
+            assert!(incomplete.is_null());
+            assert_eq!(consumed, 0);
+            assert!(invalid.is_null());
+            assert!(invalid_utf8.is_null());
+            assert!(invalid_utf8_error
+                .to_str()
+                .unwrap()
+                .starts_with("event is not valid UTF-8"));
+
+            let mut len = 0;
+            let buffer = dap_encode(event, &mut len);
+            assert_eq!(
+                std::slice::from_raw_parts(buffer, len),
+                ProtocolMessage::new(
+                    2,
+                    Event::Stopped(Box::new(
+                        StoppedEventBody::builder()
+                            .reason(StoppedEventReason::Step)
+                            .build()
+                    ))
+                )
+                .to_string()
+                .as_bytes()
+            );
+            dap_buffer_free(buffer, len);
+            dap_message_free(request);
+            dap_message_free(event);
+        }
+    }
+
+    #[cfg(feature = "ts-rs")]
+    #[test]
+    fn test_typescript_export() {
+        use ts_rs::{Config, TS};
+
+        // given:
+        let out_dir = std::env::temp_dir().join("debug-adapter-protocol-ts-export");
+        let config = Config::new().with_out_dir(&out_dir);
+
+        // when:
+        ProtocolMessage::export_all(&config).unwrap();
+
+        // then:
+        assert_eq!(
+            Response::decl(&config),
+            r#"type Response = { request_seq: number, } & ({ "success": true } & SuccessResponse | { "success": false } & ErrorResponse);"#
+        );
+        assert!(Source::decl(&config).contains("path?: string,"));
+        for file in ["ProtocolMessage.ts", "Response.ts", "StoppedEventBody.ts"] {
+            assert!(out_dir.join(file).is_file(), "{} was not exported", file);
+        }
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_recording_round_trip() {
+        use crate::recording::{read_message, to_wire, write_message};
+
+        // given:
+        let messages = vec![
+            ProtocolMessage::new(1, Request::Threads),
+            ProtocolMessage::new(
+                2,
+                Event::Stopped(Box::new(
+                    StoppedEventBody::builder()
+                        .reason(StoppedEventReason::Breakpoint)
+                        .thread_id(Some(-1))
+                        .build(),
+                )),
+            ),
+        ];
+        let mut recording = Vec::new();
+        for message in &messages {
+            write_message(&mut recording, message).unwrap();
+        }
+
+        // when:
+        let mut reader = recording.as_slice();
+        let mut read = Vec::new();
+        while let Some(message) = read_message(&mut reader).unwrap() {
+            read.push(message);
+        }
+        let mut wire = Vec::new();
+        let count = to_wire(recording.as_slice(), &mut wire).unwrap();
+
+        // then:
+        assert_eq!(read, messages);
+        assert_eq!(count, 2);
+        let expected = messages.iter().map(ToString::to_string).collect::<String>();
+        assert_eq!(String::from_utf8(wire).unwrap(), expected);
+        assert!(recording.len() < expected.len());
+    }
+
+    #[test]
+    fn test_spec_version() {
+        use crate::spec::{SpecVersion, VERSION};
+
+        // when:
+        let parsed = "1.47".parse::<SpecVersion>().unwrap();
+        let major_only = "2".parse::<SpecVersion>().unwrap();
+
+        // then:
+        assert_eq!(parsed, SpecVersion::V1_47);
+        #[cfg(feature = "spec-1_70")]
+        assert_eq!(VERSION.to_string(), "1.70");
+        #[cfg(not(feature = "spec-1_70"))]
+        assert_eq!(VERSION.to_string(), "1.47");
+        assert_eq!(major_only, SpecVersion::new(2, 0));
+        assert!(SpecVersion::new(1, 9) < SpecVersion::V1_47);
+        assert!(SpecVersion::V1_47 < SpecVersion::V1_70);
+        assert!("1.x".parse::<SpecVersion>().is_err());
+    }
+
+    #[test]
+    fn test_localize_and_render_message() {
+        use crate::localize::{Localize, Localizer};
+
+        // given:
+        let localizer = |text: &str| match text {
+            "Cannot evaluate '{expression}': {_reason}" => {
+                Some("'{expression}' kann nicht ausgewertet werden: {_reason}".to_string())
+            }
+            "Locals" => Some("Lokale Variablen".to_string()),
+            _ => None,
+        };
+        let message = Message::builder()
+            .id(1)
+            .format("Cannot evaluate '{expression}': {_reason}".into())
+            .variables(Some(HashMap::from([(
+                "expression".to_string(),
+                "x {y}".to_string(),
+            )])))
+            .build();
+        let mut scopes = vec![Scope::builder()
+            .name("Locals".into())
+            .variables_reference(1)
+            .expensive(false)
+            .build()];
+
+        // when:
+        let rendered = message.render(&localizer);
+        let untranslated = message.render(&|_: &str| None);
+        scopes.localize(&localizer);
+
+        // then:
+        assert_eq!(rendered, "'x {y}' kann nicht ausgewertet werden: {_reason}");
+        assert_eq!(untranslated, "Cannot evaluate 'x {y}': {_reason}");
+        assert_eq!(&*scopes[0].name, "Lokale Variablen");
+        assert!(localizer.translate("Registers").is_none());
+    }
+
+    #[cfg(feature = "requests")]
+    #[test]
+    fn test_launch_json() {
+        use crate::{
+            launch::{LaunchJson, LaunchRequestKind},
+            requests::Request,
+        };
+
+        // given:
+        let json = r#"{
+            // Use IntelliSense to learn about possible attributes.
+            "version": "0.2.0",
+            "configurations": [
+                {
+                    "type": "lldb",
+                    "request": "launch",
+                    "name": "Debug // main",
+                    "program": "${workspaceFolder}/target/debug/main",
+                    "args": ["--home", "${env:HOME}", "${unknown}"], /* trailing comma */
+                    "windows": { "program": "${workspaceFolder}\\main.exe" },
+                },
+                { "type": "lldb", "request": "attach", "name": "Attach", "pid": 42 },
+            ],
+        }"#;
+
+        // when:
+        let launch_json = LaunchJson::parse(json).unwrap();
+        let mut launch = launch_json.configuration("Debug // main").unwrap().clone();
+        launch.apply_platform("linux");
+        launch.substitute(|variable| match variable {
+            "workspaceFolder" => Some("/ws".to_string()),
+            "env:HOME" => Some("/home/user".to_string()),
+            _ => None,
+        });
+        let attach = launch_json.configuration("Attach").unwrap().clone();
+
+        // then:
+        assert_eq!(launch_json.version.as_deref(), Some("0.2.0"));
+        assert_eq!(launch.request, LaunchRequestKind::Launch);
+        assert_eq!(launch.attributes["program"], "/ws/target/debug/main");
+        assert_eq!(
+            launch.attributes["args"],
+            serde_json::json!(["--home", "/home/user", "${unknown}"])
+        );
+        assert!(!launch.attributes.contains_key("windows"));
+        match launch.into_request().unwrap() {
+            Request::Launch(args) => {
+                assert_eq!(args.additional_attributes["type"], "lldb");
+                assert_eq!(args.additional_attributes["name"], "Debug // main");
+            }
+            request => panic!("unexpected request: {:?}", request),
+        }
+        match attach.into_request().unwrap() {
+            Request::Attach(args) => assert_eq!(args.additional_attributes["pid"], 42),
+            request => panic!("unexpected request: {:?}", request),
+        }
+    }
+
+    #[cfg(all(feature = "events", feature = "requests", feature = "responses"))]
+    #[test]
+    fn test_driver() {
+        use crate::{
+            driver::{Driver, DriverEvent, SessionState},
+            requests::{InitializeRequestArguments, Request},
+        };
+
+        // given:
+        let mut driver = Driver::new();
+        let frame = |json: &str| format!("Content-Length: {}\r\n\r\n{}", json.len(), json);
+        let input = [
+            frame(r#"{"seq":1,"type":"response","request_seq":1,"command":"initialize","success":true,"body":{"supportsConfigurationDoneRequest":true}}"#),
+            frame(r#"{"seq":2,"type":"event","event":"initialized"}"#),
+            frame(r#"{"seq":3,"type":"response","request_seq":7,"command":"threads","success":true,"body":{"threads":[]}}"#),
+            frame(r#"{"seq":4,"type":"event","event":"terminated","body":{}}"#),
+        ]
+        .concat();
+
+        // when:
+        let seq = driver.enqueue_request(
+            InitializeRequestArguments::builder()
+                .adapter_id("mock".into())
+                .build()
+                .into(),
+        );
+        let output = driver.poll_output().unwrap();
+        let (first, second) = input.as_bytes().split_at(100);
+        driver.handle_input(first).unwrap();
+        let state_after_first_chunk = driver.state();
+        driver.handle_input(second).unwrap();
+        let events = std::iter::from_fn(|| driver.poll_event()).collect::<Vec<_>>();
+
+        // then:
+        assert_eq!(seq, 1);
+        assert!(output.starts_with(b"Content-Length: "));
+        assert_eq!(driver.poll_output(), None);
+        assert_eq!(state_after_first_chunk, SessionState::Initializing);
+        assert_eq!(driver.state(), SessionState::Terminated);
+        assert!(driver
+            .capabilities()
+            .unwrap()
+            .supports_configuration_done_request());
+        assert!(driver.pending_requests().is_empty());
+        assert_eq!(events.len(), 7);
+        assert_eq!(
+            events[0],
+            DriverEvent::StateChanged(SessionState::Initializing)
+        );
+        assert_eq!(
+            events[1],
+            DriverEvent::StateChanged(SessionState::Initialized)
+        );
+        assert!(matches!(
+            &events[2],
+            DriverEvent::Response {
+                request: Request::Initialize(_),
+                ..
+            }
+        ));
+        assert!(matches!(&events[3], DriverEvent::Event(Event::Initialized)));
+        assert!(matches!(
+            &events[4],
+            DriverEvent::UncorrelatedResponse { .. }
+        ));
+        assert_eq!(
+            events[5],
+            DriverEvent::StateChanged(SessionState::Terminated)
+        );
+    }
+
+    #[cfg(all(feature = "events", feature = "requests", feature = "responses"))]
+    #[test]
+    fn test_injected_sequence_and_clock() {
+        use crate::{
+            driver::Driver,
+            sources::{Counter, FixedClock},
+            trace::{Direction, TraceWriter},
+        };
+        use std::time::{Duration, UNIX_EPOCH};
+
+        // given:
+        let mut driver = Driver::with_sequence(Counter::starting_at(41));
+        let clock = FixedClock(UNIX_EPOCH + Duration::from_millis(1_704_164_645_678));
+        let mut writer = TraceWriter::with_clock(Vec::new(), clock);
+
+        // when:
+        let first = driver.enqueue_request(Request::Threads);
+        let second = driver.enqueue_request(Request::ConfigurationDone);
+        let output = driver.poll_output().unwrap();
+        writer
+            .write(
+                Direction::ToAdapter,
+                &ProtocolMessage::new(first, Request::Threads),
+            )
+            .unwrap();
+        let trace = String::from_utf8(writer.into_inner()).unwrap();
+
+        // then:
+        assert_eq!((first, second), (41, 42));
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            concat!(
+                "Content-Length: 47\r\n\r\n",
+                r#"{"seq":41,"type":"request","command":"threads"}"#,
+                "Content-Length: 57\r\n\r\n",
+                r#"{"seq":42,"type":"request","command":"configurationDone"}"#,
+            )
+        );
+        assert!(trace.starts_with("[2024-01-02T03:04:05.678Z] --> request 'threads' (seq 41)\n"));
+    }
+
+    #[test]
+    fn test_protocol_linter() {
+        use crate::lint::{Diagnostic, Lint, ProtocolLinter};
+
+        // given:
+        let mut linter = ProtocolLinter::new();
+        let client = [
+            ProtocolMessage::new(1, Request::Threads),
+            ProtocolMessage::new(
+                2,
+                InitializeRequestArguments::builder()
+                    .adapter_id("mock".into())
+                    .build(),
+            ),
+            ProtocolMessage::new(3, Request::ConfigurationDone),
+            ProtocolMessage::new(4, Request::LoadedSources),
+        ];
+        let adapter = [
+            ProtocolMessage::new(
+                1,
+                Response::new(
+                    2,
+                    Ok(SuccessResponse::Initialize(Box::new(
+                        Capabilities::builder()
+                            .flags(CapabilityFlags::SUPPORTS_CONFIGURATION_DONE_REQUEST)
+                            .build(),
+                    ))),
+                ),
+            ),
+            ProtocolMessage::new(2, Response::new(1, Ok(SuccessResponse::ConfigurationDone))),
+            ProtocolMessage::new(
+                3,
+                Event::ProgressEnd(
+                    ProgressEndEventBody::builder()
+                        .progress_id("1".into())
+                        .build(),
+                ),
+            ),
+        ];
+
+        // when:
+        let mut diagnostics = Vec::new();
+        diagnostics.extend(linter.client_message(&client[0]));
+        diagnostics.extend(linter.client_message(&client[1]));
+        diagnostics.extend(linter.adapter_message(&adapter[0]));
+        diagnostics.extend(linter.client_message(&client[2]));
+        diagnostics.extend(linter.client_message(&client[3]));
+        diagnostics.extend(linter.adapter_message(&adapter[1]));
+        diagnostics.extend(linter.adapter_message(&adapter[2]));
+
+        // then:
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    seq: 1,
+                    lint: Lint::RequestBeforeInitialize {
+                        command: "threads".to_string()
+                    }
+                },
+                Diagnostic {
+                    seq: 3,
+                    lint: Lint::ConfigurationBeforeInitialized {
+                        command: "configurationDone".to_string()
+                    }
+                },
+                Diagnostic {
+                    seq: 4,
+                    lint: Lint::MissingCapability {
+                        command: "loadedSources".to_string(),
+                        capability: "supportsLoadedSourcesRequest"
+                    }
+                },
+                Diagnostic {
+                    seq: 2,
+                    lint: Lint::UncorrelatedResponse(CorrelationError::CommandMismatch {
+                        request_seq: 1,
+                        expected: "threads".to_string(),
+                        actual: "configurationDone".to_string()
+                    })
+                },
+                Diagnostic {
+                    seq: 3,
+                    lint: Lint::UnsupportedEvent {
+                        event: "progressEnd".to_string(),
+                        capability: "supportsProgressReporting"
+                    }
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "spec-1_70")]
+    #[test]
+    fn test_lint_args_can_be_interpreted_by_shell() {
+        use crate::lint::{Diagnostic, Lint, ProtocolLinter};
+
+        // given:
+        let mut linter = ProtocolLinter::new();
+        let initialize = ProtocolMessage::new(
+            1,
+            InitializeRequestArguments::builder()
+                .adapter_id("mock".into())
+                .supports_run_in_terminal_request(true)
+                .build(),
+        );
+        let run_in_terminal = ProtocolMessage::new(
+            1,
+            RunInTerminalRequestArguments::builder()
+                .cwd("/tmp".into())
+                .args(vec!["echo".into(), "$HOME".into()])
+                .args_can_be_interpreted_by_shell(true)
+                .build(),
+        );
+
+        // when:
+        linter.client_message(&initialize);
+        let actual = linter.adapter_message(&run_in_terminal);
+
+        // then:
+        assert_eq!(
+            actual,
+            vec![Diagnostic {
+                seq: 1,
+                lint: Lint::MissingClientCapability {
+                    command: "runInTerminal".to_string(),
+                    capability: "supportsArgsCanBeInterpretedByShell"
+                }
+            }]
+        );
+    }
+
+    #[test]
+    fn test_custom_scope_presentation_hint() {
+        // given:
+        let json = r#"{"name":"Globals","presentationHint":"globals","variablesReference":3,"expensive":true}"#;
+
+        // when:
+        let actual = serde_json::from_str::<Scope>(json).unwrap();
+
+        // then:
+        assert_eq!(
+            actual.presentation_hint,
+            Some(ScopePresentationHint::Other("globals".into()))
+        );
+        assert_eq!(serde_json::to_string(&actual).unwrap(), json);
+        assert_eq!(
+            serde_json::from_str::<ScopePresentationHint>(r#""locals""#).unwrap(),
+            ScopePresentationHint::Locals
+        );
+        #[cfg(feature = "spec-1_70")]
+        assert_eq!(
+            serde_json::from_str::<ScopePresentationHint>(r#""returnValue""#).unwrap(),
+            ScopePresentationHint::ReturnValue
+        );
+    }
+
+    #[test]
+    fn test_custom_request() {
+        // given:
+        let json =
+            r#"{"seq":1,"type":"request","command":"cdp","arguments":{"method":"Runtime.enable"}}"#;
+        let reordered =
+            r#"{"arguments":{"method":"Runtime.enable"},"seq":1,"type":"request","command":"cdp"}"#;
+        let without_arguments = r#"{"seq":2,"type":"request","command":"rr"}"#;
+
+        // when:
+        let actual = ProtocolMessage::from_str(json).unwrap();
+        let reordered = ProtocolMessage::from_str(reordered).unwrap();
+        let without_arguments = ProtocolMessage::from_str(without_arguments).unwrap();
+
+        // then:
+        let expected = Request::Custom {
+            command: "cdp".into(),
+            arguments: Some(Box::new(serde_json::json!({"method": "Runtime.enable"}))),
+        };
+        assert_eq!(actual, ProtocolMessage::new(1, expected.clone()));
+        assert_eq!(reordered, actual);
+        assert_eq!(expected.command(), "cdp");
+        assert_eq!(serde_json::to_string(&actual).unwrap(), json);
+        assert_eq!(
+            without_arguments,
+            ProtocolMessage::new(
+                2,
+                Request::Custom {
+                    command: "rr".into(),
+                    arguments: None,
+                }
+            )
+        );
+        assert_eq!(
+            serde_json::to_string(&without_arguments).unwrap(),
+            r#"{"seq":2,"type":"request","command":"rr"}"#
+        );
+    }
+
+    #[test]
+    fn test_custom_event() {
+        // given:
+        let json = r#"{"seq":7,"type":"event","event":"telemetry","body":{"name":"launch"}}"#;
+        let without_body = r#"{"seq":8,"type":"event","event":"rr/checkpoint"}"#;
+
+        // when:
+        let actual = ProtocolMessage::from_str(json).unwrap();
+        let without_body = ProtocolMessage::from_str(without_body).unwrap();
+
+        // then:
+        let expected = Event::Custom {
+            event: "telemetry".into(),
+            body: Some(Box::new(serde_json::json!({"name": "launch"}))),
+        };
+        assert_eq!(expected.event(), "telemetry");
+        assert_eq!(actual, ProtocolMessage::new(7, expected));
+        assert_eq!(serde_json::to_string(&actual).unwrap(), json);
+        assert_eq!(
+            without_body,
+            ProtocolMessage::new(
+                8,
+                Event::Custom {
+                    event: "rr/checkpoint".into(),
+                    body: None,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_response_to_custom_request() {
+        // given:
+        let json = r#"{"seq":3,"type":"response","request_seq":2,"success":true,"command":"rr","body":{"when":42}}"#;
+        let body_first = r#"{"body":{"when":42},"seq":3,"type":"response","request_seq":2,"success":true,"command":"rr"}"#;
+        let error = r#"{"seq":4,"type":"response","request_seq":2,"success":false,"command":"rr","message":"no recording"}"#;
+
+        // when:
+        let actual = ProtocolMessage::from_str(json).unwrap();
+        let body_first = ProtocolMessage::from_str(body_first).unwrap();
+        let error = ProtocolMessage::from_str(error).unwrap();
+
+        // then:
+        let expected = SuccessResponse::Other {
+            command: "rr".into(),
+            body: Some(Box::new(serde_json::json!({"when": 42}))),
+        };
+        assert_eq!(expected.command(), "rr");
+        assert_eq!(
+            actual,
+            ProtocolMessage::new(3, Response::new(2, Ok(expected)))
+        );
+        assert_eq!(body_first, actual);
+        assert_eq!(serde_json::to_string(&actual).unwrap(), json);
+        assert_eq!(
+            error,
+            ProtocolMessage::new(
+                4,
+                Response::new(
+                    2,
+                    Err(ErrorResponse::builder()
+                        .command("rr".into())
+                        .message("no recording".into())
+                        .build())
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_custom_enum_values() {
+        // given:
+        let stopped =
+            r#"{"seq":3,"type":"event","event":"stopped","body":{"reason":"signal","threadId":1}}"#;
+        let output = r#"{"seq":4,"type":"event","event":"output","body":{"category":"debug","output":"hello"}}"#;
+
+        // when:
+        let stopped = ProtocolMessage::from_str(stopped).unwrap();
+        let output = ProtocolMessage::from_str(output).unwrap();
+
+        // then:
+        assert_eq!(
+            stopped,
+            ProtocolMessage::new(
+                3,
+                StoppedEventBody::builder()
+                    .reason(StoppedEventReason::Other("signal".into()))
+                    .thread_id(Some(1))
+                    .build(),
+            )
+        );
+        assert_eq!(
+            output,
+            ProtocolMessage::new(
+                4,
+                OutputEventBody::builder()
+                    .category(OutputCategory::Other("debug".into()))
+                    .output("hello".into())
+                    .build(),
+            )
+        );
+        assert_eq!(
+            serde_json::from_str::<StoppedEventReason>(r#""breakpoint""#).unwrap(),
+            StoppedEventReason::Breakpoint
+        );
+    }
+
+    #[test]
+    fn test_custom_evaluate_context() {
+        // given:
+        let json = r#"{"seq":7,"type":"request","command":"evaluate","arguments":{"expression":"x","context":"notebook"}}"#;
+
+        // when:
+        let actual = ProtocolMessage::from_str(json).unwrap();
+
+        // then:
+        let expected = EvaluateRequestArguments::builder()
+            .expression("x".into())
+            .context(Some(EvaluateRequestContext::Other("notebook".into())))
+            .build();
+        assert_eq!(actual, ProtocolMessage::new(7, expected));
+        assert_eq!(serde_json::to_string(&actual).unwrap(), json);
+        #[cfg(feature = "spec-1_70")]
+        assert_eq!(
+            serde_json::from_str::<EvaluateRequestContext>(r#""variables""#).unwrap(),
+            EvaluateRequestContext::Variables
+        );
+    }
+
+    #[cfg(feature = "spec-1_70")]
+    #[test]
+    fn test_disassembled_instruction_presentation_hint() {
+        // given:
+        let json = r#"{"address":"0x0","instruction":"??","presentationHint":"invalid"}"#;
+
+        // when:
+        let actual = serde_json::from_str::<DisassembledInstruction>(json).unwrap();
+
+        // then:
+        assert_eq!(
+            actual.presentation_hint,
+            Some(DisassembledInstructionPresentationHint::Invalid)
+        );
+        assert_eq!(serde_json::to_string(&actual).unwrap(), json);
+    }
+
+    #[test]
+    fn test_disassembly_view() {
+        use crate::disassembly::{parse_address, DisassemblyError, DisassemblyView};
+
+        // given:
+        let mut view = DisassemblyView::new("0x1000", 4);
+        let respond = |request: &DisassembleRequestArguments| DisassembleResponseBody {
+            instructions: (request.instruction_offset..)
+                .take(request.instruction_count as usize)
+                .map(|offset| {
+                    DisassembledInstruction::builder()
+                        .address(Str::from(format!("0x{:x}", 0x1000 + 4 * offset)))
+                        .instruction("nop".into())
+                        .build()
+                })
+                .collect(),
+        };
+
+        // when:
+        let first = view.fetch().unwrap();
+        view.handle_response(&first, &respond(&first)).unwrap();
+        let after_first = view.fetch();
+        let scroll_within = view.scroll(-4);
+        let scroll_beyond = view.scroll(-2).unwrap();
+        view.handle_response(&scroll_beyond, &respond(&scroll_beyond))
+            .unwrap();
+        let revealed = view.reveal(0x1000);
+        let wrong_count = view.handle_response(
+            &first,
+            &DisassembleResponseBody {
+                instructions: vec![],
+            },
+        );
+
+        // then:
+        assert_eq!(
+            (first.instruction_offset, first.instruction_count),
+            (-6, 12)
+        );
+        assert_eq!(after_first, None);
+        assert_eq!(scroll_within, None);
+        assert_eq!(
+            (
+                scroll_beyond.instruction_offset,
+                scroll_beyond.instruction_count
+            ),
+            (-12, 6)
+        );
+        assert!(revealed);
+        assert_eq!(view.top(), -2);
+        assert_eq!(
+            view.visible()
+                .map(|(offset, instruction)| (offset, instruction.map(|i| &*i.address)))
+                .collect::<Vec<_>>(),
+            vec![
+                (-2, Some("0xff8")),
+                (-1, Some("0xffc")),
+                (0, Some("0x1000")),
+                (1, Some("0x1004"))
+            ]
+        );
+        assert_eq!(
+            view.get_by_address(0xfd0).map(|i| &*i.address),
+            Some("0xfd0")
+        );
+        assert_eq!(
+            wrong_count,
+            Err(DisassemblyError::WrongCount {
+                expected: 12,
+                actual: 0
+            })
+        );
+        assert_eq!(parse_address("4096"), Some(0x1000));
+        assert_eq!(parse_address("0xzz"), None);
+    }
+
+    #[test]
+    fn test_watch_list() {
+        use crate::watch::{WatchList, WatchState};
+
+        // given:
+        let mut watches = WatchList::new();
+        let (x, _) = watches.add("x");
+        watches.add("y");
+        let (z, _) = watches.add("z");
+        let value = EvaluateResponseBody::builder()
+            .result("42".into())
+            .type_(Some("int".into()))
+            .variables_reference(0)
+            .build();
+        let error = ErrorResponse::builder()
+            .command("evaluate".into())
+            .message("failed".into())
+            .body(ErrorResponseBody::new(Some(
+                Message::builder()
+                    .id(1)
+                    .format("'{name}' is not defined".into())
+                    .variables(Some(
+                        [("name".to_string(), "y".to_string())]
+                            .into_iter()
+                            .collect(),
+                    ))
+                    .build(),
+            )))
+            .build();
+
+        // when:
+        let requests = watches.select_frame(Some(7));
+        let stored_x = watches.handle_response(&requests[0], &Ok(value.clone().into()));
+        let stored_y = watches.handle_response(&requests[1], &Err(error));
+        let variables = watches.variables();
+        watches.remove(z);
+        let outdated = watches.select_frame(Some(8));
+        let stored_outdated = watches.handle_response(&requests[0], &Ok(value.into()));
+
+        // then:
+        assert_eq!(
+            requests
+                .iter()
+                .map(|request| (
+                    &*request.expression,
+                    request.frame_id,
+                    request.context.clone()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("x", Some(7), Some(EvaluateRequestContext::Watch)),
+                ("y", Some(7), Some(EvaluateRequestContext::Watch)),
+                ("z", Some(7), Some(EvaluateRequestContext::Watch)),
+            ]
+        );
+        assert!(stored_x);
+        assert!(stored_y);
+        assert_eq!(
+            variables
+                .iter()
+                .map(|variable| (&*variable.name, &*variable.value, variable.type_.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("x", "42", Some("int")),
+                ("y", "'y' is not defined", None),
+                ("z", "", None),
+            ]
+        );
+        assert_eq!(outdated.len(), 2);
+        assert!(!stored_outdated);
+        assert_eq!(watches.get(x).unwrap().state, WatchState::Pending);
+        assert!(watches.get(z).is_none());
+    }
+
+    #[test]
+    fn test_repl() {
+        use crate::repl::Repl;
+
+        // given:
+        let mut repl = Repl::new();
+        repl.set_capabilities(
+            &Capabilities::builder()
+                .flags(CapabilityFlags::SUPPORTS_COMPLETIONS_REQUEST)
+                .completion_trigger_characters(Some(vec!["::".into()]))
+                .build(),
+        );
+        repl.select_frame(Some(3));
+        let item = CompletionItem::builder()
+            .label("length".into())
+            .start(Some(3))
+            .length(2)
+            .build();
+        let result = EvaluateResponseBody::builder()
+            .result("[1, 2]".into())
+            .variables_reference(9)
+            .build();
+        let children = VariablesResponseBody::builder()
+            .variables(vec![Variable::builder()
+                .name("0".into())
+                .value("1".into())
+                .variables_reference(0)
+                .build()])
+            .build();
+
+        // when:
+        let is_trigger = (repl.is_trigger("std::"), repl.is_trigger("a."));
+        let completions = repl.complete("ä.le", 5).unwrap();
+        let completed = repl.apply_completion("ä.le", 5, &item);
+        let evaluate = repl.submit("v");
+        repl.submit("v");
+        repl.submit("w");
+        let stored = repl.handle_evaluate(&evaluate, &Ok(result.into()));
+        let expand = repl.expand(9).unwrap();
+        repl.handle_variables(&expand, &Ok(children.into()));
+        let history = [
+            repl.history_previous().map(str::to_string),
+            repl.history_previous().map(str::to_string),
+            repl.history_previous().map(str::to_string),
+            repl.history_next().map(str::to_string),
+            repl.history_next().map(str::to_string),
+        ];
+
+        // then:
+        assert_eq!(is_trigger, (true, false));
+        assert_eq!((completions.column, completions.line), (5, None));
+        assert_eq!(completed, ("ä.length".to_string(), 9));
+        assert_eq!(evaluate.context, Some(EvaluateRequestContext::REPL));
+        assert_eq!(evaluate.frame_id, Some(3));
+        assert!(stored);
+        assert_eq!(
+            repl.entries()
+                .iter()
+                .map(|entry| entry.result.is_some())
+                .collect::<Vec<_>>(),
+            vec![true, false, false]
+        );
+        assert_eq!(repl.children(9).map(<[_]>::len), Some(1));
+        assert_eq!(repl.expand(9), None);
+        assert_eq!(
+            history,
+            [
+                Some("w".to_string()),
+                Some("v".to_string()),
+                None,
+                Some("w".to_string()),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_data_breakpoints() {
+        use crate::data_breakpoints::{variable_info, DataBreakpointError, DataBreakpoints};
+
+        // given:
+        let mut breakpoints = DataBreakpoints::new();
+        let info = |data_id: Option<&str>| {
+            DataBreakpointInfoResponseBody::builder()
+                .data_id(data_id.map(Str::from))
+                .description("counter".into())
+                .access_types(Some(vec![DataBreakpointAccessType::Write]))
+                .build()
+        };
+        let verified = |id, verified| {
+            Breakpoint::builder()
+                .id(Some(id))
+                .verified(verified)
+                .build()
+        };
+
+        // when:
+        let info_request = variable_info(5, "counter");
+        let unavailable = breakpoints.add(&info(None), None);
+        let unsupported = breakpoints.add(&info(Some("a")), Some(DataBreakpointAccessType::Read));
+        let first = breakpoints
+            .add(&info(Some("a")), Some(DataBreakpointAccessType::Write))
+            .unwrap();
+        let second = breakpoints.add(&info(Some("b")), None).unwrap();
+        let outdated = breakpoints.handle_response(
+            &first,
+            &Ok(SetDataBreakpointsResponseBody::builder()
+                .breakpoints(vec![verified(1, true)])
+                .build()
+                .into()),
+        );
+        let wrong_count = breakpoints.handle_response(
+            &second,
+            &Ok(SetDataBreakpointsResponseBody::builder()
+                .breakpoints(vec![])
+                .build()
+                .into()),
+        );
+        breakpoints
+            .handle_response(
+                &second,
+                &Ok(SetDataBreakpointsResponseBody::builder()
+                    .breakpoints(vec![verified(1, true), verified(2, false)])
+                    .build()
+                    .into()),
+            )
+            .unwrap();
+        let updated = breakpoints.update(&verified(2, true));
+        let removed = breakpoints.remove("a").unwrap();
+
+        // then:
+        assert_eq!(info_request.variables_reference, Some(5));
+        assert_eq!(
+            unavailable,
+            Err(DataBreakpointError::Unavailable {
+                description: "counter".into()
+            })
+        );
+        assert!(matches!(
+            unsupported,
+            Err(DataBreakpointError::UnsupportedAccessType { .. })
+        ));
+        assert_eq!(first.breakpoints.len(), 1);
+        assert_eq!(second.breakpoints.len(), 2);
+        assert_eq!(outdated, Ok(()));
+        assert_eq!(
+            wrong_count,
+            Err(DataBreakpointError::WrongCount {
+                expected: 2,
+                actual: 0
+            })
+        );
+        assert!(updated);
+        assert_eq!(
+            removed
+                .breakpoints
+                .iter()
+                .map(|breakpoint| &*breakpoint.data_id)
+                .collect::<Vec<_>>(),
+            vec!["b"]
+        );
+        assert_eq!(
+            breakpoints
+                .iter()
+                .map(|(breakpoint, result)| (&*breakpoint.data_id, result.map(|it| it.verified)))
+                .collect::<Vec<_>>(),
+            vec![("b", Some(true))]
+        );
+    }
+
+    #[cfg(feature = "spec-1_70")]
+    #[test]
+    fn test_data_breakpoint_address_info() {
+        use crate::data_breakpoints::address_info;
+
+        // when:
+        let request = address_info("0x1000", 8);
+
+        // then:
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"name":"0x1000","bytes":8,"asAddress":true}"#
+        );
+    }
+
+    #[test]
+    fn test_exception_node() {
+        use crate::exception::{ExceptionNode, ExceptionNodeKind};
+
+        // given:
+        let details = ExceptionDetails::builder()
+            .type_name(Some("IOException".into()))
+            .full_type_name(Some("System.IO.IOException".into()))
+            .stack_trace(Some("   at Main()\n\n   at Program()\n".into()))
+            .inner_exception(vec![ExceptionDetails::builder()
+                .message(Some("access denied".into()))
+                .build()])
+            .build();
+        let body = ExceptionInfoResponseBody::builder()
+            .exception_id("io".into())
+            .description(Some("file not found".into()))
+            .break_mode(ExceptionBreakMode::Unhandled)
+            .details(Some(details))
+            .build();
+        let minimal = ExceptionInfoResponseBody::builder()
+            .exception_id("io".into())
+            .break_mode(ExceptionBreakMode::Always)
+            .build();
+
+        // when:
+        let tree = ExceptionNode::from_exception_info(&body);
+        let minimal = ExceptionNode::from_exception_info(&minimal);
+
+        // then:
+        assert_eq!(
+            tree.children
+                .iter()
+                .map(|child| child.kind)
+                .collect::<Vec<_>>(),
+            vec![
+                ExceptionNodeKind::StackTraceLine,
+                ExceptionNodeKind::StackTraceLine,
+                ExceptionNodeKind::InnerException
+            ]
+        );
+        assert_eq!(
+            tree.to_string().lines().collect::<Vec<_>>(),
+            vec![
+                "System.IO.IOException: file not found (unhandled)",
+                "  at Main()",
+                "  at Program()",
+                "  Inner exception: <unknown exception>: access denied",
+            ]
+        );
+        assert_eq!(minimal.to_string(), "io\n");
+    }
+
+    #[test]
+    fn test_stream_transport() {
+        use crate::transport::{StreamTransport, Transport};
+
+        // given:
+        let input =
+            b"Content-Length: 46\r\n\r\n{\"seq\":1,\"type\":\"event\",\"event\":\"initialized\"}";
+        let mut output = Vec::new();
+        let mut transport = StreamTransport::new(&input[..], &mut output);
+
+        // when:
+        let received = transport.receive().unwrap();
+        let end = transport.receive().unwrap();
+        transport
+            .send(ProtocolMessage::new(2, Request::ConfigurationDone))
+            .unwrap();
+
+        // then:
+        assert_eq!(received, Some(ProtocolMessage::new(1, Event::Initialized)));
+        assert_eq!(end, None);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Content-Length: 56\r\n\r\n{\"seq\":2,\"type\":\"request\",\"command\":\"configurationDone\"}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_transport_flushes_every_message() {
+        use crate::transport::{StreamTransport, Transport};
+        use std::os::unix::net::UnixStream;
+
+        // given:
+        let (client, adapter) = UnixStream::pair().unwrap();
+        let mut client = StreamTransport::unix(client).unwrap();
+        let mut adapter = StreamTransport::unix(adapter).unwrap();
+
+        // when:
+        client
+            .send(ProtocolMessage::new(1, Request::ConfigurationDone))
+            .unwrap();
+        let received = adapter.receive().unwrap();
+
+        // then:
+        assert_eq!(
+            received,
+            Some(ProtocolMessage::new(1, Request::ConfigurationDone))
+        );
+    }
+
+    #[test]
+    fn test_memory_transport() {
+        use crate::transport::{AsyncTransport, MemoryTransport, Transport};
+        use std::{
+            future::Future,
+            pin::pin,
+            task::{Context, Poll, Waker},
+        };
+
+        // given:
+        let (mut client, mut adapter) = MemoryTransport::pair();
+        let mut cx = Context::from_waker(Waker::noop());
+
+        // when:
+        let pending = pin!(AsyncTransport::receive(&mut adapter))
+            .as_mut()
+            .poll(&mut cx)
+            .is_pending();
+        Transport::send(&mut client, ProtocolMessage::new(1, Request::Threads)).unwrap();
+        let received = pin!(AsyncTransport::receive(&mut adapter))
+            .as_mut()
+            .poll(&mut cx);
+        drop(client);
+        let sent_after_close =
+            Transport::send(&mut adapter, ProtocolMessage::new(1, Event::Initialized));
+        let end = Transport::receive(&mut adapter).unwrap();
+
+        // then:
+        assert!(pending);
+        assert!(matches!(
+            received,
+            Poll::Ready(Ok(Some(ProtocolMessage {
+                seq: 1,
+                content: ProtocolMessageContent::Request(Request::Threads)
+            })))
+        ));
+        assert_eq!(
+            sent_after_close.unwrap_err().kind(),
+            io::ErrorKind::BrokenPipe
+        );
+        assert_eq!(end, None);
+    }
+
+    #[test]
+    fn test_output_coalescer() {
+        use crate::coalesce::OutputCoalescer;
+        use std::{
+            cell::Cell,
+            rc::Rc,
+            time::{Duration, Instant},
+        };
+
+        // given:
+        let start = Instant::now();
+        let now = Rc::new(Cell::new(start));
+        let clock = {
+            let now = now.clone();
+            move || now.get()
+        };
+        let mut coalescer = OutputCoalescer::with_clock(Duration::from_millis(10), 8, clock);
+        let output = |category, output: &str| {
+            OutputEventBody::builder()
+                .category(category)
+                .output(output.into())
+                .build()
+        };
+        let outputs = |bodies: Vec<OutputEventBody>| {
+            bodies
+                .into_iter()
+                .map(|body| (body.category, body.output.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        // when:
+        let batched = ["h", "e", "l", "l", "o"]
+            .into_iter()
+            .flat_map(|byte| coalescer.push(output(OutputCategory::Stdout, byte)))
+            .collect::<Vec<_>>();
+        let other_category = coalescer.push(output(OutputCategory::Stderr, "!"));
+        let too_early = coalescer.poll();
+        now.set(start + Duration::from_millis(10));
+        let expired = coalescer.poll();
+        let oversized = coalescer.push(output(OutputCategory::Stdout, "0123456789abcdefXY"));
+        let located = coalescer.push(
+            OutputEventBody::builder()
+                .output("at".into())
+                .line(Some(1))
+                .build(),
+        );
+
+        // then:
+        assert_eq!(batched, vec![]);
+        assert_eq!(
+            outputs(other_category),
+            vec![(OutputCategory::Stdout, "hello".to_string())]
+        );
+        assert_eq!(too_early, None);
+        assert_eq!(
+            outputs(expired.into_iter().collect()),
+            vec![(OutputCategory::Stderr, "!".to_string())]
+        );
+        assert_eq!(
+            outputs(oversized),
+            vec![
+                (OutputCategory::Stdout, "01234567".to_string()),
+                (OutputCategory::Stdout, "89abcdef".to_string()),
+            ]
+        );
+        assert_eq!(
+            outputs(located),
+            vec![
+                (OutputCategory::Stdout, "XY".to_string()),
+                (OutputCategory::Console, "at".to_string()),
+            ]
+        );
+        assert_eq!(coalescer.flush(), None);
+    }
+
+    #[test]
+    fn test_adapter_registry() {
+        use crate::{
+            registry::{AdapterRegistry, ServeError},
+            transport::{MemoryTransport, Transport},
+        };
+
+        // given:
+        let mut registry = AdapterRegistry::new();
+        for adapter_id in ["native", "script"] {
+            registry.register(
+                adapter_id,
+                move |seq,
+                      _arguments: InitializeRequestArguments,
+                      transport: &mut dyn Transport| {
+                    let capabilities = Capabilities::builder()
+                        .flags(if adapter_id == "script" {
+                            CapabilityFlags::SUPPORTS_TERMINATE_REQUEST
+                        } else {
+                            CapabilityFlags::empty()
+                        })
+                        .build();
+                    let result = Ok(SuccessResponse::Initialize(Box::new(capabilities)));
+                    transport.send(ProtocolMessage::new(1, Response::new(seq, result)))
+                },
+            );
+        }
+        let initialize = |adapter_id: &str| {
+            let arguments = InitializeRequestArguments::builder()
+                .adapter_id(adapter_id.into())
+                .build();
+            ProtocolMessage::new(1, Request::Initialize(Box::new(arguments)))
+        };
+        let session = |message| {
+            let (mut client, mut adapter) = MemoryTransport::pair();
+            client.send(message).unwrap();
+            let result = registry.serve(&mut adapter);
+            drop(adapter);
+            (result, client.receive().unwrap().unwrap())
+        };
+
+        // when:
+        let (script_result, script_response) = session(initialize("script"));
+        let (unknown_result, unknown_response) = session(initialize("java"));
+
+        // then:
+        assert_eq!(
+            registry.adapter_ids().collect::<Vec<_>>(),
+            vec!["native", "script"]
+        );
+        assert!(script_result.is_ok());
+        match script_response.content {
+            ProtocolMessageContent::Response(Response {
+                result: Ok(SuccessResponse::Initialize(capabilities)),
+                ..
+            }) => assert!(capabilities.supports_terminate_request()),
+            content => panic!("unexpected {:?}", content),
+        }
+        assert!(matches!(
+            unknown_result,
+            Err(ServeError::UnknownAdapter { adapter_id }) if &*adapter_id == "java"
+        ));
+        match unknown_response.content {
+            ProtocolMessageContent::Response(Response {
+                request_seq: 1,
+                result: Err(error),
+            }) => assert_eq!(&*error.message, "no debug adapter registered for 'java'"),
+            content => panic!("unexpected {:?}", content),
+        }
+    }
+
+    #[test]
+    fn test_trace_writer() {
+        use crate::trace::{Direction, TraceWriter};
+        use std::time::{Duration, UNIX_EPOCH};
+
+        // given:
+        let time = UNIX_EPOCH + Duration::from_millis(1_704_164_645_678);
+        let request = ProtocolMessage::new(1, Request::Threads);
+        let response = ProtocolMessage::new(
+            2,
+            Response {
+                request_seq: 1,
+                result: Err(ErrorResponse::builder()
+                    .command("threads".into())
+                    .message("cancelled".into())
+                    .build()),
+            },
+        );
+        let event = ProtocolMessage::new(3, Event::Initialized);
+        let mut writer = TraceWriter::new(Vec::new());
+
+        // when:
+        writer
+            .write_at(time, Direction::ToAdapter, &request)
+            .unwrap();
+        writer
+            .write_at(time, Direction::ToClient, &response)
+            .unwrap();
+        writer.write_at(time, Direction::ToClient, &event).unwrap();
+        let trace = String::from_utf8(writer.into_inner()).unwrap();
+
+        // then:
+        let lines = trace.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[..6],
+            [
+                "[2024-01-02T03:04:05.678Z] --> request 'threads' (seq 1)",
+                "{",
+                r#"  "seq": 1,"#,
+                r#"  "type": "request","#,
+                r#"  "command": "threads""#,
+                "}",
+            ]
+        );
+        assert!(lines.contains(&"[2024-01-02T03:04:05.678Z] <-- response 'threads' to 1 failed"));
+        assert!(trace.ends_with(
+            "[2024-01-02T03:04:05.678Z] <-- event 'initialized'\n{\n  \"seq\": 3,\n  \"type\": \"event\",\n  \"event\": \"initialized\"\n}\n\n"
+        ));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics() {
+        use crate::metrics::{Metrics, MetricsRecorder};
+        use std::time::{Duration, Instant};
+
+        // given:
+        let start = Instant::now();
+        let mut recorder = MetricsRecorder::new(Metrics::default());
+        let threads = |request_seq| Response {
+            request_seq,
+            result: Ok(ThreadsResponseBody::builder()
+                .threads(vec![])
+                .build()
+                .into()),
+        };
+        let failed = Response {
+            request_seq: 3,
+            result: Err(ErrorResponse::builder()
+                .command("threads".into())
+                .message("cancelled".into())
+                .build()),
+        };
+
+        // when:
+        for seq in 1..=3 {
+            recorder.request_sent_at(seq, &Request::Threads, start);
+        }
+        recorder.response_received_at(&threads(1), start + Duration::from_millis(3));
+        recorder.response_received_at(&threads(2), start + Duration::from_millis(40));
+        recorder.response_received_at(&failed, start + Duration::from_secs(20));
+        recorder.response_received_at(&threads(4), start);
+        recorder.event_received(&Event::Initialized);
+        let metrics = recorder.into_sink();
+
+        // then:
+        let threads = &metrics.commands["threads"];
+        assert_eq!(metrics.commands.len(), 1);
+        assert_eq!(threads.count, 3);
+        assert_eq!(threads.errors, 1);
+        assert!((threads.error_rate() - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(threads.latency.count(), 3);
+        assert_eq!(threads.latency.sum(), Duration::from_millis(20_043));
+        assert_eq!(
+            threads.latency.quantile(0.5),
+            Some(Duration::from_millis(50))
+        );
+        assert_eq!(
+            threads.latency.quantile(0.1),
+            Some(Duration::from_millis(5))
         );
+        assert_eq!(threads.latency.quantile(1.0), None);
+        assert_eq!(metrics.events["initialized"], 1);
     }
 }
@@ -0,0 +1,311 @@
+//! A linter that flags violations of the rules of a session as the messages pass by.
+//!
+//! Unlike [Validate](crate::validate::Validate), which checks a single message, the
+//! [ProtocolLinter] tracks the state of a session: the capabilities that both sides advertised,
+//! whether the debug adapter is initialized and which requests are outstanding. Attach it to a
+//! client, debug adapter or proxy by passing every message to [ProtocolLinter::client_message] or
+//! [ProtocolLinter::adapter_message], depending on which side sent it. Both return the
+//! [Diagnostic]s found in the message.
+
+use crate::{
+    capabilities::CapabilityFlags,
+    correlation::{CorrelationError, PendingRequests},
+    events::Event,
+    requests::{InitializeRequestArguments, Request},
+    responses::{Response, SuccessResponse},
+    types::Capabilities,
+    ProtocolMessage, ProtocolMessageContent, SequenceNumber,
+};
+use std::fmt::{self, Display};
+
+/// A violation of the rules of a session.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Lint {
+    /// A request other than 'initialize' was sent before the 'initialize' request.
+    RequestBeforeInitialize { command: String },
+
+    /// A configuration request, like 'setBreakpoints' or 'configurationDone', was sent before the
+    /// 'initialized' event.
+    ConfigurationBeforeInitialized { command: String },
+
+    /// A request was sent although the debug adapter does not advertise the capability it needs.
+    MissingCapability {
+        command: String,
+        capability: &'static str,
+    },
+
+    /// A reverse request was sent although the client does not advertise the capability it needs,
+    /// like `supportsRunInTerminalRequest`.
+    MissingClientCapability {
+        command: String,
+        capability: &'static str,
+    },
+
+    /// An event was sent although the client does not advertise the capability it needs, like
+    /// `supportsProgressReporting`.
+    UnsupportedEvent {
+        event: String,
+        capability: &'static str,
+    },
+
+    /// A response does not answer an outstanding request.
+    UncorrelatedResponse(CorrelationError),
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Lint::RequestBeforeInitialize { command } => {
+                write!(
+                    f,
+                    "'{}' request sent before the 'initialize' request",
+                    command
+                )
+            }
+            Lint::ConfigurationBeforeInitialized { command } => write!(
+                f,
+                "'{}' request sent before the 'initialized' event",
+                command
+            ),
+            Lint::MissingCapability {
+                command,
+                capability,
+            } => write!(
+                f,
+                "'{}' request sent, but the debug adapter does not advertise '{}'",
+                command, capability
+            ),
+            Lint::MissingClientCapability {
+                command,
+                capability,
+            } => write!(
+                f,
+                "'{}' request sent, but the client does not advertise '{}'",
+                command, capability
+            ),
+            Lint::UnsupportedEvent { event, capability } => write!(
+                f,
+                "'{}' event sent, but the client does not advertise '{}'",
+                event, capability
+            ),
+            Lint::UncorrelatedResponse(error) => error.fmt(f),
+        }
+    }
+}
+
+/// A [Lint] found in the message with the sequence number `seq`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub seq: SequenceNumber,
+    pub lint: Lint,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "message {}: {}", self.seq, self.lint)
+    }
+}
+
+/// Tracks the state of a session to flag violations of its rules. See the
+/// [module documentation](self).
+#[derive(Clone, Debug, Default)]
+pub struct ProtocolLinter {
+    client: Option<InitializeRequestArguments>,
+    adapter: Option<Capabilities>,
+    initialized: bool,
+    requests: PendingRequests,
+    reverse_requests: PendingRequests,
+}
+
+impl ProtocolLinter {
+    pub fn new() -> ProtocolLinter {
+        ProtocolLinter::default()
+    }
+
+    /// Checks `message`, which the client sent to the debug adapter.
+    pub fn client_message(&mut self, message: &ProtocolMessage) -> Vec<Diagnostic> {
+        let mut lints = Vec::new();
+        match &message.content {
+            ProtocolMessageContent::Request(request) => {
+                self.lint_request(request, &mut lints);
+                self.requests.insert(message.seq, request.clone());
+            }
+            ProtocolMessageContent::Response(response) => {
+                if let Err(error) = self.reverse_requests.complete(response) {
+                    lints.push(Lint::UncorrelatedResponse(error));
+                }
+            }
+            ProtocolMessageContent::Event(_) => {}
+        }
+        diagnostics(message.seq, lints)
+    }
+
+    /// Checks `message`, which the debug adapter sent to the client.
+    pub fn adapter_message(&mut self, message: &ProtocolMessage) -> Vec<Diagnostic> {
+        let mut lints = Vec::new();
+        match &message.content {
+            ProtocolMessageContent::Request(request) => {
+                self.lint_reverse_request(request, &mut lints);
+                self.reverse_requests.insert(message.seq, request.clone());
+            }
+            ProtocolMessageContent::Response(response) => {
+                self.handle_response(response, &mut lints)
+            }
+            ProtocolMessageContent::Event(event) => {
+                if let Event::Initialized = event {
+                    self.initialized = true;
+                }
+                self.lint_event(event, &mut lints);
+            }
+        }
+        diagnostics(message.seq, lints)
+    }
+
+    fn lint_request(&mut self, request: &Request, lints: &mut Vec<Lint>) {
+        let command = request.command();
+        if let Request::Initialize(arguments) = request {
+            self.client = Some((**arguments).clone());
+            return;
+        }
+        if self.client.is_none() {
+            lints.push(Lint::RequestBeforeInitialize {
+                command: command.to_string(),
+            });
+            return;
+        }
+        if is_configuration_request(request) && !self.initialized {
+            lints.push(Lint::ConfigurationBeforeInitialized {
+                command: command.to_string(),
+            });
+        }
+        if let (Some(capabilities), Some(flag)) = (&self.adapter, required_capability(request)) {
+            if !capabilities.flags().contains(flag) {
+                let capability = flag.names().next().unwrap_or_default();
+                lints.push(Lint::MissingCapability {
+                    command: command.to_string(),
+                    capability,
+                });
+            }
+        }
+    }
+
+    fn lint_reverse_request(&self, request: &Request, lints: &mut Vec<Lint>) {
+        let client = match &self.client {
+            Some(client) => client,
+            None => return,
+        };
+        if let Request::RunInTerminal(_) = request {
+            if !client.supports_run_in_terminal_request {
+                lints.push(Lint::MissingClientCapability {
+                    command: request.command().to_string(),
+                    capability: "supportsRunInTerminalRequest",
+                });
+            }
+        }
+        #[cfg(feature = "spec-1_70")]
+        if let Request::RunInTerminal(arguments) = request {
+            if arguments.args_can_be_interpreted_by_shell
+                && !client.supports_args_can_be_interpreted_by_shell
+            {
+                lints.push(Lint::MissingClientCapability {
+                    command: request.command().to_string(),
+                    capability: "supportsArgsCanBeInterpretedByShell",
+                });
+            }
+        }
+    }
+
+    fn handle_response(&mut self, response: &Response, lints: &mut Vec<Lint>) {
+        match self.requests.complete(response) {
+            Ok(Request::Initialize(_)) => {
+                if let Ok(SuccessResponse::Initialize(capabilities)) = &response.result {
+                    self.adapter = Some((**capabilities).clone());
+                }
+            }
+            Ok(_) => {}
+            Err(error) => lints.push(Lint::UncorrelatedResponse(error)),
+        }
+    }
+
+    fn lint_event(&mut self, event: &Event, lints: &mut Vec<Lint>) {
+        if let Event::Capabilities(body) = event {
+            if let Some(capabilities) = &mut self.adapter {
+                capabilities.set_flags(capabilities.flags() | body.capabilities.flags());
+            }
+        }
+        let client = match &self.client {
+            Some(client) => client,
+            None => return,
+        };
+        let required = match event {
+            Event::ProgressStart(_) | Event::ProgressUpdate(_) | Event::ProgressEnd(_) => Some((
+                "supportsProgressReporting",
+                client.supports_progress_reporting,
+            )),
+            Event::Invalidated(_) => Some((
+                "supportsInvalidatedEvent",
+                client.supports_invalidated_event,
+            )),
+            _ => None,
+        };
+        if let Some((capability, false)) = required {
+            lints.push(Lint::UnsupportedEvent {
+                event: event.event().to_string(),
+                capability,
+            });
+        }
+    }
+}
+
+fn diagnostics(seq: SequenceNumber, lints: Vec<Lint>) -> Vec<Diagnostic> {
+    lints
+        .into_iter()
+        .map(|lint| Diagnostic { seq, lint })
+        .collect()
+}
+
+/// Whether `request` configures the debug adapter between the 'initialized' event and the
+/// 'configurationDone' request.
+fn is_configuration_request(request: &Request) -> bool {
+    matches!(
+        request,
+        Request::SetBreakpoints(_)
+            | Request::SetFunctionBreakpoints(_)
+            | Request::SetExceptionBreakpoints(_)
+            | Request::SetDataBreakpoints(_)
+            | Request::SetInstructionBreakpoints(_)
+            | Request::ConfigurationDone
+    )
+}
+
+/// The capability the debug adapter must advertise before `request` may be sent.
+fn required_capability(request: &Request) -> Option<CapabilityFlags> {
+    Some(match request {
+        Request::BreakpointLocations(_) => CapabilityFlags::SUPPORTS_BREAKPOINT_LOCATIONS_REQUEST,
+        Request::Cancel(_) => CapabilityFlags::SUPPORTS_CANCEL_REQUEST,
+        Request::Completions(_) => CapabilityFlags::SUPPORTS_COMPLETIONS_REQUEST,
+        Request::ConfigurationDone => CapabilityFlags::SUPPORTS_CONFIGURATION_DONE_REQUEST,
+        Request::DataBreakpointInfo(_) | Request::SetDataBreakpoints(_) => {
+            CapabilityFlags::SUPPORTS_DATA_BREAKPOINTS
+        }
+        Request::Disassemble(_) => CapabilityFlags::SUPPORTS_DISASSEMBLE_REQUEST,
+        Request::ExceptionInfo(_) => CapabilityFlags::SUPPORTS_EXCEPTION_INFO_REQUEST,
+        Request::Goto(_) | Request::GotoTargets(_) => {
+            CapabilityFlags::SUPPORTS_GOTO_TARGETS_REQUEST
+        }
+        Request::LoadedSources => CapabilityFlags::SUPPORTS_LOADED_SOURCES_REQUEST,
+        Request::Modules(_) => CapabilityFlags::SUPPORTS_MODULES_REQUEST,
+        Request::ReadMemory(_) => CapabilityFlags::SUPPORTS_READ_MEMORY_REQUEST,
+        Request::RestartFrame(_) => CapabilityFlags::SUPPORTS_RESTART_FRAME,
+        Request::ReverseContinue(_) | Request::StepBack(_) => CapabilityFlags::SUPPORTS_STEP_BACK,
+        Request::SetExpression(_) => CapabilityFlags::SUPPORTS_SET_EXPRESSION,
+        Request::SetFunctionBreakpoints(_) => CapabilityFlags::SUPPORTS_FUNCTION_BREAKPOINTS,
+        Request::SetInstructionBreakpoints(_) => CapabilityFlags::SUPPORTS_INSTRUCTION_BREAKPOINTS,
+        Request::SetVariable(_) => CapabilityFlags::SUPPORTS_SET_VARIABLE,
+        Request::StepInTargets(_) => CapabilityFlags::SUPPORTS_STEP_IN_TARGETS_REQUEST,
+        Request::Terminate(_) => CapabilityFlags::SUPPORTS_TERMINATE_REQUEST,
+        Request::TerminateThreads(_) => CapabilityFlags::SUPPORTS_TERMINATE_THREADS_REQUEST,
+        _ => return None,
+    })
+}
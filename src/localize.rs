@@ -0,0 +1,126 @@
+//! Translation of the user facing strings that the specification says must be translated, like
+//! the format of a [Message] or the label of an [ExceptionBreakpointsFilter].
+
+#[cfg(feature = "events")]
+use crate::events::StoppedEventBody;
+use crate::{
+    types::{ExceptionBreakpointsFilter, Message, Scope},
+    Str,
+};
+use std::collections::HashMap;
+
+/// Translates user facing strings, for example by looking them up in a message catalog.
+///
+/// Implemented for closures, so `|text: &str| catalog.get(text).cloned()` is a localizer.
+pub trait Localizer {
+    /// Returns the translation of `text`, or `None` to show it as is.
+    fn translate(&self, text: &str) -> Option<String>;
+}
+
+impl<F: Fn(&str) -> Option<String>> Localizer for F {
+    fn translate(&self, text: &str) -> Option<String> {
+        self(text)
+    }
+}
+
+/// A value with user facing strings that can be translated.
+pub trait Localize {
+    /// Replaces the user facing strings of `self` with their translation by `localizer`.
+    fn localize(&mut self, localizer: &dyn Localizer);
+}
+
+fn translate(text: &mut Str, localizer: &dyn Localizer) {
+    if let Some(translation) = localizer.translate(text) {
+        *text = Str::from(translation);
+    }
+}
+
+fn translate_option(text: &mut Option<Str>, localizer: &dyn Localizer) {
+    if let Some(text) = text {
+        translate(text, localizer);
+    }
+}
+
+impl Localize for Message {
+    /// Translates the format, so variables can be placed differently in other languages.
+    fn localize(&mut self, localizer: &dyn Localizer) {
+        translate(&mut self.format, localizer);
+        translate_option(&mut self.url_label, localizer);
+    }
+}
+
+impl Localize for ExceptionBreakpointsFilter {
+    fn localize(&mut self, localizer: &dyn Localizer) {
+        translate(&mut self.label, localizer);
+        translate_option(&mut self.description, localizer);
+        translate_option(&mut self.condition_description, localizer);
+    }
+}
+
+impl Localize for Scope {
+    fn localize(&mut self, localizer: &dyn Localizer) {
+        translate(&mut self.name, localizer);
+    }
+}
+
+#[cfg(feature = "events")]
+impl Localize for StoppedEventBody {
+    /// Translates the description. The reason is shown if the description is missing, but must not
+    /// be translated.
+    fn localize(&mut self, localizer: &dyn Localizer) {
+        translate_option(&mut self.description, localizer);
+    }
+}
+
+impl<T: Localize> Localize for Vec<T> {
+    fn localize(&mut self, localizer: &dyn Localizer) {
+        for value in self {
+            value.localize(localizer);
+        }
+    }
+}
+
+impl<T: Localize> Localize for Option<T> {
+    fn localize(&mut self, localizer: &dyn Localizer) {
+        if let Some(value) = self {
+            value.localize(localizer);
+        }
+    }
+}
+
+impl Message {
+    /// Renders the message for the user: translates the format with `localizer` and replaces the
+    /// embedded variables of the form '{name}' with their values. Variables without a value are
+    /// kept as is.
+    pub fn render(&self, localizer: &dyn Localizer) -> String {
+        let format = localizer
+            .translate(&self.format)
+            .unwrap_or_else(|| self.format.to_string());
+        let empty = HashMap::new();
+        substitute(&format, self.variables.as_ref().unwrap_or(&empty))
+    }
+}
+
+fn substitute(format: &str, variables: &HashMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(format.len());
+    let mut rest = format;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let variable = &rest[start + 1..];
+        match variable
+            .find('}')
+            .and_then(|end| Some((end, variables.get(&variable[..end])?)))
+        {
+            Some((end, value)) => {
+                rendered.push_str(value);
+                rest = &variable[end + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = variable;
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
@@ -0,0 +1,203 @@
+//! Metrics of a session from the client side, for example to find slow debug adapters.
+//!
+//! [MetricsRecorder] observes the messages of a session and reports the latency and success of
+//! every request and the type of every event to a [MetricsSink]. Implement the trait to forward
+//! the metrics to Prometheus or another monitoring system, or use [Metrics] to collect them in
+//! memory.
+
+#[cfg(feature = "events")]
+use crate::events::Event;
+use crate::{requests::Request, responses::Response, SequenceNumber, Str};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Receives the metrics recorded by a [MetricsRecorder].
+pub trait MetricsSink {
+    /// Records that the request with the given command was answered after `latency`.
+    fn record_response(&mut self, command: &str, success: bool, latency: Duration);
+
+    /// Records an event of the given type.
+    fn record_event(&mut self, event: &str);
+}
+
+/// Measures the latency of requests and reports it with the events of a session to a
+/// [MetricsSink].
+#[derive(Debug)]
+pub struct MetricsRecorder<S> {
+    sink: S,
+    sent: HashMap<SequenceNumber, (Str, Instant)>,
+}
+
+impl<S: MetricsSink> MetricsRecorder<S> {
+    pub fn new(sink: S) -> MetricsRecorder<S> {
+        MetricsRecorder {
+            sink,
+            sent: HashMap::new(),
+        }
+    }
+
+    /// Records that `request` was sent now with the sequence number `seq`.
+    pub fn request_sent(&mut self, seq: SequenceNumber, request: &Request) {
+        self.request_sent_at(seq, request, Instant::now());
+    }
+
+    /// Records that `request` was sent at `time` with the sequence number `seq`.
+    pub fn request_sent_at(&mut self, seq: SequenceNumber, request: &Request, time: Instant) {
+        self.sent.insert(seq, (request.command().into(), time));
+    }
+
+    /// Records that `response` was received now.
+    pub fn response_received(&mut self, response: &Response) {
+        self.response_received_at(response, Instant::now());
+    }
+
+    /// Records that `response` was received at `time`. Responses to requests that were not
+    /// recorded as sent are ignored.
+    pub fn response_received_at(&mut self, response: &Response, time: Instant) {
+        if let Some((command, sent)) = self.sent.remove(&response.request_seq) {
+            let latency = time.saturating_duration_since(sent);
+            self.sink
+                .record_response(&command, response.result.is_ok(), latency);
+        }
+    }
+
+    #[cfg(feature = "events")]
+    pub fn event_received(&mut self, event: &Event) {
+        self.sink.record_event(event.event());
+    }
+
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    pub fn into_sink(self) -> S {
+        self.sink
+    }
+}
+
+/// The upper bounds of the buckets of a [Histogram].
+const BUCKET_BOUNDS: [Duration; 12] = [
+    Duration::from_millis(1),
+    Duration::from_millis(2),
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(20),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(200),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+
+/// A histogram of latencies with fixed buckets from 1ms to 10s.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Histogram {
+    /// The counts of the buckets of [BUCKET_BOUNDS] followed by the count of larger latencies.
+    counts: [u64; BUCKET_BOUNDS.len() + 1],
+    sum: Duration,
+}
+
+impl Histogram {
+    pub fn record(&mut self, latency: Duration) {
+        let bucket = BUCKET_BOUNDS.partition_point(|bound| *bound < latency);
+        self.counts[bucket] += 1;
+        self.sum += latency;
+    }
+
+    /// The number of recorded latencies.
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// The sum of all recorded latencies.
+    pub fn sum(&self) -> Duration {
+        self.sum
+    }
+
+    /// The buckets as pairs of their upper bound and the number of latencies in them. The bound
+    /// of the last bucket is `None`.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        BUCKET_BOUNDS
+            .iter()
+            .copied()
+            .map(Some)
+            .chain([None])
+            .zip(self.counts.iter().copied())
+    }
+
+    /// The upper bound of the bucket that contains the quantile `q` (between 0 and 1), or `None`
+    /// if there are no latencies or the quantile is in the last bucket.
+    pub fn quantile(&self, q: f64) -> Option<Duration> {
+        let rank = (q.clamp(0.0, 1.0) * self.count() as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bound, count) in self.buckets() {
+            seen += count;
+            if seen >= rank {
+                return bound;
+            }
+        }
+        None
+    }
+}
+
+/// The metrics of the requests with the same command.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CommandMetrics {
+    /// The number of answered requests.
+    pub count: u64,
+
+    /// The number of requests that were answered with an error response.
+    pub errors: u64,
+
+    pub latency: Histogram,
+}
+
+impl CommandMetrics {
+    /// The fraction of requests that were answered with an error response.
+    pub fn error_rate(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.count as f64
+        }
+    }
+}
+
+/// A [MetricsSink] that collects the metrics in memory.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metrics {
+    /// The metrics of the requests by command.
+    pub commands: HashMap<String, CommandMetrics>,
+
+    /// The number of events by type.
+    pub events: HashMap<String, u64>,
+}
+
+impl MetricsSink for Metrics {
+    fn record_response(&mut self, command: &str, success: bool, latency: Duration) {
+        let metrics = self.commands.entry(command.to_string()).or_default();
+        metrics.count += 1;
+        if !success {
+            metrics.errors += 1;
+        }
+        metrics.latency.record(latency);
+    }
+
+    fn record_event(&mut self, event: &str) {
+        *self.events.entry(event.to_string()).or_default() += 1;
+    }
+}
+
+impl<S: MetricsSink + ?Sized> MetricsSink for &mut S {
+    fn record_response(&mut self, command: &str, success: bool, latency: Duration) {
+        (**self).record_response(command, success, latency);
+    }
+
+    fn record_event(&mut self, event: &str) {
+        (**self).record_event(event);
+    }
+}
@@ -0,0 +1,390 @@
+//! A scriptable client for testing debug adapters.
+//!
+//! A [Script] lists the requests to send and the events to expect, for example 'initialize',
+//! 'setBreakpoints', 'launch', a 'stopped' event and an 'evaluate'. [MockClient::run] plays it
+//! against a debug adapter over a [Connection] and stops at the first step that fails. Use
+//! [Connection::memory] to test an adapter in the same process, or [Connection::from_transport]
+//! to test one that runs in a separate process.
+//!
+//! Events that arrive before they are expected are buffered, and events that are never expected,
+//! like most 'output' events, are skipped. Reverse requests of the debug adapter are answered by
+//! [MockClient::on_reverse_request].
+
+use crate::{
+    connection::Connection,
+    events::Event,
+    requests::Request,
+    responses::{ErrorResponse, ErrorResponseBody, Response, SuccessResponse},
+    sources::{Counter, SequenceSource},
+    ProtocolMessage, ProtocolMessageContent, SequenceNumber, Str,
+};
+use crossbeam_channel::{select, RecvTimeoutError};
+use std::{
+    collections::VecDeque,
+    fmt::{self, Debug, Display},
+    time::Duration,
+};
+
+type Check<T> = Box<dyn Fn(&T) -> Result<(), String> + Send>;
+
+type ReverseRequestHandler = Box<dyn FnMut(&Request) -> Result<SuccessResponse, String> + Send>;
+
+enum Step {
+    Request {
+        request: Request,
+        check: Option<Check<SuccessResponse>>,
+    },
+    FailingRequest {
+        request: Request,
+    },
+    Event {
+        event: String,
+        check: Option<Check<Event>>,
+    },
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Request { request, .. } => write!(f, "'{}' request", request.command()),
+            Step::FailingRequest { request } => {
+                write!(f, "failing '{}' request", request.command())
+            }
+            Step::Event { event, .. } => write!(f, "'{}' event", event),
+        }
+    }
+}
+
+/// The steps a [MockClient] runs through.
+#[derive(Default)]
+pub struct Script {
+    steps: Vec<Step>,
+}
+
+impl Debug for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.steps.iter().map(|step| step.to_string()))
+            .finish()
+    }
+}
+
+impl Script {
+    pub fn new() -> Script {
+        Script::default()
+    }
+
+    /// Sends `request` and expects a successful response.
+    pub fn request(mut self, request: impl Into<Request>) -> Script {
+        let request = request.into();
+        self.steps.push(Step::Request {
+            request,
+            check: None,
+        });
+        self
+    }
+
+    /// Sends `request` and expects a successful response that passes `check`.
+    pub fn request_with(
+        mut self,
+        request: impl Into<Request>,
+        check: impl Fn(&SuccessResponse) -> Result<(), String> + Send + 'static,
+    ) -> Script {
+        let request = request.into();
+        self.steps.push(Step::Request {
+            request,
+            check: Some(Box::new(check)),
+        });
+        self
+    }
+
+    /// Sends `request` and expects an error response.
+    pub fn failing_request(mut self, request: impl Into<Request>) -> Script {
+        let request = request.into();
+        self.steps.push(Step::FailingRequest { request });
+        self
+    }
+
+    /// Expects an event named `event`, like 'stopped'.
+    pub fn event(mut self, event: &str) -> Script {
+        self.steps.push(Step::Event {
+            event: event.to_string(),
+            check: None,
+        });
+        self
+    }
+
+    /// Expects an event named `event` that passes `check`.
+    pub fn event_with(
+        mut self,
+        event: &str,
+        check: impl Fn(&Event) -> Result<(), String> + Send + 'static,
+    ) -> Script {
+        self.steps.push(Step::Event {
+            event: event.to_string(),
+            check: Some(Box::new(check)),
+        });
+        self
+    }
+}
+
+/// Why a [MockClient] could not complete a step.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum MockError {
+    /// The debug adapter closed the connection.
+    Disconnected,
+
+    /// Nothing arrived for the timeout of the client.
+    Timeout,
+
+    /// The debug adapter answered a request with an error.
+    ErrorResponse { command: String, message: String },
+
+    /// The debug adapter answered a request successfully that was expected to fail.
+    UnexpectedSuccess { command: String },
+
+    /// The debug adapter sent a response to a request the client did not send or no longer waits
+    /// for.
+    UnexpectedResponse { request_seq: SequenceNumber },
+
+    /// A response or event did not pass the check of a step.
+    Check(String),
+
+    /// The debug adapter sent a message that could not be parsed.
+    Malformed(String),
+}
+
+impl Display for MockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MockError::Disconnected => write!(f, "the debug adapter closed the connection"),
+            MockError::Timeout => write!(f, "timed out"),
+            MockError::ErrorResponse { command, message } => {
+                write!(f, "'{}' request failed: {}", command, message)
+            }
+            MockError::UnexpectedSuccess { command } => {
+                write!(f, "'{}' request succeeded unexpectedly", command)
+            }
+            MockError::UnexpectedResponse { request_seq } => {
+                write!(f, "unexpected response to request {}", request_seq)
+            }
+            MockError::Check(message) => write!(f, "check failed: {}", message),
+            MockError::Malformed(error) => write!(f, "malformed message: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for MockError {}
+
+/// A [MockError] in a step of a [Script].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScriptError {
+    /// The index of the step that failed.
+    pub step: usize,
+
+    /// A description of the step that failed, like `'launch' request`.
+    pub description: String,
+
+    pub error: MockError,
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "step {} ({}): {}",
+            self.step, self.description, self.error
+        )
+    }
+}
+
+impl std::error::Error for ScriptError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// A client that drives a debug adapter under test. See the [module documentation](self).
+pub struct MockClient {
+    connection: Connection,
+    sequence: Box<dyn SequenceSource + Send>,
+    timeout: Duration,
+    events: VecDeque<Event>,
+    reverse_requests: ReverseRequestHandler,
+}
+
+impl Debug for MockClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockClient")
+            .field("connection", &self.connection)
+            .field("timeout", &self.timeout)
+            .field("events", &self.events)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MockClient {
+    /// Creates a client that talks to a debug adapter over `connection`. It waits up to five
+    /// seconds for each message and rejects all reverse requests.
+    pub fn new(connection: Connection) -> MockClient {
+        MockClient {
+            connection,
+            sequence: Box::new(Counter::new()),
+            timeout: Duration::from_secs(5),
+            events: VecDeque::new(),
+            reverse_requests: Box::new(|request| {
+                Err(format!("'{}' is not supported", request.command()))
+            }),
+        }
+    }
+
+    /// Sets how long to wait for each message of the debug adapter.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Sets where the sequence numbers of sent messages come from. By default they count up from
+    /// 1.
+    pub fn set_sequence(&mut self, sequence: impl SequenceSource + Send + 'static) {
+        self.sequence = Box::new(sequence);
+    }
+
+    /// Sets the handler that answers reverse requests of the debug adapter, like 'runInTerminal'.
+    /// An `Err` is sent as an error response with the message.
+    pub fn on_reverse_request(
+        &mut self,
+        handler: impl FnMut(&Request) -> Result<SuccessResponse, String> + Send + 'static,
+    ) {
+        self.reverse_requests = Box::new(handler);
+    }
+
+    /// Runs through the steps of `script` and returns the first one that fails.
+    pub fn run(&mut self, script: Script) -> Result<(), ScriptError> {
+        for (index, step) in script.steps.into_iter().enumerate() {
+            let description = step.to_string();
+            self.run_step(step).map_err(|error| ScriptError {
+                step: index,
+                description,
+                error,
+            })?;
+        }
+        Ok(())
+    }
+
+    fn run_step(&mut self, step: Step) -> Result<(), MockError> {
+        match step {
+            Step::Request { request, check } => {
+                let command = request.command().to_string();
+                match self.request(request)?.result {
+                    Ok(response) => match check {
+                        Some(check) => check(&response).map_err(MockError::Check),
+                        None => Ok(()),
+                    },
+                    Err(response) => Err(MockError::ErrorResponse {
+                        command,
+                        message: response.message.to_string(),
+                    }),
+                }
+            }
+            Step::FailingRequest { request } => {
+                let command = request.command().to_string();
+                match self.request(request)?.result {
+                    Ok(_) => Err(MockError::UnexpectedSuccess { command }),
+                    Err(_) => Ok(()),
+                }
+            }
+            Step::Event { event, check } => {
+                let event = self.expect_event(&event)?;
+                match check {
+                    Some(check) => check(&event).map_err(MockError::Check),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Sends `request` and waits for the response. Events that arrive in the meantime are
+    /// buffered for [MockClient::expect_event].
+    pub fn request(&mut self, request: impl Into<Request>) -> Result<Response, MockError> {
+        let seq = self.send(request.into())?;
+        loop {
+            match self.receive()? {
+                ProtocolMessageContent::Response(response) if response.request_seq == seq => {
+                    return Ok(response);
+                }
+                ProtocolMessageContent::Response(response) => {
+                    return Err(MockError::UnexpectedResponse {
+                        request_seq: response.request_seq,
+                    });
+                }
+                ProtocolMessageContent::Event(event) => self.events.push_back(event),
+                ProtocolMessageContent::Request(_) => unreachable!("answered by receive"),
+            }
+        }
+    }
+
+    /// Waits for an event named `event`, like 'stopped'. Other events are skipped.
+    pub fn expect_event(&mut self, event: &str) -> Result<Event, MockError> {
+        while let Some(buffered) = self.events.pop_front() {
+            if buffered.event() == event {
+                return Ok(buffered);
+            }
+        }
+        loop {
+            match self.receive()? {
+                ProtocolMessageContent::Event(received) if received.event() == event => {
+                    return Ok(received);
+                }
+                ProtocolMessageContent::Event(_) => {}
+                ProtocolMessageContent::Response(response) => {
+                    return Err(MockError::UnexpectedResponse {
+                        request_seq: response.request_seq,
+                    });
+                }
+                ProtocolMessageContent::Request(_) => unreachable!("answered by receive"),
+            }
+        }
+    }
+
+    fn send(
+        &mut self,
+        content: impl Into<ProtocolMessageContent>,
+    ) -> Result<SequenceNumber, MockError> {
+        let seq = self.sequence.next_seq();
+        self.connection
+            .sender
+            .send(ProtocolMessage::new(seq, content))
+            .map_err(|_| MockError::Disconnected)?;
+        Ok(seq)
+    }
+
+    /// Receives the next response or event, answering reverse requests along the way.
+    fn receive(&mut self) -> Result<ProtocolMessageContent, MockError> {
+        loop {
+            let message = select! {
+                recv(self.connection.receiver) -> message => message.ok(),
+                recv(self.connection.errors) -> error => match error {
+                    Ok(error) => return Err(MockError::Malformed(error.to_string())),
+                    // The connection does not parse messages, so only wait for the next one
+                    Err(_) => match self.connection.receiver.recv_timeout(self.timeout) {
+                        Err(RecvTimeoutError::Timeout) => return Err(MockError::Timeout),
+                        message => message.ok(),
+                    },
+                },
+                default(self.timeout) => return Err(MockError::Timeout),
+            };
+            let message = message.ok_or(MockError::Disconnected)?;
+            let request = match message.content {
+                ProtocolMessageContent::Request(request) => request,
+                content => return Ok(content),
+            };
+            let result = (self.reverse_requests)(&request).map_err(|message| ErrorResponse {
+                command: request.command().into(),
+                message: Str::from(message),
+                body: Box::new(ErrorResponseBody::default()),
+            });
+            self.send(Response::new(message.seq, result))?;
+        }
+    }
+}
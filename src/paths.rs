@@ -0,0 +1,350 @@
+#[cfg(feature = "events")]
+use crate::events::Event;
+#[cfg(feature = "requests")]
+use crate::requests::Request;
+#[cfg(feature = "responses")]
+use crate::responses::{Response, SuccessResponse};
+use crate::{
+    types::{Breakpoint, DisassembledInstruction, Scope, Source, StackFrame},
+    ProtocolMessage, ProtocolMessageContent, Str,
+};
+use std::path::Path;
+
+/// Normalizes a path for comparison without accessing the file system.
+///
+/// Windows paths (paths starting with a drive letter or `\\`) get their backslashes replaced by
+/// slashes and their drive letter lowercased, because clients differ in how they spell them. For
+/// all paths repeated separators and `.` segments are removed. `..` segments are kept, because
+/// resolving them lexically is wrong if the preceding segment is a symlink; use [resolve_path] for
+/// that.
+pub fn normalize_path(path: &str) -> String {
+    let windows = is_windows_path(path);
+    let mut normalized = if windows {
+        path.replace('\\', "/")
+    } else {
+        path.to_string()
+    };
+    if windows && has_drive_letter(&normalized) {
+        normalized[..1].make_ascii_lowercase();
+    }
+
+    // Keep the leading separators of absolute and UNC paths
+    let prefix_len = if windows && normalized.starts_with("//") {
+        2
+    } else if normalized.starts_with('/') {
+        1
+    } else {
+        0
+    };
+    let (prefix, rest) = normalized.split_at(prefix_len);
+    let segments = rest
+        .split('/')
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .collect::<Vec<_>>();
+    let mut result = prefix.to_string();
+    result.push_str(&segments.join("/"));
+    if result.is_empty() && !path.is_empty() {
+        result.push('.');
+    }
+    result
+}
+
+/// Normalizes a path like [normalize_path] and additionally resolves `..` segments and symlinks.
+///
+/// If the path exists, it is canonicalized by the file system. Otherwise `..` segments are
+/// resolved lexically.
+pub fn resolve_path(path: &str) -> String {
+    if let Some(canonical) = std::fs::canonicalize(Path::new(path))
+        .ok()
+        .and_then(|canonical| canonical.to_str().map(str::to_string))
+    {
+        return normalize_path(strip_verbatim_prefix(&canonical));
+    }
+    let normalized = normalize_path(path);
+    let (prefix, rest) = split_root(&normalized);
+    let mut segments = Vec::new();
+    for segment in rest.split('/').filter(|segment| !segment.is_empty()) {
+        match (segment, segments.last()) {
+            ("..", Some(&last)) if last != ".." => {
+                segments.pop();
+            }
+            // The parent of the root is the root
+            ("..", None) if !prefix.is_empty() => {}
+            _ => segments.push(segment),
+        }
+    }
+    let mut result = prefix.to_string();
+    result.push_str(&segments.join("/"));
+    if result.is_empty() {
+        result.push('.');
+    }
+    result
+}
+
+/// Returns true if both paths are equal after [normalize_path].
+pub fn paths_equal(a: &str, b: &str) -> bool {
+    a == b || normalize_path(a) == normalize_path(b)
+}
+
+fn is_windows_path(path: &str) -> bool {
+    has_drive_letter(path) || path.starts_with("\\\\")
+}
+
+fn has_drive_letter(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+/// Splits a normalized path into its root (like `/`, `c:/` or `//`) and the rest.
+fn split_root(path: &str) -> (&str, &str) {
+    let len = if has_drive_letter(path) {
+        if path[2..].starts_with('/') {
+            3
+        } else {
+            2
+        }
+    } else if path.starts_with("//") {
+        2
+    } else if path.starts_with('/') {
+        1
+    } else {
+        0
+    };
+    path.split_at(len)
+}
+
+/// Windows canonicalizes to `\\?\C:\...`, which clients never send.
+fn strip_verbatim_prefix(path: &str) -> &str {
+    path.strip_prefix(r"\\?\").unwrap_or(path)
+}
+
+/// Translates the paths of sources between the client and the debug adapter, for example when the
+/// debuggee runs on another machine or in a container and sees the sources under another path.
+pub trait PathMapper {
+    /// Maps a path of the client to the path the debug adapter knows, or returns `None` to keep it.
+    fn to_adapter(&self, path: &str) -> Option<String>;
+
+    /// Maps a path of the debug adapter to the path the client knows, or returns `None` to keep it.
+    fn to_client(&self, path: &str) -> Option<String>;
+}
+
+/// A [PathMapper] that replaces the prefixes of paths, like a local workspace folder and the folder
+/// it is mounted to in a container.
+///
+/// Paths are matched against the prefixes after [normalize_path], so separators and the case of
+/// drive letters don't matter. If several prefixes match, the longest one wins. The rest of the
+/// path is appended with the separator of the replacing prefix.
+#[derive(Clone, Debug, Default)]
+pub struct PrefixMapper {
+    prefixes: Vec<(String, String)>,
+    case_insensitive: bool,
+}
+
+impl PrefixMapper {
+    pub fn new() -> PrefixMapper {
+        PrefixMapper::default()
+    }
+
+    /// Maps paths of the client starting with `client` to paths of the debug adapter starting with
+    /// `adapter` and vice versa.
+    pub fn with_prefix(mut self, client: impl Into<String>, adapter: impl Into<String>) -> Self {
+        self.prefixes.push((client.into(), adapter.into()));
+        self
+    }
+
+    /// Whether prefixes are matched ignoring ASCII case, like on Windows and macOS file systems.
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    fn map<'a>(
+        &'a self,
+        path: &str,
+        prefixes: impl Iterator<Item = (&'a str, &'a str)>,
+    ) -> Option<String> {
+        let normalized = normalize_path(path);
+        let (rest, to) = prefixes
+            .filter_map(|(from, to)| Some((self.strip_prefix(&normalized, from)?, to)))
+            .min_by_key(|(rest, _)| rest.len())?;
+        let separator = if to.contains('\\') { '\\' } else { '/' };
+        let mut mapped = to.trim_end_matches(['/', '\\']).to_string();
+        for segment in rest.split('/').filter(|segment| !segment.is_empty()) {
+            mapped.push(separator);
+            mapped.push_str(segment);
+        }
+        if mapped.is_empty() {
+            mapped.push(separator);
+        }
+        Some(mapped)
+    }
+
+    fn strip_prefix<'p>(&self, normalized: &'p str, prefix: &str) -> Option<&'p str> {
+        let prefix = normalize_path(prefix);
+        let head = normalized.get(..prefix.len())?;
+        let matches = if self.case_insensitive {
+            head.eq_ignore_ascii_case(&prefix)
+        } else {
+            head == prefix
+        };
+        let rest = &normalized[prefix.len()..];
+        // Only match whole segments, so '/src' is not a prefix of '/srcs'
+        if matches && (rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/')) {
+            Some(rest)
+        } else {
+            None
+        }
+    }
+}
+
+impl PathMapper for PrefixMapper {
+    fn to_adapter(&self, path: &str) -> Option<String> {
+        let prefixes = self.prefixes.iter();
+        self.map(
+            path,
+            prefixes.map(|(client, adapter)| (&**client, &**adapter)),
+        )
+    }
+
+    fn to_client(&self, path: &str) -> Option<String> {
+        let prefixes = self.prefixes.iter();
+        self.map(
+            path,
+            prefixes.map(|(client, adapter)| (&**adapter, &**client)),
+        )
+    }
+}
+
+/// Maps the client's paths in `message`, which the client sent, to paths of the debug adapter.
+pub fn map_client_message(message: &mut ProtocolMessage, mapper: &dyn PathMapper) {
+    message.map_paths(&|path| mapper.to_adapter(path));
+}
+
+/// Maps the debug adapter's paths in `message`, which the debug adapter sent, to paths of the
+/// client.
+pub fn map_adapter_message(message: &mut ProtocolMessage, mapper: &dyn PathMapper) {
+    message.map_paths(&|path| mapper.to_client(path));
+}
+
+/// A value that contains the paths of [Source]s.
+pub trait MapPaths {
+    /// Replaces every [Source::path] in `self` for which `map` returns a new path.
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>);
+}
+
+impl<T: MapPaths> MapPaths for Option<T> {
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>) {
+        if let Some(value) = self {
+            value.map_paths(map);
+        }
+    }
+}
+
+impl<T: MapPaths> MapPaths for Vec<T> {
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>) {
+        for value in self {
+            value.map_paths(map);
+        }
+    }
+}
+
+impl<T: MapPaths> MapPaths for Box<T> {
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>) {
+        (**self).map_paths(map);
+    }
+}
+
+impl MapPaths for Source {
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>) {
+        if let Some(mapped) = self.path.as_deref().and_then(map) {
+            self.path = Some(Str::from(mapped));
+        }
+        self.sources.map_paths(map);
+    }
+}
+
+impl MapPaths for Breakpoint {
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>) {
+        self.source.map_paths(map);
+    }
+}
+
+impl MapPaths for DisassembledInstruction {
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>) {
+        self.location.map_paths(map);
+    }
+}
+
+impl MapPaths for Scope {
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>) {
+        self.source.map_paths(map);
+    }
+}
+
+impl MapPaths for StackFrame {
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>) {
+        self.source.map_paths(map);
+    }
+}
+
+#[cfg(feature = "requests")]
+impl MapPaths for Request {
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>) {
+        match self {
+            Request::BreakpointLocations(arguments) => arguments.source.map_paths(map),
+            Request::GotoTargets(arguments) => arguments.source.map_paths(map),
+            Request::SetBreakpoints(arguments) => arguments.source.map_paths(map),
+            Request::Source(arguments) => arguments.source.map_paths(map),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(feature = "responses")]
+impl MapPaths for Response {
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>) {
+        let body = match &mut self.result {
+            Ok(body) => body,
+            Err(_) => return,
+        };
+        match body {
+            SuccessResponse::Disassemble(body) => body.instructions.map_paths(map),
+            SuccessResponse::LoadedSources(body) => body.sources.map_paths(map),
+            #[cfg(feature = "spec-1_70")]
+            SuccessResponse::Locations(body) => body.source.map_paths(map),
+            SuccessResponse::Scopes(body) => body.scopes.map_paths(map),
+            SuccessResponse::SetBreakpoints(body) => body.breakpoints.map_paths(map),
+            SuccessResponse::SetDataBreakpoints(body) => body.breakpoints.map_paths(map),
+            SuccessResponse::SetExceptionBreakpoints(body) => body.breakpoints.map_paths(map),
+            SuccessResponse::SetFunctionBreakpoints(body) => body.breakpoints.map_paths(map),
+            SuccessResponse::SetInstructionBreakpoints(body) => body.breakpoints.map_paths(map),
+            SuccessResponse::StackTrace(body) => body.stack_frames.map_paths(map),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(feature = "events")]
+impl MapPaths for Event {
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>) {
+        match self {
+            Event::Breakpoint(body) => body.breakpoint.map_paths(map),
+            Event::LoadedSource(body) => body.source.map_paths(map),
+            Event::Output(body) => body.source.map_paths(map),
+            _ => {}
+        }
+    }
+}
+
+impl MapPaths for ProtocolMessage {
+    fn map_paths(&mut self, map: &dyn Fn(&str) -> Option<String>) {
+        match &mut self.content {
+            #[cfg(feature = "requests")]
+            ProtocolMessageContent::Request(request) => request.map_paths(map),
+            #[cfg(feature = "responses")]
+            ProtocolMessageContent::Response(response) => response.map_paths(map),
+            #[cfg(feature = "events")]
+            ProtocolMessageContent::Event(event) => event.map_paths(map),
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Compact binary storage of recorded sessions with bincode.
+//!
+//! bincode is not self-describing, so it can't encode the flattened and internally tagged
+//! representation of a [ProtocolMessage] directly. A recording stores every message as the tree of
+//! its JSON value instead, which saves the quotes, separators and escapes of the JSON text. Use
+//! [to_wire] to convert a recording back to the wire form for replay.
+
+use crate::{de, error::ParseError, framing::write_framed, ParseOptions, ProtocolMessage};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+use std::{
+    fmt::{self, Display},
+    io::{self, BufRead, Write},
+};
+
+const CONFIG: bincode::config::Configuration = bincode::config::standard();
+
+/// A JSON value in a representation that bincode can decode.
+#[derive(Deserialize, Serialize)]
+enum StoredValue {
+    Null,
+    Bool(bool),
+    PosInt(u64),
+    NegInt(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<StoredValue>),
+    Object(Vec<(String, StoredValue)>),
+}
+
+impl From<Value> for StoredValue {
+    fn from(value: Value) -> StoredValue {
+        match value {
+            Value::Null => StoredValue::Null,
+            Value::Bool(value) => StoredValue::Bool(value),
+            Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+                (Some(value), _) => StoredValue::PosInt(value),
+                (None, Some(value)) => StoredValue::NegInt(value),
+                (None, None) => StoredValue::Float(number.as_f64().unwrap_or_default()),
+            },
+            Value::String(value) => StoredValue::String(value),
+            Value::Array(values) => {
+                StoredValue::Array(values.into_iter().map(Into::into).collect())
+            }
+            Value::Object(map) => StoredValue::Object(
+                map.into_iter()
+                    .map(|(key, value)| (key, value.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<StoredValue> for Value {
+    fn from(value: StoredValue) -> Value {
+        match value {
+            StoredValue::Null => Value::Null,
+            StoredValue::Bool(value) => Value::Bool(value),
+            StoredValue::PosInt(value) => value.into(),
+            StoredValue::NegInt(value) => value.into(),
+            StoredValue::Float(value) => Number::from_f64(value).map_or(Value::Null, Value::Number),
+            StoredValue::String(value) => Value::String(value),
+            StoredValue::Array(values) => {
+                Value::Array(values.into_iter().map(Into::into).collect())
+            }
+            StoredValue::Object(entries) => Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into()))
+                    .collect::<Map<_, _>>(),
+            ),
+        }
+    }
+}
+
+/// An error reading or writing a recording.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RecordingError {
+    Io(io::Error),
+    Encode(bincode::error::EncodeError),
+    Decode(bincode::error::DecodeError),
+    /// A stored message is not a valid protocol message.
+    Parse(ParseError),
+}
+
+impl Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordingError::Io(error) => write!(f, "failed to access recording: {}", error),
+            RecordingError::Encode(error) => write!(f, "failed to encode message: {}", error),
+            RecordingError::Decode(error) => write!(f, "failed to decode message: {}", error),
+            RecordingError::Parse(error) => write!(f, "invalid recorded message: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RecordingError::Io(error) => Some(error),
+            RecordingError::Encode(error) => Some(error),
+            RecordingError::Decode(error) => Some(error),
+            RecordingError::Parse(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for RecordingError {
+    fn from(error: io::Error) -> RecordingError {
+        RecordingError::Io(error)
+    }
+}
+
+/// Appends `message` to a recording.
+pub fn write_message<W: Write>(
+    mut writer: W,
+    message: &ProtocolMessage,
+) -> Result<(), RecordingError> {
+    let value = serde_json::to_value(message).map_err(|error| {
+        RecordingError::Encode(bincode::error::EncodeError::OtherString(error.to_string()))
+    })?;
+    bincode::serde::encode_into_std_write(StoredValue::from(value), &mut writer, CONFIG)
+        .map_err(RecordingError::Encode)?;
+    Ok(())
+}
+
+/// Reads the next message of a recording. Returns `None` at the end of the recording.
+pub fn read_message<R: BufRead>(mut reader: R) -> Result<Option<ProtocolMessage>, RecordingError> {
+    if reader.fill_buf()?.is_empty() {
+        return Ok(None);
+    }
+    let value: StoredValue = bincode::serde::decode_from_std_read(&mut reader, CONFIG)
+        .map_err(RecordingError::Decode)?;
+    de::from_value(&value.into(), &ParseOptions::default())
+        .map(Some)
+        .map_err(RecordingError::Parse)
+}
+
+/// Converts a recording to the wire form, every message with its Content-Length header. Returns
+/// the number of converted messages.
+pub fn to_wire<R: BufRead, W: Write>(
+    mut reader: R,
+    mut writer: W,
+) -> Result<usize, RecordingError> {
+    let mut count = 0;
+    while let Some(message) = read_message(&mut reader)? {
+        write_framed(&mut writer, &message)?;
+        count += 1;
+    }
+    Ok(count)
+}
@@ -0,0 +1,79 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::{self, Debug};
+
+/// The replacement for redacted user data.
+pub const REDACTED: &str = "<redacted>";
+
+/// Attributes whose string values are defined by the specification or the debug adapter and don't
+/// contain user data.
+const SAFE_ATTRIBUTES: &[&str] = &[
+    "accessType",
+    "adapterID",
+    "algorithm",
+    "attributes",
+    "breakMode",
+    "category",
+    "clientID",
+    "clientName",
+    "command",
+    "context",
+    "event",
+    "filter",
+    "filters",
+    "format",
+    "granularity",
+    "group",
+    "kind",
+    "locale",
+    "pathFormat",
+    "presentationHint",
+    "reason",
+    "startMethod",
+    "visibility",
+];
+
+/// Formats the wrapped value as JSON with all user data redacted, so protocol traffic can be logged
+/// without leaking user paths and values.
+///
+/// String values are replaced by [REDACTED] unless they belong to an attribute that only holds
+/// values defined by the specification like 'command' or 'reason'. Following the convention of
+/// [Message](crate::types::Message), variables whose name starts with an underscore are kept and
+/// all other variables are redacted, whatever their name. Numbers and booleans are kept.
+pub struct Redacted<'a, T>(pub &'a T);
+
+impl<T: Serialize> Debug for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut value = serde_json::to_value(self.0).map_err(|_| fmt::Error)?;
+        redact(&mut value, None);
+        write!(f, "{}", value)
+    }
+}
+
+fn redact(value: &mut Value, attribute: Option<&str>) {
+    match value {
+        Value::String(string) => {
+            if !attribute.is_some_and(|attribute| SAFE_ATTRIBUTES.contains(&attribute)) {
+                *string = REDACTED.to_string();
+            }
+        }
+        Value::Array(array) => {
+            for value in array {
+                redact(value, attribute);
+            }
+        }
+        Value::Object(object) => {
+            let variables = attribute == Some("variables");
+            for (key, value) in object {
+                if !variables {
+                    redact(value, Some(key));
+                } else if !key.starts_with('_') {
+                    // The names of variables are chosen freely, so they don't tell whether the
+                    // value is safe
+                    redact(value, None);
+                }
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
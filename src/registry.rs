@@ -0,0 +1,172 @@
+//! A registry of debug adapters, so that one host process can serve several debugger backends.
+//!
+//! Every debug adapter is registered under the `adapterID` that clients send in the 'initialize'
+//! request. [AdapterRegistry::serve] receives that request from a [Transport] and hands the
+//! session to the factory registered under its `adapterID`, which instantiates the debug adapter
+//! and serves the rest of the session.
+
+use crate::{
+    requests::{InitializeRequestArguments, Request},
+    responses::{ErrorResponse, Response},
+    transport::Transport,
+    ProtocolMessage, ProtocolMessageContent, SequenceNumber, Str,
+};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    io,
+};
+
+/// Creates a debug adapter for a session and serves it.
+pub trait AdapterFactory {
+    /// Serves a session whose 'initialize' request was already received. `seq` is the sequence
+    /// number of that request, which the debug adapter must still respond to.
+    fn serve(
+        &self,
+        seq: SequenceNumber,
+        arguments: InitializeRequestArguments,
+        transport: &mut dyn Transport,
+    ) -> io::Result<()>;
+}
+
+impl<F> AdapterFactory for F
+where
+    F: Fn(SequenceNumber, InitializeRequestArguments, &mut dyn Transport) -> io::Result<()>,
+{
+    fn serve(
+        &self,
+        seq: SequenceNumber,
+        arguments: InitializeRequestArguments,
+        transport: &mut dyn Transport,
+    ) -> io::Result<()> {
+        self(seq, arguments, transport)
+    }
+}
+
+/// Why [AdapterRegistry::serve] could not serve a session.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ServeError {
+    /// The transport failed.
+    Io(io::Error),
+
+    /// The first message of the session was not an 'initialize' request.
+    ExpectedInitialize { seq: SequenceNumber },
+
+    /// No debug adapter is registered under the `adapterID` of the 'initialize' request. The
+    /// request was answered with an error response.
+    UnknownAdapter { adapter_id: Str },
+}
+
+impl Display for ServeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServeError::Io(error) => error.fmt(f),
+            ServeError::ExpectedInitialize { seq } => write!(
+                f,
+                "expected an 'initialize' request, but message {} is something else",
+                seq
+            ),
+            ServeError::UnknownAdapter { adapter_id } => {
+                write!(f, "no debug adapter registered for '{}'", adapter_id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ServeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ServeError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ServeError {
+    fn from(error: io::Error) -> Self {
+        ServeError::Io(error)
+    }
+}
+
+/// The debug adapters of a host process by their `adapterID`. See the
+/// [module documentation](self).
+#[derive(Default)]
+pub struct AdapterRegistry {
+    factories: BTreeMap<String, Box<dyn AdapterFactory + Send + Sync>>,
+}
+
+impl fmt::Debug for AdapterRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdapterRegistry")
+            .field("adapter_ids", &self.factories.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl AdapterRegistry {
+    pub fn new() -> AdapterRegistry {
+        AdapterRegistry::default()
+    }
+
+    /// Registers `factory` under `adapter_id`. Returns whether a factory was registered under it
+    /// before, which is replaced.
+    pub fn register(
+        &mut self,
+        adapter_id: impl Into<String>,
+        factory: impl AdapterFactory + Send + Sync + 'static,
+    ) -> bool {
+        self.factories
+            .insert(adapter_id.into(), Box::new(factory))
+            .is_some()
+    }
+
+    /// Removes the factory registered under `adapter_id`. Returns whether there was one.
+    pub fn unregister(&mut self, adapter_id: &str) -> bool {
+        self.factories.remove(adapter_id).is_some()
+    }
+
+    /// Whether a factory is registered under `adapter_id`.
+    pub fn contains(&self, adapter_id: &str) -> bool {
+        self.factories.contains_key(adapter_id)
+    }
+
+    /// The registered `adapterID`s in alphabetical order.
+    pub fn adapter_ids(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+
+    /// Serves a session on `transport`: receives the 'initialize' request and hands the session to
+    /// the factory registered under its `adapterID`. Returns once the factory returns, or right
+    /// away if the transport closes before the first message.
+    pub fn serve(&self, transport: &mut dyn Transport) -> Result<(), ServeError> {
+        let message = match transport.receive()? {
+            Some(message) => message,
+            None => return Ok(()),
+        };
+        let arguments = match message.content {
+            ProtocolMessageContent::Request(Request::Initialize(arguments)) => *arguments,
+            _ => return Err(ServeError::ExpectedInitialize { seq: message.seq }),
+        };
+        match self.factories.get(&*arguments.adapter_id) {
+            Some(factory) => Ok(factory.serve(message.seq, arguments, transport)?),
+            None => {
+                let error = ErrorResponse {
+                    command: Str::from("initialize"),
+                    message: Str::from(format!(
+                        "no debug adapter registered for '{}'",
+                        arguments.adapter_id
+                    )),
+                    body: Box::default(),
+                };
+                transport.send(ProtocolMessage::new(
+                    1,
+                    Response::new(message.seq, Err(error)),
+                ))?;
+                Err(ServeError::UnknownAdapter {
+                    adapter_id: arguments.adapter_id,
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! A client side model of a debug console, which evaluates the expressions the user enters.
+//!
+//! A [Repl] creates the requests a debug console needs and keeps their results:
+//!
+//! * [Repl::complete] creates a 'completions' request, which clients usually send when
+//!   [Repl::is_trigger] reports that the user typed one of the `completionTriggerCharacters` of
+//!   the debug adapter. [Repl::apply_completion] inserts a proposal into the input.
+//! * [Repl::submit] adds the input to the history and creates an 'evaluate' request in the `repl`
+//!   context, whose result [Repl::handle_evaluate] stores in the [entries](Repl::entries).
+//! * [Repl::expand] creates the 'variables' request for the children of a structured result,
+//!   which [Repl::handle_variables] stores.
+//!
+//! Columns are measured in UTF-16 code units, like in the rest of the protocol, while the cursor
+//! positions taken and returned by the [Repl] are byte offsets into the input.
+
+use crate::{
+    requests::{
+        CompletionsRequestArguments, EvaluateRequestArguments, EvaluateRequestContext,
+        InitializeRequestArguments, VariablesRequestArguments,
+    },
+    responses::{ErrorResponse, EvaluateResponseBody, SuccessResponse},
+    types::{Capabilities, CompletionItem, Variable},
+    watch::error_message,
+    Str,
+};
+use std::collections::HashMap;
+
+/// An expression entered into the debug console and its result.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReplEntry {
+    pub expression: Str,
+
+    /// `None` while the 'evaluate' request is outstanding, otherwise the response body or the
+    /// error message.
+    pub result: Option<Result<Box<EvaluateResponseBody>, Str>>,
+}
+
+/// The state of a debug console. See the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct Repl {
+    lines_start_at_1: bool,
+    columns_start_at_1: bool,
+    supports_completions: bool,
+    trigger_characters: Vec<Str>,
+    frame_id: Option<i32>,
+    history: Vec<Str>,
+    history_position: usize,
+    entries: Vec<ReplEntry>,
+    children: HashMap<i32, Vec<Variable>>,
+}
+
+impl Default for Repl {
+    fn default() -> Repl {
+        Repl {
+            lines_start_at_1: true,
+            columns_start_at_1: true,
+            supports_completions: false,
+            trigger_characters: vec![Str::from(".")],
+            frame_id: None,
+            history: Vec::new(),
+            history_position: 0,
+            entries: Vec::new(),
+            children: HashMap::new(),
+        }
+    }
+}
+
+impl Repl {
+    pub fn new() -> Repl {
+        Repl::default()
+    }
+
+    /// Takes whether lines and columns start at 1 from the 'initialize' request of the client.
+    pub fn set_client(&mut self, arguments: &InitializeRequestArguments) {
+        self.lines_start_at_1 = arguments.lines_start_at_1;
+        self.columns_start_at_1 = arguments.columns_start_at_1;
+    }
+
+    /// Takes whether completions are supported and which characters trigger them from the
+    /// capabilities of the debug adapter. Without `completionTriggerCharacters`, '.' triggers them.
+    pub fn set_capabilities(&mut self, capabilities: &Capabilities) {
+        self.supports_completions = capabilities.supports_completions_request();
+        self.trigger_characters = match &capabilities.completion_trigger_characters {
+            Some(characters) => characters.clone(),
+            None => vec![Str::from(".")],
+        };
+    }
+
+    /// Selects the frame to evaluate and complete expressions in, or `None` for the global scope.
+    pub fn select_frame(&mut self, frame_id: Option<i32>) {
+        self.frame_id = frame_id;
+    }
+
+    /// Whether the input before the cursor ends with a character that triggers completions.
+    pub fn is_trigger(&self, before_cursor: &str) -> bool {
+        self.trigger_characters
+            .iter()
+            .any(|trigger| !trigger.is_empty() && before_cursor.ends_with(&**trigger))
+    }
+
+    /// Creates the request for the completions at the byte offset `cursor` of `text`, or `None` if
+    /// the debug adapter does not support completions.
+    pub fn complete(&self, text: &str, cursor: usize) -> Option<CompletionsRequestArguments> {
+        if !self.supports_completions {
+            return None;
+        }
+        let (line, line_start) = line_of(text, cursor);
+        let column = utf16_len(&text[line_start..cursor]);
+        Some(CompletionsRequestArguments {
+            frame_id: self.frame_id,
+            text: Str::from(text),
+            column: column + i32::from(self.columns_start_at_1),
+            line: if line == 0 && !text.contains('\n') {
+                None
+            } else {
+                Some(line + i32::from(self.lines_start_at_1))
+            },
+        })
+    }
+
+    /// Inserts `item`, a proposal for the completions at the byte offset `cursor` of `text`.
+    /// Returns the new text and the byte offset of the new cursor, which selects nothing or the
+    /// start of the selection of `item`.
+    pub fn apply_completion(
+        &self,
+        text: &str,
+        cursor: usize,
+        item: &CompletionItem,
+    ) -> (String, usize) {
+        let (_, line_start) = line_of(text, cursor);
+        let line_end = text[line_start..]
+            .find('\n')
+            .map_or(text.len(), |end| line_start + end);
+        let line = &text[line_start..line_end];
+        let start = match item.start {
+            Some(start) => {
+                let start = start - i32::from(self.columns_start_at_1);
+                line_start + byte_offset(line, start)
+            }
+            None => cursor,
+        };
+        let end = start + byte_offset(&text[start..line_end], item.length);
+        let insert = item.text.as_deref().unwrap_or(&item.label);
+        let cursor = start
+            + match item.selection_start {
+                Some(selection_start) => byte_offset(insert, selection_start),
+                None => insert.len(),
+            };
+        let mut completed = String::with_capacity(text.len() + insert.len());
+        completed.push_str(&text[..start]);
+        completed.push_str(insert);
+        completed.push_str(&text[end..]);
+        (completed, cursor)
+    }
+
+    /// Adds `expression` to the history and the entries, and creates the request to evaluate it.
+    pub fn submit(&mut self, expression: impl Into<Str>) -> EvaluateRequestArguments {
+        let expression = expression.into();
+        if !expression.trim().is_empty() && self.history.last() != Some(&expression) {
+            self.history.push(expression.clone());
+        }
+        self.history_position = self.history.len();
+        self.entries.push(ReplEntry {
+            expression: expression.clone(),
+            result: None,
+        });
+        EvaluateRequestArguments {
+            expression,
+            frame_id: self.frame_id,
+            context: Some(EvaluateRequestContext::REPL),
+            format: None,
+        }
+    }
+
+    /// Moves back in the history and returns the expression to show, or `None` at its start.
+    pub fn history_previous(&mut self) -> Option<&str> {
+        self.history_position = self.history_position.checked_sub(1)?;
+        self.history.get(self.history_position).map(|it| &**it)
+    }
+
+    /// Moves forward in the history and returns the expression to show, or `None` after its end,
+    /// where the input is empty.
+    pub fn history_next(&mut self) -> Option<&str> {
+        self.history_position = (self.history_position + 1).min(self.history.len());
+        self.history.get(self.history_position).map(|it| &**it)
+    }
+
+    /// The expressions entered so far, oldest first.
+    pub fn history(&self) -> &[Str] {
+        &self.history
+    }
+
+    /// Stores the result of `request` in the oldest pending entry of its expression. Returns
+    /// whether there was one.
+    pub fn handle_evaluate(
+        &mut self,
+        request: &EvaluateRequestArguments,
+        result: &Result<SuccessResponse, ErrorResponse>,
+    ) -> bool {
+        let result = match result {
+            Ok(SuccessResponse::Evaluate(body)) => Ok(body.clone()),
+            Ok(_) => return false,
+            Err(error) => Err(error_message(error)),
+        };
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.result.is_none() && entry.expression == request.expression)
+        {
+            Some(entry) => {
+                entry.result = Some(result);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The expressions entered so far and their results, oldest first.
+    pub fn entries(&self) -> &[ReplEntry] {
+        &self.entries
+    }
+
+    /// Forgets the entries, but not the history.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.children.clear();
+    }
+
+    /// Creates the request for the children of a result or variable with `variables_reference`,
+    /// or `None` if it has no children or they were received already.
+    pub fn expand(&self, variables_reference: i32) -> Option<VariablesRequestArguments> {
+        if variables_reference <= 0 || self.children.contains_key(&variables_reference) {
+            return None;
+        }
+        Some(VariablesRequestArguments {
+            variables_reference,
+            filter: None,
+            start: 0,
+            count: 0,
+            format: None,
+        })
+    }
+
+    /// Stores the children that answer `request`. Returns whether `result` is a 'variables'
+    /// response.
+    pub fn handle_variables(
+        &mut self,
+        request: &VariablesRequestArguments,
+        result: &Result<SuccessResponse, ErrorResponse>,
+    ) -> bool {
+        let variables = match result {
+            Ok(SuccessResponse::Variables(body)) => body.variables.clone(),
+            Ok(_) => return false,
+            Err(_) => Vec::new(),
+        };
+        self.children.insert(request.variables_reference, variables);
+        true
+    }
+
+    /// The children of the result or variable with `variables_reference`, if they were received.
+    pub fn children(&self, variables_reference: i32) -> Option<&[Variable]> {
+        self.children.get(&variables_reference).map(Vec::as_slice)
+    }
+
+    /// Forgets the children of all results. Variable references are only valid while the debuggee
+    /// is suspended, so call this when it continues.
+    pub fn clear_variables(&mut self) {
+        self.children.clear();
+    }
+}
+
+/// The zero based line of the byte offset `cursor` of `text` and the byte offset where it starts.
+fn line_of(text: &str, cursor: usize) -> (i32, usize) {
+    let before = &text[..cursor];
+    let line = before.matches('\n').count();
+    let start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    (i32::try_from(line).unwrap_or(i32::MAX), start)
+}
+
+fn utf16_len(text: &str) -> i32 {
+    i32::try_from(text.encode_utf16().count()).unwrap_or(i32::MAX)
+}
+
+/// The byte offset of the zero based column `units` of `text`, measured in UTF-16 code units.
+fn byte_offset(text: &str, units: i32) -> usize {
+    let mut count = 0;
+    for (offset, c) in text.char_indices() {
+        if count >= units {
+            return offset;
+        }
+        count += c.len_utf16() as i32;
+    }
+    text.len()
+}
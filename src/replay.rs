@@ -0,0 +1,342 @@
+//! Replays a recorded session against a debug adapter, for regression testing adapters against
+//! real captured sessions.
+//!
+//! The recording is a list of the messages of both sides, for example read from a recording of
+//! the `bincode` feature. [replay] sends the messages of the client to the debug adapter and
+//! compares what the debug adapter sends back with the rest of the recording.
+//! Sequence numbers are never compared, since they differ between sessions. Other volatile
+//! properties like process ids can be ignored with [ReplayOptions].
+//!
+//! Messages of the client are requests, except the reverse request 'runInTerminal', and the
+//! responses to reverse requests. Everything else is expected from the debug adapter. A recorded
+//! message of the debug adapter is matched with the first received message of the same kind and
+//! command or event, so messages that arrive in a different order than recorded don't fail the
+//! replay.
+
+use crate::{
+    connection::Connection, requests::Request, utils::json_differences, ProtocolMessage,
+    ProtocolMessageContent, SequenceNumber, Str,
+};
+use crossbeam_channel::RecvTimeoutError;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    time::Duration,
+};
+
+/// What a [replay] tolerates.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ReplayOptions {
+    /// Properties that are not compared at any depth, like `systemProcessId`.
+    pub ignored_properties: Vec<String>,
+
+    /// Events that are neither expected nor reported if unexpected, like `output`.
+    pub ignored_events: Vec<String>,
+
+    /// How long to wait for each message of the debug adapter before reporting it as missing.
+    pub timeout: Duration,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> ReplayOptions {
+        ReplayOptions {
+            ignored_properties: Vec::new(),
+            ignored_events: Vec::new(),
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// A property that differs between a recorded and a received message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difference {
+    /// The JSON pointer of the property, like `/body/threads/0/name`.
+    pub path: String,
+
+    /// The recorded value, or `None` if the property was not recorded.
+    pub expected: Option<Value>,
+
+    /// The received value, or `None` if the property was not received.
+    pub actual: Option<Value>,
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn format(value: &Option<Value>) -> String {
+            value
+                .as_ref()
+                .map_or("nothing".to_string(), Value::to_string)
+        }
+        write!(
+            f,
+            "{}: expected {} but got {}",
+            self.path,
+            format(&self.expected),
+            format(&self.actual)
+        )
+    }
+}
+
+/// A recorded message of the debug adapter that was received with different content.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    pub expected: ProtocolMessage,
+    pub actual: ProtocolMessage,
+    pub differences: Vec<Difference>,
+}
+
+/// The outcome of a [replay].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReplayReport {
+    /// The number of recorded messages of the debug adapter that were received unchanged.
+    pub matched: usize,
+
+    pub mismatches: Vec<Mismatch>,
+
+    /// Recorded messages of the debug adapter that were not received.
+    pub missing: Vec<ProtocolMessage>,
+
+    /// Received messages that are not in the recording.
+    pub unexpected: Vec<ProtocolMessage>,
+}
+
+impl ReplayReport {
+    /// Whether the debug adapter behaved as recorded.
+    pub fn is_success(&self) -> bool {
+        self.mismatches.is_empty() && self.missing.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+impl Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} messages matched", self.matched)?;
+        for mismatch in &self.mismatches {
+            writeln!(f, "mismatch in {}", describe(&mismatch.expected))?;
+            for difference in &mismatch.differences {
+                writeln!(f, "  {}", difference)?;
+            }
+        }
+        for message in &self.missing {
+            writeln!(f, "missing {}", describe(message))?;
+        }
+        for message in &self.unexpected {
+            writeln!(f, "unexpected {}", describe(message))?;
+        }
+        Ok(())
+    }
+}
+
+fn describe(message: &ProtocolMessage) -> String {
+    match &message.content {
+        ProtocolMessageContent::Request(request) => format!("'{}' request", request.command()),
+        ProtocolMessageContent::Response(response) => {
+            format!("response to '{}'", response.command())
+        }
+        ProtocolMessageContent::Event(event) => format!("'{}' event", event.event()),
+    }
+}
+
+fn is_reverse_request(request: &Request) -> bool {
+    matches!(request, Request::RunInTerminal(_))
+}
+
+/// Replays the client side of `recording` to the debug adapter at the other end of `connection`
+/// and compares its messages with the recording. See the [module documentation](self).
+///
+/// A replay stops early if the debug adapter closes the connection, reporting the remaining
+/// messages as missing. Messages that arrive after the last recorded one are not awaited.
+pub fn replay(
+    connection: &Connection,
+    recording: Vec<ProtocolMessage>,
+    options: &ReplayOptions,
+) -> ReplayReport {
+    Replay {
+        connection,
+        options,
+        next_seq: 1,
+        requests: HashMap::new(),
+        reverse_requests: HashMap::new(),
+        received: Vec::new(),
+        disconnected: false,
+        report: ReplayReport::default(),
+    }
+    .run(recording)
+}
+
+struct Replay<'a> {
+    connection: &'a Connection,
+    options: &'a ReplayOptions,
+    next_seq: SequenceNumber,
+    /// Maps the recorded to the sent sequence numbers of requests of the client.
+    requests: HashMap<SequenceNumber, SequenceNumber>,
+    /// Maps the recorded sequence numbers of unanswered reverse requests to their command and
+    /// received sequence number.
+    reverse_requests: HashMap<SequenceNumber, (Str, Option<SequenceNumber>)>,
+    /// Received messages that were not matched yet.
+    received: Vec<ProtocolMessage>,
+    disconnected: bool,
+    report: ReplayReport,
+}
+
+impl Replay<'_> {
+    fn run(mut self, recording: Vec<ProtocolMessage>) -> ReplayReport {
+        for message in recording {
+            if self.is_ignored(&message) {
+                continue;
+            }
+            if self.is_from_client(&message) {
+                self.send(message);
+            } else {
+                self.expect(message);
+            }
+        }
+        let received = std::mem::take(&mut self.received);
+        let unexpected = received
+            .into_iter()
+            .filter(|message| !self.is_ignored(message))
+            .collect::<Vec<_>>();
+        self.report.unexpected.extend(unexpected);
+        self.report
+    }
+
+    fn is_ignored(&self, message: &ProtocolMessage) -> bool {
+        match &message.content {
+            ProtocolMessageContent::Event(event) => self
+                .options
+                .ignored_events
+                .iter()
+                .any(|ignored| ignored == event.event()),
+            _ => false,
+        }
+    }
+
+    fn is_from_client(&self, message: &ProtocolMessage) -> bool {
+        match &message.content {
+            ProtocolMessageContent::Request(request) => !is_reverse_request(request),
+            ProtocolMessageContent::Response(response) => self
+                .reverse_requests
+                .get(&response.request_seq)
+                .is_some_and(|(command, _)| &**command == response.command()),
+            ProtocolMessageContent::Event(_) => false,
+        }
+    }
+
+    fn send(&mut self, message: ProtocolMessage) {
+        if self.disconnected {
+            return;
+        }
+        let mut content = message.content;
+        match &mut content {
+            ProtocolMessageContent::Request(_) => {
+                self.requests.insert(message.seq, self.next_seq);
+            }
+            ProtocolMessageContent::Response(response) => {
+                let received = self.reverse_requests.remove(&response.request_seq);
+                if let Some((_, Some(seq))) = received {
+                    response.request_seq = seq;
+                }
+            }
+            ProtocolMessageContent::Event(_) => {}
+        }
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let message = ProtocolMessage::new(seq, content);
+        if self.connection.sender.send(message).is_err() {
+            self.disconnected = true;
+        }
+    }
+
+    fn expect(&mut self, expected: ProtocolMessage) {
+        if let ProtocolMessageContent::Request(request) = &expected.content {
+            self.reverse_requests
+                .insert(expected.seq, (request.command().into(), None));
+        }
+        let actual = match self.receive_matching(&expected) {
+            Some(actual) => actual,
+            None => {
+                self.report.missing.push(expected);
+                return;
+            }
+        };
+        if let ProtocolMessageContent::Request(request) = &actual.content {
+            self.reverse_requests
+                .insert(expected.seq, (request.command().into(), Some(actual.seq)));
+        }
+
+        let mut differences = Vec::new();
+        json_differences(
+            &self.comparable(&expected),
+            &self.comparable(&actual),
+            &self.options.ignored_properties,
+            &mut |path, expected, actual| {
+                differences.push(Difference {
+                    path: path.to_string(),
+                    expected: expected.cloned(),
+                    actual: actual.cloned(),
+                })
+            },
+        );
+        if differences.is_empty() {
+            self.report.matched += 1;
+        } else {
+            self.report.mismatches.push(Mismatch {
+                expected,
+                actual,
+                differences,
+            });
+        }
+    }
+
+    /// Takes the first received message that corresponds to `expected`, receiving more until one
+    /// arrives.
+    fn receive_matching(&mut self, expected: &ProtocolMessage) -> Option<ProtocolMessage> {
+        if let Some(index) = self
+            .received
+            .iter()
+            .position(|actual| self.corresponds(expected, actual))
+        {
+            return Some(self.received.remove(index));
+        }
+        while !self.disconnected {
+            match self.connection.receiver.recv_timeout(self.options.timeout) {
+                Ok(actual) if self.corresponds(expected, &actual) => return Some(actual),
+                Ok(actual) => self.received.push(actual),
+                Err(RecvTimeoutError::Timeout) => return None,
+                Err(RecvTimeoutError::Disconnected) => self.disconnected = true,
+            }
+        }
+        None
+    }
+
+    fn corresponds(&self, expected: &ProtocolMessage, actual: &ProtocolMessage) -> bool {
+        match (&expected.content, &actual.content) {
+            (
+                ProtocolMessageContent::Request(expected),
+                ProtocolMessageContent::Request(actual),
+            ) => expected.command() == actual.command(),
+            (
+                ProtocolMessageContent::Response(expected),
+                ProtocolMessageContent::Response(actual),
+            ) => {
+                expected.command() == actual.command()
+                    && self.requests.get(&expected.request_seq) == Some(&actual.request_seq)
+            }
+            (ProtocolMessageContent::Event(expected), ProtocolMessageContent::Event(actual)) => {
+                expected.event() == actual.event()
+            }
+            _ => false,
+        }
+    }
+
+    /// The JSON of `message` without its sequence numbers, which differ between sessions.
+    fn comparable(&self, message: &ProtocolMessage) -> Value {
+        let mut value = serde_json::to_value(message).expect("failed to serialize message");
+        if let Value::Object(object) = &mut value {
+            object.remove("seq");
+            object.remove("request_seq");
+        }
+        value
+    }
+}
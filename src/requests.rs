@@ -1,30 +1,56 @@
+#[cfg(feature = "de")]
+use crate::dispatch::{TagDispatch, Variants};
+#[cfg(any(feature = "de", feature = "schemars"))]
+use crate::utils::true_;
+#[cfg(any(feature = "ser", feature = "schemars"))]
+use crate::utils::{eq_default, is_none_or_empty, is_true};
 use crate::{
     types::{
         DataBreakpoint, ExceptionFilterOptions, ExceptionOptions, FunctionBreakpoint,
         InstructionBreakpoint, Source, SourceBreakpoint, StackFrameFormat, SteppingGranularity,
         ValueFormat,
     },
-    utils::{eq_default, true_},
-    ProtocolMessageContent,
+    ProtocolMessageContent, SmallVec, Str,
 };
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "ser")]
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+#[cfg(feature = "de")]
+use serde::{Deserialize, Deserializer};
 use serde_json::{Map, Value};
 use std::collections::HashMap;
+#[cfg(feature = "ts-rs")]
+use ts_rs::TS;
+#[cfg(feature = "builders")]
 use typed_builder::TypedBuilder;
 
 /// A client or debug adapter initiated request.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(rename_all = "camelCase", tag = "command", content = "arguments")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[serde(
+    remote = "Self",
+    rename_all = "camelCase",
+    tag = "command",
+    content = "arguments"
+)]
+#[non_exhaustive]
 pub enum Request {
     /// The attach request is sent from the client to the debug adapter to attach to a debuggee that is already running.
     ///
     /// Since attaching is debugger/runtime specific, the arguments for this request are not part of this specification.
-    Attach(AttachRequestArguments),
+    Attach(Box<AttachRequestArguments>),
 
     /// The 'breakpointLocations' request returns all possible locations for source breakpoints in a given range.
     ///
     /// Clients should only call this request if the capability 'supportsBreakpointLocationsRequest' is true.
-    BreakpointLocations(BreakpointLocationsRequestArguments),
+    BreakpointLocations(Box<BreakpointLocationsRequestArguments>),
 
     /// The 'cancel' request is used by the frontend in two situations:
     ///
@@ -67,7 +93,7 @@ pub enum Request {
     /// Obtains information on a possible data breakpoint that could be set on an expression or variable.
     ///
     /// Clients should only call this request if the capability 'supportsDataBreakpoints' is true.
-    DataBreakpointInfo(DataBreakpointInfoRequestArguments),
+    DataBreakpointInfo(Box<DataBreakpointInfoRequestArguments>),
 
     /// Disassembles code stored at the provided location.
     ///
@@ -111,7 +137,7 @@ pub enum Request {
     /// These targets can be used in the 'goto' request.
     ///
     /// Clients should only call this request if the capability 'supportsGotoTargetsRequest' is true.
-    GotoTargets(GotoTargetsRequestArguments),
+    GotoTargets(Box<GotoTargetsRequestArguments>),
 
     /// The 'initialize' request is sent as the first request from the client to the debug adapter
     ///
@@ -122,18 +148,22 @@ pub enum Request {
     /// In addition the debug adapter is not allowed to send any requests or events to the client until it has responded with an 'initialize' response.
     ///
     /// The 'initialize' request may only be sent once.
-    Initialize(InitializeRequestArguments),
+    Initialize(Box<InitializeRequestArguments>),
 
     /// This launch request is sent from the client to the debug adapter to start the debuggee with or without debugging (if 'noDebug' is true).
     ///
     /// Since launching is debugger/runtime specific, the arguments for this request are not part of this specification.
-    Launch(LaunchRequestArguments),
+    Launch(Box<LaunchRequestArguments>),
 
     /// Retrieves the set of all sources currently loaded by the debugged process.
     ///
     /// Clients should only call this request if the capability 'supportsLoadedSourcesRequest' is true.
     LoadedSources,
 
+    /// Looks up information about a location reference previously returned by the debug adapter.
+    #[cfg(feature = "spec-1_70")]
+    Locations(LocationsRequestArguments),
+
     /// Modules can be retrieved from the debug adapter with this request which can either return all modules or a range of modules to support paging.
     ///
     /// Clients should only call this request if the capability 'supportsModulesRequest' is true.
@@ -175,7 +205,7 @@ pub enum Request {
     /// This is typically used to launch the debuggee in a terminal provided by the client.
     ///
     /// This request should only be called if the client has passed the value true for the 'supportsRunInTerminalRequest' capability of the 'initialize' request.
-    RunInTerminal(RunInTerminalRequestArguments),
+    RunInTerminal(Box<RunInTerminalRequestArguments>),
 
     /// The request returns the variable scopes for a given stackframe ID.
     Scopes(ScopesRequestArguments),
@@ -185,7 +215,7 @@ pub enum Request {
     /// To clear all breakpoint for a source, specify an empty array.
     ///
     /// When a breakpoint is hit, a 'stopped' event (with reason 'breakpoint') is generated.
-    SetBreakpoints(SetBreakpointsRequestArguments),
+    SetBreakpoints(Box<SetBreakpointsRequestArguments>),
 
     /// Replaces all existing data breakpoints with new data breakpoints.
     ///
@@ -236,7 +266,7 @@ pub enum Request {
     SetVariable(SetVariableRequestArguments),
 
     /// The request retrieves the source code for a given source reference.
-    Source(SourceRequestArguments),
+    Source(Box<SourceRequestArguments>),
 
     /// The request returns a stacktrace from the current execution state of a given thread.
     ///
@@ -294,14 +324,173 @@ pub enum Request {
     ///
     /// An optional filter can be used to limit the fetched children to either named or indexed children.
     Variables(VariablesRequestArguments),
+
+    /// A request with a command that is not part of the specification, like the extensions of some
+    /// debug adapters. Requests with an unknown command deserialize as this variant.
+    #[cfg_attr(feature = "ser", serde(skip_serializing))]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    #[cfg_attr(feature = "ts-rs", ts(skip))]
+    Custom {
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))]
+        command: Str,
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_boxed_value))]
+        arguments: Option<Box<Value>>,
+    },
 }
+
+// Large variants are boxed to keep every message small
+const _: () = assert!(std::mem::size_of::<Request>() <= 80);
+impl Request {
+    /// The command of this request as it is sent in the 'command' attribute.
+    pub fn command(&self) -> &str {
+        match self {
+            Request::Attach(_) => "attach",
+            Request::BreakpointLocations(_) => "breakpointLocations",
+            Request::Cancel(_) => "cancel",
+            Request::Completions(_) => "completions",
+            Request::ConfigurationDone => "configurationDone",
+            Request::Continue(_) => "continue",
+            Request::DataBreakpointInfo(_) => "dataBreakpointInfo",
+            Request::Disassemble(_) => "disassemble",
+            Request::Disconnect(_) => "disconnect",
+            Request::Evaluate(_) => "evaluate",
+            Request::ExceptionInfo(_) => "exceptionInfo",
+            Request::Goto(_) => "goto",
+            Request::GotoTargets(_) => "gotoTargets",
+            Request::Initialize(_) => "initialize",
+            Request::Launch(_) => "launch",
+            Request::LoadedSources => "loadedSources",
+            #[cfg(feature = "spec-1_70")]
+            Request::Locations(_) => "locations",
+            Request::Modules(_) => "modules",
+            Request::Next(_) => "next",
+            Request::Pause(_) => "pause",
+            Request::ReadMemory(_) => "readMemory",
+            Request::RestartFrame(_) => "restartFrame",
+            Request::ReverseContinue(_) => "reverseContinue",
+            Request::RunInTerminal(_) => "runInTerminal",
+            Request::Scopes(_) => "scopes",
+            Request::SetBreakpoints(_) => "setBreakpoints",
+            Request::SetDataBreakpoints(_) => "setDataBreakpoints",
+            Request::SetExceptionBreakpoints(_) => "setExceptionBreakpoints",
+            Request::SetExpression(_) => "setExpression",
+            Request::SetFunctionBreakpoints(_) => "setFunctionBreakpoints",
+            Request::SetInstructionBreakpoints(_) => "setInstructionBreakpoints",
+            Request::SetVariable(_) => "setVariable",
+            Request::Source(_) => "source",
+            Request::StackTrace(_) => "stackTrace",
+            Request::StepBack(_) => "stepBack",
+            Request::StepIn(_) => "stepIn",
+            Request::StepInTargets(_) => "stepInTargets",
+            Request::StepOut(_) => "stepOut",
+            Request::Terminate(_) => "terminate",
+            Request::TerminateThreads(_) => "terminateThreads",
+            Request::Threads => "threads",
+            Request::Variables(_) => "variables",
+            Request::Custom { command, .. } => command,
+        }
+    }
+}
+
+#[cfg(feature = "ser")]
+impl Serialize for Request {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Request::Custom { command, arguments } => {
+                let mut state = serializer.serialize_struct("Request", 2)?;
+                state.serialize_field("command", command)?;
+                match arguments {
+                    Some(arguments) => state.serialize_field("arguments", arguments)?,
+                    None => state.skip_field("arguments")?,
+                }
+                state.end()
+            }
+            _ => Request::serialize(self, serializer),
+        }
+    }
+}
+
+#[cfg(feature = "de")]
+impl<'de> Deserialize<'de> for Request {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Request::deserialize(TagDispatch {
+            deserializer,
+            tag: "command",
+            content: "arguments",
+            variants: &COMMANDS,
+        })
+    }
+}
+
+/// The commands of the variants of [Request] in declaration order, followed by
+/// [Request::Custom].
+#[cfg(feature = "de")]
+pub(crate) static COMMANDS: Variants = Variants::new(&[
+    "attach",
+    "breakpointLocations",
+    "cancel",
+    "completions",
+    "configurationDone",
+    "continue",
+    "dataBreakpointInfo",
+    "disassemble",
+    "disconnect",
+    "evaluate",
+    "exceptionInfo",
+    "goto",
+    "gotoTargets",
+    "initialize",
+    "launch",
+    "loadedSources",
+    #[cfg(feature = "spec-1_70")]
+    "locations",
+    "modules",
+    "next",
+    "pause",
+    "readMemory",
+    "restartFrame",
+    "reverseContinue",
+    "runInTerminal",
+    "scopes",
+    "setBreakpoints",
+    "setDataBreakpoints",
+    "setExceptionBreakpoints",
+    "setExpression",
+    "setFunctionBreakpoints",
+    "setInstructionBreakpoints",
+    "setVariable",
+    "source",
+    "stackTrace",
+    "stepBack",
+    "stepIn",
+    "stepInTargets",
+    "stepOut",
+    "terminate",
+    "terminateThreads",
+    "threads",
+    "variables",
+]);
+
 impl From<Request> for ProtocolMessageContent {
     fn from(request: Request) -> Self {
         Self::Request(request)
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct AttachRequestArguments {
     /// Optional data from the previous, restarted session.
     ///
@@ -309,16 +498,20 @@ pub struct AttachRequestArguments {
     ///
     /// The client should leave the data intact.
     #[serde(rename = "__restart", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_value))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub restart: Option<Value>,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// Additional attributes are implementation specific.
+    #[serde(flatten)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::map))]
+    pub additional_attributes: Map<String, Value>,
 }
 impl From<AttachRequestArguments> for Request {
     fn from(args: AttachRequestArguments) -> Self {
-        Self::Attach(args)
+        Self::Attach(Box::new(args))
     }
 }
 impl From<AttachRequestArguments> for ProtocolMessageContent {
@@ -327,7 +520,14 @@ impl From<AttachRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct BreakpointLocationsRequestArguments {
     /// The source location of the breakpoints; either 'source.path' or 'source.reference' must be specified.
     #[serde(rename = "source")]
@@ -339,26 +539,36 @@ pub struct BreakpointLocationsRequestArguments {
 
     /// Optional start column of range to search possible breakpoint locations in. If no start column is given, the first column in the start line is assumed.
     #[serde(rename = "column", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub column: Option<i32>,
 
     /// Optional end line of range to search possible breakpoint locations in. If no end line is given, then the end line is assumed to be the start line.
     #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_line: Option<i32>,
 
     /// Optional end column of range to search possible breakpoint locations in. If no end column is given, then it is assumed to be in the last column of the end line.
     #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_column: Option<i32>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl BreakpointLocationsRequestArguments {
+    pub fn new(source: Source, line: i32) -> BreakpointLocationsRequestArguments {
+        BreakpointLocationsRequestArguments {
+            source,
+            line,
+            column: Default::default(),
+            end_line: Default::default(),
+            end_column: Default::default(),
+        }
+    }
 }
 impl From<BreakpointLocationsRequestArguments> for Request {
     fn from(args: BreakpointLocationsRequestArguments) -> Self {
-        Self::BreakpointLocations(args)
+        Self::BreakpointLocations(Box::new(args))
     }
 }
 impl From<BreakpointLocationsRequestArguments> for ProtocolMessageContent {
@@ -367,25 +577,30 @@ impl From<BreakpointLocationsRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct CancelRequestArguments {
     /// The ID (attribute 'seq') of the request to cancel. If missing no request is cancelled.
     ///
     /// Both a 'requestId' and a 'progressId' can be specified in one request.
     #[serde(rename = "requestId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub request_id: Option<i32>,
 
     /// The ID (attribute 'progressId') of the progress to cancel. If missing no progress is cancelled.
     ///
     /// Both a 'requestId' and a 'progressId' can be specified in one request.
     #[serde(rename = "progressId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub progress_id: Option<String>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub progress_id: Option<Str>,
 }
 impl From<CancelRequestArguments> for Request {
     fn from(args: CancelRequestArguments) -> Self {
@@ -398,16 +613,24 @@ impl From<CancelRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct CompletionsRequestArguments {
     /// Returns completions in the scope of this stack frame. If not specified, the completions are returned for the global scope.
     #[serde(rename = "frameId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub frame_id: Option<i32>,
 
     /// One or more source lines. Typically this is the text a user has typed into the debug console before he asked for completion.
     #[serde(rename = "text")]
-    pub text: String,
+    pub text: Str,
 
     /// The character position for which to determine the completion proposals.
     #[serde(rename = "column")]
@@ -415,12 +638,19 @@ pub struct CompletionsRequestArguments {
 
     /// An optional line for which to determine the completion proposals. If missing the first line of the text is assumed.
     #[serde(rename = "line", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub line: Option<i32>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl CompletionsRequestArguments {
+    pub fn new(text: Str, column: i32) -> CompletionsRequestArguments {
+        CompletionsRequestArguments {
+            frame_id: Default::default(),
+            text,
+            column,
+            line: Default::default(),
+        }
+    }
 }
 impl From<CompletionsRequestArguments> for Request {
     fn from(args: CompletionsRequestArguments) -> Self {
@@ -433,7 +663,14 @@ impl From<CompletionsRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ContinueRequestArguments {
     /// Continue execution for the specified thread (if possible).
     ///
@@ -441,9 +678,22 @@ pub struct ContinueRequestArguments {
     #[serde(rename = "threadId")]
     pub thread_id: i32,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// If this flag is true, execution is resumed only for the thread with given 'threadId'.
+    ///
+    /// The attribute is only honored by a debug adapter if the capability 'supportsSingleThreadExecutionRequests' is true.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "singleThread", default, skip_serializing_if = "eq_default")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub single_thread: bool,
+}
+impl ContinueRequestArguments {
+    pub fn new(thread_id: i32) -> ContinueRequestArguments {
+        ContinueRequestArguments {
+            thread_id,
+            #[cfg(feature = "spec-1_70")]
+            single_thread: Default::default(),
+        }
+    }
 }
 impl From<ContinueRequestArguments> for Request {
     fn from(args: ContinueRequestArguments) -> Self {
@@ -456,26 +706,77 @@ impl From<ContinueRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct DataBreakpointInfoRequestArguments {
     /// Reference to the Variable container if the data breakpoint is requested for a child of the container.
     #[serde(rename = "variablesReference", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub variables_reference: Option<i32>,
 
     /// The name of the Variable's child to obtain data breakpoint information for.
     ///
-    /// If variablesReference isn’t provided, this can be an expression.
+    /// If variablesReference isn’t provided, this can be an expression, or an address if 'asAddress' is also true.
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: Str,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// When 'name' is an expression, evaluate it in the scope of this stack frame. If not specified, the expression is evaluated in the global scope. When 'variablesReference' is specified, this property has no effect.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "frameId", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub frame_id: Option<i32>,
+
+    /// If specified, a debug adapter should return information for the range of memory extending 'bytes' number of bytes from the address or variable specified by 'name'. Breakpoints set using the resulting data ID should pause on data access anywhere within that range.
+    ///
+    /// Clients may set this property only if the 'supportsDataBreakpointBytes' capability is true.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "bytes", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub bytes: Option<i32>,
+
+    /// If 'true', the 'name' is a memory address and the debugger should interpret it as a decimal value, or hex value if it is prefixed with '0x'.
+    ///
+    /// Clients may set this property only if the 'supportsDataBreakpointBytes' capability is true.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "asAddress", default, skip_serializing_if = "eq_default")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub as_address: bool,
+
+    /// The mode of the desired breakpoint. If defined, this must be one of the 'breakpointModes' the debug adapter advertised in its 'Capabilities'.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "mode", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub mode: Option<Str>,
+}
+impl DataBreakpointInfoRequestArguments {
+    pub fn new(name: Str) -> DataBreakpointInfoRequestArguments {
+        DataBreakpointInfoRequestArguments {
+            variables_reference: Default::default(),
+            name,
+            #[cfg(feature = "spec-1_70")]
+            frame_id: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            bytes: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            as_address: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            mode: Default::default(),
+        }
+    }
 }
 impl From<DataBreakpointInfoRequestArguments> for Request {
     fn from(args: DataBreakpointInfoRequestArguments) -> Self {
-        Self::DataBreakpointInfo(args)
+        Self::DataBreakpointInfo(Box::new(args))
     }
 }
 impl From<DataBreakpointInfoRequestArguments> for ProtocolMessageContent {
@@ -484,15 +785,22 @@ impl From<DataBreakpointInfoRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct DisassembleRequestArguments {
     /// Memory reference to the base location containing the instructions to disassemble.
     #[serde(rename = "memoryReference")]
-    pub memory_reference: String,
+    pub memory_reference: Str,
 
     /// Optional offset (in bytes) to be applied to the reference location before disassembling. Can be negative.
     #[serde(rename = "offset", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub offset: i32,
 
     /// Optional offset (in instructions) to be applied after the byte offset (if any) before disassembling. Can be negative.
@@ -501,7 +809,7 @@ pub struct DisassembleRequestArguments {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub instruction_offset: i32,
 
     /// Number of instructions to disassemble starting at the specified location and offset.
@@ -512,12 +820,19 @@ pub struct DisassembleRequestArguments {
 
     /// If true, the adapter should attempt to resolve memory addresses and other values to symbolic names.
     #[serde(rename = "resolveSymbols", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub resolve_symbols: bool,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl DisassembleRequestArguments {
+    pub fn new(memory_reference: Str, instruction_count: i32) -> DisassembleRequestArguments {
+        DisassembleRequestArguments {
+            memory_reference,
+            offset: Default::default(),
+            instruction_offset: Default::default(),
+            instruction_count,
+            resolve_symbols: Default::default(),
+        }
+    }
 }
 impl From<DisassembleRequestArguments> for Request {
     fn from(args: DisassembleRequestArguments) -> Self {
@@ -530,11 +845,18 @@ impl From<DisassembleRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct DisconnectRequestArguments {
     /// A value of true indicates that this 'disconnect' request is part of a restart sequence.
     #[serde(rename = "restart", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub restart: bool,
 
     /// Indicates whether the debuggee should be terminated when the debugger is disconnected.
@@ -543,7 +865,8 @@ pub struct DisconnectRequestArguments {
     ///
     /// The attribute is only honored by a debug adapter if the capability 'supportTerminateDebuggee' is true.
     #[serde(rename = "terminateDebuggee", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub terminate_debuggee: Option<bool>,
 
     /// Indicates whether the debuggee should stay suspended when the debugger is disconnected.
@@ -556,12 +879,8 @@ pub struct DisconnectRequestArguments {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub suspend_debuggee: bool,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 impl From<DisconnectRequestArguments> for Request {
     fn from(args: DisconnectRequestArguments) -> Self {
@@ -574,32 +893,48 @@ impl From<DisconnectRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct EvaluateRequestArguments {
     /// The expression to evaluate.
     #[serde(rename = "expression")]
-    pub expression: String,
+    pub expression: Str,
 
     /// Evaluate the expression in the scope of this stack frame. If not specified, the expression is evaluated in the global scope.
     #[serde(rename = "frameId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub frame_id: Option<i32>,
 
     /// The context in which the evaluate request is run.
     #[serde(rename = "context", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub context: Option<EvaluateRequestContext>,
 
     /// Specifies details on how to format the Evaluate result.
     ///
     /// The attribute is only honored by a debug adapter if the capability 'supportsValueFormattingOptions' is true.
     #[serde(rename = "format", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub format: Option<ValueFormat>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl EvaluateRequestArguments {
+    pub fn new(expression: Str) -> EvaluateRequestArguments {
+        EvaluateRequestArguments {
+            expression,
+            frame_id: Default::default(),
+            context: Default::default(),
+            format: Default::default(),
+        }
+    }
 }
 impl From<EvaluateRequestArguments> for Request {
     fn from(args: EvaluateRequestArguments) -> Self {
@@ -612,8 +947,14 @@ impl From<EvaluateRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum EvaluateRequestContext {
     /// evaluate is run in a watch.
     Watch,
@@ -628,17 +969,33 @@ pub enum EvaluateRequestContext {
     ///
     /// The attribute is only honored by a debug adapter if the capability 'supportsClipboardContext' is true.
     Clipboard,
+
+    /// evaluate is called from a variables view context.
+    #[cfg(feature = "spec-1_70")]
+    Variables,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ExceptionInfoRequestArguments {
     /// Thread for which exception information should be retrieved.
     #[serde(rename = "threadId")]
     pub thread_id: i32,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ExceptionInfoRequestArguments {
+    pub fn new(thread_id: i32) -> ExceptionInfoRequestArguments {
+        ExceptionInfoRequestArguments { thread_id }
+    }
 }
 impl From<ExceptionInfoRequestArguments> for Request {
     fn from(args: ExceptionInfoRequestArguments) -> Self {
@@ -651,7 +1008,14 @@ impl From<ExceptionInfoRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct GotoRequestArguments {
     /// Set the goto target for this thread.
     #[serde(rename = "threadId")]
@@ -660,10 +1024,14 @@ pub struct GotoRequestArguments {
     /// The location where the debuggee will continue to run.
     #[serde(rename = "targetId")]
     pub target_id: i32,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl GotoRequestArguments {
+    pub fn new(thread_id: i32, target_id: i32) -> GotoRequestArguments {
+        GotoRequestArguments {
+            thread_id,
+            target_id,
+        }
+    }
 }
 impl From<GotoRequestArguments> for Request {
     fn from(args: GotoRequestArguments) -> Self {
@@ -676,7 +1044,14 @@ impl From<GotoRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct GotoTargetsRequestArguments {
     /// The source location for which the goto targets are determined.
     #[serde(rename = "source")]
@@ -688,16 +1063,22 @@ pub struct GotoTargetsRequestArguments {
 
     /// An optional column location for which the goto targets are determined.
     #[serde(rename = "column", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub column: Option<i32>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl GotoTargetsRequestArguments {
+    pub fn new(source: Source, line: i32) -> GotoTargetsRequestArguments {
+        GotoTargetsRequestArguments {
+            source,
+            line,
+            column: Default::default(),
+        }
+    }
 }
 impl From<GotoTargetsRequestArguments> for Request {
     fn from(args: GotoTargetsRequestArguments) -> Self {
-        Self::GotoTargets(args)
+        Self::GotoTargets(Box::new(args))
     }
 }
 impl From<GotoTargetsRequestArguments> for ProtocolMessageContent {
@@ -706,40 +1087,58 @@ impl From<GotoTargetsRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct InitializeRequestArguments {
     /// The ID of the (frontend) client using this adapter.
     #[serde(rename = "clientID", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub client_id: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub client_id: Option<Str>,
 
     /// The human readable name of the (frontend) client using this adapter.
     #[serde(rename = "clientName", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub client_name: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub client_name: Option<Str>,
 
     /// The ID of the debug adapter.
     #[serde(rename = "adapterID")]
-    pub adapter_id: String,
+    pub adapter_id: Str,
 
     /// The ISO-639 locale of the (frontend) client using this adapter, e.g. en-US or de-CH.
     #[serde(rename = "locale", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub locale: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub locale: Option<Str>,
 
     /// If true all line numbers are 1-based (default).
-    #[serde(rename = "linesStartAt1", default = "true_")]
-    #[builder(default = true)]
+    #[serde(
+        rename = "linesStartAt1",
+        default = "true_",
+        skip_serializing_if = "is_true"
+    )]
+    #[cfg_attr(feature = "builders", builder(default = true))]
     pub lines_start_at_1: bool,
 
     /// If true all column numbers are 1-based (default).
-    #[serde(rename = "columnsStartAt1", default = "true_")]
-    #[builder(default = true)]
+    #[serde(
+        rename = "columnsStartAt1",
+        default = "true_",
+        skip_serializing_if = "is_true"
+    )]
+    #[cfg_attr(feature = "builders", builder(default = true))]
     pub columns_start_at_1: bool,
 
     /// Determines in what format paths are specified. The default is 'path', which is the native format.
     #[serde(rename = "pathFormat", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub path_format: PathFormat,
 
     /// Client supports the optional type attribute for variables.
@@ -748,7 +1147,7 @@ pub struct InitializeRequestArguments {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub supports_variable_type: bool,
 
     /// Client supports the paging of variables.
@@ -757,7 +1156,7 @@ pub struct InitializeRequestArguments {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub supports_variable_paging: bool,
 
     /// Client supports the runInTerminal request.
@@ -766,7 +1165,7 @@ pub struct InitializeRequestArguments {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub supports_run_in_terminal_request: bool,
 
     /// Client supports memory references.
@@ -775,7 +1174,7 @@ pub struct InitializeRequestArguments {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub supports_memory_references: bool,
 
     /// Client supports progress reporting.
@@ -784,7 +1183,7 @@ pub struct InitializeRequestArguments {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub supports_progress_reporting: bool,
 
     /// Client supports the invalidated event.
@@ -793,16 +1192,79 @@ pub struct InitializeRequestArguments {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub supports_invalidated_event: bool,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// Client supports the 'memory' event.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(
+        rename = "supportsMemoryEvent",
+        default,
+        skip_serializing_if = "eq_default"
+    )]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub supports_memory_event: bool,
+
+    /// Client supports the 'argsCanBeInterpretedByShell' attribute on the 'runInTerminal' request.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(
+        rename = "supportsArgsCanBeInterpretedByShell",
+        default,
+        skip_serializing_if = "eq_default"
+    )]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub supports_args_can_be_interpreted_by_shell: bool,
+
+    /// Client supports the 'startDebugging' request.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(
+        rename = "supportsStartDebuggingRequest",
+        default,
+        skip_serializing_if = "eq_default"
+    )]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub supports_start_debugging_request: bool,
+
+    /// The client will interpret ANSI escape sequences in the display of 'OutputEvent.output' and 'Variable.value' fields when 'Capabilities.supportsANSIStyling' is also enabled.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(
+        rename = "supportsANSIStyling",
+        default,
+        skip_serializing_if = "eq_default"
+    )]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub supports_ansi_styling: bool,
+}
+impl InitializeRequestArguments {
+    pub fn new(adapter_id: Str) -> InitializeRequestArguments {
+        InitializeRequestArguments {
+            client_id: Default::default(),
+            client_name: Default::default(),
+            adapter_id,
+            locale: Default::default(),
+            lines_start_at_1: Default::default(),
+            columns_start_at_1: Default::default(),
+            path_format: Default::default(),
+            supports_variable_type: Default::default(),
+            supports_variable_paging: Default::default(),
+            supports_run_in_terminal_request: Default::default(),
+            supports_memory_references: Default::default(),
+            supports_progress_reporting: Default::default(),
+            supports_invalidated_event: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            supports_memory_event: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            supports_args_can_be_interpreted_by_shell: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            supports_start_debugging_request: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            supports_ansi_styling: Default::default(),
+        }
+    }
 }
 impl From<InitializeRequestArguments> for Request {
     fn from(args: InitializeRequestArguments) -> Self {
-        Self::Initialize(args)
+        Self::Initialize(Box::new(args))
     }
 }
 impl From<InitializeRequestArguments> for ProtocolMessageContent {
@@ -811,24 +1273,36 @@ impl From<InitializeRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum PathFormat {
+    #[default]
     Path,
     URI,
-}
 
-impl Default for PathFormat {
-    fn default() -> Self {
-        PathFormat::Path
-    }
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct LaunchRequestArguments {
     /// If noDebug is true the launch request should launch the program without enabling debugging.
     #[serde(rename = "noDebug", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub no_debug: bool,
 
     /// Optional data from the previous, restarted session.
@@ -837,21 +1311,20 @@ pub struct LaunchRequestArguments {
     ///
     /// The client should leave the data intact.
     #[serde(rename = "__restart", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_value))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub restart: Option<Value>,
 
     /// Additional attributes are implementation specific.
     #[serde(flatten)]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::map))]
     pub additional_attributes: Map<String, Value>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 impl From<LaunchRequestArguments> for Request {
     fn from(args: LaunchRequestArguments) -> Self {
-        Self::Launch(args)
+        Self::Launch(Box::new(args))
     }
 }
 impl From<LaunchRequestArguments> for ProtocolMessageContent {
@@ -860,21 +1333,57 @@ impl From<LaunchRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[cfg(feature = "spec-1_70")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
+pub struct LocationsRequestArguments {
+    /// Location reference to resolve.
+    #[serde(rename = "locationReference")]
+    pub location_reference: i32,
+}
+#[cfg(feature = "spec-1_70")]
+impl LocationsRequestArguments {
+    pub fn new(location_reference: i32) -> LocationsRequestArguments {
+        LocationsRequestArguments { location_reference }
+    }
+}
+#[cfg(feature = "spec-1_70")]
+impl From<LocationsRequestArguments> for Request {
+    fn from(args: LocationsRequestArguments) -> Self {
+        Self::Locations(args)
+    }
+}
+#[cfg(feature = "spec-1_70")]
+impl From<LocationsRequestArguments> for ProtocolMessageContent {
+    fn from(args: LocationsRequestArguments) -> Self {
+        Self::from(Request::from(args))
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ModulesRequestArguments {
     /// The index of the first module to return; if omitted modules start at 0.
     #[serde(rename = "startModule", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub start_module: i32,
 
     /// The number of modules to return. If moduleCount is not specified or 0, all modules are returned.
     #[serde(rename = "moduleCount", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub module_count: i32,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 impl From<ModulesRequestArguments> for Request {
     fn from(args: ModulesRequestArguments) -> Self {
@@ -887,7 +1396,14 @@ impl From<ModulesRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct NextRequestArguments {
     /// Execute 'next' for this thread.
     #[serde(rename = "threadId")]
@@ -895,12 +1411,26 @@ pub struct NextRequestArguments {
 
     /// Optional granularity to step. If no granularity is specified, a granularity of 'statement' is assumed.
     #[serde(rename = "granularity", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub granularity: SteppingGranularity,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// If this flag is true, all other suspended threads are not resumed.
+    ///
+    /// The attribute is only honored by a debug adapter if the capability 'supportsSingleThreadExecutionRequests' is true.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "singleThread", default, skip_serializing_if = "eq_default")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub single_thread: bool,
+}
+impl NextRequestArguments {
+    pub fn new(thread_id: i32) -> NextRequestArguments {
+        NextRequestArguments {
+            thread_id,
+            granularity: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            single_thread: Default::default(),
+        }
+    }
 }
 impl From<NextRequestArguments> for Request {
     fn from(args: NextRequestArguments) -> Self {
@@ -913,15 +1443,23 @@ impl From<NextRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct PauseRequestArguments {
     /// Pause execution for this thread.
     #[serde(rename = "threadId")]
     pub thread_id: i32,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl PauseRequestArguments {
+    pub fn new(thread_id: i32) -> PauseRequestArguments {
+        PauseRequestArguments { thread_id }
+    }
 }
 impl From<PauseRequestArguments> for Request {
     fn from(args: PauseRequestArguments) -> Self {
@@ -934,24 +1472,36 @@ impl From<PauseRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ReadMemoryRequestArguments {
     /// Memory reference to the base location from which data should be read.
     #[serde(rename = "memoryReference")]
-    pub memory_reference: String,
+    pub memory_reference: Str,
 
     /// Optional offset (in bytes) to be applied to the reference location before reading data. Can be negative.
     #[serde(rename = "offset", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub offset: i32,
 
     /// Number of bytes to read at the specified location and offset.
     #[serde(rename = "count")]
     pub count: i32,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ReadMemoryRequestArguments {
+    pub fn new(memory_reference: Str, count: i32) -> ReadMemoryRequestArguments {
+        ReadMemoryRequestArguments {
+            memory_reference,
+            offset: Default::default(),
+            count,
+        }
+    }
 }
 impl From<ReadMemoryRequestArguments> for Request {
     fn from(args: ReadMemoryRequestArguments) -> Self {
@@ -964,15 +1514,23 @@ impl From<ReadMemoryRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct RestartFrameRequestArguments {
     /// Restart this stackframe.
     #[serde(rename = "frameId")]
     pub frame_id: i32,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl RestartFrameRequestArguments {
+    pub fn new(frame_id: i32) -> RestartFrameRequestArguments {
+        RestartFrameRequestArguments { frame_id }
+    }
 }
 impl From<RestartFrameRequestArguments> for Request {
     fn from(args: RestartFrameRequestArguments) -> Self {
@@ -992,15 +1550,35 @@ impl From<RestartFrameRequestArguments> for ProtocolMessageContent {
 //   pub arguments: Option<TODO oneOf>,
 // }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ReverseContinueRequestArguments {
     /// Execute 'reverseContinue' for this thread.
     #[serde(rename = "threadId")]
     pub thread_id: i32,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// If this flag is true, backward execution is resumed only for the thread with given 'threadId'.
+    ///
+    /// The attribute is only honored by a debug adapter if the capability 'supportsSingleThreadExecutionRequests' is true.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "singleThread", default, skip_serializing_if = "eq_default")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub single_thread: bool,
+}
+impl ReverseContinueRequestArguments {
+    pub fn new(thread_id: i32) -> ReverseContinueRequestArguments {
+        ReverseContinueRequestArguments {
+            thread_id,
+            #[cfg(feature = "spec-1_70")]
+            single_thread: Default::default(),
+        }
+    }
 }
 impl From<ReverseContinueRequestArguments> for Request {
     fn from(args: ReverseContinueRequestArguments) -> Self {
@@ -1013,38 +1591,66 @@ impl From<ReverseContinueRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct RunInTerminalRequestArguments {
-    /// What kind of terminal to launch.
-    #[serde(rename = "kind", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub kind: Option<TerminalKind>,
+    /// What kind of terminal to launch. Defaults to 'integrated' if not specified.
+    #[serde(rename = "kind", default, skip_serializing_if = "eq_default")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub kind: TerminalKind,
 
     /// Optional title of the terminal.
     #[serde(rename = "title", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub title: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub title: Option<Str>,
 
     /// Working directory for the command. For non-empty, valid paths this typically results in execution of a change directory command.
     #[serde(rename = "cwd")]
-    pub cwd: String,
+    pub cwd: Str,
 
     /// List of arguments. The first argument is the command to run.
     #[serde(rename = "args")]
-    pub args: Vec<String>,
+    pub args: Vec<Str>,
 
     /// Environment key-value pairs that are added to or removed from the default environment.
-    #[serde(rename = "env", default, skip_serializing_if = "HashMap::is_empty")]
-    #[builder(default)]
-    pub env: HashMap<String, Option<String>>,
+    #[serde(rename = "env", default, skip_serializing_if = "is_none_or_empty")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_hash_map))]
+    pub env: Option<HashMap<String, Option<String>>>,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// This property should only be set if the corresponding capability 'supportsArgsCanBeInterpretedByShell' is true. If the client uses an intermediary shell to launch the application, then the client must not attempt to escape characters with special meanings for the shell. The user is fully responsible for escaping as needed and that arguments using special characters may not be portable across shells.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(
+        rename = "argsCanBeInterpretedByShell",
+        default,
+        skip_serializing_if = "eq_default"
+    )]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub args_can_be_interpreted_by_shell: bool,
+}
+impl RunInTerminalRequestArguments {
+    pub fn new(cwd: Str, args: Vec<Str>) -> RunInTerminalRequestArguments {
+        RunInTerminalRequestArguments {
+            kind: Default::default(),
+            title: Default::default(),
+            cwd,
+            args,
+            env: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            args_can_be_interpreted_by_shell: Default::default(),
+        }
+    }
 }
 impl From<RunInTerminalRequestArguments> for Request {
     fn from(args: RunInTerminalRequestArguments) -> Self {
-        Self::RunInTerminal(args)
+        Self::RunInTerminal(Box::new(args))
     }
 }
 impl From<RunInTerminalRequestArguments> for ProtocolMessageContent {
@@ -1053,23 +1659,38 @@ impl From<RunInTerminalRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum TerminalKind {
+    #[default]
     Integrated,
 
     External,
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ScopesRequestArguments {
     /// Retrieve the scopes for this stackframe.
     #[serde(rename = "frameId")]
     pub frame_id: i32,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ScopesRequestArguments {
+    pub fn new(frame_id: i32) -> ScopesRequestArguments {
+        ScopesRequestArguments { frame_id }
+    }
 }
 impl From<ScopesRequestArguments> for Request {
     fn from(args: ScopesRequestArguments) -> Self {
@@ -1082,7 +1703,14 @@ impl From<ScopesRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetBreakpointsRequestArguments {
     /// The source location of the breakpoints; either 'source.path' or 'source.reference' must be specified.
     #[serde(rename = "source")]
@@ -1090,26 +1718,32 @@ pub struct SetBreakpointsRequestArguments {
 
     /// The code locations of the breakpoints.
     #[serde(rename = "breakpoints", default, skip_serializing_if = "Vec::is_empty")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub breakpoints: Vec<SourceBreakpoint>,
 
     /// Deprecated: The code locations of the breakpoints.
     #[serde(rename = "lines", default, skip_serializing_if = "Vec::is_empty")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub lines: Vec<i32>,
 
     /// A value of true indicates that the underlying source has been modified which results in new breakpoint locations.
     #[serde(rename = "sourceModified", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub source_modified: bool,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl SetBreakpointsRequestArguments {
+    pub fn new(source: Source) -> SetBreakpointsRequestArguments {
+        SetBreakpointsRequestArguments {
+            source,
+            breakpoints: Default::default(),
+            lines: Default::default(),
+            source_modified: Default::default(),
+        }
+    }
 }
 impl From<SetBreakpointsRequestArguments> for Request {
     fn from(args: SetBreakpointsRequestArguments) -> Self {
-        Self::SetBreakpoints(args)
+        Self::SetBreakpoints(Box::new(args))
     }
 }
 impl From<SetBreakpointsRequestArguments> for ProtocolMessageContent {
@@ -1118,15 +1752,23 @@ impl From<SetBreakpointsRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetDataBreakpointsRequestArguments {
     /// The contents of this array replaces all existing data breakpoints. An empty array clears all data breakpoints.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<DataBreakpoint>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl SetDataBreakpointsRequestArguments {
+    pub fn new(breakpoints: Vec<DataBreakpoint>) -> SetDataBreakpointsRequestArguments {
+        SetDataBreakpointsRequestArguments { breakpoints }
+    }
 }
 impl From<SetDataBreakpointsRequestArguments> for Request {
     fn from(args: SetDataBreakpointsRequestArguments) -> Self {
@@ -1139,11 +1781,18 @@ impl From<SetDataBreakpointsRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetExceptionBreakpointsRequestArguments {
     /// Set of exception filters specified by their ID. The set of all possible exception filters is defined by the 'exceptionBreakpointFilters' capability. The 'filter' and 'filterOptions' sets are additive.
     #[serde(rename = "filters")]
-    pub filters: Vec<String>,
+    pub filters: SmallVec<Str>,
 
     /// Set of exception filters and their options. The set of all possible exception filters is defined by the 'exceptionBreakpointFilters' capability. This attribute is only honored by a debug adapter if the capability 'supportsExceptionFilterOptions' is true. The 'filter' and 'filterOptions' sets are additive.
     #[serde(
@@ -1151,7 +1800,7 @@ pub struct SetExceptionBreakpointsRequestArguments {
         default,
         skip_serializing_if = "Vec::is_empty"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub filter_options: Vec<ExceptionFilterOptions>,
 
     /// Configuration options for selected exceptions.
@@ -1162,12 +1811,17 @@ pub struct SetExceptionBreakpointsRequestArguments {
         default,
         skip_serializing_if = "Vec::is_empty"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub exception_options: Vec<ExceptionOptions>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl SetExceptionBreakpointsRequestArguments {
+    pub fn new(filters: SmallVec<Str>) -> SetExceptionBreakpointsRequestArguments {
+        SetExceptionBreakpointsRequestArguments {
+            filters,
+            filter_options: Default::default(),
+            exception_options: Default::default(),
+        }
+    }
 }
 impl From<SetExceptionBreakpointsRequestArguments> for Request {
     fn from(args: SetExceptionBreakpointsRequestArguments) -> Self {
@@ -1180,29 +1834,44 @@ impl From<SetExceptionBreakpointsRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetExpressionRequestArguments {
     /// The l-value expression to assign to.
     #[serde(rename = "expression")]
-    pub expression: String,
+    pub expression: Str,
 
     /// The value expression to assign to the l-value expression.
     #[serde(rename = "value")]
-    pub value: String,
+    pub value: Str,
 
     /// Evaluate the expressions in the scope of this stack frame. If not specified, the expressions are evaluated in the global scope.
     #[serde(rename = "frameId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub frame_id: Option<i32>,
 
     /// Specifies how the resulting value should be formatted.
     #[serde(rename = "format", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub format: Option<ValueFormat>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl SetExpressionRequestArguments {
+    pub fn new(expression: Str, value: Str) -> SetExpressionRequestArguments {
+        SetExpressionRequestArguments {
+            expression,
+            value,
+            frame_id: Default::default(),
+            format: Default::default(),
+        }
+    }
 }
 impl From<SetExpressionRequestArguments> for Request {
     fn from(args: SetExpressionRequestArguments) -> Self {
@@ -1215,15 +1884,23 @@ impl From<SetExpressionRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetFunctionBreakpointsRequestArguments {
     /// The function names of the breakpoints.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<FunctionBreakpoint>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl SetFunctionBreakpointsRequestArguments {
+    pub fn new(breakpoints: Vec<FunctionBreakpoint>) -> SetFunctionBreakpointsRequestArguments {
+        SetFunctionBreakpointsRequestArguments { breakpoints }
+    }
 }
 impl From<SetFunctionBreakpointsRequestArguments> for Request {
     fn from(args: SetFunctionBreakpointsRequestArguments) -> Self {
@@ -1236,15 +1913,25 @@ impl From<SetFunctionBreakpointsRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetInstructionBreakpointsRequestArguments {
     /// The instruction references of the breakpoints
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<InstructionBreakpoint>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl SetInstructionBreakpointsRequestArguments {
+    pub fn new(
+        breakpoints: Vec<InstructionBreakpoint>,
+    ) -> SetInstructionBreakpointsRequestArguments {
+        SetInstructionBreakpointsRequestArguments { breakpoints }
+    }
 }
 impl From<SetInstructionBreakpointsRequestArguments> for Request {
     fn from(args: SetInstructionBreakpointsRequestArguments) -> Self {
@@ -1257,7 +1944,14 @@ impl From<SetInstructionBreakpointsRequestArguments> for ProtocolMessageContent
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetVariableRequestArguments {
     /// The reference of the variable container.
     #[serde(rename = "variablesReference")]
@@ -1265,20 +1959,27 @@ pub struct SetVariableRequestArguments {
 
     /// The name of the variable in the container.
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: Str,
 
     /// The value of the variable.
     #[serde(rename = "value")]
-    pub value: String,
+    pub value: Str,
 
     /// Specifies details on how to format the response value.
     #[serde(rename = "format", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub format: Option<ValueFormat>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl SetVariableRequestArguments {
+    pub fn new(variables_reference: i32, name: Str, value: Str) -> SetVariableRequestArguments {
+        SetVariableRequestArguments {
+            variables_reference,
+            name,
+            value,
+            format: Default::default(),
+        }
+    }
 }
 impl From<SetVariableRequestArguments> for Request {
     fn from(args: SetVariableRequestArguments) -> Self {
@@ -1291,11 +1992,19 @@ impl From<SetVariableRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SourceRequestArguments {
     /// Specifies the source content to load. Either source.path or source.sourceReference must be specified.
     #[serde(rename = "source", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub source: Option<Source>,
 
     /// The reference to the source. This is the same as source.sourceReference.
@@ -1303,14 +2012,18 @@ pub struct SourceRequestArguments {
     /// This is provided for backward compatibility since old backends do not understand the 'source' attribute.
     #[serde(rename = "sourceReference")]
     pub source_reference: i32,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl SourceRequestArguments {
+    pub fn new(source_reference: i32) -> SourceRequestArguments {
+        SourceRequestArguments {
+            source: Default::default(),
+            source_reference,
+        }
+    }
 }
 impl From<SourceRequestArguments> for Request {
     fn from(args: SourceRequestArguments) -> Self {
-        Self::Source(args)
+        Self::Source(Box::new(args))
     }
 }
 impl From<SourceRequestArguments> for ProtocolMessageContent {
@@ -1319,7 +2032,14 @@ impl From<SourceRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct StackTraceRequestArguments {
     /// Retrieve the stacktrace for this thread.
     #[serde(rename = "threadId")]
@@ -1327,24 +2047,31 @@ pub struct StackTraceRequestArguments {
 
     /// The index of the first frame to return; if omitted frames start at 0.
     #[serde(rename = "startFrame", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub start_frame: i32,
 
     /// The maximum number of frames to return. If levels is not specified or 0, all frames are returned.
     #[serde(rename = "levels", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub levels: i32,
 
     /// Specifies details on how to format the stack frames.
     ///
     /// The attribute is only honored by a debug adapter if the capability 'supportsValueFormattingOptions' is true.
     #[serde(rename = "format", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub format: Option<StackFrameFormat>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl StackTraceRequestArguments {
+    pub fn new(thread_id: i32) -> StackTraceRequestArguments {
+        StackTraceRequestArguments {
+            thread_id,
+            start_frame: Default::default(),
+            levels: Default::default(),
+            format: Default::default(),
+        }
+    }
 }
 impl From<StackTraceRequestArguments> for Request {
     fn from(args: StackTraceRequestArguments) -> Self {
@@ -1357,7 +2084,14 @@ impl From<StackTraceRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct StepBackRequestArguments {
     /// Execute 'stepBack' for this thread.
     #[serde(rename = "threadId")]
@@ -1365,12 +2099,26 @@ pub struct StepBackRequestArguments {
 
     /// Optional granularity to step. If no granularity is specified, a granularity of 'statement' is assumed.
     #[serde(rename = "granularity", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub granularity: SteppingGranularity,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// If this flag is true, all other suspended threads are not resumed.
+    ///
+    /// The attribute is only honored by a debug adapter if the capability 'supportsSingleThreadExecutionRequests' is true.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "singleThread", default, skip_serializing_if = "eq_default")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub single_thread: bool,
+}
+impl StepBackRequestArguments {
+    pub fn new(thread_id: i32) -> StepBackRequestArguments {
+        StepBackRequestArguments {
+            thread_id,
+            granularity: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            single_thread: Default::default(),
+        }
+    }
 }
 impl From<StepBackRequestArguments> for Request {
     fn from(args: StepBackRequestArguments) -> Self {
@@ -1383,7 +2131,14 @@ impl From<StepBackRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct StepInRequestArguments {
     /// Execute 'stepIn' for this thread.
     #[serde(rename = "threadId")]
@@ -1391,17 +2146,33 @@ pub struct StepInRequestArguments {
 
     /// Optional id of the target to step into.
     #[serde(rename = "targetId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub target_id: Option<i32>,
 
     /// Optional granularity to step. If no granularity is specified, a granularity of 'statement' is assumed.
     #[serde(rename = "granularity", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub granularity: SteppingGranularity,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// If this flag is true, all other suspended threads are not resumed.
+    ///
+    /// The attribute is only honored by a debug adapter if the capability 'supportsSingleThreadExecutionRequests' is true.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "singleThread", default, skip_serializing_if = "eq_default")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub single_thread: bool,
+}
+impl StepInRequestArguments {
+    pub fn new(thread_id: i32) -> StepInRequestArguments {
+        StepInRequestArguments {
+            thread_id,
+            target_id: Default::default(),
+            granularity: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            single_thread: Default::default(),
+        }
+    }
 }
 impl From<StepInRequestArguments> for Request {
     fn from(args: StepInRequestArguments) -> Self {
@@ -1414,15 +2185,23 @@ impl From<StepInRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct StepInTargetsRequestArguments {
     /// The stack frame for which to retrieve the possible stepIn targets.
     #[serde(rename = "frameId")]
     pub frame_id: i32,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl StepInTargetsRequestArguments {
+    pub fn new(frame_id: i32) -> StepInTargetsRequestArguments {
+        StepInTargetsRequestArguments { frame_id }
+    }
 }
 impl From<StepInTargetsRequestArguments> for Request {
     fn from(args: StepInTargetsRequestArguments) -> Self {
@@ -1435,7 +2214,14 @@ impl From<StepInTargetsRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct StepOutRequestArguments {
     /// Execute 'stepOut' for this thread.
     #[serde(rename = "threadId")]
@@ -1443,12 +2229,26 @@ pub struct StepOutRequestArguments {
 
     /// Optional granularity to step. If no granularity is specified, a granularity of 'statement' is assumed.
     #[serde(rename = "granularity", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub granularity: SteppingGranularity,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// If this flag is true, all other suspended threads are not resumed.
+    ///
+    /// The attribute is only honored by a debug adapter if the capability 'supportsSingleThreadExecutionRequests' is true.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "singleThread", default, skip_serializing_if = "eq_default")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub single_thread: bool,
+}
+impl StepOutRequestArguments {
+    pub fn new(thread_id: i32) -> StepOutRequestArguments {
+        StepOutRequestArguments {
+            thread_id,
+            granularity: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            single_thread: Default::default(),
+        }
+    }
 }
 impl From<StepOutRequestArguments> for Request {
     fn from(args: StepOutRequestArguments) -> Self {
@@ -1461,16 +2261,19 @@ impl From<StepOutRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct TerminateRequestArguments {
     /// A value of true indicates that this 'terminate' request is part of a restart sequence.
     #[serde(rename = "restart", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub restart: bool,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 impl From<TerminateRequestArguments> for Request {
     fn from(args: TerminateRequestArguments) -> Self {
@@ -1483,16 +2286,19 @@ impl From<TerminateRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct TerminateThreadsRequestArguments {
     /// Ids of threads to be terminated.
     #[serde(rename = "threadIds", default, skip_serializing_if = "Vec::is_empty")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub thread_ids: Vec<i32>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 impl From<TerminateThreadsRequestArguments> for Request {
     fn from(args: TerminateThreadsRequestArguments) -> Self {
@@ -1505,7 +2311,14 @@ impl From<TerminateThreadsRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct VariablesRequestArguments {
     /// The Variable reference.
     #[serde(rename = "variablesReference")]
@@ -1513,29 +2326,38 @@ pub struct VariablesRequestArguments {
 
     /// Optional filter to limit the child variables to either named or indexed. If omitted, both types are fetched.
     #[serde(rename = "filter", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub filter: Option<VariablesFilter>,
 
     /// The index of the first variable to return; if omitted children start at 0.
     #[serde(rename = "start", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub start: i32,
 
     /// The number of variables to return. If count is missing or 0, all variables are returned.
     #[serde(rename = "count", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub count: i32,
 
     /// Specifies details on how to format the Variable values.
     ///
     /// The attribute is only honored by a debug adapter if the capability 'supportsValueFormattingOptions' is true.
     #[serde(rename = "format", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub format: Option<ValueFormat>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl VariablesRequestArguments {
+    pub fn new(variables_reference: i32) -> VariablesRequestArguments {
+        VariablesRequestArguments {
+            variables_reference,
+            filter: Default::default(),
+            start: Default::default(),
+            count: Default::default(),
+            format: Default::default(),
+        }
+    }
 }
 impl From<VariablesRequestArguments> for Request {
     fn from(args: VariablesRequestArguments) -> Self {
@@ -1548,8 +2370,14 @@ impl From<VariablesRequestArguments> for ProtocolMessageContent {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum VariablesFilter {
     Indexed,
 
@@ -1,42 +1,90 @@
+#[cfg(feature = "de")]
+use crate::dispatch::{TagDispatch, Variants};
+#[cfg(any(feature = "de", feature = "schemars"))]
+use crate::utils::true_;
+#[cfg(any(feature = "ser", feature = "schemars"))]
+use crate::utils::{eq_default, is_true};
 use crate::{
     types::{
         Breakpoint, BreakpointLocation, Capabilities, CompletionItem, DataBreakpointAccessType,
         DisassembledInstruction, ExceptionBreakMode, ExceptionDetails, GotoTarget, Message, Module,
         Scope, Source, StackFrame, StepInTarget, Thread, Variable, VariablePresentationHint,
     },
-    utils::{eq_default, true_},
-    ProtocolMessageContent, SequenceNumber,
+    ProtocolMessageContent, SequenceNumber, Str,
 };
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "de")]
 use serde::{
-    de::{Error, Unexpected},
-    Deserialize, Deserializer, Serialize, Serializer,
+    de::{
+        value::{MapAccessDeserializer, StrDeserializer},
+        DeserializeSeed, Error, IgnoredAny, MapAccess, Visitor,
+    },
+    Deserialize, Deserializer,
 };
-use serde_json::{Number, Value};
+#[cfg(feature = "ser")]
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use serde_json::{Map, Value};
+#[cfg(feature = "de")]
+use std::fmt::{self, Display};
+#[cfg(feature = "ts-rs")]
+use ts_rs::TS;
+#[cfg(feature = "builders")]
 use typed_builder::TypedBuilder;
 
 /// Response for a request.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[non_exhaustive]
 pub struct Response {
     /// Sequence number of the corresponding request.
     pub request_seq: SequenceNumber,
 
-    #[serde(
-        flatten,
-        deserialize_with = "deserialize_response_result",
-        serialize_with = "serialize_response_result"
-    )]
+    #[cfg_attr(any(feature = "ser", feature = "schemars"), serde(flatten))]
+    #[cfg_attr(feature = "ser", serde(serialize_with = "serialize_response_result"))]
     pub result: Result<SuccessResponse, ErrorResponse>,
 }
+impl Response {
+    pub fn new(
+        request_seq: SequenceNumber,
+        result: Result<SuccessResponse, ErrorResponse>,
+    ) -> Response {
+        Response {
+            request_seq,
+            result,
+        }
+    }
+
+    /// The command of the request this is a response to.
+    pub fn command(&self) -> &str {
+        match &self.result {
+            Ok(response) => response.command(),
+            Err(response) => &response.command,
+        }
+    }
+}
+const _: () = assert!(std::mem::size_of::<Response>() <= 88);
 impl From<Response> for ProtocolMessageContent {
     fn from(response: Response) -> Self {
         Self::Response(response)
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ErrorResponse {
     /// The command requested.
-    pub command: String,
+    pub command: Str,
 
     /// Contains the raw error in short form if 'success' is false.
     /// This raw error might be interpreted by the frontend and is not shown in the
@@ -45,27 +93,48 @@ pub struct ErrorResponse {
     /// Values:
     /// 'cancelled': request was cancelled.
     /// etc.
-    pub message: String,
+    pub message: Str,
 
-    #[builder(default)]
-    pub body: ErrorResponseBody,
+    /// Many debug adapters omit the body, so it defaults to an empty one.
+    #[cfg_attr(feature = "de", serde(default))]
+    #[cfg_attr(feature = "builders", builder(default, setter(into)))]
+    pub body: Box<ErrorResponseBody>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl ErrorResponse {
+    pub fn new(command: Str, message: Str) -> ErrorResponse {
+        ErrorResponse {
+            command,
+            message,
+            body: Default::default(),
+        }
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub struct ErrorResponseBody {
     /// An optional, structured error message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub error: Option<Message>,
 
-    #[serde(skip)]
-    private: (),
+    /// Additional attributes are implementation specific, for example diagnostic information.
+    #[serde(flatten)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::map))]
+    pub additional_attributes: Map<String, Value>,
 }
 impl ErrorResponseBody {
     pub fn new(error: Option<Message>) -> Self {
-        Self { error, private: () }
+        Self {
+            error,
+            additional_attributes: Map::new(),
+        }
     }
 }
 impl Default for ErrorResponseBody {
@@ -75,8 +144,19 @@ impl Default for ErrorResponseBody {
 }
 
 /// Contains request result if success is true and optional error details if success is false.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(rename_all = "camelCase", tag = "command", content = "body")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[serde(
+    remote = "Self",
+    rename_all = "camelCase",
+    tag = "command",
+    content = "body"
+)]
+#[non_exhaustive]
 pub enum SuccessResponse {
     /// Response to 'attach' request. This is just an acknowledgement, so no body field is required.
     Attach,
@@ -108,10 +188,10 @@ pub enum SuccessResponse {
     Disconnect,
 
     /// Response to 'evaluate' request.
-    Evaluate(EvaluateResponseBody),
+    Evaluate(Box<EvaluateResponseBody>),
 
     /// Response to 'exceptionInfo' request.
-    ExceptionInfo(ExceptionInfoResponseBody),
+    ExceptionInfo(Box<ExceptionInfoResponseBody>),
 
     /// Response to 'goto' request. This is just an acknowledgement, so no body field is required.
     Goto,
@@ -120,7 +200,7 @@ pub enum SuccessResponse {
     GotoTargets(GotoTargetsResponseBody),
 
     /// Response to 'initialize' request.
-    Initialize(Capabilities),
+    Initialize(Box<Capabilities>),
 
     /// Response to 'launch' request. This is just an acknowledgement, so no body field is required.
     Launch,
@@ -128,6 +208,10 @@ pub enum SuccessResponse {
     /// Response to 'loadedSources' request.
     LoadedSources(LoadedSourcesResponseBody),
 
+    /// Response to 'locations' request.
+    #[cfg(feature = "spec-1_70")]
+    Locations(Box<LocationsResponseBody>),
+
     /// Response to 'modules' request.
     Modules(ModulesResponseBody),
 
@@ -181,7 +265,7 @@ pub enum SuccessResponse {
     SetExceptionBreakpoints(SetExceptionBreakpointsResponseBody),
 
     /// Response to 'setExpression' request.
-    SetExpression(SetExpressionResponseBody),
+    SetExpression(Box<SetExpressionResponseBody>),
 
     /// Response to 'setFunctionBreakpoints' request.
     ///
@@ -192,7 +276,7 @@ pub enum SuccessResponse {
     SetInstructionBreakpoints(SetInstructionBreakpointsResponseBody),
 
     /// Response to 'setVariable' request.
-    SetVariable(SetVariableResponseBody),
+    SetVariable(Box<SetVariableResponseBody>),
 
     /// Response to 'source' request.
     Source(SourceResponseBody),
@@ -223,17 +307,180 @@ pub enum SuccessResponse {
 
     /// Response to 'variables' request.
     Variables(VariablesResponseBody),
+
+    /// Response to a request with a command that is not part of the specification, like
+    /// [Request::Custom](crate::requests::Request::Custom). Responses with an unknown command
+    /// deserialize as this variant.
+    #[cfg_attr(feature = "ser", serde(skip_serializing))]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    #[cfg_attr(feature = "ts-rs", ts(skip))]
+    Other {
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))]
+        command: Str,
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_boxed_value))]
+        body: Option<Box<Value>>,
+    },
+}
+
+// Large variants are boxed to keep every message small
+const _: () = assert!(std::mem::size_of::<SuccessResponse>() <= 80);
+
+#[cfg(feature = "ser")]
+impl Serialize for SuccessResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            SuccessResponse::Other { command, body } => {
+                let mut state = serializer.serialize_struct("SuccessResponse", 2)?;
+                state.serialize_field("command", command)?;
+                match body {
+                    Some(body) => state.serialize_field("body", body)?,
+                    None => state.skip_field("body")?,
+                }
+                state.end()
+            }
+            _ => SuccessResponse::serialize(self, serializer),
+        }
+    }
+}
+
+#[cfg(feature = "de")]
+impl<'de> Deserialize<'de> for SuccessResponse {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        SuccessResponse::deserialize(TagDispatch {
+            deserializer,
+            tag: "command",
+            content: "body",
+            variants: &COMMANDS,
+        })
+    }
+}
+
+/// The commands of the variants of [SuccessResponse] in declaration order, followed by
+/// [SuccessResponse::Other].
+#[cfg(feature = "de")]
+pub(crate) static COMMANDS: Variants = Variants::new(&[
+    "attach",
+    "breakpointLocations",
+    "cancel",
+    "completions",
+    "configurationDone",
+    "continue",
+    "dataBreakpointInfo",
+    "disassemble",
+    "disconnect",
+    "evaluate",
+    "exceptionInfo",
+    "goto",
+    "gotoTargets",
+    "initialize",
+    "launch",
+    "loadedSources",
+    #[cfg(feature = "spec-1_70")]
+    "locations",
+    "modules",
+    "next",
+    "pause",
+    "readMemory",
+    "restartFrame",
+    "restart",
+    "reverseContinue",
+    "runInTerminal",
+    "scopes",
+    "setBreakpoints",
+    "setDataBreakpoints",
+    "setExceptionBreakpoints",
+    "setExpression",
+    "setFunctionBreakpoints",
+    "setInstructionBreakpoints",
+    "setVariable",
+    "source",
+    "stackTrace",
+    "stepBack",
+    "stepIn",
+    "stepInTargets",
+    "stepOut",
+    "terminate",
+    "terminateThreads",
+    "threads",
+    "variables",
+]);
+
+impl SuccessResponse {
+    /// The command of the request this is a response to as it is sent in the 'command' attribute.
+    pub fn command(&self) -> &str {
+        match self {
+            SuccessResponse::Attach => "attach",
+            SuccessResponse::BreakpointLocations(_) => "breakpointLocations",
+            SuccessResponse::Cancel => "cancel",
+            SuccessResponse::Completions(_) => "completions",
+            SuccessResponse::ConfigurationDone => "configurationDone",
+            SuccessResponse::Continue(_) => "continue",
+            SuccessResponse::DataBreakpointInfo(_) => "dataBreakpointInfo",
+            SuccessResponse::Disassemble(_) => "disassemble",
+            SuccessResponse::Disconnect => "disconnect",
+            SuccessResponse::Evaluate(_) => "evaluate",
+            SuccessResponse::ExceptionInfo(_) => "exceptionInfo",
+            SuccessResponse::Goto => "goto",
+            SuccessResponse::GotoTargets(_) => "gotoTargets",
+            SuccessResponse::Initialize(_) => "initialize",
+            SuccessResponse::Launch => "launch",
+            SuccessResponse::LoadedSources(_) => "loadedSources",
+            #[cfg(feature = "spec-1_70")]
+            SuccessResponse::Locations(_) => "locations",
+            SuccessResponse::Modules(_) => "modules",
+            SuccessResponse::Next => "next",
+            SuccessResponse::Pause => "pause",
+            SuccessResponse::ReadMemory(_) => "readMemory",
+            SuccessResponse::RestartFrame => "restartFrame",
+            SuccessResponse::Restart => "restart",
+            SuccessResponse::ReverseContinue => "reverseContinue",
+            SuccessResponse::RunInTerminal(_) => "runInTerminal",
+            SuccessResponse::Scopes(_) => "scopes",
+            SuccessResponse::SetBreakpoints(_) => "setBreakpoints",
+            SuccessResponse::SetDataBreakpoints(_) => "setDataBreakpoints",
+            SuccessResponse::SetExceptionBreakpoints(_) => "setExceptionBreakpoints",
+            SuccessResponse::SetExpression(_) => "setExpression",
+            SuccessResponse::SetFunctionBreakpoints(_) => "setFunctionBreakpoints",
+            SuccessResponse::SetInstructionBreakpoints(_) => "setInstructionBreakpoints",
+            SuccessResponse::SetVariable(_) => "setVariable",
+            SuccessResponse::Source(_) => "source",
+            SuccessResponse::StackTrace(_) => "stackTrace",
+            SuccessResponse::StepBack => "stepBack",
+            SuccessResponse::StepIn => "stepIn",
+            SuccessResponse::StepInTargets(_) => "stepInTargets",
+            SuccessResponse::StepOut => "stepOut",
+            SuccessResponse::Terminate => "terminate",
+            SuccessResponse::TerminateThreads => "terminateThreads",
+            SuccessResponse::Threads(_) => "threads",
+            SuccessResponse::Variables(_) => "variables",
+            SuccessResponse::Other { command, .. } => command,
+        }
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct BreakpointLocationsResponseBody {
     /// Sorted set of possible breakpoint locations.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<BreakpointLocation>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl BreakpointLocationsResponseBody {
+    pub fn new(breakpoints: Vec<BreakpointLocation>) -> BreakpointLocationsResponseBody {
+        BreakpointLocationsResponseBody { breakpoints }
+    }
 }
 impl From<BreakpointLocationsResponseBody> for SuccessResponse {
     fn from(args: BreakpointLocationsResponseBody) -> Self {
@@ -241,15 +488,23 @@ impl From<BreakpointLocationsResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct CompletionsResponseBody {
     /// The possible completions for .
     #[serde(rename = "targets")]
     pub targets: Vec<CompletionItem>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl CompletionsResponseBody {
+    pub fn new(targets: Vec<CompletionItem>) -> CompletionsResponseBody {
+        CompletionsResponseBody { targets }
+    }
 }
 impl From<CompletionsResponseBody> for SuccessResponse {
     fn from(args: CompletionsResponseBody) -> Self {
@@ -257,18 +512,25 @@ impl From<CompletionsResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ContinueResponseBody {
     /// If true, the 'continue' request has ignored the specified thread and continued all threads instead.
     ///
     /// If this attribute is missing a value of 'true' is assumed for backward compatibility.
-    #[serde(rename = "allThreadsContinued", default = "true_")]
-    #[builder(default)]
+    #[serde(
+        rename = "allThreadsContinued",
+        default = "true_",
+        skip_serializing_if = "is_true"
+    )]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub all_threads_continued: bool,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 impl From<ContinueResponseBody> for SuccessResponse {
     fn from(args: ContinueResponseBody) -> Self {
@@ -276,30 +538,44 @@ impl From<ContinueResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct DataBreakpointInfoResponseBody {
     /// An identifier for the data on which a data breakpoint can be registered with the setDataBreakpoints request or null if no data breakpoint is available.
     #[serde(rename = "dataId")]
-    #[builder(default)]
-    pub data_id: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub data_id: Option<Str>,
 
     /// UI string that describes on what data the breakpoint is set on or why a data breakpoint is not available.
     #[serde(rename = "description")]
-    pub description: String,
+    pub description: Str,
 
     /// Optional attribute listing the available access types for a potential data breakpoint. A UI frontend could surface this information.
     #[serde(rename = "accessTypes", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub access_types: Option<Vec<DataBreakpointAccessType>>,
 
     /// Optional attribute indicating that a potential data breakpoint could be persisted across sessions.
     #[serde(rename = "canPersist", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub can_persist: bool,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl DataBreakpointInfoResponseBody {
+    pub fn new(description: Str) -> DataBreakpointInfoResponseBody {
+        DataBreakpointInfoResponseBody {
+            data_id: Default::default(),
+            description,
+            access_types: Default::default(),
+            can_persist: Default::default(),
+        }
+    }
 }
 impl From<DataBreakpointInfoResponseBody> for SuccessResponse {
     fn from(args: DataBreakpointInfoResponseBody) -> Self {
@@ -307,15 +583,23 @@ impl From<DataBreakpointInfoResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct DisassembleResponseBody {
     /// The list of disassembled instructions.
     #[serde(rename = "instructions")]
     pub instructions: Vec<DisassembledInstruction>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl DisassembleResponseBody {
+    pub fn new(instructions: Vec<DisassembledInstruction>) -> DisassembleResponseBody {
+        DisassembleResponseBody { instructions }
+    }
 }
 impl From<DisassembleResponseBody> for SuccessResponse {
     fn from(args: DisassembleResponseBody) -> Self {
@@ -323,22 +607,31 @@ impl From<DisassembleResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct EvaluateResponseBody {
     /// The result of the evaluate request.
     #[serde(rename = "result")]
-    pub result: String,
+    pub result: Str,
 
     /// The optional type of the evaluate result.
     ///
     /// This attribute should only be returned by a debug adapter if the client has passed the value true for the 'supportsVariableType' capability of the 'initialize' request.
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub type_: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub type_: Option<Str>,
 
     /// Properties of a evaluate result that can be used to determine how to render the result in the UI.
     #[serde(rename = "presentationHint", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub presentation_hint: Option<VariablePresentationHint>,
 
     /// If variablesReference is > 0, the evaluate result is structured and its children can be retrieved by passing variablesReference to the VariablesRequest.
@@ -353,7 +646,8 @@ pub struct EvaluateResponseBody {
     ///
     /// The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(rename = "namedVariables", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub named_variables: Option<i32>,
 
     /// The number of indexed child variables.
@@ -362,7 +656,8 @@ pub struct EvaluateResponseBody {
     ///
     /// The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(rename = "indexedVariables", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub indexed_variables: Option<i32>,
 
     /// Optional memory reference to a location appropriate for this result.
@@ -371,29 +666,61 @@ pub struct EvaluateResponseBody {
     ///
     /// This attribute should be returned by a debug adapter if the client has passed the value true for the 'supportsMemoryReferences' capability of the 'initialize' request.
     #[serde(rename = "memoryReference", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub memory_reference: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub memory_reference: Option<Str>,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// A reference that allows the client to request the location where the returned value is declared. For example, if a function pointer is returned, the adapter may be able to look up the function's location. This should be present only if the adapter is likely to be able to resolve the location.
+    ///
+    /// This reference shares the same lifetime as the 'variablesReference'.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(
+        rename = "valueLocationReference",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub value_location_reference: Option<i32>,
+}
+impl EvaluateResponseBody {
+    pub fn new(result: Str, variables_reference: i32) -> EvaluateResponseBody {
+        EvaluateResponseBody {
+            result,
+            type_: Default::default(),
+            presentation_hint: Default::default(),
+            variables_reference,
+            named_variables: Default::default(),
+            indexed_variables: Default::default(),
+            memory_reference: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            value_location_reference: Default::default(),
+        }
+    }
 }
 impl From<EvaluateResponseBody> for SuccessResponse {
     fn from(args: EvaluateResponseBody) -> Self {
-        Self::Evaluate(args)
+        Self::Evaluate(Box::new(args))
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ExceptionInfoResponseBody {
     /// ID of the exception that was thrown.
     #[serde(rename = "exceptionId")]
-    pub exception_id: String,
+    pub exception_id: Str,
 
     /// Descriptive text for the exception provided by the debug adapter.
     #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub description: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub description: Option<Str>,
 
     /// Mode that caused the exception notification to be raised.
     #[serde(rename = "breakMode")]
@@ -401,28 +728,43 @@ pub struct ExceptionInfoResponseBody {
 
     /// Detailed information about the exception.
     #[serde(rename = "details", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub details: Option<ExceptionDetails>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ExceptionInfoResponseBody {
+    pub fn new(exception_id: Str, break_mode: ExceptionBreakMode) -> ExceptionInfoResponseBody {
+        ExceptionInfoResponseBody {
+            exception_id,
+            description: Default::default(),
+            break_mode,
+            details: Default::default(),
+        }
+    }
 }
 impl From<ExceptionInfoResponseBody> for SuccessResponse {
     fn from(args: ExceptionInfoResponseBody) -> Self {
-        Self::ExceptionInfo(args)
+        Self::ExceptionInfo(Box::new(args))
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct GotoTargetsResponseBody {
     /// The possible goto targets of the specified location.
     #[serde(rename = "targets")]
     pub targets: Vec<GotoTarget>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl GotoTargetsResponseBody {
+    pub fn new(targets: Vec<GotoTarget>) -> GotoTargetsResponseBody {
+        GotoTargetsResponseBody { targets }
+    }
 }
 impl From<GotoTargetsResponseBody> for SuccessResponse {
     fn from(args: GotoTargetsResponseBody) -> Self {
@@ -430,15 +772,23 @@ impl From<GotoTargetsResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct LoadedSourcesResponseBody {
     /// Set of loaded sources.
     #[serde(rename = "sources")]
     pub sources: Vec<Source>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl LoadedSourcesResponseBody {
+    pub fn new(sources: Vec<Source>) -> LoadedSourcesResponseBody {
+        LoadedSourcesResponseBody { sources }
+    }
 }
 impl From<LoadedSourcesResponseBody> for SuccessResponse {
     fn from(args: LoadedSourcesResponseBody) -> Self {
@@ -446,7 +796,69 @@ impl From<LoadedSourcesResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[cfg(feature = "spec-1_70")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
+pub struct LocationsResponseBody {
+    /// The source containing the location; either 'source.path' or 'source.sourceReference' must be specified.
+    #[serde(rename = "source")]
+    pub source: Source,
+
+    /// The line number of the location. The client capability 'linesStartAt1' determines whether it is 0- or 1-based.
+    #[serde(rename = "line")]
+    pub line: i32,
+
+    /// Position of the location within the 'line'. It is measured in UTF-16 code units and the client capability 'columnsStartAt1' determines whether it is 0- or 1-based. If no column is given, the first position in the start line is assumed.
+    #[serde(rename = "column", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub column: Option<i32>,
+
+    /// End line of the location, present if the location refers to a range. The client capability 'linesStartAt1' determines whether it is 0- or 1-based.
+    #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub end_line: Option<i32>,
+
+    /// End position of the location within 'endLine', present if the location refers to a range. It is measured in UTF-16 code units and the client capability 'columnsStartAt1' determines whether it is 0- or 1-based.
+    #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub end_column: Option<i32>,
+}
+#[cfg(feature = "spec-1_70")]
+impl LocationsResponseBody {
+    pub fn new(source: Source, line: i32) -> LocationsResponseBody {
+        LocationsResponseBody {
+            source,
+            line,
+            column: Default::default(),
+            end_line: Default::default(),
+            end_column: Default::default(),
+        }
+    }
+}
+#[cfg(feature = "spec-1_70")]
+impl From<LocationsResponseBody> for SuccessResponse {
+    fn from(args: LocationsResponseBody) -> Self {
+        Self::Locations(Box::new(args))
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ModulesResponseBody {
     /// All modules or range of modules.
     #[serde(rename = "modules")]
@@ -454,12 +866,17 @@ pub struct ModulesResponseBody {
 
     /// The total number of modules available.
     #[serde(rename = "totalModules", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub total_modules: Option<i32>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ModulesResponseBody {
+    pub fn new(modules: Vec<Module>) -> ModulesResponseBody {
+        ModulesResponseBody {
+            modules,
+            total_modules: Default::default(),
+        }
+    }
 }
 impl From<ModulesResponseBody> for SuccessResponse {
     fn from(args: ModulesResponseBody) -> Self {
@@ -467,29 +884,43 @@ impl From<ModulesResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ReadMemoryResponseBody {
     /// The address of the first byte of data returned.
     ///
     /// Treated as a hex value if prefixed with '0x', or as a decimal value otherwise.
     #[serde(rename = "address")]
-    pub address: String,
+    pub address: Str,
 
     /// The number of unreadable bytes encountered after the last successfully read byte.
     ///
     /// This can be used to determine the number of bytes that must be skipped before a subsequent 'readMemory' request will succeed.
     #[serde(rename = "unreadableBytes", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub unreadable_bytes: Option<i32>,
 
     /// The bytes read from memory, encoded using base64.
     #[serde(rename = "data", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub data: Option<String>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub data: Option<Str>,
+}
+impl ReadMemoryResponseBody {
+    pub fn new(address: Str) -> ReadMemoryResponseBody {
+        ReadMemoryResponseBody {
+            address,
+            unreadable_bytes: Default::default(),
+            data: Default::default(),
+        }
+    }
 }
 impl From<ReadMemoryResponseBody> for SuccessResponse {
     fn from(args: ReadMemoryResponseBody) -> Self {
@@ -497,21 +928,26 @@ impl From<ReadMemoryResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct RunInTerminalResponseBody {
     /// The process ID. The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(rename = "processId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub process_id: Option<i32>,
 
     /// The process ID of the terminal shell. The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(rename = "shellProcessId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub shell_process_id: Option<i32>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 impl From<RunInTerminalResponseBody> for SuccessResponse {
     fn from(args: RunInTerminalResponseBody) -> Self {
@@ -519,15 +955,23 @@ impl From<RunInTerminalResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ScopesResponseBody {
     /// The scopes of the stackframe. If the array has length zero, there are no scopes available.
     #[serde(rename = "scopes")]
     pub scopes: Vec<Scope>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ScopesResponseBody {
+    pub fn new(scopes: Vec<Scope>) -> ScopesResponseBody {
+        ScopesResponseBody { scopes }
+    }
 }
 impl From<ScopesResponseBody> for SuccessResponse {
     fn from(args: ScopesResponseBody) -> Self {
@@ -535,17 +979,25 @@ impl From<ScopesResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetBreakpointsResponseBody {
     /// Information about the breakpoints.
     ///
     /// The array elements are in the same order as the elements of the 'breakpoints' (or the deprecated 'lines') array in the arguments.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<Breakpoint>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl SetBreakpointsResponseBody {
+    pub fn new(breakpoints: Vec<Breakpoint>) -> SetBreakpointsResponseBody {
+        SetBreakpointsResponseBody { breakpoints }
+    }
 }
 impl From<SetBreakpointsResponseBody> for SuccessResponse {
     fn from(args: SetBreakpointsResponseBody) -> Self {
@@ -553,15 +1005,23 @@ impl From<SetBreakpointsResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetDataBreakpointsResponseBody {
     /// Information about the data breakpoints. The array elements correspond to the elements of the input argument 'breakpoints' array.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<Breakpoint>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl SetDataBreakpointsResponseBody {
+    pub fn new(breakpoints: Vec<Breakpoint>) -> SetDataBreakpointsResponseBody {
+        SetDataBreakpointsResponseBody { breakpoints }
+    }
 }
 impl From<SetDataBreakpointsResponseBody> for SuccessResponse {
     fn from(args: SetDataBreakpointsResponseBody) -> Self {
@@ -569,18 +1029,22 @@ impl From<SetDataBreakpointsResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetExceptionBreakpointsResponseBody {
     /// Information about the exception breakpoints or filters.
     ///
     /// The breakpoints returned are in the same order as the elements of the 'filters', 'filterOptions', 'exceptionOptions' arrays in the arguments. If both 'filters' and 'filterOptions' are given, the returned array must start with 'filters' information first, followed by 'filterOptions' information.
     #[serde(rename = "breakpoints", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub breakpoints: Option<Vec<Breakpoint>>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 impl From<SetExceptionBreakpointsResponseBody> for SuccessResponse {
     fn from(args: SetExceptionBreakpointsResponseBody) -> Self {
@@ -588,29 +1052,39 @@ impl From<SetExceptionBreakpointsResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetExpressionResponseBody {
     /// The new value of the expression.
     #[serde(rename = "value")]
-    pub value: String,
+    pub value: Str,
 
     /// The optional type of the value.
     ///
     /// This attribute should only be returned by a debug adapter if the client has passed the value true for the 'supportsVariableType' capability of the 'initialize' request.
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub type_: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub type_: Option<Str>,
 
     /// Properties of a value that can be used to determine how to render the result in the UI.
     #[serde(rename = "presentationHint", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub presentation_hint: Option<VariablePresentationHint>,
 
     /// If variablesReference is > 0, the value is structured and its children can be retrieved by passing variablesReference to the VariablesRequest.
     ///
     /// The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(rename = "variablesReference", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub variables_reference: Option<i32>,
 
     /// The number of named child variables.
@@ -619,7 +1093,8 @@ pub struct SetExpressionResponseBody {
     ///
     /// The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(rename = "namedVariables", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub named_variables: Option<i32>,
 
     /// The number of indexed child variables.
@@ -628,28 +1103,70 @@ pub struct SetExpressionResponseBody {
     ///
     /// The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(rename = "indexedVariables", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub indexed_variables: Option<i32>,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// A memory reference to a location appropriate for this result. For pointer type eval results, this is generally a reference to the memory address contained in the pointer.
+    ///
+    /// This attribute may be returned by a debug adapter if the client has passed the value true for the 'supportsMemoryReferences' capability of the 'initialize' request.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "memoryReference", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub memory_reference: Option<Str>,
+
+    /// A reference that allows the client to request the location where the new value is declared. For example, if the new value is a function pointer, the adapter may be able to look up the function's location. This should be present only if the adapter is likely to be able to resolve the location.
+    ///
+    /// This reference shares the same lifetime as the 'variablesReference'.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(
+        rename = "valueLocationReference",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub value_location_reference: Option<i32>,
+}
+impl SetExpressionResponseBody {
+    pub fn new(value: Str) -> SetExpressionResponseBody {
+        SetExpressionResponseBody {
+            value,
+            type_: Default::default(),
+            presentation_hint: Default::default(),
+            variables_reference: Default::default(),
+            named_variables: Default::default(),
+            indexed_variables: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            memory_reference: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            value_location_reference: Default::default(),
+        }
+    }
 }
 impl From<SetExpressionResponseBody> for SuccessResponse {
     fn from(args: SetExpressionResponseBody) -> Self {
-        Self::SetExpression(args)
+        Self::SetExpression(Box::new(args))
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetFunctionBreakpointsResponseBody {
     /// Information about the breakpoints. The array elements correspond to the elements of the 'breakpoints' array.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<Breakpoint>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl SetFunctionBreakpointsResponseBody {
+    pub fn new(breakpoints: Vec<Breakpoint>) -> SetFunctionBreakpointsResponseBody {
+        SetFunctionBreakpointsResponseBody { breakpoints }
+    }
 }
 impl From<SetFunctionBreakpointsResponseBody> for SuccessResponse {
     fn from(args: SetFunctionBreakpointsResponseBody) -> Self {
@@ -657,15 +1174,23 @@ impl From<SetFunctionBreakpointsResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetInstructionBreakpointsResponseBody {
     /// Information about the breakpoints. The array elements correspond to the elements of the 'breakpoints' array.
     #[serde(rename = "breakpoints")]
     pub breakpoints: Vec<Breakpoint>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl SetInstructionBreakpointsResponseBody {
+    pub fn new(breakpoints: Vec<Breakpoint>) -> SetInstructionBreakpointsResponseBody {
+        SetInstructionBreakpointsResponseBody { breakpoints }
+    }
 }
 impl From<SetInstructionBreakpointsResponseBody> for SuccessResponse {
     fn from(args: SetInstructionBreakpointsResponseBody) -> Self {
@@ -673,22 +1198,31 @@ impl From<SetInstructionBreakpointsResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SetVariableResponseBody {
     /// The new value of the variable.
     #[serde(rename = "value")]
-    pub value: String,
+    pub value: Str,
 
     /// The type of the new value. Typically shown in the UI when hovering over the value.
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub type_: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub type_: Option<Str>,
 
     /// If variablesReference is > 0, the new value is structured and its children can be retrieved by passing variablesReference to the VariablesRequest.
     ///
     /// The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(rename = "variablesReference", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub variables_reference: Option<i32>,
 
     /// The number of named child variables.
@@ -697,7 +1231,8 @@ pub struct SetVariableResponseBody {
     ///
     /// The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(rename = "namedVariables", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub named_variables: Option<i32>,
 
     /// The number of indexed child variables.
@@ -706,33 +1241,78 @@ pub struct SetVariableResponseBody {
     ///
     /// The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(rename = "indexedVariables", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub indexed_variables: Option<i32>,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// A memory reference to a location appropriate for this result. For pointer type eval results, this is generally a reference to the memory address contained in the pointer.
+    ///
+    /// This attribute may be returned by a debug adapter if the client has passed the value true for the 'supportsMemoryReferences' capability of the 'initialize' request.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "memoryReference", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub memory_reference: Option<Str>,
+
+    /// A reference that allows the client to request the location where the new value is declared. For example, if the new value is a function pointer, the adapter may be able to look up the function's location. This should be present only if the adapter is likely to be able to resolve the location.
+    ///
+    /// This reference shares the same lifetime as the 'variablesReference'.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(
+        rename = "valueLocationReference",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub value_location_reference: Option<i32>,
+}
+impl SetVariableResponseBody {
+    pub fn new(value: Str) -> SetVariableResponseBody {
+        SetVariableResponseBody {
+            value,
+            type_: Default::default(),
+            variables_reference: Default::default(),
+            named_variables: Default::default(),
+            indexed_variables: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            memory_reference: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            value_location_reference: Default::default(),
+        }
+    }
 }
 impl From<SetVariableResponseBody> for SuccessResponse {
     fn from(args: SetVariableResponseBody) -> Self {
-        Self::SetVariable(args)
+        Self::SetVariable(Box::new(args))
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SourceResponseBody {
     /// Content of the source reference.
     #[serde(rename = "content")]
-    pub content: String,
+    pub content: Str,
 
     /// Optional content type (mime type) of the source.
     #[serde(rename = "mimeType", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub mime_type: Option<String>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub mime_type: Option<Str>,
+}
+impl SourceResponseBody {
+    pub fn new(content: Str) -> SourceResponseBody {
+        SourceResponseBody {
+            content,
+            mime_type: Default::default(),
+        }
+    }
 }
 impl From<SourceResponseBody> for SuccessResponse {
     fn from(args: SourceResponseBody) -> Self {
@@ -740,7 +1320,14 @@ impl From<SourceResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct StackTraceResponseBody {
     /// The frames of the stackframe. If the array has length zero, there are no stackframes available.
     ///
@@ -750,12 +1337,17 @@ pub struct StackTraceResponseBody {
 
     /// The total number of frames available in the stack. If omitted or if totalFrames is larger than the available frames, a client is expected to request frames until a request returns less frames than requested (which indicates the end of the stack). Returning monotonically increasing totalFrames values for subsequent requests can be used to enforce paging in the client.
     #[serde(rename = "totalFrames", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub total_frames: Option<i32>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl StackTraceResponseBody {
+    pub fn new(stack_frames: Vec<StackFrame>) -> StackTraceResponseBody {
+        StackTraceResponseBody {
+            stack_frames,
+            total_frames: Default::default(),
+        }
+    }
 }
 impl From<StackTraceResponseBody> for SuccessResponse {
     fn from(args: StackTraceResponseBody) -> Self {
@@ -763,15 +1355,23 @@ impl From<StackTraceResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct StepInTargetsResponseBody {
     /// The possible stepIn targets of the specified source location.
     #[serde(rename = "targets")]
     pub targets: Vec<StepInTarget>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl StepInTargetsResponseBody {
+    pub fn new(targets: Vec<StepInTarget>) -> StepInTargetsResponseBody {
+        StepInTargetsResponseBody { targets }
+    }
 }
 impl From<StepInTargetsResponseBody> for SuccessResponse {
     fn from(args: StepInTargetsResponseBody) -> Self {
@@ -779,15 +1379,23 @@ impl From<StepInTargetsResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ThreadsResponseBody {
     /// All threads.
     #[serde(rename = "threads")]
     pub threads: Vec<Thread>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl ThreadsResponseBody {
+    pub fn new(threads: Vec<Thread>) -> ThreadsResponseBody {
+        ThreadsResponseBody { threads }
+    }
 }
 impl From<ThreadsResponseBody> for SuccessResponse {
     fn from(args: ThreadsResponseBody) -> Self {
@@ -795,15 +1403,23 @@ impl From<ThreadsResponseBody> for SuccessResponse {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct VariablesResponseBody {
     /// All (or a range) of variables for the given variable reference.
     #[serde(rename = "variables")]
     pub variables: Vec<Variable>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+}
+impl VariablesResponseBody {
+    pub fn new(variables: Vec<Variable>) -> VariablesResponseBody {
+        VariablesResponseBody { variables }
+    }
 }
 impl From<VariablesResponseBody> for SuccessResponse {
     fn from(args: VariablesResponseBody) -> Self {
@@ -811,54 +1427,231 @@ impl From<VariablesResponseBody> for SuccessResponse {
     }
 }
 
-// Workaround from https://stackoverflow.com/a/65576570
-// for https://github.com/serde-rs/serde/issues/745
+#[cfg(feature = "de")]
+impl<'de> Deserialize<'de> for Response {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(ResponseVisitor)
+    }
+}
+
+#[cfg(feature = "de")]
+#[derive(Deserialize)]
+#[serde(field_identifier)]
+enum ResponseField {
+    #[serde(rename = "request_seq")]
+    RequestSeq,
+    #[serde(rename = "success")]
+    Success,
+    #[serde(rename = "command")]
+    Command,
+    #[serde(rename = "message")]
+    Message,
+    #[serde(rename = "body")]
+    Body,
+    #[serde(other)]
+    Other,
+}
 
-fn deserialize_response_result<'de, D>(
-    deserializer: D,
-) -> Result<Result<SuccessResponse, ErrorResponse>, D::Error>
+#[cfg(feature = "de")]
+enum ResponseBody {
+    Success(SuccessResponse),
+    Error(ErrorResponseBody),
+    /// The body preceded 'success' or 'command', so its type was not known yet.
+    Buffered(Value),
+}
+
+/// Deserializes a response in a single pass: the body is deserialized directly into the type
+/// selected by 'success' and 'command' if these precede it, which is the common case. Only a body
+/// that comes first is buffered.
+#[cfg(feature = "de")]
+struct ResponseVisitor;
+
+#[cfg(feature = "de")]
+impl<'de> Visitor<'de> for ResponseVisitor {
+    type Value = Response;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a response")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Response, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut request_seq = None;
+        let mut success = None;
+        let mut command: Option<Str> = None;
+        let mut message = None;
+        let mut body = None;
+        while let Some(field) = map.next_key()? {
+            match field {
+                ResponseField::RequestSeq => set(&mut request_seq, "request_seq", &mut map)?,
+                ResponseField::Success => set(&mut success, "success", &mut map)?,
+                ResponseField::Command => set(&mut command, "command", &mut map)?,
+                ResponseField::Message => set(&mut message, "message", &mut map)?,
+                ResponseField::Body => {
+                    if body.is_some() {
+                        return Err(Error::duplicate_field("body"));
+                    }
+                    body = Some(match (success, &command) {
+                        (Some(true), Some(command)) => ResponseBody::Success(
+                            map.next_value_seed(SuccessResponseSeed(command))
+                                .map_err(|e| context(true, command, e))?,
+                        ),
+                        (Some(false), Some(command)) => ResponseBody::Error(
+                            map.next_value().map_err(|e| context(false, command, e))?,
+                        ),
+                        _ => ResponseBody::Buffered(map.next_value()?),
+                    });
+                }
+                ResponseField::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        let request_seq = request_seq.ok_or_else(|| Error::missing_field("request_seq"))?;
+        let success = success.ok_or_else(|| Error::missing_field("success"))?;
+        let command = command.ok_or_else(|| Error::missing_field("command"))?;
+        let result = if success {
+            Ok(match body {
+                Some(ResponseBody::Success(response)) => response,
+                Some(ResponseBody::Buffered(body)) => {
+                    <SuccessResponse as Deserialize>::deserialize(MapAccessDeserializer::new(
+                        TaggedBody {
+                            command: Some(&command),
+                            body: Some(body),
+                        },
+                    ))
+                    .map_err(|e| context(true, &command, e))?
+                }
+                Some(ResponseBody::Error(_)) => unreachable_body()?,
+                None => <SuccessResponse as Deserialize>::deserialize(MapAccessDeserializer::new(
+                    TaggedBody {
+                        command: Some(&command),
+                        body: None::<Value>,
+                    },
+                ))
+                .map_err(|e| context(true, &command, e))?,
+            })
+        } else {
+            let message = message.ok_or_else(|| Error::missing_field("message"))?;
+            let body = match body {
+                Some(ResponseBody::Error(body)) => body,
+                Some(ResponseBody::Buffered(body)) => {
+                    ErrorResponseBody::deserialize(body).map_err(|e| context(false, &command, e))?
+                }
+                Some(ResponseBody::Success(_)) => unreachable_body()?,
+                None => ErrorResponseBody::default(),
+            };
+            Err(ErrorResponse {
+                command,
+                message,
+                body: Box::new(body),
+            })
+        };
+        Ok(Response {
+            request_seq,
+            result,
+        })
+    }
+}
+
+#[cfg(feature = "de")]
+fn set<'de, T, A>(slot: &mut Option<T>, field: &'static str, map: &mut A) -> Result<(), A::Error>
 where
-    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+    A: MapAccess<'de>,
 {
-    let value = Value::deserialize(deserializer)?;
+    if slot.is_some() {
+        return Err(Error::duplicate_field(field));
+    }
+    *slot = Some(map.next_value()?);
+    Ok(())
+}
 
-    let success = value
-        .get("success")
-        .ok_or_else(|| Error::missing_field("success"))?
-        .as_bool()
-        .ok_or_else(|| Error::invalid_type(unexpected_value(&value), &"success bool"))?;
+/// The body is only deserialized eagerly after 'success' and a second 'success' is rejected, so
+/// its type always agrees with 'success'.
+#[cfg(feature = "de")]
+fn unreachable_body<T, E: Error>() -> Result<T, E> {
+    Err(Error::custom("body does not match success"))
+}
 
-    Ok(if success {
-        Ok(Deserialize::deserialize(value).map_err(|e| Error::custom(e.to_string()))?)
+#[cfg(feature = "de")]
+fn context<E: Display, F: Error>(success: bool, command: &str, e: E) -> F {
+    let kind = if success {
+        "response"
     } else {
-        Err(Deserialize::deserialize(value).map_err(|e| Error::custom(e.to_string()))?)
-    })
+        "error response"
+    };
+    F::custom(format_args!("invalid '{}' {}: {}", command, kind, e))
 }
 
-fn unexpected_value<'l>(value: &'l Value) -> Unexpected<'l> {
-    match value {
-        Value::Null => Unexpected::Other("null"),
-        Value::Bool(b) => Unexpected::Bool(*b),
-        Value::Number(n) => unexpected_number(n),
-        Value::String(s) => Unexpected::Str(s),
-        Value::Array(_) => Unexpected::Seq,
-        Value::Object(_) => Unexpected::Map,
+/// Deserializes a [SuccessResponse] from the body of a response with the given command.
+#[cfg(feature = "de")]
+struct SuccessResponseSeed<'a>(&'a str);
+
+#[cfg(feature = "de")]
+impl<'de> DeserializeSeed<'de> for SuccessResponseSeed<'_> {
+    type Value = SuccessResponse;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<SuccessResponse, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <SuccessResponse as Deserialize>::deserialize(MapAccessDeserializer::new(TaggedBody {
+            command: Some(self.0),
+            body: Some(deserializer),
+        }))
     }
 }
 
-fn unexpected_number(number: &Number) -> Unexpected<'static> {
-    if number.is_f64() {
-        return Unexpected::Float(number.as_f64().unwrap());
-    }
-    if number.is_u64() {
-        return Unexpected::Unsigned(number.as_u64().unwrap());
+/// Presents a command and an optional body as the map `{"command": ..., "body": ...}` expected by
+/// the adjacently tagged [SuccessResponse].
+#[cfg(feature = "de")]
+struct TaggedBody<'a, D> {
+    command: Option<&'a str>,
+    body: Option<D>,
+}
+
+#[cfg(feature = "de")]
+impl<'de, D> MapAccess<'de> for TaggedBody<'_, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, D::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let key = if self.command.is_some() {
+            "command"
+        } else if self.body.is_some() {
+            "body"
+        } else {
+            return Ok(None);
+        };
+        seed.deserialize(StrDeserializer::new(key)).map(Some)
     }
-    if number.is_i64() {
-        return Unexpected::Signed(number.as_i64().unwrap());
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, D::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        if let Some(command) = self.command.take() {
+            seed.deserialize(StrDeserializer::new(command))
+        } else if let Some(body) = self.body.take() {
+            seed.deserialize(body)
+        } else {
+            Err(Error::custom("value is missing"))
+        }
     }
-    panic!("Unknown number {}", number)
 }
 
+#[cfg(feature = "ser")]
 fn serialize_response_result<S>(
     result: &Result<SuccessResponse, ErrorResponse>,
     serializer: S,
@@ -892,3 +1685,46 @@ where
     };
     serializable.serialize(serializer)
 }
+
+/// Written by hand, because the derive can't flatten the result into the response.
+#[cfg(feature = "ts-rs")]
+impl TS for Response {
+    type WithoutGenerics = Self;
+    type OptionInnerType = Self;
+
+    fn docs() -> Option<String> {
+        Some(ts_rs::format_docs(&[" Response for a request."]))
+    }
+
+    fn name(_: &ts_rs::Config) -> String {
+        "Response".to_string()
+    }
+
+    fn inline(config: &ts_rs::Config) -> String {
+        format!(
+            "{{ request_seq: number, }} & ({{ \"success\": true }} & {} | {{ \"success\": false }} & {})",
+            SuccessResponse::name(config),
+            ErrorResponse::name(config)
+        )
+    }
+
+    fn decl(config: &ts_rs::Config) -> String {
+        format!("type Response = {};", Self::inline(config))
+    }
+
+    fn decl_concrete(config: &ts_rs::Config) -> String {
+        Self::decl(config)
+    }
+
+    fn visit_dependencies(visitor: &mut impl ts_rs::TypeVisitor)
+    where
+        Self: 'static,
+    {
+        visitor.visit::<SuccessResponse>();
+        visitor.visit::<ErrorResponse>();
+    }
+
+    fn output_path() -> Option<std::path::PathBuf> {
+        Some(std::path::PathBuf::from("Response.ts"))
+    }
+}
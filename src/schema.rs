@@ -0,0 +1,268 @@
+//! Validation of raw JSON against the JSON schema of the specification.
+//!
+//! The types of this crate accept some messages that violate the specification and reject others
+//! as a whole, which makes it hard to tell what exactly a third party adapter or client got wrong.
+//! A [Schema] checks the raw JSON of a message against `debugAdapterProtocol.json` and reports
+//! every violation with the path of the offending field.
+//!
+//! [Schema::bundled] returns the schema that ships with this crate. It is the
+//! `debugAdapterProtocol.json` that debugpy 1.8.7 vendors, so it predates version 1.65 of the
+//! specification. Attributes added later are not described by it but still accepted, because the
+//! schema allows additional properties.
+//!
+//! Only the keywords used by the specification are supported: `$ref` to local definitions,
+//! `allOf`, `anyOf`, `oneOf`, `type`, `enum`, `properties`, `required`, `additionalProperties` and
+//! `items`.
+
+use crate::validate::{key, Violation};
+use serde_json::{Map, Value};
+use std::str::FromStr;
+
+const BUNDLED: &str = include_str!("debugAdapterProtocol.json");
+
+/// The JSON schema of the specification, `debugAdapterProtocol.json`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schema {
+    definitions: Map<String, Value>,
+}
+
+impl Schema {
+    /// Creates a schema from the parsed content of `debugAdapterProtocol.json`.
+    pub fn new(mut schema: Value) -> Schema {
+        let definitions = match schema.get_mut("definitions").map(Value::take) {
+            Some(Value::Object(definitions)) => definitions,
+            _ => Map::new(),
+        };
+        Schema { definitions }
+    }
+
+    /// Returns the schema that ships with this crate, see the [module documentation](self).
+    pub fn bundled() -> Schema {
+        BUNDLED.parse().expect("the bundled schema is valid JSON")
+    }
+
+    /// Returns the schema of the definition called `name`, for example `StackFrame`.
+    pub fn definition(&self, name: &str) -> Option<&Value> {
+        self.definitions.get(name)
+    }
+
+    /// Checks a message against the definition for its type and command or event, for example
+    /// `StoppedEvent` or `ContinueResponse`. Messages with a command or event that the schema does
+    /// not define are checked against `Request`, `Response` or `Event`.
+    pub fn validate_message(&self, message: &Value) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let name = self
+            .message_definition(message)
+            .unwrap_or("ProtocolMessage");
+        self.validate_definition_at(name, message, "", &mut violations);
+        violations
+    }
+
+    /// Parses `json` and checks it with [validate_message](Schema::validate_message).
+    pub fn validate_str(&self, json: &str) -> serde_json::Result<Vec<Violation>> {
+        Ok(self.validate_message(&serde_json::from_str(json)?))
+    }
+
+    /// Checks `value` against the definition called `name`, for example `Source`.
+    pub fn validate_definition(&self, name: &str, value: &Value) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        self.validate_definition_at(name, value, "", &mut violations);
+        violations
+    }
+
+    fn message_definition(&self, message: &Value) -> Option<&str> {
+        let (base, name, suffix) = match message.get("type")?.as_str()? {
+            "request" => ("Request", message.get("command"), "Request"),
+            "event" => ("Event", message.get("event"), "Event"),
+            "response" if message.get("success") == Some(&Value::Bool(false)) => {
+                return Some("ErrorResponse").filter(|name| self.definitions.contains_key(*name))
+            }
+            "response" => ("Response", message.get("command"), "Response"),
+            _ => return None,
+        };
+        let specific = name
+            .and_then(Value::as_str)
+            .map(|name| format!("{}{}", capitalize(name), suffix));
+        let definition = [specific.as_deref(), Some(base)]
+            .into_iter()
+            .flatten()
+            .find_map(|name| self.definitions.get_key_value(name));
+        definition.map(|(name, _)| name.as_str())
+    }
+
+    fn validate_definition_at(
+        &self,
+        name: &str,
+        value: &Value,
+        path: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        match self.definitions.get(name) {
+            Some(schema) => self.validate_at(schema, value, path, violations),
+            None => violations.push(Violation::new(
+                path.to_string(),
+                format!("the schema does not define '{}'", name),
+            )),
+        }
+    }
+
+    fn validate_at(
+        &self,
+        schema: &Value,
+        value: &Value,
+        path: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = reference.trim_start_matches("#/definitions/");
+            self.validate_definition_at(name, value, path, violations);
+        }
+        for schema in subschemas(schema, "allOf") {
+            self.validate_at(schema, value, path, violations);
+        }
+        let any_of = subschemas(schema, "anyOf");
+        if !any_of.is_empty() && !any_of.iter().any(|schema| self.is_valid(schema, value)) {
+            violations.push(Violation::new(
+                path.to_string(),
+                "value does not match any schema of 'anyOf'",
+            ));
+        }
+        let one_of = subschemas(schema, "oneOf");
+        if !one_of.is_empty() {
+            match one_of
+                .iter()
+                .filter(|schema| self.is_valid(schema, value))
+                .count()
+            {
+                1 => {}
+                0 => violations.push(Violation::new(
+                    path.to_string(),
+                    "value does not match any schema of 'oneOf'",
+                )),
+                matches => violations.push(Violation::new(
+                    path.to_string(),
+                    format!("value matches {} schemas of 'oneOf'", matches),
+                )),
+            }
+        }
+        if let Some(types) = schema.get("type") {
+            let matches = match types {
+                Value::String(expected) => has_type(value, expected),
+                Value::Array(types) => types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .any(|expected| has_type(value, expected)),
+                _ => true,
+            };
+            if !matches {
+                violations.push(Violation::new(
+                    path.to_string(),
+                    format!("expected {} but got {}", types, type_name(value)),
+                ));
+                return;
+            }
+        }
+        if let Some(Value::Array(values)) = schema.get("enum") {
+            if !values.contains(value) {
+                violations.push(Violation::new(
+                    path.to_string(),
+                    format!("{} is not one of {}", value, Value::Array(values.clone())),
+                ));
+            }
+        }
+        if let Value::Object(object) = value {
+            self.validate_object_at(schema, object, path, violations);
+        }
+        if let (Some(items), Value::Array(values)) = (schema.get("items"), value) {
+            for (index, value) in values.iter().enumerate() {
+                self.validate_at(items, value, &format!("{}[{}]", path, index), violations);
+            }
+        }
+    }
+
+    fn validate_object_at(
+        &self,
+        schema: &Value,
+        object: &Map<String, Value>,
+        path: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    violations.push(Violation::new(
+                        key(path, name),
+                        "required property is missing",
+                    ));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let additional = schema.get("additionalProperties");
+        for (name, value) in object {
+            match (
+                properties.and_then(|properties| properties.get(name)),
+                additional,
+            ) {
+                (Some(property), _) => {
+                    self.validate_at(property, value, &key(path, name), violations)
+                }
+                (None, Some(Value::Bool(false))) => {
+                    violations.push(Violation::new(key(path, name), "property is not allowed"))
+                }
+                (None, Some(additional @ Value::Object(_))) => {
+                    self.validate_at(additional, value, &key(path, name), violations)
+                }
+                (None, _) => {}
+            }
+        }
+    }
+
+    fn is_valid(&self, schema: &Value, value: &Value) -> bool {
+        let mut violations = Vec::new();
+        self.validate_at(schema, value, "", &mut violations);
+        violations.is_empty()
+    }
+}
+
+impl FromStr for Schema {
+    type Err = serde_json::Error;
+
+    fn from_str(json: &str) -> serde_json::Result<Schema> {
+        Ok(Schema::new(serde_json::from_str(json)?))
+    }
+}
+
+fn subschemas<'a>(schema: &'a Value, keyword: &str) -> &'a [Value] {
+    match schema.get(keyword) {
+        Some(Value::Array(schemas)) => schemas,
+        _ => &[],
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        expected => type_name(value) == expected,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
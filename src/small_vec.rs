@@ -0,0 +1,300 @@
+//! A vector that stores up to one element inline.
+
+use serde::{
+    de::{SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    iter::FromIterator,
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    option, vec,
+};
+
+/// A vector for arrays that almost always hold zero or one elements, like the
+/// 'hitBreakpointIds' of a 'stopped' event.
+///
+/// Unlike a [Vec] it only allocates once it holds a second element. It dereferences to a slice and
+/// serializes like a [Vec].
+#[derive(Clone)]
+pub struct InlineVec<T>(Repr<T>);
+
+#[derive(Clone)]
+enum Repr<T> {
+    Inline(Option<T>),
+    Heap(Vec<T>),
+}
+
+impl<T> InlineVec<T> {
+    pub const fn new() -> InlineVec<T> {
+        InlineVec(Repr::Inline(None))
+    }
+
+    /// Returns true if the elements are stored on the heap.
+    pub fn spilled(&self) -> bool {
+        matches!(self.0, Repr::Heap(_))
+    }
+
+    pub fn push(&mut self, value: T) {
+        match &mut self.0 {
+            Repr::Inline(inline @ None) => *inline = Some(value),
+            Repr::Inline(inline @ Some(_)) => {
+                let first = inline.take();
+                self.0 = Repr::Heap(first.into_iter().chain(Some(value)).collect());
+            }
+            Repr::Heap(heap) => heap.push(value),
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        match &mut self.0 {
+            Repr::Inline(inline) => inline.take(),
+            Repr::Heap(heap) => heap.pop(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.0 = Repr::Inline(None);
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        match self.0 {
+            Repr::Inline(inline) => inline.into_iter().collect(),
+            Repr::Heap(heap) => heap,
+        }
+    }
+}
+
+impl<T> Default for InlineVec<T> {
+    fn default() -> Self {
+        InlineVec::new()
+    }
+}
+
+impl<T> Deref for InlineVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match &self.0 {
+            Repr::Inline(inline) => inline.as_slice(),
+            Repr::Heap(heap) => heap,
+        }
+    }
+}
+
+impl<T> DerefMut for InlineVec<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        match &mut self.0 {
+            Repr::Inline(inline) => inline.as_mut_slice(),
+            Repr::Heap(heap) => heap,
+        }
+    }
+}
+
+impl<T: Debug> Debug for InlineVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// Compares elements rather than representations, since a popped heap may hold a single element.
+impl<T: PartialEq> PartialEq for InlineVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Eq> Eq for InlineVec<T> {}
+
+impl<T: Hash> Hash for InlineVec<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<T> From<Vec<T>> for InlineVec<T> {
+    fn from(mut vec: Vec<T>) -> Self {
+        if vec.len() <= 1 {
+            InlineVec(Repr::Inline(vec.pop()))
+        } else {
+            InlineVec(Repr::Heap(vec))
+        }
+    }
+}
+
+impl<T> From<InlineVec<T>> for Vec<T> {
+    fn from(vec: InlineVec<T>) -> Self {
+        vec.into_vec()
+    }
+}
+
+impl<T> FromIterator<T> for InlineVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut iter = iter.into_iter().fuse();
+        let first = iter.next();
+        match iter.next() {
+            None => InlineVec(Repr::Inline(first)),
+            Some(second) => InlineVec(Repr::Heap(
+                first.into_iter().chain(Some(second)).chain(iter).collect(),
+            )),
+        }
+    }
+}
+
+impl<T> Extend<T> for InlineVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T> IntoIterator for InlineVec<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(match self.0 {
+            Repr::Inline(inline) => IntoIterRepr::Inline(inline.into_iter()),
+            Repr::Heap(heap) => IntoIterRepr::Heap(heap.into_iter()),
+        })
+    }
+}
+
+impl<'a, T> IntoIterator for &'a InlineVec<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An owning iterator over the elements of an [InlineVec].
+pub struct IntoIter<T>(IntoIterRepr<T>);
+
+enum IntoIterRepr<T> {
+    Inline(option::IntoIter<T>),
+    Heap(vec::IntoIter<T>),
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match &mut self.0 {
+            IntoIterRepr::Inline(inline) => inline.next(),
+            IntoIterRepr::Heap(heap) => heap.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            IntoIterRepr::Inline(inline) => inline.size_hint(),
+            IntoIterRepr::Heap(heap) => heap.size_hint(),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for InlineVec<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for InlineVec<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct InlineVecVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for InlineVecVisitor<T> {
+            type Value = InlineVec<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut vec = InlineVec::new();
+                while let Some(value) = seq.next_element()? {
+                    vec.push(value);
+                }
+                Ok(vec)
+            }
+        }
+
+        deserializer.deserialize_seq(InlineVecVisitor(PhantomData))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T: arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for InlineVec<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_iter()?.collect()
+    }
+
+    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_take_rest_iter()?.collect()
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Vec::<T>::size_hint(depth)
+    }
+}
+
+/// Describes the same schema as a [Vec].
+#[cfg(feature = "schemars")]
+impl<T: schemars::JsonSchema> schemars::JsonSchema for InlineVec<T> {
+    fn inline_schema() -> bool {
+        Vec::<T>::inline_schema()
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        Vec::<T>::schema_name()
+    }
+
+    fn schema_id() -> std::borrow::Cow<'static, str> {
+        Vec::<T>::schema_id()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        Vec::<T>::json_schema(generator)
+    }
+}
+
+/// Exports the same TypeScript type as a [Vec].
+#[cfg(feature = "ts-rs")]
+impl<T: ts_rs::TS> ts_rs::TS for InlineVec<T> {
+    type WithoutGenerics = Vec<ts_rs::Dummy>;
+    type OptionInnerType = Self;
+
+    fn ident(config: &ts_rs::Config) -> String {
+        Vec::<T>::ident(config)
+    }
+
+    fn name(config: &ts_rs::Config) -> String {
+        Vec::<T>::name(config)
+    }
+
+    fn inline(config: &ts_rs::Config) -> String {
+        Vec::<T>::inline(config)
+    }
+
+    fn visit_dependencies(visitor: &mut impl ts_rs::TypeVisitor)
+    where
+        Self: 'static,
+    {
+        Vec::<T>::visit_dependencies(visitor)
+    }
+
+    fn visit_generics(visitor: &mut impl ts_rs::TypeVisitor)
+    where
+        Self: 'static,
+    {
+        Vec::<T>::visit_generics(visitor)
+    }
+}
+
+const _: () = assert!(mem::size_of::<InlineVec<i32>>() <= mem::size_of::<Vec<i32>>());
@@ -0,0 +1,105 @@
+//! Sources of the sequence numbers and timestamps of a session.
+//!
+//! The [Driver](crate::driver::Driver), the `MockClient` of the `mock` feature and the
+//! [TraceWriter](crate::trace::TraceWriter) take these as parameters, so tests can inject
+//! deterministic values and compare the produced traffic byte for byte. Time windows, like the
+//! one of the `OutputCoalescer` of the `events` feature, are measured with a [MonotonicClock]
+//! instead, which cannot jump when the system time is adjusted.
+
+use crate::SequenceNumber;
+use std::time::{Instant, SystemTime};
+
+/// Assigns the sequence numbers of outgoing messages.
+pub trait SequenceSource {
+    /// Returns the sequence number for the next outgoing message.
+    fn next_seq(&mut self) -> SequenceNumber;
+}
+
+impl<F: FnMut() -> SequenceNumber> SequenceSource for F {
+    fn next_seq(&mut self) -> SequenceNumber {
+        self()
+    }
+}
+
+/// Counts up from 1, which is how sequence numbers are assigned by default.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Counter {
+    next: SequenceNumber,
+}
+
+impl Default for Counter {
+    fn default() -> Counter {
+        Counter::starting_at(1)
+    }
+}
+
+impl Counter {
+    pub fn new() -> Counter {
+        Counter::default()
+    }
+
+    /// Creates a counter whose first sequence number is `seq`.
+    pub fn starting_at(seq: SequenceNumber) -> Counter {
+        Counter { next: seq }
+    }
+}
+
+impl SequenceSource for Counter {
+    fn next_seq(&mut self) -> SequenceNumber {
+        let seq = self.next;
+        self.next += 1;
+        seq
+    }
+}
+
+/// Tells the time at which messages are captured.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+impl<F: Fn() -> SystemTime> Clock for F {
+    fn now(&self) -> SystemTime {
+        self()
+    }
+}
+
+/// The system clock, used by default.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that always tells the same time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedClock(pub SystemTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+/// Tells the time at which time windows start and end.
+pub trait MonotonicClock {
+    fn now(&self) -> Instant;
+}
+
+impl<F: Fn() -> Instant> MonotonicClock for F {
+    fn now(&self) -> Instant {
+        self()
+    }
+}
+
+/// The monotonic clock of the system, used by default.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct InstantClock;
+
+impl MonotonicClock for InstantClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
@@ -0,0 +1,56 @@
+//! Versions of the specification.
+//!
+//! Every version of the specification that this crate implements has a feature, for example
+//! `spec-1_47`, and `spec-latest` enables the newest one. Types whose shape did not change are
+//! shared by all versions, while additions of a version are only compiled with its feature. This
+//! lets consumers that talk to an older generation of debug adapters pin a version and avoid
+//! fields that those adapters don't know, while others track the latest specification.
+//!
+//! 1.47 is the oldest version this crate implements, so all of its types are shared. The next
+//! version it implements is 1.70, so `spec-1_70` gates everything that was added in between.
+
+use std::{
+    fmt::{self, Display},
+    num::ParseIntError,
+    str::FromStr,
+};
+
+/// A version of the specification, for example 1.47.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SpecVersion {
+    pub major: u16,
+    pub minor: u16,
+}
+
+impl SpecVersion {
+    pub const V1_47: SpecVersion = SpecVersion::new(1, 47);
+    pub const V1_70: SpecVersion = SpecVersion::new(1, 70);
+
+    pub const fn new(major: u16, minor: u16) -> SpecVersion {
+        SpecVersion { major, minor }
+    }
+}
+
+/// The version of the specification that the enabled features implement.
+#[cfg(feature = "spec-1_70")]
+pub const VERSION: SpecVersion = SpecVersion::V1_70;
+
+/// The version of the specification that the enabled features implement.
+#[cfg(not(feature = "spec-1_70"))]
+pub const VERSION: SpecVersion = SpecVersion::V1_47;
+
+impl Display for SpecVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for SpecVersion {
+    type Err = ParseIntError;
+
+    /// Parses a version of the form `1.47`. A missing minor version is parsed as 0.
+    fn from_str(version: &str) -> Result<SpecVersion, ParseIntError> {
+        let (major, minor) = version.split_once('.').unwrap_or((version, "0"));
+        Ok(SpecVersion::new(major.parse()?, minor.parse()?))
+    }
+}
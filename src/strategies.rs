@@ -0,0 +1,504 @@
+//! [proptest](mod@proptest) strategies for protocol messages.
+//!
+//! Unlike the `Arbitrary` implementations of the `arbitrary` feature, these generate values a debug
+//! adapter could actually see: small positive ids, one-based lines, file paths and identifiers,
+//! with the common variants of an enum more likely than the rare ones. They cover the requests,
+//! responses and events of a typical stepping session. Combine them with the usual proptest
+//! combinators to build strategies for other messages.
+
+#[cfg(feature = "events")]
+use crate::events::{
+    BreakpointEventBody, BreakpointEventReason, ContinuedEventBody, Event, ExitedEventBody,
+    OutputCategory, OutputEventBody, StoppedEventBody, StoppedEventReason, TerminatedEventBody,
+    ThreadEventBody, ThreadEventReason,
+};
+#[cfg(feature = "requests")]
+use crate::{
+    requests::{
+        ContinueRequestArguments, DisconnectRequestArguments, EvaluateRequestArguments,
+        EvaluateRequestContext, InitializeRequestArguments, NextRequestArguments, PathFormat,
+        PauseRequestArguments, Request, ScopesRequestArguments, SetBreakpointsRequestArguments,
+        StackTraceRequestArguments, StepInRequestArguments, StepOutRequestArguments,
+        VariablesRequestArguments,
+    },
+    types::SteppingGranularity,
+};
+#[cfg(feature = "responses")]
+use crate::{
+    responses::{
+        ContinueResponseBody, ErrorResponse, ErrorResponseBody, EvaluateResponseBody, Response,
+        ScopesResponseBody, SetBreakpointsResponseBody, StackTraceResponseBody, SuccessResponse,
+        ThreadsResponseBody, VariablesResponseBody,
+    },
+    types::Message,
+};
+use crate::{
+    types::{
+        Breakpoint, Scope, ScopePresentationHint, Source, SourceBreakpoint, StackFrame, Thread,
+        Variable,
+    },
+    ProtocolMessage, ProtocolMessageContent, SequenceNumber, Str,
+};
+#[cfg(any(feature = "requests", feature = "responses"))]
+use proptest::collection::vec;
+use proptest::{option, prelude::*, strategy::Union};
+#[cfg(feature = "responses")]
+use serde_json::Map;
+
+/// A protocol message of any of the enabled kinds.
+pub fn protocol_message() -> impl Strategy<Value = ProtocolMessage> {
+    let contents: Vec<BoxedStrategy<ProtocolMessageContent>> = vec![
+        #[cfg(feature = "requests")]
+        request().prop_map(ProtocolMessageContent::from).boxed(),
+        #[cfg(feature = "responses")]
+        response().prop_map(ProtocolMessageContent::from).boxed(),
+        #[cfg(feature = "events")]
+        event().prop_map(ProtocolMessageContent::from).boxed(),
+    ];
+    (seq(), Union::new(contents)).prop_map(|(seq, content)| ProtocolMessage::new(seq, content))
+}
+
+pub fn seq() -> impl Strategy<Value = SequenceNumber> {
+    1..=100_000u64
+}
+
+/// An id of a thread, frame, breakpoint or similar, which adapters hand out counting up from one.
+pub fn id() -> impl Strategy<Value = i32> {
+    1..=1_000
+}
+
+/// A 'variablesReference', which is zero for the many variables without children.
+pub fn variables_reference() -> impl Strategy<Value = i32> {
+    prop_oneof![3 => Just(0), 1 => id()]
+}
+
+pub fn line() -> impl Strategy<Value = i32> {
+    1..=5_000
+}
+
+pub fn column() -> impl Strategy<Value = i32> {
+    1..=120
+}
+
+/// A name of a function, variable or thread.
+pub fn identifier() -> impl Strategy<Value = Str> {
+    "[a-z_][a-z0-9_]{0,15}".prop_map(Str::from)
+}
+
+/// An absolute path of a source file.
+pub fn path() -> impl Strategy<Value = Str> {
+    "(/[a-z][a-z0-9_]{0,9}){1,4}\\.(c|js|py|rs)".prop_map(Str::from)
+}
+
+pub fn source() -> impl Strategy<Value = Source> {
+    path().prop_map(|path| Source {
+        name: path.rsplit('/').next().map(Str::from),
+        path: Some(path),
+        source_reference: None,
+        presentation_hint: None,
+        origin: None,
+        sources: Vec::new(),
+        adapter_data: None,
+        checksums: Default::default(),
+    })
+}
+
+pub fn source_breakpoint() -> impl Strategy<Value = SourceBreakpoint> {
+    (
+        line(),
+        option::weighted(0.1, column()),
+        option::weighted(0.2, "[a-z] [<>=]= [0-9]{1,3}".prop_map(Str::from)),
+    )
+        .prop_map(|(line, column, condition)| SourceBreakpoint {
+            line,
+            column,
+            condition,
+            hit_condition: None,
+            log_message: None,
+            #[cfg(feature = "spec-1_70")]
+            mode: None,
+        })
+}
+
+pub fn breakpoint() -> impl Strategy<Value = Breakpoint> {
+    (id(), prop::bool::weighted(0.9), source(), line()).prop_map(|(id, verified, source, line)| {
+        Breakpoint {
+            id: Some(id),
+            verified,
+            message: None,
+            source: Some(source),
+            line: Some(line),
+            column: None,
+            end_line: None,
+            end_column: None,
+            instruction_reference: None,
+            offset: None,
+        }
+    })
+}
+
+pub fn thread() -> impl Strategy<Value = Thread> {
+    (id(), identifier()).prop_map(|(id, name)| Thread { id, name })
+}
+
+pub fn stack_frame() -> impl Strategy<Value = StackFrame> {
+    (
+        id(),
+        identifier(),
+        option::weighted(0.9, source()),
+        line(),
+        column(),
+    )
+        .prop_map(|(id, name, source, line, column)| StackFrame {
+            id,
+            name,
+            source,
+            line,
+            column,
+            end_line: None,
+            end_column: None,
+            can_restart: None,
+            instruction_pointer_reference: None,
+            module_id: None,
+            presentation_hint: None,
+        })
+}
+
+pub fn scope() -> impl Strategy<Value = Scope> {
+    (
+        prop_oneof![
+            Just((ScopePresentationHint::Arguments, "Arguments")),
+            Just((ScopePresentationHint::Locals, "Locals")),
+            Just((ScopePresentationHint::Registers, "Registers")),
+        ],
+        id(),
+        prop::bool::weighted(0.1),
+    )
+        .prop_map(
+            |((presentation_hint, name), variables_reference, expensive)| Scope {
+                name: Str::from(name),
+                presentation_hint: Some(presentation_hint),
+                variables_reference,
+                named_variables: None,
+                indexed_variables: None,
+                expensive,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            },
+        )
+}
+
+pub fn variable() -> impl Strategy<Value = Variable> {
+    (
+        identifier(),
+        prop_oneof![
+            any::<i32>().prop_map(|value| (value.to_string(), "i32")),
+            any::<bool>().prop_map(|value| (value.to_string(), "bool")),
+            "\"[ -~]{0,20}\"".prop_map(|value| (value, "String")),
+        ],
+        prop::bool::weighted(0.8),
+        variables_reference(),
+    )
+        .prop_map(
+            |(name, (value, type_), typed, variables_reference)| Variable {
+                name,
+                value: Str::from(value),
+                type_: typed.then(|| Str::from(type_)),
+                presentation_hint: None,
+                evaluate_name: None,
+                variables_reference,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                #[cfg(feature = "spec-1_70")]
+                declaration_location_reference: None,
+                #[cfg(feature = "spec-1_70")]
+                value_location_reference: None,
+            },
+        )
+}
+
+/// A common request, with stepping and inspecting requests more likely than the requests that
+/// start or end a session.
+#[cfg(feature = "requests")]
+pub fn request() -> impl Strategy<Value = Request> {
+    let granularity = || {
+        prop_oneof![
+            8 => Just(SteppingGranularity::Statement),
+            1 => Just(SteppingGranularity::Line),
+            1 => Just(SteppingGranularity::Instruction),
+        ]
+    };
+    prop_oneof![
+        1 => (
+            option::of(prop_oneof![Just("vscode"), Just("vim")]),
+            identifier(),
+            any::<[bool; 4]>(),
+        )
+            .prop_map(|(client_id, adapter_id, supports)| {
+                Request::from(InitializeRequestArguments {
+                    client_id: client_id.map(Str::from),
+                    client_name: None,
+                    adapter_id,
+                    locale: None,
+                    lines_start_at_1: true,
+                    columns_start_at_1: true,
+                    path_format: PathFormat::Path,
+                    supports_variable_type: supports[0],
+                    supports_variable_paging: supports[1],
+                    supports_run_in_terminal_request: supports[2],
+                    supports_memory_references: supports[3],
+                    supports_progress_reporting: false,
+                    supports_invalidated_event: false,
+                    #[cfg(feature = "spec-1_70")]
+                    supports_memory_event: false,
+                    #[cfg(feature = "spec-1_70")]
+                    supports_args_can_be_interpreted_by_shell: false,
+                    #[cfg(feature = "spec-1_70")]
+                    supports_start_debugging_request: false,
+                    #[cfg(feature = "spec-1_70")]
+                    supports_ansi_styling: false,
+                })
+            }),
+        2 => (source(), vec(source_breakpoint(), 0..8)).prop_map(|(source, breakpoints)| {
+            Request::from(SetBreakpointsRequestArguments {
+                source,
+                breakpoints,
+                lines: Vec::new(),
+                source_modified: false,
+            })
+        }),
+        1 => Just(Request::ConfigurationDone),
+        3 => id().prop_map(|thread_id| Request::from(ContinueRequestArguments {
+            thread_id,
+            #[cfg(feature = "spec-1_70")]
+            single_thread: false,
+        })),
+        5 => (id(), granularity()).prop_map(|(thread_id, granularity)| {
+            Request::from(NextRequestArguments {
+                thread_id,
+                granularity,
+                #[cfg(feature = "spec-1_70")]
+                single_thread: false,
+            })
+        }),
+        3 => (id(), granularity()).prop_map(|(thread_id, granularity)| {
+            Request::from(StepInRequestArguments {
+                thread_id,
+                target_id: None,
+                granularity,
+                #[cfg(feature = "spec-1_70")]
+                single_thread: false,
+            })
+        }),
+        2 => (id(), granularity()).prop_map(|(thread_id, granularity)| {
+            Request::from(StepOutRequestArguments {
+                thread_id,
+                granularity,
+                #[cfg(feature = "spec-1_70")]
+                single_thread: false,
+            })
+        }),
+        1 => id().prop_map(|thread_id| Request::from(PauseRequestArguments { thread_id })),
+        4 => Just(Request::Threads),
+        4 => (id(), 0..20, 0..50).prop_map(|(thread_id, start_frame, levels)| {
+            Request::from(StackTraceRequestArguments {
+                thread_id,
+                start_frame,
+                levels,
+                format: None,
+            })
+        }),
+        4 => id().prop_map(|frame_id| Request::from(ScopesRequestArguments { frame_id })),
+        6 => id().prop_map(|variables_reference| {
+            Request::from(VariablesRequestArguments {
+                variables_reference,
+                filter: None,
+                start: 0,
+                count: 0,
+                format: None,
+            })
+        }),
+        2 => (
+            identifier(),
+            option::of(id()),
+            option::of(prop_oneof![
+                Just(EvaluateRequestContext::Watch),
+                Just(EvaluateRequestContext::REPL),
+                Just(EvaluateRequestContext::Hover),
+            ]),
+        )
+            .prop_map(|(expression, frame_id, context)| {
+                Request::from(EvaluateRequestArguments {
+                    expression,
+                    frame_id,
+                    context,
+                    format: None,
+                })
+            }),
+        1 => option::of(any::<bool>()).prop_map(|terminate_debuggee| {
+            Request::from(DisconnectRequestArguments {
+                restart: false,
+                terminate_debuggee,
+                suspend_debuggee: false,
+            })
+        }),
+    ]
+}
+
+/// A successful response to a common request or, less likely, an error response.
+#[cfg(feature = "responses")]
+pub fn response() -> impl Strategy<Value = Response> {
+    (
+        seq(),
+        prop_oneof![9 => success_response().prop_map(Ok), 1 => error_response().prop_map(Err)],
+    )
+        .prop_map(|(request_seq, result)| Response::new(request_seq, result))
+}
+
+#[cfg(feature = "responses")]
+pub fn success_response() -> impl Strategy<Value = SuccessResponse> {
+    prop_oneof![
+        2 => vec(breakpoint(), 0..8).prop_map(|breakpoints| {
+            SuccessResponse::SetBreakpoints(SetBreakpointsResponseBody { breakpoints })
+        }),
+        1 => Just(SuccessResponse::ConfigurationDone),
+        3 => any::<bool>().prop_map(|all_threads_continued| {
+            SuccessResponse::Continue(ContinueResponseBody {
+                all_threads_continued,
+            })
+        }),
+        5 => Just(SuccessResponse::Next),
+        3 => Just(SuccessResponse::StepIn),
+        2 => Just(SuccessResponse::StepOut),
+        4 => vec(thread(), 1..8)
+            .prop_map(|threads| SuccessResponse::Threads(ThreadsResponseBody { threads })),
+        4 => vec(stack_frame(), 1..20).prop_map(|stack_frames| {
+            SuccessResponse::StackTrace(StackTraceResponseBody {
+                stack_frames,
+                total_frames: None,
+            })
+        }),
+        4 => vec(scope(), 1..4)
+            .prop_map(|scopes| SuccessResponse::Scopes(ScopesResponseBody { scopes })),
+        6 => vec(variable(), 0..20).prop_map(|variables| {
+            SuccessResponse::Variables(VariablesResponseBody { variables })
+        }),
+        2 => variable().prop_map(|variable| {
+            SuccessResponse::Evaluate(Box::new(EvaluateResponseBody {
+                result: variable.value,
+                type_: variable.type_,
+                presentation_hint: None,
+                variables_reference: variable.variables_reference,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                #[cfg(feature = "spec-1_70")]
+                value_location_reference: None,
+            }))
+        }),
+    ]
+}
+
+#[cfg(feature = "responses")]
+pub fn error_response() -> impl Strategy<Value = ErrorResponse> {
+    (
+        prop_oneof![Just("evaluate"), Just("setBreakpoints"), Just("variables")],
+        prop_oneof![Just("cancelled"), Just("notStopped")],
+        option::of((id(), "[A-Z][a-z ]{0,30}")),
+    )
+        .prop_map(|(command, message, error)| ErrorResponse {
+            command: Str::from(command),
+            message: Str::from(message),
+            body: Box::new(ErrorResponseBody::new(error.map(|(id, format)| Message {
+                id,
+                format: Str::from(format),
+                variables: None,
+                send_telemetry: false,
+                show_user: true,
+                url: None,
+                url_label: None,
+                additional_attributes: Map::new(),
+            }))),
+        })
+}
+
+/// A common event, with 'stopped', 'continued' and 'output' events more likely than the events
+/// that start or end a session.
+#[cfg(feature = "events")]
+pub fn event() -> impl Strategy<Value = Event> {
+    prop_oneof![
+        1 => Just(Event::Initialized),
+        6 => (
+            prop_oneof![
+                5 => Just(StoppedEventReason::Step),
+                3 => Just(StoppedEventReason::Breakpoint),
+                1 => Just(StoppedEventReason::Exception),
+                1 => Just(StoppedEventReason::Pause),
+                1 => Just(StoppedEventReason::Entry),
+            ],
+            id(),
+            any::<bool>(),
+        )
+            .prop_map(|(reason, thread_id, all_threads_stopped)| {
+                Event::from(StoppedEventBody {
+                    reason,
+                    description: None,
+                    thread_id: Some(thread_id),
+                    preserve_focus_hint: false,
+                    text: None,
+                    all_threads_stopped,
+                    hit_breakpoint_ids: Default::default(),
+                })
+            }),
+        4 => (id(), any::<bool>()).prop_map(|(thread_id, all_threads_continued)| {
+            Event::from(ContinuedEventBody {
+                thread_id,
+                all_threads_continued,
+            })
+        }),
+        5 => (
+            prop_oneof![
+                Just(OutputCategory::Console),
+                Just(OutputCategory::Stdout),
+                Just(OutputCategory::Stderr),
+            ],
+            "[ -~]{0,80}\n",
+        )
+            .prop_map(|(category, output)| {
+                Event::from(OutputEventBody {
+                    category,
+                    output: Str::from(output),
+                    group: None,
+                    variables_reference: None,
+                    source: None,
+                    line: None,
+                    column: None,
+                    data: None,
+                    #[cfg(feature = "spec-1_70")]
+                    location_reference: None,
+                })
+            }),
+        2 => (
+            prop_oneof![Just(ThreadEventReason::Started), Just(ThreadEventReason::Exited)],
+            id(),
+        )
+            .prop_map(|(reason, thread_id)| Event::from(ThreadEventBody { reason, thread_id })),
+        1 => (
+            prop_oneof![
+                Just(BreakpointEventReason::Changed),
+                Just(BreakpointEventReason::New),
+                Just(BreakpointEventReason::Removed),
+            ],
+            breakpoint(),
+        )
+            .prop_map(|(reason, breakpoint)| {
+                Event::from(BreakpointEventBody { reason, breakpoint })
+            }),
+        1 => (0..=255).prop_map(|exit_code| Event::from(ExitedEventBody { exit_code })),
+        1 => Just(Event::from(TerminatedEventBody { restart: None })),
+    ]
+}
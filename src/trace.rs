@@ -0,0 +1,160 @@
+//! A writer for the textual trace of a session, in the format of the debug adapter trace of VS
+//! Code.
+//!
+//! Every message is written as a line with the time it was captured, an arrow for its direction
+//! and a short summary, followed by the pretty printed JSON of the message and an empty line:
+//!
+//! ```text
+//! [2024-01-02T03:04:05.678Z] --> request 'initialize' (seq 1)
+//! {
+//!   "seq": 1,
+//!   ...
+//! }
+//! ```
+//!
+//! This makes sessions recorded with this crate comparable with the logs of the editor.
+
+use crate::{
+    sources::{Clock, SystemClock},
+    ProtocolMessage, ProtocolMessageContent,
+};
+use std::{
+    io::{self, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The direction in which a message was sent.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    /// From the client to the debug adapter, traced as `-->`.
+    ToAdapter,
+
+    /// From the debug adapter to the client, traced as `<--`.
+    ToClient,
+}
+
+impl Direction {
+    /// The arrow that marks messages of this direction in a trace.
+    pub fn arrow(self) -> &'static str {
+        match self {
+            Direction::ToAdapter => "-->",
+            Direction::ToClient => "<--",
+        }
+    }
+}
+
+/// Writes the messages of a session to a trace.
+///
+/// The time of [write](TraceWriter::write) is taken from `C`, which is the system clock by default.
+#[derive(Debug)]
+pub struct TraceWriter<W, C = SystemClock> {
+    writer: W,
+    clock: C,
+}
+
+impl<W: Write> TraceWriter<W> {
+    pub fn new(writer: W) -> TraceWriter<W> {
+        TraceWriter::with_clock(writer, SystemClock)
+    }
+}
+
+impl<W: Write, C: Clock> TraceWriter<W, C> {
+    /// Creates a trace writer that tells the time with `clock`.
+    pub fn with_clock(writer: W, clock: C) -> TraceWriter<W, C> {
+        TraceWriter { writer, clock }
+    }
+
+    /// Traces `message` with the current time of the clock.
+    pub fn write(&mut self, direction: Direction, message: &ProtocolMessage) -> io::Result<()> {
+        self.write_at(self.clock.now(), direction, message)
+    }
+
+    /// Traces `message` with the time it was captured, for example when converting a recording.
+    pub fn write_at(
+        &mut self,
+        time: SystemTime,
+        direction: Direction,
+        message: &ProtocolMessage,
+    ) -> io::Result<()> {
+        writeln!(
+            self.writer,
+            "[{}] {} {}",
+            format_time(time),
+            direction.arrow(),
+            summary(message)
+        )?;
+        serde_json::to_writer_pretty(&mut self.writer, message)?;
+        self.writer.write_all(b"\n\n")
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Summarizes `message` in one line like `request 'initialize' (seq 1)`, as in the header of every
+/// traced message.
+pub fn summary(message: &ProtocolMessage) -> String {
+    match &message.content {
+        #[cfg(feature = "requests")]
+        ProtocolMessageContent::Request(request) => {
+            format!("request '{}' (seq {})", request.command(), message.seq)
+        }
+        #[cfg(feature = "responses")]
+        ProtocolMessageContent::Response(response) => format!(
+            "response '{}' to {}{}",
+            response.command(),
+            response.request_seq,
+            if response.result.is_ok() {
+                ""
+            } else {
+                " failed"
+            }
+        ),
+        #[cfg(feature = "events")]
+        ProtocolMessageContent::Event(event) => format!("event '{}'", event.event()),
+    }
+}
+
+/// Formats `time` as an RFC 3339 timestamp in UTC with milliseconds, like JavaScript's
+/// `Date.toISOString`.
+fn format_time(time: SystemTime) -> String {
+    let duration = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = duration.as_secs();
+    let (days, seconds_of_day) = (seconds / 86400, seconds % 86400);
+    let (year, month, day) = civil_from_days(days as i64);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60,
+        duration.subsec_millis()
+    )
+}
+
+/// Converts days since 1970-01-01 to a date of the proleptic Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
@@ -0,0 +1,288 @@
+//! Transports that exchange framed messages with a client or debug adapter.
+//!
+//! [Transport] is the blocking flavor and [AsyncTransport] the async one. Both send and receive
+//! whole [ProtocolMessage]s, so custom byte channels like SSH tunnels or serial links only need to
+//! implement framing once, or can wrap their streams in a [StreamTransport]. The built-in
+//! transports are:
+//!
+//! * [StreamTransport] over any pair of byte streams, like stdin and stdout, a `TcpStream` or a
+//!   `UnixStream`. Its constructors for these are not available on `wasm32`, which has none of
+//!   them.
+//! * [MemoryTransport], a pair of in-memory pipes that implements both flavors, for tests and for
+//!   debug adapters that run in the same process as their client.
+//! * The `Connection` of the `connection` feature.
+
+use crate::{framing::read_frame, ProtocolMessage};
+use std::{
+    collections::VecDeque,
+    future::{poll_fn, Future},
+    io::{self, BufRead, BufReader, Write},
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    task::{Poll, Waker},
+};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    io::{BufWriter, Stdin, Stdout},
+    net::TcpStream,
+};
+
+/// A blocking transport of messages.
+pub trait Transport {
+    /// Sends `message`, blocking until it was written.
+    fn send(&mut self, message: ProtocolMessage) -> io::Result<()>;
+
+    /// Receives the next message, blocking until one arrives. Returns `None` once the other side
+    /// closed the transport.
+    fn receive(&mut self) -> io::Result<Option<ProtocolMessage>>;
+}
+
+/// An async transport of messages. Like [Transport], but the operations complete asynchronously,
+/// so the transport does not need a thread of its own.
+pub trait AsyncTransport {
+    /// Sends `message`, completing once it was written.
+    fn send(&mut self, message: ProtocolMessage) -> impl Future<Output = io::Result<()>> + Send;
+
+    /// Receives the next message. Completes with `None` once the other side closed the transport.
+    fn receive(&mut self) -> impl Future<Output = io::Result<Option<ProtocolMessage>>> + Send;
+}
+
+impl<T: Transport + ?Sized> Transport for &mut T {
+    fn send(&mut self, message: ProtocolMessage) -> io::Result<()> {
+        (**self).send(message)
+    }
+
+    fn receive(&mut self) -> io::Result<Option<ProtocolMessage>> {
+        (**self).receive()
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for Box<T> {
+    fn send(&mut self, message: ProtocolMessage) -> io::Result<()> {
+        (**self).send(message)
+    }
+
+    fn receive(&mut self) -> io::Result<Option<ProtocolMessage>> {
+        (**self).receive()
+    }
+}
+
+/// A [Transport] that reads messages with a 'Content-Length' header from `R` and writes them to
+/// `W`.
+#[derive(Debug)]
+pub struct StreamTransport<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> StreamTransport<R, W> {
+    /// Creates a transport over a buffered reader and a writer. Every message is flushed after it
+    /// was written, so the writer may be buffered as well.
+    pub fn new(reader: R, writer: W) -> StreamTransport<R, W> {
+        StreamTransport { reader, writer }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl StreamTransport<BufReader<Stdin>, Stdout> {
+    /// Creates a transport over stdin and stdout, which is how a debug adapter talks to the client
+    /// that launched it.
+    pub fn stdio() -> Self {
+        StreamTransport::new(BufReader::new(io::stdin()), io::stdout())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl StreamTransport<BufReader<TcpStream>, BufWriter<TcpStream>> {
+    /// Creates a transport over a TCP connection. Messages are buffered while they are written, so
+    /// each one goes out in as few segments as possible.
+    pub fn tcp(stream: TcpStream) -> io::Result<Self> {
+        Ok(StreamTransport::new(
+            BufReader::new(stream.try_clone()?),
+            BufWriter::new(stream),
+        ))
+    }
+}
+
+#[cfg(unix)]
+impl
+    StreamTransport<
+        BufReader<std::os::unix::net::UnixStream>,
+        BufWriter<std::os::unix::net::UnixStream>,
+    >
+{
+    /// Creates a transport over a Unix domain socket. Like [StreamTransport::tcp], messages are
+    /// buffered while they are written.
+    pub fn unix(stream: std::os::unix::net::UnixStream) -> io::Result<Self> {
+        Ok(StreamTransport::new(
+            BufReader::new(stream.try_clone()?),
+            BufWriter::new(stream),
+        ))
+    }
+}
+
+impl<R: BufRead, W: Write> Transport for StreamTransport<R, W> {
+    fn send(&mut self, message: ProtocolMessage) -> io::Result<()> {
+        message.write_to(&mut self.writer)?;
+        self.writer.flush()
+    }
+
+    fn receive(&mut self) -> io::Result<Option<ProtocolMessage>> {
+        match read_frame(&mut self.reader)? {
+            Some(content) => ProtocolMessage::from_slice(&content)
+                .map(Some)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// One direction of a [MemoryTransport].
+#[derive(Debug, Default)]
+struct Pipe {
+    state: Mutex<PipeState>,
+    ready: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct PipeState {
+    messages: VecDeque<ProtocolMessage>,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+impl Pipe {
+    fn lock(&self) -> MutexGuard<'_, PipeState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn push(&self, message: ProtocolMessage) -> io::Result<()> {
+        let mut state = self.lock();
+        if state.closed {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+        state.messages.push_back(message);
+        self.wake(state);
+        Ok(())
+    }
+
+    fn close(&self) {
+        let mut state = self.lock();
+        state.closed = true;
+        self.wake(state);
+    }
+
+    fn wake(&self, mut state: MutexGuard<'_, PipeState>) {
+        let waker = state.waker.take();
+        drop(state);
+        self.ready.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Takes the next message, or `None` if the pipe is closed, or registers `waker` to be woken
+    /// once there is one.
+    fn poll_pop(&self, waker: &Waker) -> Poll<Option<ProtocolMessage>> {
+        let mut state = self.lock();
+        match state.messages.pop_front() {
+            Some(message) => Poll::Ready(Some(message)),
+            None if state.closed => Poll::Ready(None),
+            None => {
+                state.waker = Some(waker.clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    fn pop(&self) -> Option<ProtocolMessage> {
+        let mut state = self.lock();
+        loop {
+            if let Some(message) = state.messages.pop_front() {
+                return Some(message);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self
+                .ready
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+/// One end of a pair of in-memory pipes, see [MemoryTransport::pair]. Dropping it closes both
+/// pipes: the other end receives the remaining messages and then `None`, and sending fails with
+/// [BrokenPipe](io::ErrorKind::BrokenPipe).
+#[derive(Debug)]
+pub struct MemoryTransport {
+    incoming: Arc<Pipe>,
+    outgoing: Arc<Pipe>,
+}
+
+impl MemoryTransport {
+    /// Creates two transports that are connected to each other.
+    pub fn pair() -> (MemoryTransport, MemoryTransport) {
+        let first = Arc::new(Pipe::default());
+        let second = Arc::new(Pipe::default());
+        (
+            MemoryTransport {
+                incoming: first.clone(),
+                outgoing: second.clone(),
+            },
+            MemoryTransport {
+                incoming: second,
+                outgoing: first,
+            },
+        )
+    }
+}
+
+impl Drop for MemoryTransport {
+    fn drop(&mut self) {
+        self.incoming.close();
+        self.outgoing.close();
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn send(&mut self, message: ProtocolMessage) -> io::Result<()> {
+        self.outgoing.push(message)
+    }
+
+    fn receive(&mut self) -> io::Result<Option<ProtocolMessage>> {
+        Ok(self.incoming.pop())
+    }
+}
+
+impl AsyncTransport for MemoryTransport {
+    fn send(&mut self, message: ProtocolMessage) -> impl Future<Output = io::Result<()>> + Send {
+        // Pipes are unbounded, so sending never has to wait
+        std::future::ready(self.outgoing.push(message))
+    }
+
+    fn receive(&mut self) -> impl Future<Output = io::Result<Option<ProtocolMessage>>> + Send {
+        let incoming = &self.incoming;
+        poll_fn(move |cx| incoming.poll_pop(cx.waker()).map(Ok))
+    }
+}
+
+#[cfg(feature = "connection")]
+impl Transport for crate::connection::Connection {
+    fn send(&mut self, message: ProtocolMessage) -> io::Result<()> {
+        self.sender
+            .send(message)
+            .map_err(|_| io::ErrorKind::BrokenPipe.into())
+    }
+
+    fn receive(&mut self) -> io::Result<Option<ProtocolMessage>> {
+        crossbeam_channel::select! {
+            recv(self.receiver) -> message => Ok(message.ok()),
+            recv(self.errors) -> error => match error {
+                Ok(error) => Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+                // The connection does not parse messages, so only wait for the next one
+                Err(_) => Ok(self.receiver.recv().ok()),
+            },
+        }
+    }
+}
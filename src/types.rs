@@ -1,15 +1,37 @@
-use crate::utils::eq_default;
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
+#[cfg(any(feature = "ser", feature = "schemars"))]
+use crate::utils::{eq_default, is_none_or_empty};
+use crate::{capabilities::CapabilityFlags, paths::paths_equal, redact::REDACTED, SmallVec, Str};
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset};
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "de")]
+use serde::Deserialize;
+#[cfg(feature = "ser")]
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
 use std::collections::HashMap;
+#[cfg(feature = "ts-rs")]
+use ts_rs::TS;
+#[cfg(feature = "builders")]
 use typed_builder::TypedBuilder;
 
 /// Information about a Breakpoint created in setBreakpoints, setFunctionBreakpoints, setInstructionBreakpoints, or setDataBreakpoints.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct Breakpoint {
     /// An optional identifier for the breakpoint. It is needed if breakpoint events are used to update or remove breakpoints.
     #[serde(rename = "id", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub id: Option<i32>,
 
     /// If true breakpoint could be set (but not necessarily at the desired location).
@@ -20,34 +42,40 @@ pub struct Breakpoint {
     ///
     /// This is shown to the user and can be used to explain why a breakpoint could not be verified.
     #[serde(rename = "message", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub message: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub message: Option<Str>,
 
     /// The source where the breakpoint is located.
     #[serde(rename = "source", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub source: Option<Source>,
 
     /// The start line of the actual range covered by the breakpoint.
     #[serde(rename = "line", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub line: Option<i32>,
 
     /// An optional start column of the actual range covered by the breakpoint.
     #[serde(rename = "column", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub column: Option<i32>,
 
     /// An optional end line of the actual range covered by the breakpoint.
     #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_line: Option<i32>,
 
     /// An optional end column of the actual range covered by the breakpoint.
     ///
     /// If no end line is given, then the end column is assumed to be in the start line.
     #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_column: Option<i32>,
 
     /// An optional memory reference to where the breakpoint is set.
@@ -55,23 +83,45 @@ pub struct Breakpoint {
         rename = "instructionReference",
         skip_serializing_if = "Option::is_none"
     )]
-    #[builder(default)]
-    pub instruction_reference: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub instruction_reference: Option<Str>,
 
     /// An optional offset from the instruction reference.
     ///
     /// This can be negative.
     #[serde(rename = "offset", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub offset: Option<i32>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl Breakpoint {
+    pub fn new(verified: bool) -> Breakpoint {
+        Breakpoint {
+            id: Default::default(),
+            verified,
+            message: Default::default(),
+            source: Default::default(),
+            line: Default::default(),
+            column: Default::default(),
+            end_line: Default::default(),
+            end_column: Default::default(),
+            instruction_reference: Default::default(),
+            offset: Default::default(),
+        }
+    }
 }
 
 /// Properties of a breakpoint location returned from the 'breakpointLocations' request.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct BreakpointLocation {
     /// Start line of breakpoint location.
     #[serde(rename = "line")]
@@ -79,71 +129,125 @@ pub struct BreakpointLocation {
 
     /// Optional start column of breakpoint location.
     #[serde(rename = "column", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub column: Option<i32>,
 
     /// Optional end line of breakpoint location if the location covers a range.
     #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_line: Option<i32>,
 
     /// Optional end column of breakpoint location if the location covers a range.
     #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_column: Option<i32>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl BreakpointLocation {
+    pub fn new(line: i32) -> BreakpointLocation {
+        BreakpointLocation {
+            line,
+            column: Default::default(),
+            end_line: Default::default(),
+            end_column: Default::default(),
+        }
+    }
 }
 
-/// Information about the capabilities of a debug adapter.
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
-pub struct Capabilities {
-    /// The debug adapter supports the 'configurationDone' request.
-    #[serde(
-        rename = "supportsConfigurationDoneRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_configuration_done_request: bool,
+/// A BreakpointMode is provided as a option when setting breakpoints on sources or instructions.
+#[cfg(feature = "spec-1_70")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
+pub struct BreakpointMode {
+    /// The internal ID of the mode. This value is passed to the 'setBreakpoints' request.
+    #[serde(rename = "mode")]
+    pub mode: Str,
+
+    /// The name of the breakpoint mode. This is shown in the UI.
+    #[serde(rename = "label")]
+    pub label: Str,
 
-    /// The debug adapter supports function breakpoints.
-    #[serde(
-        rename = "supportsFunctionBreakpoints",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_function_breakpoints: bool,
+    /// A help text providing additional information about the breakpoint mode. This string is typically shown as a hover and can be translated.
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub description: Option<Str>,
 
-    /// The debug adapter supports conditional breakpoints.
-    #[serde(
-        rename = "supportsConditionalBreakpoints",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_conditional_breakpoints: bool,
+    /// Describes one or more type of breakpoint this mode applies to.
+    #[serde(rename = "appliesTo")]
+    pub applies_to: Vec<BreakpointModeApplicability>,
+}
 
-    /// The debug adapter supports breakpoints that break execution after a specified number of hits.
-    #[serde(
-        rename = "supportsHitConditionalBreakpoints",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_hit_conditional_breakpoints: bool,
+#[cfg(feature = "spec-1_70")]
+impl BreakpointMode {
+    pub fn new(
+        mode: Str,
+        label: Str,
+        applies_to: Vec<BreakpointModeApplicability>,
+    ) -> BreakpointMode {
+        BreakpointMode {
+            mode,
+            label,
+            description: Default::default(),
+            applies_to,
+        }
+    }
+}
 
-    /// The debug adapter supports a (side effect free) evaluate request for data hovers.
-    #[serde(
-        rename = "supportsEvaluateForHovers",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_evaluate_for_hovers: bool,
+/// Describes one or more type of breakpoint a BreakpointMode applies to. This is a non-exhaustive enumeration and may expand as future breakpoint types are added.
+#[cfg(feature = "spec-1_70")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
+pub enum BreakpointModeApplicability {
+    /// In SourceBreakpoints
+    #[serde(rename = "source")]
+    Source,
+
+    /// In exception breakpoints applied in the ExceptionFilterOptions
+    #[serde(rename = "exception")]
+    Exception,
+
+    /// In data breakpoints requested in the 'dataBreakpointInfo' request
+    #[serde(rename = "data")]
+    Data,
+
+    /// In InstructionBreakpoints
+    #[serde(rename = "instruction")]
+    Instruction,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
+}
+
+/// Information about the capabilities of a debug adapter.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// The boolean capabilities, accessed with [Capabilities::flags] and the `supports_*` methods.
+    #[serde(flatten)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub(crate) flags: CapabilityFlags,
 
     /// Available exception filter options for the 'setExceptionBreakpoints' request.
     #[serde(
@@ -151,79 +255,17 @@ pub struct Capabilities {
         default,
         skip_serializing_if = "Vec::is_empty"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub exception_breakpoint_filters: Vec<ExceptionBreakpointsFilter>,
 
-    /// The debug adapter supports stepping back via the 'stepBack' and 'reverseContinue' requests.
-    #[serde(
-        rename = "supportsStepBack",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_step_back: bool,
-
-    /// The debug adapter supports setting a variable to a value.
-    #[serde(
-        rename = "supportsSetVariable",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_set_variable: bool,
-
-    /// The debug adapter supports restarting a frame.
-    #[serde(
-        rename = "supportsRestartFrame",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_restart_frame: bool,
-
-    /// The debug adapter supports the 'gotoTargets' request.
-    #[serde(
-        rename = "supportsGotoTargetsRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_goto_targets_request: bool,
-
-    /// The debug adapter supports the 'stepInTargets' request.
-    #[serde(
-        rename = "supportsStepInTargetsRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_step_in_targets_request: bool,
-
-    /// The debug adapter supports the 'completions' request.
-    #[serde(
-        rename = "supportsCompletionsRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_completions_request: bool,
-
     /// The set of characters that should trigger completion in a REPL. If not specified, the UI should assume the '.' character.
     #[serde(
         rename = "completionTriggerCharacters",
         skip_serializing_if = "Option::is_none"
     )]
-    #[builder(default)]
-    pub completion_trigger_characters: Option<Vec<String>>,
-
-    /// The debug adapter supports the 'modules' request.
-    #[serde(
-        rename = "supportsModulesRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_modules_request: bool,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub completion_trigger_characters: Option<Vec<Str>>,
 
     /// The set of additional module information exposed by the debug adapter.
     #[serde(
@@ -231,7 +273,7 @@ pub struct Capabilities {
         default,
         skip_serializing_if = "Vec::is_empty"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub additional_module_columns: Vec<ColumnDescriptor>,
 
     /// Checksum algorithms supported by the debug adapter.
@@ -240,205 +282,38 @@ pub struct Capabilities {
         default,
         skip_serializing_if = "Vec::is_empty"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub supported_checksum_algorithms: Vec<ChecksumAlgorithm>,
 
-    /// The debug adapter supports the 'restart' request. In this case a client should not implement 'restart' by terminating and relaunching the adapter but by calling the RestartRequest.
-    #[serde(
-        rename = "supportsRestartRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_restart_request: bool,
-
-    /// The debug adapter supports 'exceptionOptions' on the setExceptionBreakpoints request.
-    #[serde(
-        rename = "supportsExceptionOptions",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_exception_options: bool,
-
-    /// The debug adapter supports a 'format' attribute on the stackTraceRequest, variablesRequest, and evaluateRequest.
-    #[serde(
-        rename = "supportsValueFormattingOptions",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_value_formatting_options: bool,
-
-    /// The debug adapter supports the 'exceptionInfo' request.
-    #[serde(
-        rename = "supportsExceptionInfoRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_exception_info_request: bool,
-
-    /// The debug adapter supports the 'terminateDebuggee' attribute on the 'disconnect' request.
-    #[serde(
-        rename = "supportTerminateDebuggee",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub support_terminate_debuggee: bool,
-
-    /// The debug adapter supports the 'suspendDebuggee' attribute on the 'disconnect' request.
-    #[serde(
-        rename = "supportSuspendDebuggee",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub support_suspend_debuggee: bool,
-
-    /// The debug adapter supports the delayed loading of parts of the stack, which requires that both the 'startFrame' and 'levels' arguments and an optional 'totalFrames' result of the 'StackTrace' request are supported.
-    #[serde(
-        rename = "supportsDelayedStackTraceLoading",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_delayed_stack_trace_loading: bool,
-
-    /// The debug adapter supports the 'loadedSources' request.
-    #[serde(
-        rename = "supportsLoadedSourcesRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_loaded_sources_request: bool,
-
-    /// The debug adapter supports logpoints by interpreting the 'logMessage' attribute of the SourceBreakpoint.
-    #[serde(
-        rename = "supportsLogPoints",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_log_points: bool,
-
-    /// The debug adapter supports the 'terminateThreads' request.
-    #[serde(
-        rename = "supportsTerminateThreadsRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_terminate_threads_request: bool,
-
-    /// The debug adapter supports the 'setExpression' request.
-    #[serde(
-        rename = "supportsSetExpression",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_set_expression: bool,
-
-    /// The debug adapter supports the 'terminate' request.
-    #[serde(
-        rename = "supportsTerminateRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_terminate_request: bool,
-
-    /// The debug adapter supports data breakpoints.
-    #[serde(
-        rename = "supportsDataBreakpoints",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_data_breakpoints: bool,
-
-    /// The debug adapter supports the 'readMemory' request.
-    #[serde(
-        rename = "supportsReadMemoryRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_read_memory_request: bool,
-
-    /// The debug adapter supports the 'disassemble' request.
-    #[serde(
-        rename = "supportsDisassembleRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_disassemble_request: bool,
-
-    /// The debug adapter supports the 'cancel' request.
-    #[serde(
-        rename = "supportsCancelRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_cancel_request: bool,
-
-    /// The debug adapter supports the 'breakpointLocations' request.
-    #[serde(
-        rename = "supportsBreakpointLocationsRequest",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_breakpoint_locations_request: bool,
-
-    /// The debug adapter supports the 'clipboard' context value in the 'evaluate' request.
-    #[serde(
-        rename = "supportsClipboardContext",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_clipboard_context: bool,
-
-    /// The debug adapter supports stepping granularities (argument 'granularity') for the stepping requests.
-    #[serde(
-        rename = "supportsSteppingGranularity",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_stepping_granularity: bool,
-
-    /// The debug adapter supports adding breakpoints based on instruction references.
-    #[serde(
-        rename = "supportsInstructionBreakpoints",
-        default,
-        skip_serializing_if = "eq_default"
-    )]
-    #[builder(default)]
-    pub supports_instruction_breakpoints: bool,
-
-    /// The debug adapter supports 'filterOptions' as an argument on the 'setExceptionBreakpoints' request.
+    /// Modes of breakpoints supported by the debug adapter, such as 'hardware' or 'software'. If present, the client may allow the user to select a mode and include it in its 'setBreakpoints' request.
+    ///
+    /// Clients may present the first applicable mode in this array as the 'default' mode in gestures that set breakpoints.
+    #[cfg(feature = "spec-1_70")]
     #[serde(
-        rename = "supportsExceptionFilterOptions",
+        rename = "breakpointModes",
         default,
-        skip_serializing_if = "eq_default"
+        skip_serializing_if = "Vec::is_empty"
     )]
-    #[builder(default)]
-    pub supports_exception_filter_options: bool,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub breakpoint_modes: Vec<BreakpointMode>,
+
+    /// Additional capabilities are implementation specific, for example support for a custom
+    /// request.
+    #[serde(flatten)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::map))]
+    pub additional_attributes: Map<String, Value>,
 }
 
 /// The checksum of an item calculated by the specified algorithm.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct Checksum {
     /// The algorithm used to calculate this checksum.
     #[serde(rename = "algorithm")]
@@ -446,15 +321,26 @@ pub struct Checksum {
 
     /// Value of the checksum.
     #[serde(rename = "checksum")]
-    pub checksum: String,
+    pub checksum: Str,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl Checksum {
+    pub fn new(algorithm: ChecksumAlgorithm, checksum: Str) -> Checksum {
+        Checksum {
+            algorithm,
+            checksum,
+        }
+    }
 }
 
 /// Names of checksum algorithms that may be supported by a debug adapter.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum ChecksumAlgorithm {
     #[serde(rename = "MD5")]
     MD5,
@@ -467,6 +353,20 @@ pub enum ChecksumAlgorithm {
 
     #[serde(rename = "timestamp")]
     Timestamp,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
+}
+
+#[cfg(feature = "chrono")]
+impl Checksum {
+    /// Parses the value of a checksum of the 'timestamp' algorithm as an RFC 3339 timestamp.
+    /// Returns `None` for the other algorithms.
+    pub fn timestamp(&self) -> Option<Result<DateTime<FixedOffset>, chrono::ParseError>> {
+        (self.algorithm == ChecksumAlgorithm::Timestamp)
+            .then(|| DateTime::parse_from_rfc3339(&self.checksum))
+    }
 }
 
 /// A ColumnDescriptor specifies what module attribute to show in a column of the ModulesView, how to format it,
@@ -474,39 +374,63 @@ pub enum ChecksumAlgorithm {
 /// and what the column's label should be.
 ///
 /// It is only used if the underlying UI actually supports this level of customization.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ColumnDescriptor {
     /// Name of the attribute rendered in this column.
     #[serde(rename = "attributeName")]
-    pub attribute_name: String,
+    pub attribute_name: Str,
 
     /// Header UI label of column.
     #[serde(rename = "label")]
-    pub label: String,
+    pub label: Str,
 
     /// Format to use for the rendered values in this column. TBD how the format strings looks like.
     #[serde(rename = "format", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub format: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub format: Option<Str>,
 
     /// Datatype of values in this column.  Defaults to 'string' if not specified.
     #[serde(rename = "type", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub type_: ColumnDescriptorType,
 
     /// Width of this column in characters (hint only).
     #[serde(rename = "width", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub width: Option<i32>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl ColumnDescriptor {
+    pub fn new(attribute_name: Str, label: Str) -> ColumnDescriptor {
+        ColumnDescriptor {
+            attribute_name,
+            label,
+            format: Default::default(),
+            type_: Default::default(),
+            width: Default::default(),
+        }
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum ColumnDescriptorType {
     #[serde(rename = "string")]
+    #[default]
     String,
 
     #[serde(rename = "number")]
@@ -517,48 +441,57 @@ pub enum ColumnDescriptorType {
 
     #[serde(rename = "unixTimestampUTC")]
     UnixTimestampUTC,
-}
 
-impl Default for ColumnDescriptorType {
-    fn default() -> Self {
-        ColumnDescriptorType::String
-    }
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
 /// CompletionItems are the suggestions returned from the CompletionsRequest.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct CompletionItem {
     /// The label of this completion item. By default this is also the text that is inserted when selecting this completion.
     #[serde(rename = "label")]
-    pub label: String,
+    pub label: Str,
 
     /// If text is not falsy then it is inserted instead of the label.
     #[serde(rename = "text", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub text: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub text: Option<Str>,
 
     /// A string that should be used when comparing this item with other items. When `falsy` the label is used.
     #[serde(rename = "sortText", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub sort_text: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub sort_text: Option<Str>,
 
     /// The item's type. Typically the client uses this information to render the item in the UI with an icon.
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub type_: Option<CompletionItemType>,
 
     /// This value determines the location (in the CompletionsRequest's 'text' attribute) where the completion text is added.
     ///
     /// If missing the text is added at the location specified by the CompletionsRequest's 'column' attribute.
     #[serde(rename = "start", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub start: Option<i32>,
 
     /// This value determines how many characters are overwritten by the completion text.
     ///
     /// If missing the value 0 is assumed which results in the completion text being inserted.
     #[serde(rename = "length", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub length: i32,
 
     /// Determines the start of the new selection after the text has been inserted (or replaced).
@@ -567,7 +500,8 @@ pub struct CompletionItem {
     ///
     /// If omitted the selection starts at the end of the completion text.
     #[serde(rename = "selectionStart", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub selection_start: Option<i32>,
 
     /// Determines the length of the new selection after the text has been inserted (or replaced).
@@ -580,16 +514,33 @@ pub struct CompletionItem {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub selection_length: i32,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl CompletionItem {
+    pub fn new(label: Str) -> CompletionItem {
+        CompletionItem {
+            label,
+            text: Default::default(),
+            sort_text: Default::default(),
+            type_: Default::default(),
+            start: Default::default(),
+            length: Default::default(),
+            selection_start: Default::default(),
+            selection_length: Default::default(),
+        }
+    }
 }
 
 /// Some predefined types for the CompletionItem. Please note that not all clients have specific icons for all of them.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum CompletionItemType {
     #[serde(rename = "method")]
     Method,
@@ -647,39 +598,75 @@ pub enum CompletionItemType {
 
     #[serde(rename = "customcolor")]
     Customcolor,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
 /// Properties of a data breakpoint passed to the setDataBreakpoints request.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct DataBreakpoint {
     /// An id representing the data. This id is returned from the dataBreakpointInfo request.
     #[serde(rename = "dataId")]
-    pub data_id: String,
+    pub data_id: Str,
 
     /// The access type of the data.
     #[serde(rename = "accessType", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub access_type: Option<DataBreakpointAccessType>,
 
     /// An optional expression for conditional breakpoints.
     #[serde(rename = "condition", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub condition: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub condition: Option<Str>,
 
     /// An optional expression that controls how many hits of the breakpoint are ignored.
     ///
     /// The backend is expected to interpret the expression as needed.
     #[serde(rename = "hitCondition", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub hit_condition: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub hit_condition: Option<Str>,
+
+    /// The mode of this breakpoint. If defined, this must be one of the 'breakpointModes' the debug adapter advertised in its Capabilities.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "mode", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub mode: Option<Str>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl DataBreakpoint {
+    pub fn new(data_id: Str) -> DataBreakpoint {
+        DataBreakpoint {
+            data_id,
+            access_type: Default::default(),
+            condition: Default::default(),
+            hit_condition: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            mode: Default::default(),
+        }
+    }
 }
 
 /// This enumeration defines all possible access types for data breakpoints.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum DataBreakpointAccessType {
     #[serde(rename = "read")]
     Read,
@@ -689,28 +676,41 @@ pub enum DataBreakpointAccessType {
 
     #[serde(rename = "readWrite")]
     ReadWrite,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
 /// Represents a single disassembled instruction.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct DisassembledInstruction {
     /// The address of the instruction. Treated as a hex value if prefixed with '0x', or as a decimal value otherwise.
     #[serde(rename = "address")]
-    pub address: String,
+    pub address: Str,
 
     /// Optional raw bytes representing the instruction and its operands, in an implementation-defined format.
     #[serde(rename = "instructionBytes", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub instruction_bytes: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub instruction_bytes: Option<Str>,
 
     /// Text representing the instruction and its operands, in an implementation-defined format.
     #[serde(rename = "instruction")]
-    pub instruction: String,
+    pub instruction: Str,
 
     /// Name of the symbol that corresponds with the location of this instruction, if any.
     #[serde(rename = "symbol", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub symbol: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub symbol: Option<Str>,
 
     /// Source location that corresponds to this instruction, if any.
     ///
@@ -718,32 +718,81 @@ pub struct DisassembledInstruction {
     ///
     /// but can be omitted afterwards if this instruction maps to the same source file as the previous instruction.
     #[serde(rename = "location", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub location: Option<Source>,
 
     /// The line within the source location that corresponds to this instruction, if any.
     #[serde(rename = "line", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub line: Option<i32>,
 
     /// The column within the line that corresponds to this instruction, if any.
     #[serde(rename = "column", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub column: Option<i32>,
 
     /// The end line of the range that corresponds to this instruction, if any.
     #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_line: Option<i32>,
 
     /// The end column of the range that corresponds to this instruction, if any.
     #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_column: Option<i32>,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// A hint for how to present the instruction in the UI.
+    ///
+    /// A value of 'invalid' may be used to indicate this instruction is 'filler' and cannot be reached by the program. For example, unreadable memory addresses may be presented is 'invalid.'
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "presentationHint", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub presentation_hint: Option<DisassembledInstructionPresentationHint>,
+}
+
+impl DisassembledInstruction {
+    pub fn new(address: Str, instruction: Str) -> DisassembledInstruction {
+        DisassembledInstruction {
+            address,
+            instruction_bytes: Default::default(),
+            instruction,
+            symbol: Default::default(),
+            location: Default::default(),
+            line: Default::default(),
+            column: Default::default(),
+            end_line: Default::default(),
+            end_column: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            presentation_hint: Default::default(),
+        }
+    }
+}
+
+/// A hint for how to present the instruction in the UI.
+#[cfg(feature = "spec-1_70")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
+pub enum DisassembledInstructionPresentationHint {
+    #[serde(rename = "normal")]
+    Normal,
+
+    #[serde(rename = "invalid")]
+    Invalid,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
 /// This enumeration defines all possible conditions when a thrown exception should result in a break.
@@ -755,9 +804,18 @@ pub struct DisassembledInstruction {
 /// unhandled: breaks when exception unhandled,
 ///
 /// userUnhandled: breaks if the exception is not handled by user code.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+///
+/// Exceptions that no [ExceptionOptions] select never break, so 'never' is the default.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum ExceptionBreakMode {
     #[serde(rename = "never")]
+    #[default]
     Never,
 
     #[serde(rename = "always")]
@@ -768,27 +826,39 @@ pub enum ExceptionBreakMode {
 
     #[serde(rename = "userUnhandled")]
     UserUnhandled,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
 /// An ExceptionBreakpointsFilter is shown in the UI as an filter option for configuring how exceptions are dealt with.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ExceptionBreakpointsFilter {
     /// The internal ID of the filter option. This value is passed to the 'setExceptionBreakpoints' request.
     #[serde(rename = "filter")]
-    pub filter: String,
+    pub filter: Str,
 
     /// The name of the filter option. This will be shown in the UI.
     #[serde(rename = "label")]
-    pub label: String,
+    pub label: Str,
 
     /// An optional help text providing additional information about the exception filter. This string is typically shown as a hover and must be translated.
     #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub description: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub description: Option<Str>,
 
     /// Initial value of the filter option. If not specified a value 'false' is assumed.
     #[serde(rename = "default", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub default: bool,
 
     /// Controls whether a condition can be specified for this filter option. If false or missing, a condition can not be set.
@@ -797,7 +867,7 @@ pub struct ExceptionBreakpointsFilter {
         default,
         skip_serializing_if = "eq_default"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub supports_condition: bool,
 
     /// An optional help text providing information about the condition. This string is shown as the placeholder text for a text box and must be translated.
@@ -805,41 +875,63 @@ pub struct ExceptionBreakpointsFilter {
         rename = "conditionDescription",
         skip_serializing_if = "Option::is_none"
     )]
-    #[builder(default)]
-    pub condition_description: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub condition_description: Option<Str>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl ExceptionBreakpointsFilter {
+    pub fn new(filter: Str, label: Str) -> ExceptionBreakpointsFilter {
+        ExceptionBreakpointsFilter {
+            filter,
+            label,
+            description: Default::default(),
+            default: Default::default(),
+            supports_condition: Default::default(),
+            condition_description: Default::default(),
+        }
+    }
 }
 
 /// Detailed information about an exception that has occurred.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ExceptionDetails {
     /// Message contained in the exception.
     #[serde(rename = "message", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub message: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub message: Option<Str>,
 
     /// Short type name of the exception object.
     #[serde(rename = "typeName", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub type_name: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub type_name: Option<Str>,
 
     /// Fully-qualified type name of the exception object.
     #[serde(rename = "fullTypeName", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub full_type_name: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub full_type_name: Option<Str>,
 
     /// Optional expression that can be evaluated in the current scope to obtain the exception object.
     #[serde(rename = "evaluateName", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub evaluate_name: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub evaluate_name: Option<Str>,
 
     /// Stack trace at the time the exception was thrown.
     #[serde(rename = "stackTrace", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub stack_trace: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub stack_trace: Option<Str>,
 
     /// Details of the exception contained by this exception, if any.
     #[serde(
@@ -847,50 +939,112 @@ pub struct ExceptionDetails {
         default,
         skip_serializing_if = "Vec::is_empty"
     )]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub inner_exception: Vec<ExceptionDetails>,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// Additional attributes are implementation specific, for example the source file of the
+    /// exception.
+    #[serde(flatten)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::map))]
+    pub additional_attributes: Map<String, Value>,
 }
 
 /// An ExceptionFilterOptions is used to specify an exception filter together with a condition for the setExceptionsFilter request.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ExceptionFilterOptions {
     /// ID of an exception filter returned by the 'exceptionBreakpointFilters' capability.
     #[serde(rename = "filterId")]
-    pub filter_id: String,
+    pub filter_id: Str,
 
     /// An optional expression for conditional exceptions.
     ///
     /// The exception will break into the debugger if the result of the condition is true.
     #[serde(rename = "condition", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub condition: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub condition: Option<Str>,
+
+    /// The mode of this exception breakpoint. If defined, this must be one of the 'breakpointModes' the debug adapter advertised in its Capabilities.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "mode", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub mode: Option<Str>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl ExceptionFilterOptions {
+    pub fn new(filter_id: Str) -> ExceptionFilterOptions {
+        ExceptionFilterOptions {
+            filter_id,
+            condition: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            mode: Default::default(),
+        }
+    }
 }
 
 /// An ExceptionOptions assigns configuration options to a set of exceptions.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ExceptionOptions {
     /// A path that selects a single or multiple exceptions in a tree. If 'path' is missing, the whole tree is selected.
     ///
     /// By convention the first segment of the path is a category that is used to group exceptions in the UI.
     #[serde(rename = "path", default, skip_serializing_if = "Vec::is_empty")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub path: Vec<ExceptionPathSegment>,
 
     /// Condition when a thrown exception should result in a break.
     #[serde(rename = "breakMode")]
     pub break_mode: ExceptionBreakMode,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl ExceptionOptions {
+    pub fn new(break_mode: ExceptionBreakMode) -> ExceptionOptions {
+        ExceptionOptions {
+            path: Default::default(),
+            break_mode,
+        }
+    }
+
+    /// Returns true if these options select the exception with the given path of categories and
+    /// type names. A path selects all exceptions in the subtree it leads to.
+    pub fn matches<S: AsRef<str>>(&self, exception_path: &[S]) -> bool {
+        self.path.len() <= exception_path.len()
+            && self
+                .path
+                .iter()
+                .zip(exception_path)
+                .all(|(segment, name)| segment.matches(name.as_ref()))
+    }
+
+    /// Returns the break mode of the most specific options that select the exception with the given
+    /// path, or `None` if no options select it. Options with a longer path are more specific; among
+    /// equally specific options the last one wins.
+    pub fn effective_break_mode<'a, S: AsRef<str>>(
+        options: &'a [ExceptionOptions],
+        exception_path: &[S],
+    ) -> Option<&'a ExceptionBreakMode> {
+        options
+            .iter()
+            .filter(|options| options.matches(exception_path))
+            .max_by_key(|options| options.path.len())
+            .map(|options| &options.break_mode)
+    }
 }
 
 /// An ExceptionPathSegment represents a segment in a path that is used to match leafs or nodes in a tree of exceptions.
@@ -898,35 +1052,60 @@ pub struct ExceptionOptions {
 /// If a segment consists of more than one name, it matches the names provided if 'negate' is false or missing or
 ///
 /// it matches anything except the names provided if 'negate' is true.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ExceptionPathSegment {
     /// If false or missing this segment matches the names provided, otherwise it matches anything except the names provided.
     #[serde(rename = "negate", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub negate: bool,
 
     /// Depending on the value of 'negate' the names that should match or not match.
     #[serde(rename = "names")]
-    pub names: Vec<String>,
+    pub names: Vec<Str>,
+}
+
+impl ExceptionPathSegment {
+    pub fn new(names: Vec<Str>) -> ExceptionPathSegment {
+        ExceptionPathSegment {
+            negate: Default::default(),
+            names,
+        }
+    }
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// Returns true if this segment matches the given category or type name.
+    pub fn matches(&self, name: &str) -> bool {
+        self.names.iter().any(|n| **n == *name) != self.negate
+    }
 }
 
 /// Properties of a breakpoint passed to the setFunctionBreakpoints request.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct FunctionBreakpoint {
     /// The name of the function.
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: Str,
 
     /// An optional expression for conditional breakpoints.
     ///
     /// It is only honored by a debug adapter if the capability 'supportsConditionalBreakpoints' is true.
     #[serde(rename = "condition", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub condition: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub condition: Option<Str>,
 
     /// An optional expression that controls how many hits of the breakpoint are ignored.
     ///
@@ -934,18 +1113,41 @@ pub struct FunctionBreakpoint {
     ///
     /// The attribute is only honored by a debug adapter if the capability 'supportsHitConditionalBreakpoints' is true.
     #[serde(rename = "hitCondition", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub hit_condition: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub hit_condition: Option<Str>,
+
+    /// The mode of this breakpoint. If defined, this must be one of the 'breakpointModes' the debug adapter advertised in its Capabilities.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "mode", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub mode: Option<Str>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl FunctionBreakpoint {
+    pub fn new(name: Str) -> FunctionBreakpoint {
+        FunctionBreakpoint {
+            name,
+            condition: Default::default(),
+            hit_condition: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            mode: Default::default(),
+        }
+    }
 }
 
 /// A GotoTarget describes a code location that can be used as a target in the 'goto' request.
 ///
 /// The possible goto targets can be determined via the 'gotoTargets' request.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct GotoTarget {
     /// Unique identifier for a goto target. This is used in the goto request.
     #[serde(rename = "id")]
@@ -953,7 +1155,7 @@ pub struct GotoTarget {
 
     /// The name of the goto target (shown in the UI).
     #[serde(rename = "label")]
-    pub label: String,
+    pub label: Str,
 
     /// The line of the goto target.
     #[serde(rename = "line")]
@@ -961,17 +1163,20 @@ pub struct GotoTarget {
 
     /// An optional column of the goto target.
     #[serde(rename = "column", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub column: Option<i32>,
 
     /// An optional end line of the range covered by the goto target.
     #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_line: Option<i32>,
 
     /// An optional end column of the range covered by the goto target.
     #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_column: Option<i32>,
 
     /// Optional memory reference for the instruction pointer value represented by this target.
@@ -979,36 +1184,56 @@ pub struct GotoTarget {
         rename = "instructionPointerReference",
         skip_serializing_if = "Option::is_none"
     )]
-    #[builder(default)]
-    pub instruction_pointer_reference: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub instruction_pointer_reference: Option<Str>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl GotoTarget {
+    pub fn new(id: i32, label: Str, line: i32) -> GotoTarget {
+        GotoTarget {
+            id,
+            label,
+            line,
+            column: Default::default(),
+            end_line: Default::default(),
+            end_column: Default::default(),
+            instruction_pointer_reference: Default::default(),
+        }
+    }
 }
 
 /// Properties of a breakpoint passed to the setInstructionBreakpoints request
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct InstructionBreakpoint {
     /// The instruction reference of the breakpoint.
     ///
     /// This should be a memory or instruction pointer reference from an EvaluateResponse, Variable, StackFrame, GotoTarget, or Breakpoint.
     #[serde(rename = "instructionReference")]
-    pub instruction_reference: String,
+    pub instruction_reference: Str,
 
     /// An optional offset from the instruction reference.
     ///
     /// This can be negative.
     #[serde(rename = "offset", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub offset: Option<i32>,
 
     /// An optional expression for conditional breakpoints.
     ///
     /// It is only honored by a debug adapter if the capability 'supportsConditionalBreakpoints' is true.
     #[serde(rename = "condition", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub condition: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub condition: Option<Str>,
 
     /// An optional expression that controls how many hits of the breakpoint are ignored.
     ///
@@ -1016,16 +1241,39 @@ pub struct InstructionBreakpoint {
     ///
     /// The attribute is only honored by a debug adapter if the capability 'supportsHitConditionalBreakpoints' is true.
     #[serde(rename = "hitCondition", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub hit_condition: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub hit_condition: Option<Str>,
+
+    /// The mode of this breakpoint. If defined, this must be one of the 'breakpointModes' the debug adapter advertised in its Capabilities.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "mode", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub mode: Option<Str>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl InstructionBreakpoint {
+    pub fn new(instruction_reference: Str) -> InstructionBreakpoint {
+        InstructionBreakpoint {
+            instruction_reference,
+            offset: Default::default(),
+            condition: Default::default(),
+            hit_condition: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            mode: Default::default(),
+        }
+    }
 }
 
 /// Logical areas that can be invalidated by the 'invalidated' event.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum InvalidatedAreas {
     /// All previously fetched data has become invalid and needs to be refetched.
     #[serde(rename = "all")]
@@ -1042,10 +1290,21 @@ pub enum InvalidatedAreas {
     /// Previously fetched variable data has become invalid and needs to be refetched.
     #[serde(rename = "variables")]
     Variables,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
 /// A structured message object. Used to return errors from requests.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct Message {
     /// Unique identifier for the message.
     #[serde(rename = "id")]
@@ -1055,40 +1314,73 @@ pub struct Message {
     ///
     /// If variable name starts with an underscore character, the variable does not contain user data (PII) and can be safely used for telemetry purposes.
     #[serde(rename = "format")]
-    pub format: String,
+    pub format: Str,
 
     /// An object used as a dictionary for looking up the variables in the format string.
     #[serde(
         rename = "variables",
         default,
-        skip_serializing_if = "HashMap::is_empty"
+        skip_serializing_if = "is_none_or_empty"
     )]
-    #[builder(default)]
-    pub variables: HashMap<String, String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_hash_map))]
+    pub variables: Option<HashMap<String, String>>,
 
     /// If true send to telemetry.
     #[serde(rename = "sendTelemetry", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub send_telemetry: bool,
 
     /// If true show user.
     #[serde(rename = "showUser", default, skip_serializing_if = "eq_default")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub show_user: bool,
 
     /// An optional url where additional information about this message can be found.
     #[serde(rename = "url", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub url: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub url: Option<Str>,
 
     /// An optional label that is presented to the user as the UI for opening the url.
     #[serde(rename = "urlLabel", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub url_label: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub url_label: Option<Str>,
+
+    /// Additional attributes are implementation specific, for example diagnostic information.
+    #[serde(flatten)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::map))]
+    pub additional_attributes: Map<String, Value>,
+}
+
+impl Message {
+    pub fn new(id: i32, format: Str) -> Message {
+        Message {
+            id,
+            format,
+            variables: Default::default(),
+            send_telemetry: Default::default(),
+            show_user: Default::default(),
+            url: Default::default(),
+            url_label: Default::default(),
+            additional_attributes: Default::default(),
+        }
+    }
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// Returns a copy of this message that is safe to send to telemetry: the values of variables
+    /// that may contain user data (PII), which are all variables whose name does not start with an
+    /// underscore, are replaced by [REDACTED].
+    pub fn telemetry_safe(&self) -> Message {
+        let mut message = self.clone();
+        for (name, value) in message.variables.iter_mut().flatten() {
+            if !name.starts_with('_') {
+                *value = REDACTED.to_string();
+            }
+        }
+        message
+    }
 }
 
 /// A Module object represents a row in the modules view.
@@ -1106,7 +1398,14 @@ pub struct Message {
 /// To avoid an unnecessary proliferation of additional attributes with similar semantics but different names
 ///
 /// we recommend to re-use attributes from the 'recommended' list below first, and only introduce new attributes if nothing appropriate could be found.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct Module {
     /// Unique identifier for the module.
     #[serde(rename = "id")]
@@ -1114,7 +1413,7 @@ pub struct Module {
 
     /// A name of the module.
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: Str,
 
     /// optional but recommended attributes.
     ///
@@ -1124,79 +1423,140 @@ pub struct Module {
     ///
     /// Logical full path to the module. The exact definition is implementation defined, but usually this would be a full path to the on-disk file for the module.
     #[serde(rename = "path", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub path: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub path: Option<Str>,
 
     /// True if the module is optimized.
     #[serde(rename = "isOptimized", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub is_optimized: Option<bool>,
 
     /// True if the module is considered 'user code' by a debugger that supports 'Just My Code'.
     #[serde(rename = "isUserCode", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub is_user_code: Option<bool>,
 
     /// Version of Module.
     #[serde(rename = "version", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub version: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub version: Option<Str>,
 
     /// User understandable description of if symbols were found for the module (ex: 'Symbols Loaded', 'Symbols not found', etc.
     #[serde(rename = "symbolStatus", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub symbol_status: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub symbol_status: Option<Str>,
 
     /// Logical full path to the symbol file. The exact definition is implementation defined.
     #[serde(rename = "symbolFilePath", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub symbol_file_path: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub symbol_file_path: Option<Str>,
 
     /// Module created or modified.
     #[serde(rename = "dateTimeStamp", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub date_time_stamp: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub date_time_stamp: Option<Str>,
 
     /// Address range covered by this module.
     #[serde(rename = "addressRange", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub address_range: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub address_range: Option<Str>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl Module {
+    pub fn new(id: ModuleId, name: Str) -> Module {
+        Module {
+            id,
+            name,
+            path: Default::default(),
+            is_optimized: Default::default(),
+            is_user_code: Default::default(),
+            version: Default::default(),
+            symbol_status: Default::default(),
+            symbol_file_path: Default::default(),
+            date_time_stamp: Default::default(),
+            address_range: Default::default(),
+        }
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg(feature = "chrono")]
+impl Module {
+    /// Parses the dateTimeStamp, which is an RFC 3339 timestamp. Returns `None` if it is absent.
+    pub fn date_time(&self) -> Option<Result<DateTime<FixedOffset>, chrono::ParseError>> {
+        self.date_time_stamp
+            .as_deref()
+            .map(DateTime::parse_from_rfc3339)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
 #[serde(untagged)]
+#[non_exhaustive]
 pub enum ModuleId {
-    Integer(i32),
-    String(String),
+    Integer(i64),
+
+    /// A number that does not fit into an i64, for example an image base address above 2^63 or a
+    /// float.
+    Number(
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::non_i64_number))] Number,
+    ),
+
+    String(Str),
 }
 
 /// The ModulesViewDescriptor is the container for all declarative configuration options of a ModuleView.
 ///
 /// For now it only specifies the columns to be shown in the modules view.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ModulesViewDescriptor {
     #[serde(rename = "columns")]
     pub columns: Vec<ColumnDescriptor>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl ModulesViewDescriptor {
+    pub fn new(columns: Vec<ColumnDescriptor>) -> ModulesViewDescriptor {
+        ModulesViewDescriptor { columns }
+    }
 }
 
 /// A Scope is a named container for variables. Optionally a scope can map to a source or a range within a source.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct Scope {
     /// Name of the scope such as 'Arguments', 'Locals', or 'Registers'. This string is shown in the UI as is and can be translated.
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: Str,
 
     /// An optional hint for how to present this scope in the UI. If this attribute is missing, the scope is shown with a generic UI.
     #[serde(rename = "presentationHint", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub presentation_hint: Option<ScopePresentationHint>,
 
     /// The variables of this scope can be retrieved by passing the value of variablesReference to the VariablesRequest.
@@ -1207,14 +1567,16 @@ pub struct Scope {
     ///
     /// The client can use this optional information to present the variables in a paged UI and fetch them in chunks.
     #[serde(rename = "namedVariables", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub named_variables: Option<i32>,
 
     /// The number of indexed variables in this scope.
     ///
     /// The client can use this optional information to present the variables in a paged UI and fetch them in chunks.
     #[serde(rename = "indexedVariables", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub indexed_variables: Option<i32>,
 
     /// If true, the number of variables in this scope is large or expensive to retrieve.
@@ -1223,35 +1585,60 @@ pub struct Scope {
 
     /// Optional source for this scope.
     #[serde(rename = "source", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub source: Option<Source>,
 
     /// Optional start line of the range covered by this scope.
     #[serde(rename = "line", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub line: Option<i32>,
 
     /// Optional start column of the range covered by this scope.
     #[serde(rename = "column", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub column: Option<i32>,
 
     /// Optional end line of the range covered by this scope.
     #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_line: Option<i32>,
 
     /// Optional end column of the range covered by this scope.
     #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_column: Option<i32>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl Scope {
+    pub fn new(name: Str, variables_reference: i32, expensive: bool) -> Scope {
+        Scope {
+            name,
+            presentation_hint: Default::default(),
+            variables_reference,
+            named_variables: Default::default(),
+            indexed_variables: Default::default(),
+            expensive,
+            source: Default::default(),
+            line: Default::default(),
+            column: Default::default(),
+            end_line: Default::default(),
+            end_column: Default::default(),
+        }
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum ScopePresentationHint {
     /// Scope contains method arguments.
     #[serde(rename = "arguments")]
@@ -1264,26 +1651,44 @@ pub enum ScopePresentationHint {
     /// Scope contains registers. Only a single 'registers' scope should be returned from a 'scopes' request.
     #[serde(rename = "registers")]
     Registers,
+
+    /// Scope contains one or more return values.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "returnValue")]
+    ReturnValue,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
 /// A Source is a descriptor for source code.
 ///
 /// It is returned from the debug adapter as part of a StackFrame and it is used by clients when specifying breakpoints.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct Source {
     /// The short name of the source. Every source returned from the debug adapter has a name.
     ///
     /// When sending a source to the debug adapter this name is optional.
     #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub name: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub name: Option<Str>,
 
     /// The path of the source to be shown in the UI.
     ///
     /// It is only used to locate and load the content of the source if no sourceReference is specified (or its value is 0).
     #[serde(rename = "path", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub path: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub path: Option<Str>,
 
     /// If sourceReference > 0 the contents of the source must be retrieved through the SourceRequest (even if a path is specified).
     ///
@@ -1291,47 +1696,78 @@ pub struct Source {
     ///
     /// The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(rename = "sourceReference", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub source_reference: Option<i32>,
 
     /// An optional hint for how to present the source in the UI.
     ///
     /// A value of 'deemphasize' can be used to indicate that the source is not available or that it is skipped on stepping.
     #[serde(rename = "presentationHint", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub presentation_hint: Option<SourcePresentationHint>,
 
     /// The (optional) origin of this source: possible values 'internal module', 'inlined content from source map', etc.
     #[serde(rename = "origin", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub origin: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub origin: Option<Str>,
 
     /// An optional list of sources that are related to this source. These may be the source that generated this source.
     #[serde(rename = "sources", default, skip_serializing_if = "Vec::is_empty")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub sources: Vec<Source>,
 
     /// Optional data that a debug adapter might want to loop through the client.
     ///
     /// The client should leave the data intact and persist it across sessions. The client should not interpret the data.
     #[serde(rename = "adapterData", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::option_value))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub adapter_data: Option<Value>,
 
     /// The checksums associated with this file.
-    #[serde(rename = "checksums", default, skip_serializing_if = "Vec::is_empty")]
-    #[builder(default)]
-    pub checksums: Vec<Checksum>,
+    #[serde(rename = "checksums", default, skip_serializing_if = "<[_]>::is_empty")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    pub checksums: SmallVec<Checksum>,
+}
+
+impl Source {
+    /// The sourceReference if it is greater than 0. A sourceReference of 0 is treated like an absent one.
+    pub fn effective_source_reference(&self) -> Option<i32> {
+        self.source_reference.filter(|reference| *reference > 0)
+    }
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// Returns true if both sources identify the same content.
+    ///
+    /// Sources are identified by their effective sourceReference if any of them has one and by their
+    /// path otherwise. Paths are compared after [normalize_path](crate::paths::normalize_path).
+    pub fn is_same_source(&self, other: &Source) -> bool {
+        match (
+            self.effective_source_reference(),
+            other.effective_source_reference(),
+        ) {
+            (None, None) => match (&self.path, &other.path) {
+                (Some(path), Some(other_path)) => paths_equal(path, other_path),
+                _ => false,
+            },
+            (reference, other_reference) => reference == other_reference,
+        }
+    }
 }
 
 /// An optional hint for how to present the source in the UI.
 ///
 /// A value of 'deemphasize' can be used to indicate that the source is not available or that it is skipped on stepping.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum SourcePresentationHint {
     #[serde(rename = "normal")]
     Normal,
@@ -1341,10 +1777,21 @@ pub enum SourcePresentationHint {
 
     #[serde(rename = "deemphasize")]
     Deemphasize,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
 /// Properties of a breakpoint or logpoint passed to the setBreakpoints request.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct SourceBreakpoint {
     /// The source line of the breakpoint or logpoint.
     #[serde(rename = "line")]
@@ -1352,15 +1799,17 @@ pub struct SourceBreakpoint {
 
     /// An optional source column of the breakpoint.
     #[serde(rename = "column", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub column: Option<i32>,
 
     /// An optional expression for conditional breakpoints.
     ///
     /// It is only honored by a debug adapter if the capability 'supportsConditionalBreakpoints' is true.
     #[serde(rename = "condition", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub condition: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub condition: Option<Str>,
 
     /// An optional expression that controls how many hits of the breakpoint are ignored.
     ///
@@ -1368,8 +1817,9 @@ pub struct SourceBreakpoint {
     ///
     /// The attribute is only honored by a debug adapter if the capability 'supportsHitConditionalBreakpoints' is true.
     #[serde(rename = "hitCondition", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub hit_condition: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub hit_condition: Option<Str>,
 
     /// If this attribute exists and is non-empty, the backend must not 'break' (stop)
     ///
@@ -1377,16 +1827,41 @@ pub struct SourceBreakpoint {
     ///
     /// The attribute is only honored by a debug adapter if the capability 'supportsLogPoints' is true.
     #[serde(rename = "logMessage", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub log_message: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub log_message: Option<Str>,
+
+    /// The mode of this breakpoint. If defined, this must be one of the 'breakpointModes' the debug adapter advertised in its Capabilities.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "mode", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub mode: Option<Str>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl SourceBreakpoint {
+    pub fn new(line: i32) -> SourceBreakpoint {
+        SourceBreakpoint {
+            line,
+            column: Default::default(),
+            condition: Default::default(),
+            hit_condition: Default::default(),
+            log_message: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            mode: Default::default(),
+        }
+    }
 }
 
 /// A Stackframe contains the source location.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct StackFrame {
     /// An identifier for the stack frame. It must be unique across all threads.
     ///
@@ -1396,34 +1871,42 @@ pub struct StackFrame {
 
     /// The name of the stack frame, typically a method name.
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: Str,
 
     /// The optional source of the frame.
     #[serde(rename = "source", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub source: Option<Source>,
 
     /// The line within the file of the frame. If source is null or doesn't exist, line is 0 and must be ignored.
-    #[serde(rename = "line")]
+    ///
+    /// Defaults to 0 because some debug adapters omit it for label frames.
+    #[serde(rename = "line", default)]
     pub line: i32,
 
     /// The column within the line. If source is null or doesn't exist, column is 0 and must be ignored.
-    #[serde(rename = "column")]
+    ///
+    /// Defaults to 0 because some debug adapters omit it for label frames.
+    #[serde(rename = "column", default)]
     pub column: i32,
 
     /// An optional end line of the range covered by the stack frame.
     #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_line: Option<i32>,
 
     /// An optional end column of the range covered by the stack frame.
     #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub end_column: Option<i32>,
 
     /// Indicates whether this frame can be restarted with the 'restart' request. Clients should only use this if the debug adapter supports the 'restart' request (capability 'supportsRestartRequest' is true).
     #[serde(rename = "canRestart", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub can_restart: Option<bool>,
 
     /// Optional memory reference for the current instruction pointer in this frame.
@@ -1431,27 +1914,50 @@ pub struct StackFrame {
         rename = "instructionPointerReference",
         skip_serializing_if = "Option::is_none"
     )]
-    #[builder(default)]
-    pub instruction_pointer_reference: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub instruction_pointer_reference: Option<Str>,
 
     /// The module associated with this frame, if any.
     #[serde(rename = "moduleId", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub module_id: Option<ModuleId>,
 
     /// An optional hint for how to present this frame in the UI.
     ///
     /// A value of 'label' can be used to indicate that the frame is an artificial frame that is used as a visual label or separator. A value of 'subtle' can be used to change the appearance of a frame in a 'subtle' way.
     #[serde(rename = "presentationHint", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub presentation_hint: Option<StackFramePresentationHint>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl StackFrame {
+    pub fn new(id: i32, name: Str, line: i32, column: i32) -> StackFrame {
+        StackFrame {
+            id,
+            name,
+            source: Default::default(),
+            line,
+            column,
+            end_line: Default::default(),
+            end_column: Default::default(),
+            can_restart: Default::default(),
+            instruction_pointer_reference: Default::default(),
+            module_id: Default::default(),
+            presentation_hint: Default::default(),
+        }
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum StackFramePresentationHint {
     #[serde(rename = "normal")]
     Normal,
@@ -1461,53 +1967,74 @@ pub enum StackFramePresentationHint {
 
     #[serde(rename = "subtle")]
     Subtle,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
 /// Provides formatting information for a stack frame.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct StackFrameFormat {
     /// Displays parameters for the stack frame.
     #[serde(rename = "parameters", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub parameters: Option<bool>,
 
     /// Displays the types of parameters for the stack frame.
     #[serde(rename = "parameterTypes", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub parameter_types: Option<bool>,
 
     /// Displays the names of parameters for the stack frame.
     #[serde(rename = "parameterNames", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub parameter_names: Option<bool>,
 
     /// Displays the values of parameters for the stack frame.
     #[serde(rename = "parameterValues", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub parameter_values: Option<bool>,
 
     /// Displays the line number of the stack frame.
     #[serde(rename = "line", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub line: Option<bool>,
 
     /// Displays the module of the stack frame.
     #[serde(rename = "module", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub module: Option<bool>,
 
     /// Includes all stack frames, including those the debug adapter might otherwise hide.
     #[serde(rename = "includeAll", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub include_all: Option<bool>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 
 /// A StepInTarget can be used in the 'stepIn' request and determines into which single target the stepIn request should step.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct StepInTarget {
     /// Unique identifier for a stepIn target.
     #[serde(rename = "id")]
@@ -1515,15 +2042,62 @@ pub struct StepInTarget {
 
     /// The name of the stepIn target (shown in the UI).
     #[serde(rename = "label")]
-    pub label: String,
+    pub label: Str,
+
+    /// The line of the step-in target.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "line", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub line: Option<i32>,
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+    /// Start position of the range covered by the step in target. It is measured in UTF-16 code units and the client capability 'columnsStartAt1' determines whether it is 0- or 1-based.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "column", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub column: Option<i32>,
+
+    /// The end line of the range covered by the step in target.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub end_line: Option<i32>,
+
+    /// End position of the range covered by the step in target. It is measured in UTF-16 code units and the client capability 'columnsStartAt1' determines whether it is 0- or 1-based.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub end_column: Option<i32>,
+}
+
+impl StepInTarget {
+    pub fn new(id: i32, label: Str) -> StepInTarget {
+        StepInTarget {
+            id,
+            label,
+            #[cfg(feature = "spec-1_70")]
+            line: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            column: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            end_line: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            end_column: Default::default(),
+        }
+    }
 }
 
 /// The granularity of one 'step' in the stepping requests 'next', 'stepIn', 'stepOut', and 'stepBack'.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum SteppingGranularity {
     /// The step should allow the program to run until the current statement has finished executing.
     ///
@@ -1531,6 +2105,7 @@ pub enum SteppingGranularity {
     ///
     /// For example 'for(int i = 0; i < 10; i++) could be considered to have 3 statements 'int i = 0', 'i < 10', and 'i++'.
     #[serde(rename = "statement")]
+    #[default]
     Statement,
 
     /// The step should allow the program to run until the current source line has executed.
@@ -1540,16 +2115,21 @@ pub enum SteppingGranularity {
     /// The step should allow one instruction to execute (e.g. one x86 instruction).
     #[serde(rename = "instruction")]
     Instruction,
-}
 
-impl Default for SteppingGranularity {
-    fn default() -> Self {
-        SteppingGranularity::Statement
-    }
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
 /// A Thread
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct Thread {
     /// Unique identifier for the thread.
     #[serde(rename = "id")]
@@ -1557,24 +2137,30 @@ pub struct Thread {
 
     /// A name of the thread.
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: Str,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl Thread {
+    pub fn new(id: i32, name: Str) -> Thread {
+        Thread { id, name }
+    }
 }
 
 /// Provides formatting information for a value.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct ValueFormat {
     /// Display the value in hex.
     #[serde(rename = "hex", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub hex: Option<bool>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 
 /// A Variable is a name/value pair.
@@ -1588,32 +2174,42 @@ pub struct ValueFormat {
 /// If the number of named or indexed children is large, the numbers should be returned via the optional 'namedVariables' and 'indexedVariables' attributes.
 ///
 /// The client can use this optional information to present the children in a paged UI and fetch them in chunks.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct Variable {
     /// The variable's name.
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: Str,
 
     /// The variable's value. This can be a multi-line text, e.g. for a function the body of a function.
     #[serde(rename = "value")]
-    pub value: String,
+    pub value: Str,
 
     /// The type of the variable's value. Typically shown in the UI when hovering over the value.
     ///
     /// This attribute should only be returned by a debug adapter if the client has passed the value true for the 'supportsVariableType' capability of the 'initialize' request.
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub type_: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub type_: Option<Str>,
 
     /// Properties of a variable that can be used to determine how to render the variable in the UI.
     #[serde(rename = "presentationHint", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub presentation_hint: Option<VariablePresentationHint>,
 
     /// Optional evaluatable name of this variable which can be passed to the 'EvaluateRequest' to fetch the variable's value.
     #[serde(rename = "evaluateName", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub evaluate_name: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub evaluate_name: Option<Str>,
 
     /// If variablesReference is > 0, the variable is structured and its children can be retrieved by passing variablesReference to the VariablesRequest.
     #[serde(rename = "variablesReference")]
@@ -1623,52 +2219,106 @@ pub struct Variable {
     ///
     /// The client can use this optional information to present the children in a paged UI and fetch them in chunks.
     #[serde(rename = "namedVariables", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub named_variables: Option<i32>,
 
     /// The number of indexed child variables.
     ///
     /// The client can use this optional information to present the children in a paged UI and fetch them in chunks.
     #[serde(rename = "indexedVariables", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub indexed_variables: Option<i32>,
 
     /// Optional memory reference for the variable if the variable represents executable code, such as a function pointer.
     ///
     /// This attribute is only required if the client has passed the value true for the 'supportsMemoryReferences' capability of the 'initialize' request.
     #[serde(rename = "memoryReference", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
-    pub memory_reference: Option<String>,
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub memory_reference: Option<Str>,
+
+    /// A reference that allows the client to request the location where the variable is declared. This should be present only if the adapter is likely to be able to resolve the location.
+    ///
+    /// This reference shares the same lifetime as the 'variablesReference'.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(
+        rename = "declarationLocationReference",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub declaration_location_reference: Option<i32>,
+
+    /// A reference that allows the client to request the location where the variable's value is declared. For example, if the variable contains a function pointer, the adapter may be able to look up the function's location. This should be present only if the adapter is likely to be able to resolve the location.
+    ///
+    /// This reference shares the same lifetime as the 'variablesReference'.
+    #[cfg(feature = "spec-1_70")]
+    #[serde(
+        rename = "valueLocationReference",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
+    pub value_location_reference: Option<i32>,
+}
 
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
+impl Variable {
+    pub fn new(name: Str, value: Str, variables_reference: i32) -> Variable {
+        Variable {
+            name,
+            value,
+            type_: Default::default(),
+            presentation_hint: Default::default(),
+            evaluate_name: Default::default(),
+            variables_reference,
+            named_variables: Default::default(),
+            indexed_variables: Default::default(),
+            memory_reference: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            declaration_location_reference: Default::default(),
+            #[cfg(feature = "spec-1_70")]
+            value_location_reference: Default::default(),
+        }
+    }
 }
 
 /// Optional properties of a variable that can be used to determine how to render the variable in the UI.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, TypedBuilder)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[cfg_attr(feature = "builders", derive(TypedBuilder))]
+#[non_exhaustive]
 pub struct VariablePresentationHint {
     /// The kind of variable. Before introducing additional values, try to use the listed values.
     #[serde(rename = "kind", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub kind: Option<VariableKind>,
 
     /// Set of attributes represented as an array of strings. Before introducing additional values, try to use the listed values.
     #[serde(rename = "attributes", default, skip_serializing_if = "Vec::is_empty")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
     pub attributes: Vec<VariableAttribute>,
 
     /// Visibility of variable. Before introducing additional values, try to use the listed values.
     #[serde(rename = "visibility", skip_serializing_if = "Option::is_none")]
-    #[builder(default)]
+    #[cfg_attr(feature = "builders", builder(default))]
+    #[cfg_attr(feature = "ts-rs", ts(optional))]
     pub visibility: Option<VariableVisibility>,
-
-    #[serde(skip)]
-    #[builder(default, setter(skip))]
-    private: (),
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum VariableKind {
     /// Indicates that the object is a property.
     #[serde(rename = "property")]
@@ -1715,9 +2365,19 @@ pub enum VariableKind {
     /// Deprecated: Indicates that a data breakpoint is registered for the object. The 'hasDataBreakpoint' attribute should generally be used instead.
     #[serde(rename = "dataBreakpoint")]
     DataBreakpoint,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum VariableAttribute {
     /// Indicates that the object is static.
     #[serde(rename = "static")]
@@ -1750,9 +2410,19 @@ pub enum VariableAttribute {
     /// Indicates that the object has its value tracked by a data breakpoint.
     #[serde(rename = "hasDataBreakpoint")]
     HasDataBreakpoint,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "de", derive(Deserialize))]
+#[cfg_attr(feature = "ser", derive(Serialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "ts-rs", derive(TS))]
+#[non_exhaustive]
 pub enum VariableVisibility {
     #[serde(rename = "public")]
     Public,
@@ -1768,4 +2438,8 @@ pub enum VariableVisibility {
 
     #[serde(rename = "final")]
     Final,
+
+    /// A value that is not one of the above. Known values always deserialize as their variant.
+    #[serde(untagged)]
+    Other(#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::fuzz::other))] Str),
 }
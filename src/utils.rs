@@ -1,7 +1,87 @@
+#[cfg(any(feature = "ser", feature = "schemars"))]
+use std::collections::HashMap;
+
+#[cfg(all(
+    any(feature = "de", feature = "schemars"),
+    any(feature = "requests", feature = "responses")
+))]
 pub(crate) fn true_() -> bool {
     true
 }
 
+#[cfg(all(
+    any(feature = "ser", feature = "schemars"),
+    any(feature = "requests", feature = "responses")
+))]
+pub(crate) fn is_true(b: &bool) -> bool {
+    *b
+}
+
+#[cfg(any(feature = "ser", feature = "schemars"))]
 pub(crate) fn eq_default<T: Default + PartialEq>(t: &T) -> bool {
     t.eq(&Default::default())
 }
+
+/// Maps that are usually empty are optional, so that an absent map doesn't need a [HashMap].
+#[cfg(any(feature = "ser", feature = "schemars"))]
+pub(crate) fn is_none_or_empty<K, V>(map: &Option<HashMap<K, V>>) -> bool {
+    map.as_ref().is_none_or(HashMap::is_empty)
+}
+
+/// Calls `difference` with the JSON pointer and both values of every property that differs
+/// between `expected` and `actual`. Properties named in `ignored` are skipped at any depth.
+#[cfg(any(test, feature = "replay", feature = "test-fixtures"))]
+pub(crate) fn json_differences(
+    expected: &serde_json::Value,
+    actual: &serde_json::Value,
+    ignored: &[String],
+    difference: &mut impl FnMut(&str, Option<&serde_json::Value>, Option<&serde_json::Value>),
+) {
+    use serde_json::Value;
+
+    fn visit(
+        path: &mut String,
+        expected: &Value,
+        actual: &Value,
+        ignored: &[String],
+        difference: &mut impl FnMut(&str, Option<&Value>, Option<&Value>),
+    ) {
+        match (expected, actual) {
+            (Value::Object(expected), Value::Object(actual)) => {
+                let keys = expected
+                    .keys()
+                    .chain(actual.keys().filter(|key| !expected.contains_key(*key)));
+                for key in keys {
+                    if ignored.contains(key) {
+                        continue;
+                    }
+                    let len = path.len();
+                    path.push('/');
+                    path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                    match (expected.get(key), actual.get(key)) {
+                        (Some(expected), Some(actual)) => {
+                            visit(path, expected, actual, ignored, difference)
+                        }
+                        (expected, actual) => difference(path, expected, actual),
+                    }
+                    path.truncate(len);
+                }
+            }
+            (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+                for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                    let len = path.len();
+                    path.push('/');
+                    path.push_str(&index.to_string());
+                    visit(path, expected, actual, ignored, difference);
+                    path.truncate(len);
+                }
+            }
+            (expected, actual) if expected != actual => {
+                difference(path, Some(expected), Some(actual))
+            }
+            _ => {}
+        }
+    }
+
+    visit(&mut String::new(), expected, actual, ignored, difference);
+}
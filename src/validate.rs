@@ -0,0 +1,565 @@
+#[cfg(feature = "events")]
+use crate::events::{
+    BreakpointEventBody, Event, LoadedSourceEventBody, OutputEventBody, ProgressStartEventBody,
+    ProgressUpdateEventBody,
+};
+#[cfg(feature = "requests")]
+use crate::requests::{
+    BreakpointLocationsRequestArguments, DataBreakpointInfoRequestArguments,
+    GotoTargetsRequestArguments, Request, SetBreakpointsRequestArguments,
+    SetVariableRequestArguments, SourceRequestArguments, VariablesRequestArguments,
+};
+#[cfg(feature = "responses")]
+use crate::responses::{
+    BreakpointLocationsResponseBody, DisassembleResponseBody, EvaluateResponseBody,
+    GotoTargetsResponseBody, LoadedSourcesResponseBody, Response, ScopesResponseBody,
+    SetBreakpointsResponseBody, SetExpressionResponseBody, SetFunctionBreakpointsResponseBody,
+    SetInstructionBreakpointsResponseBody, SetVariableResponseBody, StackTraceResponseBody,
+    SuccessResponse, VariablesResponseBody,
+};
+#[cfg(all(feature = "responses", feature = "spec-1_70"))]
+use crate::responses::{LocationsResponseBody, StepInTargetsResponseBody};
+#[cfg(feature = "spec-1_70")]
+use crate::types::StepInTarget;
+use crate::{
+    types::{
+        Breakpoint, BreakpointLocation, DisassembledInstruction, GotoTarget, Scope, Source,
+        StackFrame, Variable,
+    },
+    ProtocolMessage, ProtocolMessageContent,
+};
+use std::fmt::{self, Display};
+
+/// A violation of a constraint of the specification that is not enforced by the types.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Violation {
+    path: String,
+    message: String,
+}
+
+impl Violation {
+    pub(crate) fn new(path: String, message: impl Into<String>) -> Violation {
+        Violation {
+            path,
+            message: message.into(),
+        }
+    }
+
+    /// The path of the offending field relative to the validated value, for example
+    /// `body.variables[2].variablesReference`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// A description of the violated constraint.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Checks invariants of the specification that can't be expressed by the types, for example that
+/// a progress percentage is within 0 and 100 or that a [Source] has either a path or a
+/// sourceReference.
+pub trait Validate {
+    /// Returns all violations found in `self`. An empty list means that `self` is valid.
+    fn validate(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        self.validate_at("", &mut violations);
+        violations
+    }
+
+    /// Appends the violations found in `self` to `violations`, prefixing their paths with `path`.
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>);
+}
+
+impl<T: Validate> Validate for Option<T> {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        if let Some(value) = self {
+            value.validate_at(path, violations);
+        }
+    }
+}
+
+impl<T: Validate> Validate for Vec<T> {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        for (index, value) in self.iter().enumerate() {
+            value.validate_at(&format!("{}[{}]", path, index), violations);
+        }
+    }
+}
+
+pub(crate) fn key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[cfg(feature = "events")]
+fn check_percentage(path: &str, percentage: Option<u8>, violations: &mut Vec<Violation>) {
+    if let Some(percentage) = percentage.filter(|percentage| *percentage > 100) {
+        violations.push(Violation::new(
+            key(path, "percentage"),
+            format!("percentage {} is not between 0 and 100", percentage),
+        ));
+    }
+}
+
+fn check_variables_reference(path: &str, reference: Option<i32>, violations: &mut Vec<Violation>) {
+    if let Some(reference) = reference.filter(|reference| *reference < 0) {
+        violations.push(Violation::new(
+            key(path, "variablesReference"),
+            format!(
+                "variablesReference {} is not between 0 and 2^31-1",
+                reference
+            ),
+        ));
+    }
+}
+
+/// Checks that the end of a range does not precede its start. A missing end line means that the
+/// range ends in the start line.
+fn check_range(
+    path: &str,
+    (line, column): (Option<i32>, Option<i32>),
+    (end_line, end_column): (Option<i32>, Option<i32>),
+    violations: &mut Vec<Violation>,
+) {
+    match (line, end_line) {
+        (Some(line), Some(end_line)) if end_line < line => {
+            violations.push(Violation::new(
+                key(path, "endLine"),
+                format!("endLine {} precedes line {}", end_line, line),
+            ));
+            return;
+        }
+        (Some(line), Some(end_line)) if end_line != line => return,
+        _ => {}
+    }
+    if let (Some(column), Some(end_column)) = (column, end_column) {
+        if end_column < column {
+            violations.push(Violation::new(
+                key(path, "endColumn"),
+                format!("endColumn {} precedes column {}", end_column, column),
+            ));
+        }
+    }
+}
+
+impl Validate for ProtocolMessage {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.content.validate_at(path, violations);
+    }
+}
+
+impl Validate for ProtocolMessageContent {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        match self {
+            #[cfg(feature = "requests")]
+            ProtocolMessageContent::Request(request) => request.validate_at(path, violations),
+            #[cfg(feature = "responses")]
+            ProtocolMessageContent::Response(response) => response.validate_at(path, violations),
+            #[cfg(feature = "events")]
+            ProtocolMessageContent::Event(event) => event.validate_at(path, violations),
+        }
+    }
+}
+
+#[cfg(feature = "requests")]
+impl Validate for Request {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        let path = &key(path, "arguments");
+        match self {
+            Request::BreakpointLocations(arguments) => arguments.validate_at(path, violations),
+            Request::DataBreakpointInfo(arguments) => arguments.validate_at(path, violations),
+            Request::GotoTargets(arguments) => arguments.validate_at(path, violations),
+            Request::SetBreakpoints(arguments) => arguments.validate_at(path, violations),
+            Request::SetVariable(arguments) => arguments.validate_at(path, violations),
+            Request::Source(arguments) => arguments.validate_at(path, violations),
+            Request::Variables(arguments) => arguments.validate_at(path, violations),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for Response {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        if let Ok(response) = &self.result {
+            response.validate_at(path, violations);
+        }
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for SuccessResponse {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        let path = &key(path, "body");
+        match self {
+            SuccessResponse::BreakpointLocations(body) => body.validate_at(path, violations),
+            SuccessResponse::Disassemble(body) => body.validate_at(path, violations),
+            SuccessResponse::Evaluate(body) => body.validate_at(path, violations),
+            SuccessResponse::GotoTargets(body) => body.validate_at(path, violations),
+            SuccessResponse::LoadedSources(body) => body.validate_at(path, violations),
+            #[cfg(feature = "spec-1_70")]
+            SuccessResponse::Locations(body) => body.validate_at(path, violations),
+            SuccessResponse::Scopes(body) => body.validate_at(path, violations),
+            SuccessResponse::SetBreakpoints(body) => body.validate_at(path, violations),
+            SuccessResponse::SetExpression(body) => body.validate_at(path, violations),
+            SuccessResponse::SetFunctionBreakpoints(body) => body.validate_at(path, violations),
+            SuccessResponse::SetInstructionBreakpoints(body) => body.validate_at(path, violations),
+            SuccessResponse::SetVariable(body) => body.validate_at(path, violations),
+            SuccessResponse::StackTrace(body) => body.validate_at(path, violations),
+            #[cfg(feature = "spec-1_70")]
+            SuccessResponse::StepInTargets(body) => body.validate_at(path, violations),
+            SuccessResponse::Variables(body) => body.validate_at(path, violations),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(feature = "events")]
+impl Validate for Event {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        let path = &key(path, "body");
+        match self {
+            Event::Breakpoint(body) => body.validate_at(path, violations),
+            Event::LoadedSource(body) => body.validate_at(path, violations),
+            Event::Output(body) => body.validate_at(path, violations),
+            Event::ProgressStart(body) => body.validate_at(path, violations),
+            Event::ProgressUpdate(body) => body.validate_at(path, violations),
+            _ => {}
+        }
+    }
+}
+
+// Requests
+
+#[cfg(feature = "requests")]
+impl Validate for BreakpointLocationsRequestArguments {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.source.validate_at(&key(path, "source"), violations);
+        check_range(
+            path,
+            (Some(self.line), self.column),
+            (self.end_line, self.end_column),
+            violations,
+        );
+    }
+}
+
+#[cfg(feature = "requests")]
+impl Validate for DataBreakpointInfoRequestArguments {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_variables_reference(path, self.variables_reference, violations);
+    }
+}
+
+#[cfg(feature = "requests")]
+impl Validate for GotoTargetsRequestArguments {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.source.validate_at(&key(path, "source"), violations);
+    }
+}
+
+#[cfg(feature = "requests")]
+impl Validate for SetBreakpointsRequestArguments {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.source.validate_at(&key(path, "source"), violations);
+    }
+}
+
+#[cfg(feature = "requests")]
+impl Validate for SetVariableRequestArguments {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_variables_reference(path, Some(self.variables_reference), violations);
+    }
+}
+
+#[cfg(feature = "requests")]
+impl Validate for SourceRequestArguments {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.source.validate_at(&key(path, "source"), violations);
+    }
+}
+
+#[cfg(feature = "requests")]
+impl Validate for VariablesRequestArguments {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_variables_reference(path, Some(self.variables_reference), violations);
+    }
+}
+
+// Responses
+
+#[cfg(feature = "responses")]
+impl Validate for BreakpointLocationsResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.breakpoints
+            .validate_at(&key(path, "breakpoints"), violations);
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for DisassembleResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.instructions
+            .validate_at(&key(path, "instructions"), violations);
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for EvaluateResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_variables_reference(path, Some(self.variables_reference), violations);
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for GotoTargetsResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.targets.validate_at(&key(path, "targets"), violations);
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for LoadedSourcesResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.sources.validate_at(&key(path, "sources"), violations);
+    }
+}
+
+#[cfg(all(feature = "responses", feature = "spec-1_70"))]
+impl Validate for LocationsResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.source.validate_at(&key(path, "source"), violations);
+        check_range(
+            path,
+            (Some(self.line), self.column),
+            (self.end_line, self.end_column),
+            violations,
+        );
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for ScopesResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.scopes.validate_at(&key(path, "scopes"), violations);
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for SetBreakpointsResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.breakpoints
+            .validate_at(&key(path, "breakpoints"), violations);
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for SetExpressionResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_variables_reference(path, self.variables_reference, violations);
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for SetFunctionBreakpointsResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.breakpoints
+            .validate_at(&key(path, "breakpoints"), violations);
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for SetInstructionBreakpointsResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.breakpoints
+            .validate_at(&key(path, "breakpoints"), violations);
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for SetVariableResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_variables_reference(path, self.variables_reference, violations);
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for StackTraceResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.stack_frames
+            .validate_at(&key(path, "stackFrames"), violations);
+    }
+}
+
+#[cfg(all(feature = "responses", feature = "spec-1_70"))]
+impl Validate for StepInTargetsResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.targets.validate_at(&key(path, "targets"), violations);
+    }
+}
+
+#[cfg(feature = "responses")]
+impl Validate for VariablesResponseBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.variables
+            .validate_at(&key(path, "variables"), violations);
+    }
+}
+
+// Events
+
+#[cfg(feature = "events")]
+impl Validate for BreakpointEventBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.breakpoint
+            .validate_at(&key(path, "breakpoint"), violations);
+    }
+}
+
+#[cfg(feature = "events")]
+impl Validate for LoadedSourceEventBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.source.validate_at(&key(path, "source"), violations);
+    }
+}
+
+#[cfg(feature = "events")]
+impl Validate for OutputEventBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_variables_reference(path, self.variables_reference, violations);
+        self.source.validate_at(&key(path, "source"), violations);
+    }
+}
+
+#[cfg(feature = "events")]
+impl Validate for ProgressStartEventBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_percentage(path, self.percentage, violations);
+    }
+}
+
+#[cfg(feature = "events")]
+impl Validate for ProgressUpdateEventBody {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_percentage(path, self.percentage, violations);
+    }
+}
+
+// Types
+
+impl Validate for Breakpoint {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.source.validate_at(&key(path, "source"), violations);
+        check_range(
+            path,
+            (self.line, self.column),
+            (self.end_line, self.end_column),
+            violations,
+        );
+    }
+}
+
+impl Validate for BreakpointLocation {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_range(
+            path,
+            (Some(self.line), self.column),
+            (self.end_line, self.end_column),
+            violations,
+        );
+    }
+}
+
+impl Validate for DisassembledInstruction {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.location
+            .validate_at(&key(path, "location"), violations);
+        check_range(
+            path,
+            (self.line, self.column),
+            (self.end_line, self.end_column),
+            violations,
+        );
+    }
+}
+
+impl Validate for GotoTarget {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_range(
+            path,
+            (Some(self.line), self.column),
+            (self.end_line, self.end_column),
+            violations,
+        );
+    }
+}
+
+impl Validate for Scope {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_variables_reference(path, Some(self.variables_reference), violations);
+        self.source.validate_at(&key(path, "source"), violations);
+        check_range(
+            path,
+            (self.line, self.column),
+            (self.end_line, self.end_column),
+            violations,
+        );
+    }
+}
+
+impl Validate for Source {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        if self.path.is_none() && self.effective_source_reference().is_none() {
+            violations.push(Violation::new(
+                path.to_string(),
+                "source must have either a path or a sourceReference greater than 0",
+            ));
+        }
+        self.sources.validate_at(&key(path, "sources"), violations);
+    }
+}
+
+impl Validate for StackFrame {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        self.source.validate_at(&key(path, "source"), violations);
+        check_range(
+            path,
+            (Some(self.line), Some(self.column)),
+            (self.end_line, self.end_column),
+            violations,
+        );
+    }
+}
+
+#[cfg(feature = "spec-1_70")]
+impl Validate for StepInTarget {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_range(
+            path,
+            (self.line, self.column),
+            (self.end_line, self.end_column),
+            violations,
+        );
+    }
+}
+
+impl Validate for Variable {
+    fn validate_at(&self, path: &str, violations: &mut Vec<Violation>) {
+        check_variables_reference(path, Some(self.variables_reference), violations);
+    }
+}
@@ -0,0 +1,215 @@
+//! A client side list of watch expressions that are re-evaluated whenever the debuggee stops.
+//!
+//! A [WatchList] stores the expressions the user watches and their results. When the debuggee
+//! stops and the client selected a stack frame, [WatchList::select_frame] returns the 'evaluate'
+//! requests for all expressions in the `watch` context. [WatchList::handle_response] stores the
+//! result or the error of each of them, and [WatchList::variables] presents the expressions like
+//! the [Variable]s of a scope, so the same tree view can show both.
+
+use crate::{
+    requests::{EvaluateRequestArguments, EvaluateRequestContext},
+    responses::{ErrorResponse, EvaluateResponseBody, SuccessResponse},
+    types::{ValueFormat, Variable},
+    Str,
+};
+
+/// Identifies a watch expression in a [WatchList]. Ids are not reused after an expression was
+/// removed.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct WatchId(u64);
+
+/// The result of evaluating a watch expression in the selected frame.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WatchState {
+    /// The expression was not evaluated in the selected frame yet.
+    Pending,
+
+    /// The expression was evaluated successfully.
+    Value(Box<EvaluateResponseBody>),
+
+    /// The evaluation failed with this message.
+    Error(Str),
+}
+
+/// A watch expression and its result.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Watch {
+    pub id: WatchId,
+    pub expression: Str,
+    pub state: WatchState,
+}
+
+impl Watch {
+    /// Presents the watch expression as a [Variable] named after the expression. Errors are shown
+    /// as its value, pending results as an empty value.
+    pub fn to_variable(&self) -> Variable {
+        let mut variable = Variable {
+            name: self.expression.clone(),
+            value: Str::default(),
+            type_: None,
+            presentation_hint: None,
+            evaluate_name: Some(self.expression.clone()),
+            variables_reference: 0,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+            #[cfg(feature = "spec-1_70")]
+            declaration_location_reference: None,
+            #[cfg(feature = "spec-1_70")]
+            value_location_reference: None,
+        };
+        match &self.state {
+            WatchState::Pending => {}
+            WatchState::Value(body) => {
+                variable.value = body.result.clone();
+                variable.type_ = body.type_.clone();
+                variable.presentation_hint = body.presentation_hint.clone();
+                variable.variables_reference = body.variables_reference;
+                variable.named_variables = body.named_variables;
+                variable.indexed_variables = body.indexed_variables;
+                variable.memory_reference = body.memory_reference.clone();
+                #[cfg(feature = "spec-1_70")]
+                {
+                    variable.value_location_reference = body.value_location_reference;
+                }
+            }
+            WatchState::Error(message) => variable.value = message.clone(),
+        }
+        variable
+    }
+}
+
+/// The watch expressions of a client and their results in the selected frame. See the
+/// [module documentation](self).
+#[derive(Clone, Debug, Default)]
+pub struct WatchList {
+    watches: Vec<Watch>,
+    next_id: u64,
+    frame_id: Option<i32>,
+    format: Option<ValueFormat>,
+}
+
+impl WatchList {
+    pub fn new() -> WatchList {
+        WatchList::default()
+    }
+
+    /// Sets how values are formatted, for example as hex. Takes effect with the next evaluation.
+    pub fn set_format(&mut self, format: Option<ValueFormat>) {
+        self.format = format;
+    }
+
+    /// The frame the expressions are evaluated in, or `None` for the global scope.
+    pub fn frame_id(&self) -> Option<i32> {
+        self.frame_id
+    }
+
+    /// Adds `expression` and returns its id and the request to evaluate it in the selected frame.
+    pub fn add(&mut self, expression: impl Into<Str>) -> (WatchId, EvaluateRequestArguments) {
+        let id = WatchId(self.next_id);
+        self.next_id += 1;
+        let expression = expression.into();
+        let request = self.request(&expression);
+        self.watches.push(Watch {
+            id,
+            expression,
+            state: WatchState::Pending,
+        });
+        (id, request)
+    }
+
+    /// Removes the expression with `id`. Returns whether it existed.
+    pub fn remove(&mut self, id: WatchId) -> bool {
+        let len = self.watches.len();
+        self.watches.retain(|watch| watch.id != id);
+        self.watches.len() != len
+    }
+
+    /// Replaces the expression with `id` and returns the request to evaluate the new one, or
+    /// `None` if there is no expression with `id`.
+    pub fn edit(
+        &mut self,
+        id: WatchId,
+        expression: impl Into<Str>,
+    ) -> Option<EvaluateRequestArguments> {
+        let expression = expression.into();
+        let request = self.request(&expression);
+        let watch = self.watches.iter_mut().find(|watch| watch.id == id)?;
+        watch.expression = expression;
+        watch.state = WatchState::Pending;
+        Some(request)
+    }
+
+    /// Selects the frame to evaluate the expressions in, usually the top frame of the thread that
+    /// stopped. Call this on every 'stopped' event and whenever the user selects another frame.
+    /// Marks all results as pending and returns the requests to re-evaluate the expressions.
+    pub fn select_frame(&mut self, frame_id: Option<i32>) -> Vec<EvaluateRequestArguments> {
+        self.frame_id = frame_id;
+        for watch in &mut self.watches {
+            watch.state = WatchState::Pending;
+        }
+        self.watches
+            .iter()
+            .map(|watch| self.request(&watch.expression))
+            .collect()
+    }
+
+    fn request(&self, expression: &Str) -> EvaluateRequestArguments {
+        EvaluateRequestArguments {
+            expression: expression.clone(),
+            frame_id: self.frame_id,
+            context: Some(EvaluateRequestContext::Watch),
+            format: self.format.clone(),
+        }
+    }
+
+    /// Stores the result of `request` in all pending watches of its expression. Results for another
+    /// frame than the selected one are outdated and ignored. Returns whether the result was stored.
+    pub fn handle_response(
+        &mut self,
+        request: &EvaluateRequestArguments,
+        result: &Result<SuccessResponse, ErrorResponse>,
+    ) -> bool {
+        if request.frame_id != self.frame_id
+            || request.context != Some(EvaluateRequestContext::Watch)
+        {
+            return false;
+        }
+        let state = match result {
+            Ok(SuccessResponse::Evaluate(body)) => WatchState::Value(body.clone()),
+            Ok(_) => return false,
+            Err(error) => WatchState::Error(error_message(error)),
+        };
+        let mut stored = false;
+        for watch in &mut self.watches {
+            if watch.expression == request.expression && watch.state == WatchState::Pending {
+                watch.state = state.clone();
+                stored = true;
+            }
+        }
+        stored
+    }
+
+    /// The watch with `id`.
+    pub fn get(&self, id: WatchId) -> Option<&Watch> {
+        self.watches.iter().find(|watch| watch.id == id)
+    }
+
+    /// The watches in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &Watch> {
+        self.watches.iter()
+    }
+
+    /// The watches presented as [Variable]s, see [Watch::to_variable].
+    pub fn variables(&self) -> Vec<Variable> {
+        self.watches.iter().map(Watch::to_variable).collect()
+    }
+}
+
+/// The structured error message of `error` if it has one, otherwise its short form.
+pub(crate) fn error_message(error: &ErrorResponse) -> Str {
+    match &error.body.error {
+        Some(message) => Str::from(message.render(&|_: &str| None::<String>)),
+        None => error.message.clone(),
+    }
+}
@@ -0,0 +1,143 @@
+//! Upper bounds for the number of allocations of encoding and decoding common messages.
+//!
+//! These run in their own test binary because they install a counting global allocator. Counts are
+//! kept per thread, so tests running in parallel don't affect each other.
+
+// Converting to `Str` is only a no-op without the shared-strings feature
+#![allow(clippy::useless_conversion)]
+
+use debug_adapter_protocol::{
+    events::{OutputCategory, OutputEventBody, StoppedEventBody, StoppedEventReason},
+    ProtocolMessage,
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    str::FromStr,
+};
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the result of `f` and the number of allocations and reallocations it made.
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+fn output_event() -> ProtocolMessage {
+    ProtocolMessage::new(
+        12,
+        OutputEventBody::builder()
+            .category(OutputCategory::Stdout)
+            .output("Hello World\n".into())
+            .build(),
+    )
+}
+
+fn stopped_event() -> ProtocolMessage {
+    ProtocolMessage::new(
+        13,
+        StoppedEventBody::builder()
+            .reason(StoppedEventReason::Breakpoint)
+            .thread_id(Some(1))
+            .all_threads_stopped(true)
+            .hit_breakpoint_ids(vec![3].into())
+            .build(),
+    )
+}
+
+#[test]
+fn test_write_output_event() {
+    // given:
+    let message = output_event();
+    let mut buffer = Vec::with_capacity(1024);
+
+    // when:
+    let (result, allocations) = count_allocations(|| message.write_to(&mut buffer));
+
+    // then:
+    result.unwrap();
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn test_write_stopped_event() {
+    // given:
+    let message = stopped_event();
+    let mut buffer = Vec::with_capacity(1024);
+
+    // when:
+    let (result, allocations) = count_allocations(|| message.write_to(&mut buffer));
+
+    // then:
+    result.unwrap();
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn test_display_output_event() {
+    // given:
+    let message = output_event();
+
+    // when:
+    let (_, allocations) = count_allocations(|| message.to_string());
+
+    // then:
+    assert!(allocations <= 4, "{} allocations", allocations);
+}
+
+#[test]
+fn test_parse_output_event() {
+    // given:
+    let json = serde_json::to_string(&output_event()).unwrap();
+
+    // when:
+    let (result, allocations) = count_allocations(|| ProtocolMessage::from_str(&json));
+
+    // then:
+    assert_eq!(result.unwrap(), output_event());
+    // A shared string is parsed into a String and then copied into an Arc
+    let limit = if cfg!(feature = "shared-strings") {
+        11
+    } else {
+        10
+    };
+    assert!(allocations <= limit, "{} allocations", allocations);
+}
+
+#[test]
+fn test_parse_stopped_event() {
+    // given:
+    let json = serde_json::to_string(&stopped_event()).unwrap();
+
+    // when:
+    let (result, allocations) = count_allocations(|| ProtocolMessage::from_str(&json));
+
+    // then:
+    assert_eq!(result.unwrap(), stopped_event());
+    assert!(allocations <= 8, "{} allocations", allocations);
+}
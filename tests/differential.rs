@@ -0,0 +1,236 @@
+//! Serializes the messages in `tests/snapshots` with this crate and with the `dap-types` crate and
+//! compares the JSON, to surface disagreements about the wire format between the implementations.
+//!
+//! Both crates parse the snapshot of a message and serialize it again: this crate the whole
+//! message, `dap-types` its body or arguments. Absent, `null` and default properties are treated
+//! alike, since `dap-types` serializes absent optional properties as `null` and this crate omits
+//! properties with default values. Run it with:
+//!
+//! ```text
+//! cargo test --features dap-types --test differential
+//! ```
+
+use debug_adapter_protocol::ProtocolMessage;
+use serde_json::{Map, Value};
+use std::{fs, path::Path, str::FromStr};
+
+/// Messages that are known to be handled differently, with the reason.
+const KNOWN_DISAGREEMENTS: &[(&str, &str)] = &[
+    (
+        "requests.json[disassemble]",
+        "dap-types declares instructionOffset as unsigned, but the specification allows negative offsets",
+    ),
+    (
+        "responses.json[variables]",
+        "dap-types predates the declarationLocationReference and valueLocationReference of variables",
+    ),
+];
+
+/// Parses `value`, the body or arguments of the message `name` of the kind `kind`, as the
+/// corresponding type of `dap-types` and serializes it again. Returns `None` if there is no such
+/// type.
+fn through_dap_types(kind: &str, name: &str, value: Value) -> Option<serde_json::Result<Value>> {
+    macro_rules! types {
+        ($($kind:literal $name:literal => $type:ty,)*) => {
+            match (kind, name) {
+                $(
+                    ($kind, $name) => Some(
+                        serde_json::from_value::<$type>(value).and_then(serde_json::to_value),
+                    ),
+                )*
+                _ => None,
+            }
+        };
+    }
+    use dap_types::*;
+    types! {
+        "event" "breakpoint" => BreakpointEvent,
+        "event" "capabilities" => CapabilitiesEvent,
+        "event" "continued" => ContinuedEvent,
+        "event" "exited" => ExitedEvent,
+        "event" "invalidated" => InvalidatedEvent,
+        "event" "loadedSource" => LoadedSourceEvent,
+        "event" "module" => ModuleEvent,
+        "event" "output" => OutputEvent,
+        "event" "process" => ProcessEvent,
+        "event" "progressEnd" => ProgressEndEvent,
+        "event" "progressStart" => ProgressStartEvent,
+        "event" "progressUpdate" => ProgressUpdateEvent,
+        "event" "stopped" => StoppedEvent,
+        "event" "terminated" => TerminatedEvent,
+        "event" "thread" => ThreadEvent,
+        "request" "attach" => AttachRequestArguments,
+        "request" "breakpointLocations" => BreakpointLocationsArguments,
+        "request" "cancel" => CancelArguments,
+        "request" "completions" => CompletionsArguments,
+        "request" "continue" => ContinueArguments,
+        "request" "dataBreakpointInfo" => DataBreakpointInfoArguments,
+        "request" "disassemble" => DisassembleArguments,
+        "request" "disconnect" => DisconnectArguments,
+        "request" "evaluate" => EvaluateArguments,
+        "request" "exceptionInfo" => ExceptionInfoArguments,
+        "request" "goto" => GotoArguments,
+        "request" "gotoTargets" => GotoTargetsArguments,
+        "request" "initialize" => InitializeRequestArguments,
+        "request" "launch" => LaunchRequestArguments,
+        "request" "modules" => ModulesArguments,
+        "request" "next" => NextArguments,
+        "request" "pause" => PauseArguments,
+        "request" "readMemory" => ReadMemoryArguments,
+        "request" "restartFrame" => RestartFrameArguments,
+        "request" "reverseContinue" => ReverseContinueArguments,
+        "request" "runInTerminal" => RunInTerminalRequestArguments,
+        "request" "scopes" => ScopesArguments,
+        "request" "setBreakpoints" => SetBreakpointsArguments,
+        "request" "setDataBreakpoints" => SetDataBreakpointsArguments,
+        "request" "setExceptionBreakpoints" => SetExceptionBreakpointsArguments,
+        "request" "setExpression" => SetExpressionArguments,
+        "request" "setFunctionBreakpoints" => SetFunctionBreakpointsArguments,
+        "request" "setInstructionBreakpoints" => SetInstructionBreakpointsArguments,
+        "request" "setVariable" => SetVariableArguments,
+        "request" "source" => SourceArguments,
+        "request" "stackTrace" => StackTraceArguments,
+        "request" "stepBack" => StepBackArguments,
+        "request" "stepIn" => StepInArguments,
+        "request" "stepInTargets" => StepInTargetsArguments,
+        "request" "stepOut" => StepOutArguments,
+        "request" "terminate" => TerminateArguments,
+        "request" "terminateThreads" => TerminateThreadsArguments,
+        "request" "variables" => VariablesArguments,
+        "response" "breakpointLocations" => BreakpointLocationsResponse,
+        "response" "completions" => CompletionsResponse,
+        "response" "continue" => ContinueResponse,
+        "response" "dataBreakpointInfo" => DataBreakpointInfoResponse,
+        "response" "disassemble" => DisassembleResponse,
+        "response" "evaluate" => EvaluateResponse,
+        "response" "exceptionInfo" => ExceptionInfoResponse,
+        "response" "gotoTargets" => GotoTargetsResponse,
+        "response" "initialize" => Capabilities,
+        "response" "loadedSources" => LoadedSourcesResponse,
+        "response" "modules" => ModulesResponse,
+        "response" "readMemory" => ReadMemoryResponse,
+        "response" "runInTerminal" => RunInTerminalResponse,
+        "response" "scopes" => ScopesResponse,
+        "response" "setBreakpoints" => SetBreakpointsResponse,
+        "response" "setDataBreakpoints" => SetDataBreakpointsResponse,
+        "response" "setExceptionBreakpoints" => SetExceptionBreakpointsResponse,
+        "response" "setExpression" => SetExpressionResponse,
+        "response" "setFunctionBreakpoints" => SetFunctionBreakpointsResponse,
+        "response" "setInstructionBreakpoints" => SetInstructionBreakpointsResponse,
+        "response" "setVariable" => SetVariableResponse,
+        "response" "source" => SourceResponse,
+        "response" "stackTrace" => StackTraceResponse,
+        "response" "stepInTargets" => StepInTargetsResponse,
+        "response" "threads" => ThreadsResponse,
+        "response" "variables" => VariablesResponse,
+    }
+}
+
+/// Whether the property `key` is absent in effect, because it is `null` or equals its default.
+fn is_default(key: &str, value: &Value) -> bool {
+    match (key, value) {
+        (_, Value::Null | Value::Bool(false)) => true,
+        (_, Value::Array(array)) => array.is_empty(),
+        (_, Value::Object(object)) => object.is_empty(),
+        ("linesStartAt1" | "columnsStartAt1", Value::Bool(true)) => true,
+        ("pathFormat", value) => value == "path",
+        ("category", value) => value == "console",
+        _ => false,
+    }
+}
+
+/// Removes the properties that are absent in effect.
+fn normalize(value: Value) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| (key, normalize(value)))
+                .filter(|(key, value)| !is_default(key, value))
+                .collect(),
+        ),
+        Value::Array(array) => Value::Array(array.into_iter().map(normalize).collect()),
+        value => value,
+    }
+}
+
+/// Collects the JSON pointers of the properties that differ between `ours` and `theirs`.
+fn differences(path: &str, ours: Option<&Value>, theirs: Option<&Value>, found: &mut Vec<String>) {
+    match (ours, theirs) {
+        (Some(Value::Object(ours)), Some(Value::Object(theirs))) => {
+            let keys = ours
+                .keys()
+                .chain(theirs.keys().filter(|key| !ours.contains_key(*key)));
+            for key in keys {
+                let path = format!("{}/{}", path, key);
+                differences(&path, ours.get(key), theirs.get(key), found);
+            }
+        }
+        (Some(Value::Array(ours)), Some(Value::Array(theirs))) if ours.len() == theirs.len() => {
+            for (index, (ours, theirs)) in ours.iter().zip(theirs).enumerate() {
+                let path = format!("{}/{}", path, index);
+                differences(&path, Some(ours), Some(theirs), found);
+            }
+        }
+        (ours, theirs) if ours != theirs => {
+            let format =
+                |value: Option<&Value>| value.map_or("nothing".to_string(), Value::to_string);
+            found.push(format!(
+                "{}: {} in this crate but {} in dap-types",
+                path,
+                format(ours),
+                format(theirs)
+            ))
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn test_serialization_agrees_with_dap_types() {
+    // given:
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots");
+    let mut compared = 0;
+    let mut failures = Vec::new();
+
+    for file in ["events.json", "requests.json", "responses.json"] {
+        let snapshots: Map<String, Value> =
+            serde_json::from_str(&fs::read_to_string(dir.join(file)).unwrap()).unwrap();
+        for (name, snapshot) in snapshots {
+            let label = format!("{}[{}]", file, name);
+            if KNOWN_DISAGREEMENTS.iter().any(|(known, _)| label == *known) {
+                continue;
+            }
+            let kind = snapshot["type"].as_str().unwrap().to_string();
+            let content_key = match kind.as_str() {
+                "request" => "arguments",
+                _ => "body",
+            };
+
+            // when:
+            let message = ProtocolMessage::from_str(&snapshot.to_string()).unwrap();
+            let mut ours = serde_json::to_value(message).unwrap();
+            let ours = normalize(ours[content_key].take());
+            let theirs = match through_dap_types(&kind, &name, snapshot[content_key].clone()) {
+                Some(Ok(theirs)) => normalize(theirs),
+                Some(Err(error)) => {
+                    failures.push(format!("{}: dap-types failed to parse: {}", label, error));
+                    continue;
+                }
+                None => continue,
+            };
+            compared += 1;
+
+            // then:
+            let mut found = Vec::new();
+            differences("", Some(&ours), Some(&theirs), &mut found);
+            failures.extend(
+                found
+                    .into_iter()
+                    .map(|difference| format!("{} {}", label, difference)),
+            );
+        }
+    }
+    assert!(compared > 0, "no snapshots in {}", dir.display());
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
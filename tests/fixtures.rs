@@ -0,0 +1,123 @@
+//! Checks that the messages in `tests/fixtures` deserialize and re-serialize losslessly.
+//!
+//! The re-serialized message must equal the original, except that properties with the default
+//! documented by the specification are omitted and a missing body or missing arguments become
+//! `{}`.
+
+use debug_adapter_protocol::{ParseOptions, ProtocolMessage};
+use serde_json::{Map, Value};
+use std::{fs, path::Path, str::FromStr};
+
+/// Whether `value` is the default that the specification documents for the property `key`. These
+/// properties are omitted when serializing their default.
+fn is_documented_default(key: &str, value: &Value) -> bool {
+    match key {
+        // "If true all line numbers are 1-based (default)."
+        "linesStartAt1" | "columnsStartAt1" => value == true,
+        // "The default is `path`, which is the native format."
+        "pathFormat" => value == "path",
+        // "If not specified or if the category is not understood by the client, `console` is assumed."
+        "category" => value == "console",
+        // "The index of the first frame to return; if omitted frames start at 0."
+        "startFrame" => value == 0,
+        // "Initial value of the filter option. If not specified a value false is assumed."
+        "default" => value == false,
+        // "A value of true hints to the client that this event should not change the focus."
+        "preserveFocusHint" => value == false,
+        _ => false,
+    }
+}
+
+/// Removes the properties of `message` that equal their documented default.
+fn without_documented_defaults(message: Value) -> Value {
+    let mut message = without_defaults(message);
+    if message["type"] == "response" && message["command"] == "continue" {
+        // "If this attribute is missing a value of 'true' is assumed for backward compatibility."
+        if message["body"]["allThreadsContinued"] == true {
+            message["body"]
+                .as_object_mut()
+                .unwrap()
+                .remove("allThreadsContinued");
+        }
+    }
+    message
+}
+
+/// Removes the properties that equal their documented default at any depth of `value`.
+fn without_defaults(value: Value) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .filter(|(key, value)| !is_documented_default(key, value))
+                .map(|(key, value)| (key, without_defaults(value)))
+                .collect(),
+        ),
+        Value::Array(array) => Value::Array(array.into_iter().map(without_defaults).collect()),
+        value => value,
+    }
+}
+
+/// Removes the empty body or arguments of `actual` if `expected` has none. A missing body or
+/// missing arguments are parsed like empty ones and so serialized as `{}`.
+fn without_added_content(mut actual: Value, expected: &Value) -> Value {
+    for content in ["body", "arguments"] {
+        if expected.get(content).is_none() && actual[content] == Value::Object(Map::new()) {
+            actual.as_object_mut().unwrap().remove(content);
+        }
+    }
+    actual
+}
+
+#[test]
+fn test_fixtures_round_trip() {
+    // given:
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut paths = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    let mut count = 0;
+    let mut failures = Vec::new();
+
+    for path in paths {
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let messages: Vec<Value> = serde_json::from_str(&fs::read_to_string(&path).unwrap())
+            .unwrap_or_else(|error| panic!("{} is not a JSON array: {}", name, error));
+        for (index, expected) in messages.into_iter().enumerate() {
+            count += 1;
+
+            // when:
+            let json = expected.to_string();
+            let parsed = ProtocolMessage::from_str(&json);
+            let parsed_with = ProtocolMessage::parse_with(&json, &ParseOptions::default());
+
+            // then:
+            let consistent = parsed.as_ref().ok() == parsed_with.as_ref().ok();
+            assert!(
+                consistent,
+                "{}[{}] parses differently with options",
+                name, index
+            );
+            let actual = parsed.map(|message| {
+                let actual = serde_json::to_value(message).unwrap();
+                without_added_content(actual, &expected)
+            });
+            match actual {
+                Ok(actual) if actual == without_documented_defaults(expected.clone()) => {}
+                Ok(actual) => failures.push(format!(
+                    "{}[{}] re-serialized as\n{}\nexpected\n{}",
+                    name, index, actual, expected
+                )),
+                Err(error) => failures.push(format!("{}[{}]: {}", name, index, error)),
+            }
+        }
+    }
+    assert!(count > 0, "no fixtures in {}", dir.display());
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
@@ -0,0 +1,179 @@
+"""Captures a debug session of debugpy as a JSON array of the messages in both directions.
+
+Usage: python3 capture_debugpy.py <output.json>
+
+Requires debugpy to be importable. The requests are those that VS Code sends for the session. The
+debuggee is written to /tmp/project/app.py, so that the paths in the trace don't depend on the
+checkout.
+"""
+
+import json
+import os
+import queue
+import subprocess
+import sys
+import threading
+import time
+
+PROGRAM = """\
+import sys
+
+
+def greet(name):
+    message = "Hello, " + name
+    print(message)
+    return message
+
+
+if __name__ == "__main__":
+    greeting = greet(sys.argv[1] if len(sys.argv) > 1 else "world")
+    try:
+        raise ValueError(greeting)
+    except ValueError:
+        pass
+"""
+
+
+class Session:
+    def __init__(self):
+        self.adapter = subprocess.Popen(
+            [sys.executable, "-m", "debugpy.adapter"],
+            stdin=subprocess.PIPE,
+            stdout=subprocess.PIPE,
+        )
+        self.log = []
+        self.seq = 0
+        self.received = queue.Queue()
+        self.pending = []
+        threading.Thread(target=self.read, daemon=True).start()
+
+    def read(self):
+        while True:
+            headers = {}
+            while True:
+                line = self.adapter.stdout.readline()
+                if not line:
+                    self.received.put(None)
+                    return
+                line = line.decode().strip()
+                if not line:
+                    break
+                key, value = line.split(":", 1)
+                headers[key.strip()] = value.strip()
+            message = json.loads(self.adapter.stdout.read(int(headers["Content-Length"])))
+            self.log.append(message)
+            self.received.put(message)
+
+    def send(self, command, arguments=None):
+        self.seq += 1
+        message = {"seq": self.seq, "type": "request", "command": command}
+        if arguments is not None:
+            message["arguments"] = arguments
+        self.log.append(message)
+        data = json.dumps(message).encode()
+        self.adapter.stdin.write(b"Content-Length: %d\r\n\r\n" % len(data) + data)
+        self.adapter.stdin.flush()
+        return self.seq
+
+    def wait(self, predicate, timeout=20):
+        for message in self.pending:
+            if predicate(message):
+                self.pending.remove(message)
+                return message
+        while True:
+            message = self.received.get(timeout=timeout)
+            if message is None:
+                raise SystemExit("the debug adapter closed the connection")
+            if predicate(message):
+                return message
+            self.pending.append(message)
+
+    def response(self, seq):
+        return self.wait(lambda m: m["type"] == "response" and m["request_seq"] == seq)
+
+    def request(self, command, arguments=None):
+        return self.response(self.send(command, arguments))
+
+    def event(self, *events):
+        return self.wait(lambda m: m["type"] == "event" and m["event"] in events)
+
+
+def main(output):
+    os.makedirs("/tmp/project", exist_ok=True)
+    with open("/tmp/project/app.py", "w") as file:
+        file.write(PROGRAM)
+
+    session = Session()
+    session.request("initialize", {
+        "clientID": "vscode",
+        "clientName": "Visual Studio Code",
+        "adapterID": "debugpy",
+        "pathFormat": "path",
+        "linesStartAt1": True,
+        "columnsStartAt1": True,
+        "supportsVariableType": True,
+        "supportsVariablePaging": True,
+        "supportsRunInTerminalRequest": True,
+        "locale": "en",
+        "supportsProgressReporting": True,
+        "supportsInvalidatedEvent": True,
+        "supportsMemoryReferences": True,
+        "supportsArgsCanBeInterpretedByShell": True,
+        "supportsMemoryEvent": True,
+        "supportsStartDebuggingRequest": True,
+    })
+    launch = session.send("launch", {
+        "name": "Python: app.py",
+        "type": "python",
+        "request": "launch",
+        "program": "/tmp/project/app.py",
+        "args": ["DAP"],
+        "console": "internalConsole",
+        "cwd": "/tmp/project",
+        "justMyCode": True,
+        "python": [sys.executable],
+        "__sessionId": "1b6e3b4e-8c1a-4d7f-9a6e-2f0c3d5b7a91",
+    })
+    session.event("initialized")
+    session.request("setBreakpoints", {
+        "source": {"name": "app.py", "path": "/tmp/project/app.py"},
+        "lines": [6, 99],
+        "breakpoints": [{"line": 6}, {"line": 99}],
+    })
+    session.request("setFunctionBreakpoints", {"breakpoints": []})
+    session.request("setExceptionBreakpoints", {"filters": ["raised"]})
+    session.request("configurationDone")
+    session.response(launch)
+
+    thread_id = session.event("stopped")["body"]["threadId"]
+    session.request("threads")
+    stack_trace = session.request("stackTrace", {"threadId": thread_id, "startFrame": 0, "levels": 20})
+    frame_id = stack_trace["body"]["stackFrames"][0]["id"]
+    scopes = session.request("scopes", {"frameId": frame_id})
+    variables_reference = scopes["body"]["scopes"][0]["variablesReference"]
+    session.request("variables", {"variablesReference": variables_reference})
+    session.request("evaluate", {"expression": "message.upper()", "frameId": frame_id, "context": "watch"})
+    session.request("evaluate", {"expression": "undefined_name", "frameId": frame_id, "context": "hover"})
+    session.request("setVariable", {"variablesReference": variables_reference, "name": "message", "value": "'Hi'"})
+    session.request("next", {"threadId": thread_id})
+    session.event("stopped")
+    session.request("stepOut", {"threadId": thread_id})
+    session.event("stopped")
+    session.request("continue", {"threadId": thread_id})
+    session.event("stopped")
+    session.request("exceptionInfo", {"threadId": thread_id})
+    while True:
+        session.request("continue", {"threadId": thread_id})
+        if session.event("stopped", "terminated")["event"] == "terminated":
+            break
+    session.request("disconnect")
+    time.sleep(1)
+    session.adapter.kill()
+
+    with open(output, "w") as file:
+        json.dump(session.log, file, indent=2)
+        file.write("\n")
+
+
+if __name__ == "__main__":
+    main(sys.argv[1])
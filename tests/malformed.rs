@@ -0,0 +1,257 @@
+//! Checks the errors that the frames in `tests/malformed` produce.
+
+use debug_adapter_protocol::{
+    error::{FrameError, MessageKind},
+    framing::split_frame,
+    ProtocolMessage,
+};
+use std::{collections::BTreeSet, fs, path::Path};
+
+/// The error a malformed frame is expected to produce.
+enum Expected {
+    Frame(FrameError),
+
+    /// The header announces more content than the stream holds.
+    Incomplete,
+
+    /// The content is not valid JSON.
+    Syntax,
+
+    /// The content is valid JSON, but not a valid protocol message. `message` is part of the
+    /// description of the error.
+    Parse {
+        kind: Option<MessageKind>,
+        name: Option<&'static str>,
+        path: Option<&'static str>,
+        message: &'static str,
+    },
+}
+
+fn parse(
+    kind: Option<MessageKind>,
+    name: Option<&'static str>,
+    path: Option<&'static str>,
+    message: &'static str,
+) -> Expected {
+    Expected::Parse {
+        kind,
+        name,
+        path,
+        message,
+    }
+}
+
+fn expectations() -> Vec<(&'static str, Expected)> {
+    use Expected::*;
+    use MessageKind::*;
+    vec![
+        (
+            "content-length-negative",
+            Frame(FrameError::InvalidContentLength),
+        ),
+        (
+            "content-length-not-a-number",
+            Frame(FrameError::InvalidContentLength),
+        ),
+        (
+            "content-length-overflow",
+            Frame(FrameError::InvalidContentLength),
+        ),
+        ("content-length-too-long", Incomplete),
+        ("content-length-too-short", Syntax),
+        ("empty-content", Syntax),
+        (
+            "event-not-a-string",
+            parse(
+                Some(Event),
+                None,
+                Some("event"),
+                "invalid type: integer `42`",
+            ),
+        ),
+        (
+            "failure-without-message",
+            parse(
+                Some(Response),
+                Some("threads"),
+                None,
+                "missing field `message`",
+            ),
+        ),
+        ("header-without-colon", Frame(FrameError::InvalidHeader)),
+        ("invalid-utf8", Syntax),
+        (
+            "missing-content-length",
+            Frame(FrameError::MissingContentLength),
+        ),
+        (
+            "missing-required-argument",
+            parse(
+                Some(Request),
+                Some("next"),
+                Some("arguments"),
+                "missing field `threadId`",
+            ),
+        ),
+        (
+            "missing-seq",
+            parse(Some(Request), Some("threads"), None, "missing field `seq`"),
+        ),
+        (
+            "missing-type",
+            parse(None, Some("threads"), None, "missing field `type`"),
+        ),
+        (
+            "negative-seq",
+            parse(Some(Request), Some("threads"), Some("seq"), "integer `-1`"),
+        ),
+        (
+            "not-an-object",
+            parse(None, None, None, "must be a JSON object"),
+        ),
+        (
+            "request-command-not-a-string",
+            parse(
+                Some(Request),
+                None,
+                Some("command"),
+                "invalid type: integer `42`",
+            ),
+        ),
+        (
+            "response-body-of-other-command",
+            parse(
+                Some(Response),
+                Some("stackTrace"),
+                Some("body"),
+                "missing field `stackFrames`",
+            ),
+        ),
+        (
+            "response-command-not-a-string",
+            parse(
+                Some(Response),
+                None,
+                Some("command"),
+                "invalid type: integer `42`",
+            ),
+        ),
+        (
+            "success-not-a-boolean",
+            parse(
+                Some(Response),
+                Some("threads"),
+                Some("success"),
+                "expected a boolean",
+            ),
+        ),
+        (
+            "success-without-body",
+            parse(
+                Some(Response),
+                Some("threads"),
+                None,
+                "missing field `body`",
+            ),
+        ),
+        ("truncated-json", Syntax),
+        (
+            "wrong-argument-type",
+            parse(
+                Some(Request),
+                Some("next"),
+                Some("arguments.threadId"),
+                "invalid type: string \"main\", expected i32",
+            ),
+        ),
+        (
+            "wrong-type",
+            parse(
+                None,
+                Some("threads"),
+                Some("type"),
+                "unknown variant `notification`",
+            ),
+        ),
+    ]
+}
+
+/// Describes the error that `frame` produces, or `None` if it is valid.
+fn check(frame: &[u8], expected: &Expected) -> Option<String> {
+    let content = match (split_frame(frame), expected) {
+        (Err(actual), Expected::Frame(expected)) if actual == *expected => return None,
+        (Ok(None), Expected::Incomplete) => return None,
+        (Err(actual), _) => return Some(format!("frame error {:?}", actual)),
+        (Ok(None), _) => return Some("incomplete frame".to_string()),
+        (Ok(Some((content, _))), _) => content,
+    };
+    let actual = match ProtocolMessage::from_slice(content) {
+        Ok(message) => return Some(format!("parsed as {:?}", message)),
+        Err(error) => error,
+    };
+    let matches = match expected {
+        Expected::Syntax => actual.is_syntax(),
+        Expected::Parse {
+            kind,
+            name,
+            path,
+            message,
+        } => {
+            !actual.is_syntax()
+                && actual.kind() == *kind
+                && actual.name() == *name
+                && actual.path() == *path
+                && actual.to_string().contains(message)
+        }
+        Expected::Frame(_) | Expected::Incomplete => false,
+    };
+    if matches {
+        None
+    } else {
+        Some(format!(
+            "{} (kind {:?}, name {:?}, path {:?})",
+            actual,
+            actual.kind(),
+            actual.name(),
+            actual.path()
+        ))
+    }
+}
+
+#[test]
+fn test_malformed_frames_produce_expected_errors() {
+    // given:
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/malformed");
+    let files = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "dap"))
+        .map(|path| path.file_stem().unwrap().to_string_lossy().into_owned())
+        .collect::<BTreeSet<_>>();
+    let expectations = expectations();
+    let mut failures = Vec::new();
+
+    for (name, expected) in &expectations {
+        // when:
+        let frame = match fs::read(dir.join(format!("{}.dap", name))) {
+            Ok(frame) => frame,
+            Err(error) => {
+                failures.push(format!("{}: {}", name, error));
+                continue;
+            }
+        };
+
+        // then:
+        if let Some(actual) = check(&frame, expected) {
+            failures.push(format!("{}: unexpected {}", name, actual));
+        }
+    }
+    let expected_files = expectations
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect::<BTreeSet<_>>();
+    for name in files.difference(&expected_files) {
+        failures.push(format!("{}: no expected error", name));
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,235 @@
+//! Property-based tests that every type of the protocol survives a round trip through its JSON
+//! serialization, with values generated by the `Arbitrary` implementations of the `arbitrary`
+//! feature from bytes that proptest shrinks on failure.
+
+use arbitrary::{Arbitrary, Unstructured};
+use debug_adapter_protocol::{
+    events::*, requests::*, responses::*, types::*, ProtocolMessage, ProtocolMessageContent,
+};
+use proptest::{collection::vec, prelude::*};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{any::type_name, fmt::Debug};
+
+/// Generates a `T` from `bytes` and checks that it deserializes from its serialization.
+fn round_trip<T>(bytes: &[u8]) -> Result<(), TestCaseError>
+where
+    T: for<'a> Arbitrary<'a> + Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    // given:
+    let value = T::arbitrary(&mut Unstructured::new(bytes)).unwrap();
+
+    // when:
+    let json = serde_json::to_value(&value).unwrap();
+    let actual = serde_json::from_value::<T>(json.clone());
+
+    // then:
+    let actual = actual
+        .map_err(|e| TestCaseError::fail(format!("{}: {}: {}", type_name::<T>(), e, json)))?;
+    prop_assert_eq!(actual, value, "{}: {}", type_name::<T>(), json);
+    Ok(())
+}
+
+macro_rules! round_trip {
+    ($bytes:expr; $($type:ty),* $(,)?) => {
+        $(round_trip::<$type>($bytes)?;)*
+    };
+}
+
+fn bytes() -> impl Strategy<Value = Vec<u8>> {
+    vec(any::<u8>(), 0..2048)
+}
+
+proptest! {
+    #[test]
+    fn test_types_round_trip(bytes in bytes()) {
+        round_trip! {
+            &bytes;
+            Breakpoint,
+            BreakpointLocation,
+            Capabilities,
+            Checksum,
+            ChecksumAlgorithm,
+            ColumnDescriptor,
+            ColumnDescriptorType,
+            CompletionItem,
+            CompletionItemType,
+            DataBreakpoint,
+            DataBreakpointAccessType,
+            DisassembledInstruction,
+            ExceptionBreakMode,
+            ExceptionBreakpointsFilter,
+            ExceptionDetails,
+            ExceptionFilterOptions,
+            ExceptionOptions,
+            ExceptionPathSegment,
+            FunctionBreakpoint,
+            GotoTarget,
+            InstructionBreakpoint,
+            InvalidatedAreas,
+            Message,
+            Module,
+            ModuleId,
+            ModulesViewDescriptor,
+            Scope,
+            ScopePresentationHint,
+            Source,
+            SourcePresentationHint,
+            SourceBreakpoint,
+            StackFrame,
+            StackFramePresentationHint,
+            StackFrameFormat,
+            StepInTarget,
+            SteppingGranularity,
+            Thread,
+            ValueFormat,
+            Variable,
+            VariablePresentationHint,
+            VariableKind,
+            VariableAttribute,
+            VariableVisibility,
+        }
+        #[cfg(feature = "spec-1_70")]
+        round_trip! {
+            &bytes;
+            BreakpointMode,
+            BreakpointModeApplicability,
+            DisassembledInstructionPresentationHint,
+        }
+    }
+
+    #[test]
+    fn test_requests_round_trip(bytes in bytes()) {
+        round_trip! {
+            &bytes;
+            Request,
+            AttachRequestArguments,
+            BreakpointLocationsRequestArguments,
+            CancelRequestArguments,
+            CompletionsRequestArguments,
+            ContinueRequestArguments,
+            DataBreakpointInfoRequestArguments,
+            DisassembleRequestArguments,
+            DisconnectRequestArguments,
+            EvaluateRequestArguments,
+            EvaluateRequestContext,
+            ExceptionInfoRequestArguments,
+            GotoRequestArguments,
+            GotoTargetsRequestArguments,
+            InitializeRequestArguments,
+            PathFormat,
+            LaunchRequestArguments,
+            ModulesRequestArguments,
+            NextRequestArguments,
+            PauseRequestArguments,
+            ReadMemoryRequestArguments,
+            RestartFrameRequestArguments,
+            ReverseContinueRequestArguments,
+            RunInTerminalRequestArguments,
+            TerminalKind,
+            ScopesRequestArguments,
+            SetBreakpointsRequestArguments,
+            SetDataBreakpointsRequestArguments,
+            SetExceptionBreakpointsRequestArguments,
+            SetExpressionRequestArguments,
+            SetFunctionBreakpointsRequestArguments,
+            SetInstructionBreakpointsRequestArguments,
+            SetVariableRequestArguments,
+            SourceRequestArguments,
+            StackTraceRequestArguments,
+            StepBackRequestArguments,
+            StepInRequestArguments,
+            StepInTargetsRequestArguments,
+            StepOutRequestArguments,
+            TerminateRequestArguments,
+            TerminateThreadsRequestArguments,
+            VariablesRequestArguments,
+            VariablesFilter,
+        }
+        #[cfg(feature = "spec-1_70")]
+        round_trip! {
+            &bytes;
+            LocationsRequestArguments,
+        }
+    }
+
+    #[test]
+    fn test_responses_round_trip(bytes in bytes()) {
+        round_trip! {
+            &bytes;
+            Response,
+            ErrorResponse,
+            ErrorResponseBody,
+            SuccessResponse,
+            BreakpointLocationsResponseBody,
+            CompletionsResponseBody,
+            ContinueResponseBody,
+            DataBreakpointInfoResponseBody,
+            DisassembleResponseBody,
+            EvaluateResponseBody,
+            ExceptionInfoResponseBody,
+            GotoTargetsResponseBody,
+            LoadedSourcesResponseBody,
+            ModulesResponseBody,
+            ReadMemoryResponseBody,
+            RunInTerminalResponseBody,
+            ScopesResponseBody,
+            SetBreakpointsResponseBody,
+            SetDataBreakpointsResponseBody,
+            SetExceptionBreakpointsResponseBody,
+            SetExpressionResponseBody,
+            SetFunctionBreakpointsResponseBody,
+            SetInstructionBreakpointsResponseBody,
+            SetVariableResponseBody,
+            SourceResponseBody,
+            StackTraceResponseBody,
+            StepInTargetsResponseBody,
+            ThreadsResponseBody,
+            VariablesResponseBody,
+        }
+        #[cfg(feature = "spec-1_70")]
+        round_trip! {
+            &bytes;
+            LocationsResponseBody,
+        }
+    }
+
+    #[test]
+    fn test_events_round_trip(bytes in bytes()) {
+        round_trip! {
+            &bytes;
+            Event,
+            BreakpointEventBody,
+            BreakpointEventReason,
+            CapabilitiesEventBody,
+            ContinuedEventBody,
+            ExitedEventBody,
+            InvalidatedEventBody,
+            LoadedSourceEventBody,
+            LoadedSourceEventReason,
+            ModuleEventBody,
+            ModuleEventReason,
+            OutputEventBody,
+            OutputCategory,
+            OutputGroup,
+            ProcessEventBody,
+            ProcessStartMethod,
+            ProgressEndEventBody,
+            ProgressStartEventBody,
+            ProgressUpdateEventBody,
+            StoppedEventBody,
+            StoppedEventReason,
+            TerminatedEventBody,
+            ThreadEventBody,
+            ThreadEventReason,
+        }
+    }
+
+    #[test]
+    fn test_protocol_messages_round_trip(bytes in bytes()) {
+        round_trip! {
+            &bytes;
+            ProtocolMessage,
+            ProtocolMessageContent,
+        }
+    }
+}
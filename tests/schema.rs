@@ -0,0 +1,117 @@
+//! Checks that the serialized form of representative messages conforms to the JSON schema of the
+//! specification.
+//!
+//! The messages are the snapshots in `tests/snapshots`, which cover every request, response and
+//! event, and the fixtures in `tests/fixtures`. Each is parsed into the typed model, serialized
+//! again and validated against `debugAdapterProtocol.json` with the `jsonschema` crate, so the
+//! check does not depend on the `Schema` of this crate. The schema is the one bundled in
+//! `src/debugAdapterProtocol.json`.
+
+use debug_adapter_protocol::ProtocolMessage;
+use jsonschema::Validator;
+use serde_json::{json, Value};
+use std::{collections::HashMap, fs, path::Path};
+
+const SCHEMA: &str = include_str!("../src/debugAdapterProtocol.json");
+
+/// The messages in the JSON files of `dir`, labeled with their file and key or index.
+fn messages(dir: &str) -> Vec<(String, Value)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(dir);
+    let mut paths = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut messages = Vec::new();
+    for path in paths {
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        match serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap() {
+            Value::Object(object) => messages.extend(
+                object
+                    .into_iter()
+                    .map(|(key, message)| (format!("{}[{}]", name, key), message)),
+            ),
+            Value::Array(array) => messages.extend(
+                array
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, message)| (format!("{}[{}]", name, index), message)),
+            ),
+            _ => panic!("{} is neither an object nor an array", name),
+        }
+    }
+    messages
+}
+
+/// The name of the definition for `message`, for example `StoppedEvent` or `ContinueResponse`.
+/// Messages with a command or event that the schema does not define are checked against
+/// `Request`, `Response` or `Event`.
+fn definition(schema: &Value, message: &Value) -> String {
+    let (base, name) = match message["type"].as_str() {
+        Some("request") => ("Request", &message["command"]),
+        Some("event") => ("Event", &message["event"]),
+        Some("response") if message["success"] == false => return "ErrorResponse".to_string(),
+        Some("response") => ("Response", &message["command"]),
+        _ => return "ProtocolMessage".to_string(),
+    };
+    let specific = name.as_str().map(|name| {
+        let mut chars = name.chars();
+        let first = chars.next().map(|first| first.to_ascii_uppercase());
+        format!(
+            "{}{}{}",
+            first.into_iter().collect::<String>(),
+            chars.as_str(),
+            base
+        )
+    });
+    match specific {
+        Some(specific) if schema["definitions"].get(&specific).is_some() => specific,
+        _ => base.to_string(),
+    }
+}
+
+#[test]
+fn test_serialized_messages_conform_to_schema() {
+    // given:
+    let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+    let mut validators = HashMap::<String, Validator>::new();
+    let mut recorded = messages("tests/snapshots");
+    recorded.extend(messages("tests/fixtures"));
+    let mut failures = Vec::new();
+
+    for (name, message) in recorded {
+        // when:
+        let message = serde_json::from_value::<ProtocolMessage>(message)
+            .unwrap_or_else(|error| panic!("{} is invalid: {}", name, error));
+        let json = serde_json::to_value(&message).unwrap();
+        let definition = definition(&schema, &json);
+        let validator = validators
+            .entry(definition)
+            .or_insert_with_key(|definition| {
+                let root = json!({
+                    "$schema": schema["$schema"],
+                    "definitions": schema["definitions"],
+                    "$ref": format!("#/definitions/{}", definition),
+                });
+                jsonschema::validator_for(&root)
+                    .unwrap_or_else(|error| panic!("invalid schema: {}", error))
+            });
+
+        // then:
+        failures.extend(
+            validator
+                .iter_errors(&json)
+                .map(|error| format!("{} at '{}': {}", name, error.instance_path(), error)),
+        );
+    }
+    assert!(
+        failures.is_empty(),
+        "serialized messages violate the schema:\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,796 @@
+//! Compares the serialized form of every request, response and event with the snapshots in
+//! `tests/snapshots`, so that changes to the wire format don't go unnoticed.
+//!
+//! After an intended change of the wire format, run the tests with `UPDATE_SNAPSHOTS=1` to rewrite
+//! the snapshots and review the diff.
+
+// Converting to `Str` is only a no-op without the shared-strings feature
+#![allow(clippy::useless_conversion)]
+
+use debug_adapter_protocol::{
+    capabilities::CapabilityFlags,
+    events::{
+        BreakpointEventBody, BreakpointEventReason, CapabilitiesEventBody, ContinuedEventBody,
+        Event, ExitedEventBody, InvalidatedEventBody, LoadedSourceEventBody,
+        LoadedSourceEventReason, ModuleEventBody, ModuleEventReason, OutputCategory,
+        OutputEventBody, OutputGroup, ProcessEventBody, ProcessStartMethod, ProgressEndEventBody,
+        ProgressStartEventBody, ProgressUpdateEventBody, StoppedEventBody, StoppedEventReason,
+        TerminatedEventBody, ThreadEventBody, ThreadEventReason,
+    },
+    requests::{
+        AttachRequestArguments, BreakpointLocationsRequestArguments, CancelRequestArguments,
+        CompletionsRequestArguments, ContinueRequestArguments, DataBreakpointInfoRequestArguments,
+        DisassembleRequestArguments, DisconnectRequestArguments, EvaluateRequestArguments,
+        EvaluateRequestContext, ExceptionInfoRequestArguments, GotoRequestArguments,
+        GotoTargetsRequestArguments, InitializeRequestArguments, LaunchRequestArguments,
+        LocationsRequestArguments, ModulesRequestArguments, NextRequestArguments, PathFormat,
+        PauseRequestArguments, ReadMemoryRequestArguments, Request, RestartFrameRequestArguments,
+        ReverseContinueRequestArguments, RunInTerminalRequestArguments, ScopesRequestArguments,
+        SetBreakpointsRequestArguments, SetDataBreakpointsRequestArguments,
+        SetExceptionBreakpointsRequestArguments, SetExpressionRequestArguments,
+        SetFunctionBreakpointsRequestArguments, SetInstructionBreakpointsRequestArguments,
+        SetVariableRequestArguments, SourceRequestArguments, StackTraceRequestArguments,
+        StepBackRequestArguments, StepInRequestArguments, StepInTargetsRequestArguments,
+        StepOutRequestArguments, TerminalKind, TerminateRequestArguments,
+        TerminateThreadsRequestArguments, VariablesFilter, VariablesRequestArguments,
+    },
+    responses::{
+        BreakpointLocationsResponseBody, CompletionsResponseBody, ContinueResponseBody,
+        DataBreakpointInfoResponseBody, DisassembleResponseBody, EvaluateResponseBody,
+        ExceptionInfoResponseBody, GotoTargetsResponseBody, LoadedSourcesResponseBody,
+        LocationsResponseBody, ModulesResponseBody, ReadMemoryResponseBody, Response,
+        RunInTerminalResponseBody, ScopesResponseBody, SetBreakpointsResponseBody,
+        SetDataBreakpointsResponseBody, SetExceptionBreakpointsResponseBody,
+        SetExpressionResponseBody, SetFunctionBreakpointsResponseBody,
+        SetInstructionBreakpointsResponseBody, SetVariableResponseBody, SourceResponseBody,
+        StackTraceResponseBody, StepInTargetsResponseBody, SuccessResponse, ThreadsResponseBody,
+        VariablesResponseBody,
+    },
+    types::{
+        Breakpoint, BreakpointLocation, BreakpointMode, BreakpointModeApplicability, Capabilities,
+        Checksum, ChecksumAlgorithm, CompletionItem, CompletionItemType, DataBreakpoint,
+        DataBreakpointAccessType, DisassembledInstruction, ExceptionBreakMode,
+        ExceptionBreakpointsFilter, ExceptionDetails, ExceptionFilterOptions, FunctionBreakpoint,
+        GotoTarget, InstructionBreakpoint, InvalidatedAreas, Module, ModuleId, Scope,
+        ScopePresentationHint, Source, SourceBreakpoint, StackFrame, StackFramePresentationHint,
+        StepInTarget, SteppingGranularity, Thread, ValueFormat, Variable, VariableAttribute,
+        VariableKind, VariablePresentationHint,
+    },
+    ProtocolMessage,
+};
+use serde_json::{json, Map, Value};
+use std::{collections::BTreeSet, env, fs, path::Path};
+
+fn source() -> Source {
+    Source::builder()
+        .name(Some("main.rs".into()))
+        .path(Some("/home/user/project/src/main.rs".into()))
+        .checksums(
+            vec![Checksum::builder()
+                .algorithm(ChecksumAlgorithm::SHA256)
+                .checksum("9f86d081884c7d659a2feaa0c55ad015".into())
+                .build()]
+            .into(),
+        )
+        .build()
+}
+
+fn breakpoint() -> Breakpoint {
+    Breakpoint::builder()
+        .id(Some(1))
+        .verified(true)
+        .source(Some(source()))
+        .line(Some(12))
+        .build()
+}
+
+fn capabilities() -> Capabilities {
+    Capabilities::builder()
+        .flags(
+            CapabilityFlags::SUPPORTS_CONFIGURATION_DONE_REQUEST
+                | CapabilityFlags::SUPPORTS_CONDITIONAL_BREAKPOINTS
+                | CapabilityFlags::SUPPORTS_SINGLE_THREAD_EXECUTION_REQUESTS,
+        )
+        .exception_breakpoint_filters(vec![ExceptionBreakpointsFilter::builder()
+            .filter("panic".into())
+            .label("Panics".into())
+            .default(true)
+            .build()])
+        .supported_checksum_algorithms(vec![ChecksumAlgorithm::SHA256])
+        .breakpoint_modes(vec![BreakpointMode::builder()
+            .mode("hardware".into())
+            .label("Hardware".into())
+            .applies_to(vec![
+                BreakpointModeApplicability::Source,
+                BreakpointModeApplicability::Instruction,
+            ])
+            .build()])
+        .build()
+}
+
+fn module() -> Module {
+    Module::builder()
+        .id(ModuleId::Integer(1))
+        .name("libstd".into())
+        .path(Some("/usr/lib/libstd.so".into()))
+        .is_optimized(Some(true))
+        .build()
+}
+
+fn presentation_hint() -> VariablePresentationHint {
+    VariablePresentationHint::builder()
+        .kind(Some(VariableKind::Data))
+        .attributes(vec![VariableAttribute::ReadOnly])
+        .build()
+}
+
+fn object(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(object) => object,
+        value => panic!("{} is not an object", value),
+    }
+}
+
+fn requests() -> Vec<Request> {
+    vec![
+        AttachRequestArguments::builder()
+            .additional_attributes(object(json!({"processId": 4242})))
+            .build()
+            .into(),
+        BreakpointLocationsRequestArguments::builder()
+            .source(source())
+            .line(12)
+            .end_line(Some(14))
+            .build()
+            .into(),
+        CancelRequestArguments::builder()
+            .request_id(Some(7))
+            .build()
+            .into(),
+        CompletionsRequestArguments::builder()
+            .frame_id(Some(1000))
+            .text("vec.le".into())
+            .column(7)
+            .build()
+            .into(),
+        Request::ConfigurationDone,
+        ContinueRequestArguments::builder()
+            .thread_id(1)
+            .single_thread(true)
+            .build()
+            .into(),
+        DataBreakpointInfoRequestArguments::builder()
+            .variables_reference(Some(5))
+            .name("counter".into())
+            .build()
+            .into(),
+        DisassembleRequestArguments::builder()
+            .memory_reference("0x1000".into())
+            .instruction_offset(-2)
+            .instruction_count(4)
+            .resolve_symbols(true)
+            .build()
+            .into(),
+        DisconnectRequestArguments::builder()
+            .terminate_debuggee(Some(true))
+            .build()
+            .into(),
+        EvaluateRequestArguments::builder()
+            .expression("vec.len()".into())
+            .frame_id(Some(1000))
+            .context(Some(EvaluateRequestContext::REPL))
+            .format(Some(ValueFormat::builder().hex(Some(true)).build()))
+            .build()
+            .into(),
+        ExceptionInfoRequestArguments::builder()
+            .thread_id(1)
+            .build()
+            .into(),
+        GotoRequestArguments::builder()
+            .thread_id(1)
+            .target_id(2)
+            .build()
+            .into(),
+        GotoTargetsRequestArguments::builder()
+            .source(source())
+            .line(20)
+            .build()
+            .into(),
+        InitializeRequestArguments::builder()
+            .client_id(Some("vscode".into()))
+            .client_name(Some("Visual Studio Code".into()))
+            .adapter_id("lldb".into())
+            .locale(Some("en".into()))
+            .lines_start_at_1(true)
+            .columns_start_at_1(true)
+            .path_format(PathFormat::Path)
+            .supports_variable_type(true)
+            .supports_run_in_terminal_request(true)
+            .build()
+            .into(),
+        LaunchRequestArguments::builder()
+            .no_debug(true)
+            .additional_attributes(object(json!({"program": "target/debug/project"})))
+            .build()
+            .into(),
+        Request::LoadedSources,
+        LocationsRequestArguments::builder()
+            .location_reference(3)
+            .build()
+            .into(),
+        ModulesRequestArguments::builder()
+            .start_module(0)
+            .module_count(10)
+            .build()
+            .into(),
+        NextRequestArguments::builder()
+            .thread_id(1)
+            .granularity(SteppingGranularity::Line)
+            .build()
+            .into(),
+        PauseRequestArguments::builder().thread_id(1).build().into(),
+        ReadMemoryRequestArguments::builder()
+            .memory_reference("0x1000".into())
+            .offset(8)
+            .count(16)
+            .build()
+            .into(),
+        RestartFrameRequestArguments::builder()
+            .frame_id(1001)
+            .build()
+            .into(),
+        ReverseContinueRequestArguments::builder()
+            .thread_id(1)
+            .build()
+            .into(),
+        RunInTerminalRequestArguments::builder()
+            .kind(TerminalKind::External)
+            .title(Some("project".into()))
+            .cwd("/home/user/project".into())
+            .args(vec!["target/debug/project".into(), "--verbose".into()])
+            .build()
+            .into(),
+        ScopesRequestArguments::builder()
+            .frame_id(1000)
+            .build()
+            .into(),
+        SetBreakpointsRequestArguments::builder()
+            .source(source())
+            .breakpoints(vec![SourceBreakpoint::builder()
+                .line(12)
+                .condition(Some("i == 3".into()))
+                .build()])
+            .build()
+            .into(),
+        SetDataBreakpointsRequestArguments::builder()
+            .breakpoints(vec![DataBreakpoint::builder()
+                .data_id("counter@0x2000".into())
+                .access_type(Some(DataBreakpointAccessType::Write))
+                .build()])
+            .build()
+            .into(),
+        SetExceptionBreakpointsRequestArguments::builder()
+            .filters(vec!["panic".into()].into())
+            .filter_options(vec![ExceptionFilterOptions::builder()
+                .filter_id("panic".into())
+                .condition(Some("!msg.is_empty()".into()))
+                .build()])
+            .build()
+            .into(),
+        SetExpressionRequestArguments::builder()
+            .expression("counter".into())
+            .value("42".into())
+            .frame_id(Some(1000))
+            .build()
+            .into(),
+        SetFunctionBreakpointsRequestArguments::builder()
+            .breakpoints(vec![FunctionBreakpoint::builder()
+                .name("main".into())
+                .hit_condition(Some(">= 2".into()))
+                .build()])
+            .build()
+            .into(),
+        SetInstructionBreakpointsRequestArguments::builder()
+            .breakpoints(vec![InstructionBreakpoint::builder()
+                .instruction_reference("0x1000".into())
+                .offset(Some(4))
+                .mode(Some("hardware".into()))
+                .build()])
+            .build()
+            .into(),
+        SetVariableRequestArguments::builder()
+            .variables_reference(5)
+            .name("counter".into())
+            .value("42".into())
+            .build()
+            .into(),
+        SourceRequestArguments::builder()
+            .source(Some(source()))
+            .source_reference(0)
+            .build()
+            .into(),
+        StackTraceRequestArguments::builder()
+            .thread_id(1)
+            .levels(20)
+            .build()
+            .into(),
+        StepBackRequestArguments::builder()
+            .thread_id(1)
+            .build()
+            .into(),
+        StepInRequestArguments::builder()
+            .thread_id(1)
+            .target_id(Some(3))
+            .granularity(SteppingGranularity::Instruction)
+            .build()
+            .into(),
+        StepInTargetsRequestArguments::builder()
+            .frame_id(1000)
+            .build()
+            .into(),
+        StepOutRequestArguments::builder()
+            .thread_id(1)
+            .build()
+            .into(),
+        TerminateRequestArguments::builder()
+            .restart(true)
+            .build()
+            .into(),
+        TerminateThreadsRequestArguments::builder()
+            .thread_ids(vec![2, 3])
+            .build()
+            .into(),
+        Request::Threads,
+        VariablesRequestArguments::builder()
+            .variables_reference(5)
+            .filter(Some(VariablesFilter::Indexed))
+            .start(0)
+            .count(100)
+            .build()
+            .into(),
+        // Named like the variant, because its command is the key in the snapshot
+        Request::Custom {
+            command: "custom".into(),
+            arguments: Some(Box::new(json!({"trace": true}))),
+        },
+    ]
+}
+
+fn responses() -> Vec<SuccessResponse> {
+    vec![
+        SuccessResponse::Attach,
+        BreakpointLocationsResponseBody::builder()
+            .breakpoints(vec![BreakpointLocation::builder()
+                .line(12)
+                .column(Some(5))
+                .build()])
+            .build()
+            .into(),
+        SuccessResponse::Cancel,
+        CompletionsResponseBody::builder()
+            .targets(vec![CompletionItem::builder()
+                .label("len".into())
+                .type_(Some(CompletionItemType::Method))
+                .start(Some(4))
+                .length(2)
+                .build()])
+            .build()
+            .into(),
+        SuccessResponse::ConfigurationDone,
+        ContinueResponseBody::builder()
+            .all_threads_continued(true)
+            .build()
+            .into(),
+        DataBreakpointInfoResponseBody::builder()
+            .data_id(Some("counter@0x2000".into()))
+            .description("counter".into())
+            .access_types(Some(vec![
+                DataBreakpointAccessType::Read,
+                DataBreakpointAccessType::Write,
+            ]))
+            .build()
+            .into(),
+        DisassembleResponseBody::builder()
+            .instructions(vec![DisassembledInstruction::builder()
+                .address("0x1000".into())
+                .instruction_bytes(Some("48 89 e5".into()))
+                .instruction("mov rbp, rsp".into())
+                .symbol(Some("main".into()))
+                .build()])
+            .build()
+            .into(),
+        SuccessResponse::Disconnect,
+        EvaluateResponseBody::builder()
+            .result("3".into())
+            .type_(Some("usize".into()))
+            .presentation_hint(Some(presentation_hint()))
+            .variables_reference(0)
+            .build()
+            .into(),
+        ExceptionInfoResponseBody::builder()
+            .exception_id("panic".into())
+            .description(Some("index out of bounds".into()))
+            .break_mode(ExceptionBreakMode::Unhandled)
+            .details(Some(
+                ExceptionDetails::builder()
+                    .message(Some("index out of bounds: the len is 3".into()))
+                    .type_name(Some("core::panicking::Panic".into()))
+                    .build(),
+            ))
+            .build()
+            .into(),
+        SuccessResponse::Goto,
+        GotoTargetsResponseBody::builder()
+            .targets(vec![GotoTarget::builder()
+                .id(2)
+                .label("main.rs:20".into())
+                .line(20)
+                .build()])
+            .build()
+            .into(),
+        SuccessResponse::Initialize(Box::new(capabilities())),
+        SuccessResponse::Launch,
+        LoadedSourcesResponseBody::builder()
+            .sources(vec![source()])
+            .build()
+            .into(),
+        LocationsResponseBody::builder()
+            .source(source())
+            .line(7)
+            .column(Some(9))
+            .build()
+            .into(),
+        ModulesResponseBody::builder()
+            .modules(vec![module()])
+            .total_modules(Some(1))
+            .build()
+            .into(),
+        SuccessResponse::Next,
+        SuccessResponse::Pause,
+        ReadMemoryResponseBody::builder()
+            .address("0x1008".into())
+            .unreadable_bytes(Some(0))
+            .data(Some("AAECAwQFBgcICQoLDA0ODw==".into()))
+            .build()
+            .into(),
+        SuccessResponse::RestartFrame,
+        SuccessResponse::Restart,
+        SuccessResponse::ReverseContinue,
+        RunInTerminalResponseBody::builder()
+            .process_id(Some(4242))
+            .build()
+            .into(),
+        ScopesResponseBody::builder()
+            .scopes(vec![Scope::builder()
+                .name("Locals".into())
+                .presentation_hint(Some(ScopePresentationHint::Locals))
+                .variables_reference(5)
+                .named_variables(Some(2))
+                .expensive(false)
+                .build()])
+            .build()
+            .into(),
+        SetBreakpointsResponseBody::builder()
+            .breakpoints(vec![breakpoint()])
+            .build()
+            .into(),
+        SetDataBreakpointsResponseBody::builder()
+            .breakpoints(vec![Breakpoint::builder().verified(true).build()])
+            .build()
+            .into(),
+        SetExceptionBreakpointsResponseBody::builder()
+            .breakpoints(Some(vec![Breakpoint::builder().verified(true).build()]))
+            .build()
+            .into(),
+        SetExpressionResponseBody::builder()
+            .value("42".into())
+            .type_(Some("i32".into()))
+            .build()
+            .into(),
+        SetFunctionBreakpointsResponseBody::builder()
+            .breakpoints(vec![Breakpoint::builder()
+                .verified(false)
+                .message(Some("function not found".into()))
+                .build()])
+            .build()
+            .into(),
+        SetInstructionBreakpointsResponseBody::builder()
+            .breakpoints(vec![Breakpoint::builder()
+                .verified(true)
+                .instruction_reference(Some("0x1000".into()))
+                .offset(Some(4))
+                .build()])
+            .build()
+            .into(),
+        SetVariableResponseBody::builder()
+            .value("42".into())
+            .variables_reference(Some(0))
+            .build()
+            .into(),
+        SourceResponseBody::builder()
+            .content("fn main() {}\n".into())
+            .mime_type(Some("text/x-rust".into()))
+            .build()
+            .into(),
+        StackTraceResponseBody::builder()
+            .stack_frames(vec![StackFrame::builder()
+                .id(1000)
+                .name("project::main".into())
+                .source(Some(source()))
+                .line(12)
+                .column(5)
+                .module_id(Some(ModuleId::Integer(1)))
+                .presentation_hint(Some(StackFramePresentationHint::Normal))
+                .build()])
+            .total_frames(Some(1))
+            .build()
+            .into(),
+        SuccessResponse::StepBack,
+        SuccessResponse::StepIn,
+        StepInTargetsResponseBody::builder()
+            .targets(vec![StepInTarget::builder()
+                .id(3)
+                .label("Vec::len".into())
+                .build()])
+            .build()
+            .into(),
+        SuccessResponse::StepOut,
+        SuccessResponse::Terminate,
+        SuccessResponse::TerminateThreads,
+        ThreadsResponseBody::builder()
+            .threads(vec![Thread::builder().id(1).name("main".into()).build()])
+            .build()
+            .into(),
+        VariablesResponseBody::builder()
+            .variables(vec![Variable::builder()
+                .name("counter".into())
+                .value("3".into())
+                .type_(Some("i32".into()))
+                .presentation_hint(Some(presentation_hint()))
+                .evaluate_name(Some("counter".into()))
+                .variables_reference(0)
+                .declaration_location_reference(Some(4))
+                .build()])
+            .build()
+            .into(),
+        // Named like the variant, because its command is the key in the snapshot
+        SuccessResponse::Other {
+            command: "other".into(),
+            body: Some(Box::new(json!({"checkpoints": [1, 2]}))),
+        },
+    ]
+}
+
+fn events() -> Vec<Event> {
+    vec![
+        BreakpointEventBody::builder()
+            .reason(BreakpointEventReason::Changed)
+            .breakpoint(breakpoint())
+            .build()
+            .into(),
+        CapabilitiesEventBody::builder()
+            .capabilities(capabilities())
+            .build()
+            .into(),
+        ContinuedEventBody::builder()
+            .thread_id(1)
+            .all_threads_continued(true)
+            .build()
+            .into(),
+        ExitedEventBody::builder().exit_code(101).build().into(),
+        Event::Initialized,
+        InvalidatedEventBody::builder()
+            .areas(vec![InvalidatedAreas::Stacks].into())
+            .thread_id(Some(1))
+            .build()
+            .into(),
+        LoadedSourceEventBody::builder()
+            .reason(LoadedSourceEventReason::New)
+            .source(source())
+            .build()
+            .into(),
+        ModuleEventBody::builder()
+            .reason(ModuleEventReason::New)
+            .module(module())
+            .build()
+            .into(),
+        OutputEventBody::builder()
+            .category(OutputCategory::Stdout)
+            .output("Hello, world!\n".into())
+            .group(Some(OutputGroup::Start))
+            .source(Some(source()))
+            .line(Some(12))
+            .build()
+            .into(),
+        ProcessEventBody::builder()
+            .name("project".into())
+            .system_process_id(Some(4242))
+            .is_local_process(Some(true))
+            .start_method(Some(ProcessStartMethod::Launch))
+            .pointer_size(Some(64))
+            .build()
+            .into(),
+        ProgressEndEventBody::builder()
+            .progress_id("symbols".into())
+            .message(Some("Done".into()))
+            .build()
+            .into(),
+        ProgressStartEventBody::builder()
+            .progress_id("symbols".into())
+            .title("Loading symbols".into())
+            .cancellable(true)
+            .percentage(Some(0))
+            .build()
+            .into(),
+        ProgressUpdateEventBody::builder()
+            .progress_id("symbols".into())
+            .percentage(Some(50))
+            .build()
+            .into(),
+        StoppedEventBody::builder()
+            .reason(StoppedEventReason::Breakpoint)
+            .thread_id(Some(1))
+            .all_threads_stopped(true)
+            .hit_breakpoint_ids(vec![1].into())
+            .build()
+            .into(),
+        TerminatedEventBody::builder()
+            .restart(Some(json!({"attempt": 2})))
+            .build()
+            .into(),
+        ThreadEventBody::builder()
+            .reason(ThreadEventReason::Started)
+            .thread_id(2)
+            .build()
+            .into(),
+        // Named like the variant, because its event is the key in the snapshot
+        Event::Custom {
+            event: "custom".into(),
+            body: Some(Box::new(json!({"name": "session.started"}))),
+        },
+    ]
+}
+
+/// The names of the variants of `T`, taken from the error of `deserialize` for an unknown `tag`.
+fn variant_names<T>(
+    tag: &str,
+    deserialize: impl FnOnce(Value) -> Result<T, serde_json::Error>,
+) -> BTreeSet<String> {
+    let message = match deserialize(json!({ tag: "?" })) {
+        Ok(_) => panic!("the unknown {} was accepted", tag),
+        Err(error) => error.to_string(),
+    };
+    let (_, names) = message
+        .split_once("expected one of ")
+        .unwrap_or_else(|| panic!("unexpected error: {}", message));
+    names
+        .split(", ")
+        .map(|name| name.trim_matches('`').to_string())
+        .collect()
+}
+
+/// Compares `messages` keyed by their command or event with the snapshot `name`.
+fn assert_snapshot(
+    name: &str,
+    messages: Vec<(String, ProtocolMessage)>,
+    expected: BTreeSet<String>,
+) {
+    let actual = messages
+        .iter()
+        .map(|(key, _)| key.clone())
+        .collect::<BTreeSet<_>>();
+    assert_eq!(actual, expected, "{} don't cover every variant", name);
+
+    let snapshot = messages
+        .into_iter()
+        .map(|(key, message)| (key, serde_json::to_value(message).unwrap()))
+        .collect::<Map<_, _>>();
+    let json = serde_json::to_string_pretty(&snapshot).unwrap() + "\n";
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.json", name));
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, &json).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|error| panic!("failed to read {}: {}", path.display(), error));
+    if json != expected {
+        let expected: Map<String, Value> = serde_json::from_str(&expected).unwrap();
+        let changed = snapshot
+            .iter()
+            .filter(|(key, value)| expected.get(*key) != Some(value))
+            .map(|(key, value)| format!("{}:\n{}", key, value))
+            .collect::<Vec<_>>();
+        panic!(
+            "the serialized {} differ from {}, rerun with UPDATE_SNAPSHOTS=1 if this is intended\n{}",
+            name,
+            path.display(),
+            changed.join("\n")
+        );
+    }
+}
+
+#[test]
+fn test_request_snapshots() {
+    // given:
+    let requests = requests();
+
+    // when:
+    let messages = requests
+        .into_iter()
+        .enumerate()
+        .map(|(index, request)| {
+            (
+                request.command().to_string(),
+                ProtocolMessage::new(index as u64 + 1, request),
+            )
+        })
+        .collect();
+
+    // then:
+    // Unknown commands deserialize as Request::Custom, so the names are taken from the derived
+    // impl, which names it 'custom'
+    assert_snapshot(
+        "requests",
+        messages,
+        variant_names("command", Request::deserialize),
+    );
+}
+
+#[test]
+fn test_response_snapshots() {
+    // given:
+    let responses = responses();
+
+    // when:
+    let messages = responses
+        .into_iter()
+        .enumerate()
+        .map(|(index, response)| {
+            let seq = index as u64 + 1;
+            let command = response.command().to_string();
+            (
+                command,
+                ProtocolMessage::new(seq + 100, Response::new(seq, Ok(response))),
+            )
+        })
+        .collect();
+
+    // then:
+    // Unknown commands deserialize as SuccessResponse::Other, so the names are taken from the
+    // derived impl, which names it 'other'
+    assert_snapshot(
+        "responses",
+        messages,
+        variant_names("command", SuccessResponse::deserialize),
+    );
+}
+
+#[test]
+fn test_event_snapshots() {
+    // given:
+    let events = events();
+
+    // when:
+    let messages = events
+        .into_iter()
+        .enumerate()
+        .map(|(index, event)| {
+            (
+                event.event().to_string(),
+                ProtocolMessage::new(index as u64 + 1, event),
+            )
+        })
+        .collect();
+
+    // then:
+    // Unknown events deserialize as Event::Custom, so the names are taken from the derived impl,
+    // which names it 'custom'
+    assert_snapshot(
+        "events",
+        messages,
+        variant_names("event", Event::deserialize),
+    );
+}
@@ -0,0 +1,220 @@
+//! Stress tests for the concurrent use of a [Connection]: many threads sending requests at once,
+//! cancellations racing the responses of the requests they cancel, events interleaved with the
+//! responses and the transport closing while messages are in flight.
+//!
+//! Every wait has a timeout, so a deadlock fails the test instead of hanging it.
+
+use debug_adapter_protocol::{
+    connection::Connection,
+    correlation::PendingRequests,
+    events::{Event, OutputEventBody},
+    requests::{CancelRequestArguments, Request},
+    responses::{ErrorResponse, Response, SuccessResponse, ThreadsResponseBody},
+    ProtocolMessage, ProtocolMessageContent, SequenceNumber, Str,
+};
+use std::{
+    collections::HashSet,
+    io::{self, Cursor, Write},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const CLIENT_THREADS: u64 = 8;
+const REQUESTS_PER_THREAD: u64 = 200;
+const ADAPTER_THREADS: usize = 4;
+
+/// Answers 'threads' requests on several threads, so responses arrive out of order, and sends an
+/// 'output' event after each of them. A 'threads' request is answered with an error if a 'cancel'
+/// request for it arrived first.
+fn spawn_adapter(connection: Connection) -> Vec<thread::JoinHandle<()>> {
+    let seq = Arc::new(AtomicU64::new(1));
+    let cancelled = Arc::new(Mutex::new(HashSet::new()));
+    (0..ADAPTER_THREADS)
+        .map(|_| {
+            let sender = connection.sender.clone();
+            let receiver = connection.receiver.clone();
+            let seq = seq.clone();
+            let cancelled = cancelled.clone();
+            thread::spawn(move || {
+                let send = |content: ProtocolMessageContent| {
+                    let seq = seq.fetch_add(1, Ordering::Relaxed);
+                    sender.send(ProtocolMessage::new(seq, content)).unwrap();
+                };
+                for message in receiver.iter() {
+                    let request = match message.content {
+                        ProtocolMessageContent::Request(request) => request,
+                        content => panic!("unexpected {:?}", content),
+                    };
+                    let result = match request {
+                        Request::Cancel(arguments) => {
+                            let request_id = arguments.request_id.unwrap() as SequenceNumber;
+                            cancelled.lock().unwrap().insert(request_id);
+                            Ok(SuccessResponse::Cancel)
+                        }
+                        Request::Threads if cancelled.lock().unwrap().remove(&message.seq) => {
+                            Err(ErrorResponse::builder()
+                                .command("threads".into())
+                                .message("cancelled".into())
+                                .build())
+                        }
+                        Request::Threads => Ok(ThreadsResponseBody::builder()
+                            .threads(vec![])
+                            .build()
+                            .into()),
+                        request => panic!("unexpected {:?}", request),
+                    };
+                    send(Response::new(message.seq, result).into());
+                    let output = OutputEventBody::builder()
+                        .output(Str::from(format!("answered {}", message.seq)))
+                        .build();
+                    send(Event::Output(Box::new(output)).into());
+                }
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_concurrent_requests_and_cancellations() {
+    // given:
+    let (client, adapter) = Connection::memory();
+    let adapters = spawn_adapter(adapter);
+    let next_seq = Arc::new(AtomicU64::new(1));
+    let pending = Arc::new(Mutex::new(PendingRequests::new()));
+    let request_count = CLIENT_THREADS * REQUESTS_PER_THREAD;
+    let cancel_count = request_count / 2;
+
+    // when:
+    let senders = (0..CLIENT_THREADS)
+        .map(|_| {
+            let sender = client.sender.clone();
+            let next_seq = next_seq.clone();
+            let pending = pending.clone();
+            thread::spawn(move || {
+                let send = |request: Request| {
+                    let seq = next_seq.fetch_add(1, Ordering::Relaxed);
+                    // Record the request before sending it, the response may arrive right away
+                    pending.lock().unwrap().insert(seq, request.clone());
+                    sender.send(ProtocolMessage::new(seq, request)).unwrap();
+                    seq
+                };
+                for index in 0..REQUESTS_PER_THREAD {
+                    let seq = send(Request::Threads);
+                    if index % 2 == 0 {
+                        let arguments = CancelRequestArguments::builder()
+                            .request_id(Some(seq as i32))
+                            .build();
+                        send(Request::Cancel(arguments));
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut answered = HashSet::new();
+    let mut cancelled = 0;
+    let mut events = 0;
+    while answered.len() < (request_count + cancel_count) as usize || events < answered.len() {
+        let message = client.receiver.recv_timeout(TIMEOUT).unwrap_or_else(|_| {
+            panic!(
+                "deadlock: {} responses and {} events received",
+                answered.len(),
+                events
+            )
+        });
+        match message.content {
+            ProtocolMessageContent::Response(response) => {
+                let request = pending.lock().unwrap().complete(&response).unwrap();
+                assert!(answered.insert(response.request_seq));
+                if let (Request::Threads, Err(_)) = (request, response.result) {
+                    cancelled += 1;
+                }
+            }
+            ProtocolMessageContent::Event(Event::Output(_)) => events += 1,
+            content => panic!("unexpected {:?}", content),
+        }
+    }
+    for sender in senders {
+        sender.join().unwrap();
+    }
+    drop(client);
+    for adapter in adapters {
+        adapter.join().unwrap();
+    }
+
+    // then:
+    assert!(pending.lock().unwrap().is_empty());
+    assert_eq!(answered.len() as u64, request_count + cancel_count);
+    assert_eq!(events, answered.len());
+    assert!(cancelled <= cancel_count);
+}
+
+/// Accepts `remaining` bytes and fails afterwards, like a pipe that was closed by the other end.
+struct ClosingWriter {
+    remaining: usize,
+}
+
+impl Write for ClosingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.remaining == 0 {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+        let len = buf.len().min(self.remaining);
+        self.remaining -= len;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_transport_closing_mid_flight() {
+    // given:
+    let frame = |seq| {
+        let json = format!(r#"{{"seq":{},"type":"event","event":"initialized"}}"#, seq);
+        format!("Content-Length: {}\r\n\r\n{}", json.len(), json)
+    };
+    let mut input = (1..=100).map(frame).collect::<String>();
+    input.push_str(&frame(101)[..30]);
+    let (connection, io_threads) =
+        Connection::from_transport(Cursor::new(input), ClosingWriter { remaining: 1000 });
+    let next_seq = Arc::new(AtomicU64::new(1));
+
+    // when:
+    let senders = (0..CLIENT_THREADS)
+        .map(|_| {
+            let sender = connection.sender.clone();
+            let next_seq = next_seq.clone();
+            thread::spawn(move || {
+                (0..REQUESTS_PER_THREAD)
+                    .map(|_| {
+                        let seq = next_seq.fetch_add(1, Ordering::Relaxed);
+                        sender.send(ProtocolMessage::new(seq, Request::Threads))
+                    })
+                    .take_while(Result::is_ok)
+                    .count()
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut received = 0;
+    while connection.receiver.recv_timeout(TIMEOUT).is_ok() {
+        received += 1;
+    }
+    let sent = senders
+        .into_iter()
+        .map(|sender| sender.join().unwrap())
+        .sum::<usize>();
+    drop(connection);
+    let result = io_threads.join();
+
+    // then:
+    assert_eq!(received, 100);
+    assert!((sent as u64) < CLIENT_THREADS * REQUESTS_PER_THREAD);
+    assert!(result.is_err());
+}
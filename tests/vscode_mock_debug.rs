@@ -0,0 +1,168 @@
+//! Drives a full session with `vscode-mock-debug`, the reference implementation of a debug adapter,
+//! to check interoperability end to end.
+//!
+//! The test is ignored by default, because it needs `git`, `node` and `npm`. It clones and builds
+//! the adapter into `target/vscode-mock-debug`, unless `VSCODE_MOCK_DEBUG` is set to the path of
+//! an already built `debugAdapter.js`:
+//!
+//! ```text
+//! cargo test --features integration-tests --test vscode_mock_debug -- --ignored
+//! ```
+
+// Converting to `Str` is only a no-op without the shared-strings feature
+#![allow(clippy::useless_conversion)]
+
+use debug_adapter_protocol::{
+    connection::Connection,
+    events::{Event, StoppedEventReason},
+    mock::{MockClient, Script},
+    requests::{
+        ContinueRequestArguments, DisconnectRequestArguments, InitializeRequestArguments,
+        LaunchRequestArguments, Request, SetBreakpointsRequestArguments,
+        StackTraceRequestArguments,
+    },
+    responses::SuccessResponse,
+    types::{Source, SourceBreakpoint},
+};
+use serde_json::json;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    time::Duration,
+};
+
+const REPOSITORY: &str = "https://github.com/microsoft/vscode-mock-debug.git";
+
+fn run(command: &mut Command) {
+    let status = command
+        .status()
+        .unwrap_or_else(|error| panic!("failed to run {:?}: {}", command, error));
+    assert!(status.success(), "{:?} failed with {}", command, status);
+}
+
+/// The path of `debugAdapter.js`, cloning and building the adapter if necessary.
+fn debug_adapter() -> PathBuf {
+    if let Some(path) = env::var_os("VSCODE_MOCK_DEBUG") {
+        return path.into();
+    }
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("target/vscode-mock-debug");
+    let adapter = dir.join("out/debugAdapter.js");
+    if !adapter.exists() {
+        if !dir.exists() {
+            run(Command::new("git")
+                .args(["clone", "--depth", "1", REPOSITORY])
+                .arg(&dir));
+        }
+        run(Command::new("npm").arg("install").current_dir(&dir));
+        run(Command::new("npm")
+            .args(["run", "compile"])
+            .current_dir(&dir));
+    }
+    adapter
+}
+
+fn spawn(adapter: &Path) -> (Connection, Child) {
+    let mut child = Command::new("node")
+        .arg(adapter)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|error| panic!("failed to spawn {}: {}", adapter.display(), error));
+    let stdin = child.stdin.take().expect("stdin is piped");
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let (connection, _io_threads) = Connection::from_transport(stdout, stdin);
+    (connection, child)
+}
+
+fn stopped(event: &Event, reason: StoppedEventReason) -> Result<(), String> {
+    match event {
+        Event::Stopped(body) if body.reason == reason => Ok(()),
+        event => Err(format!("expected a stop for {:?}: {:?}", reason, event)),
+    }
+}
+
+#[test]
+#[ignore = "needs git, node and npm to build vscode-mock-debug"]
+fn test_session_with_vscode_mock_debug() {
+    // given:
+    let program = env::temp_dir().join("debug-adapter-protocol-mock.md");
+    fs::write(
+        &program,
+        "first line\nsecond line\nthird line\nfourth line\n",
+    )
+    .unwrap();
+    let path = program.to_string_lossy().into_owned();
+    let source = Source::builder().path(Some(path.clone().into())).build();
+
+    let (connection, mut child) = spawn(&debug_adapter());
+    let mut client = MockClient::new(connection);
+    client.set_timeout(Duration::from_secs(10));
+
+    let script = Script::new()
+        .request_with(
+            InitializeRequestArguments::builder()
+                .client_id(Some("debug-adapter-protocol".into()))
+                .adapter_id("mock".into())
+                .lines_start_at_1(true)
+                .columns_start_at_1(true)
+                .build(),
+            |response| match response {
+                SuccessResponse::Initialize(capabilities)
+                    if capabilities.supports_configuration_done_request() =>
+                {
+                    Ok(())
+                }
+                response => Err(format!("unexpected capabilities: {:?}", response)),
+            },
+        )
+        .event("initialized")
+        .request(
+            SetBreakpointsRequestArguments::builder()
+                .source(source)
+                .breakpoints(vec![SourceBreakpoint::builder().line(3).build()])
+                .build(),
+        )
+        .request(Request::ConfigurationDone)
+        .request(
+            LaunchRequestArguments::builder()
+                .additional_attributes(
+                    json!({ "program": path, "stopOnEntry": true })
+                        .as_object()
+                        .cloned()
+                        .unwrap(),
+                )
+                .build(),
+        )
+        .event_with("stopped", |event| stopped(event, StoppedEventReason::Entry))
+        .request_with(Request::Threads, |response| match response {
+            SuccessResponse::Threads(body) if !body.threads.is_empty() => Ok(()),
+            response => Err(format!("no threads: {:?}", response)),
+        })
+        .request_with(
+            StackTraceRequestArguments::builder().thread_id(1).build(),
+            |response| match response {
+                SuccessResponse::StackTrace(body) if !body.stack_frames.is_empty() => Ok(()),
+                response => Err(format!("no stack frames: {:?}", response)),
+            },
+        )
+        .request(ContinueRequestArguments::builder().thread_id(1).build())
+        .event_with("stopped", |event| {
+            stopped(event, StoppedEventReason::Breakpoint)
+        })
+        .request(ContinueRequestArguments::builder().thread_id(1).build())
+        .event("terminated")
+        .request(DisconnectRequestArguments::builder().build());
+
+    // when:
+    let result = client.run(script);
+
+    // then:
+    drop(client);
+    let _ = child.kill();
+    let _ = child.wait();
+    let _ = fs::remove_file(&program);
+    if let Err(error) = result {
+        panic!("{}", error);
+    }
+}
@@ -0,0 +1,86 @@
+//! Tests of the sans-IO parts of the crate on `wasm32-unknown-unknown`, for clients running in a
+//! browser.
+//!
+//! Run with `cargo test --target wasm32-unknown-unknown --features decoder`. Other targets compile
+//! this to an empty test binary.
+
+#![cfg(all(
+    target_arch = "wasm32",
+    target_os = "unknown",
+    feature = "de",
+    feature = "events",
+    feature = "requests",
+    feature = "responses",
+    feature = "ser"
+))]
+
+use debug_adapter_protocol::{
+    events::Event, framing::split_frames, lazy::LazyProtocolMessage, requests::Request,
+    ProtocolMessage, ProtocolMessageContent,
+};
+use std::str::FromStr;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const STREAM: &[u8] = b"Content-Length: 70\r\n\r\n\
+{\"seq\":1,\"type\":\"request\",\"command\":\"next\",\"arguments\":{\"threadId\":1}}\
+Content-Length: 46\r\n\r\n\
+{\"seq\":2,\"type\":\"event\",\"event\":\"initialized\"}";
+
+#[wasm_bindgen_test]
+fn test_parse_and_serialize() {
+    // given:
+    let json = r#"{"seq":1,"type":"request","command":"threads"}"#;
+
+    // when:
+    let message = ProtocolMessage::from_str(json).unwrap();
+
+    // then:
+    assert_eq!(
+        message.content,
+        ProtocolMessageContent::Request(Request::Threads)
+    );
+    assert_eq!(message.to_string(), json);
+}
+
+#[wasm_bindgen_test]
+fn test_split_frames() {
+    // when:
+    let frames = split_frames(STREAM).unwrap();
+
+    // then:
+    let messages = frames
+        .into_iter()
+        .map(|frame| ProtocolMessage::from_slice(frame).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(
+        messages[1].content,
+        ProtocolMessageContent::Event(Event::Initialized)
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_lazy_protocol_message() {
+    // given:
+    let json = br#"{"seq":3,"type":"event","event":"initialized"}"#;
+
+    // when:
+    let message = LazyProtocolMessage::from_slice(json).unwrap();
+
+    // then:
+    assert_eq!(message.name(), Some("initialized"));
+    assert_eq!(message.parse().unwrap().seq, 3);
+}
+
+#[cfg(feature = "decoder")]
+#[wasm_bindgen_test]
+fn test_decoder() {
+    use debug_adapter_protocol::decoder::Decoder;
+
+    // when:
+    let frames = Decoder::new(STREAM).collect::<Result<Vec<_>, _>>().unwrap();
+
+    // then:
+    assert_eq!(frames.len(), 2);
+    assert_eq!(ProtocolMessage::from_slice(&frames[0]).unwrap().seq, 1);
+}